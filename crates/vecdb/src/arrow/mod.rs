@@ -0,0 +1,208 @@
+//! Apache Arrow conversions, behind the `arrow` feature.
+
+use std::{io::Write, sync::Arc};
+
+use arrow_array::{Array, ArrayRef, RecordBatch, UInt64Array};
+use arrow_ipc::writer::{FileWriter, StreamWriter};
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef};
+
+#[cfg(feature = "parquet")]
+mod parquet_table;
+mod value;
+
+pub use arrow_array;
+pub use arrow_ipc;
+pub use arrow_schema;
+#[cfg(feature = "parquet")]
+pub use parquet;
+#[cfg(feature = "parquet")]
+pub use parquet_table::*;
+pub use value::*;
+
+use crate::{AnyVec, ReadableVec, Result, TypedVec, VecIndex, WritableVec};
+
+/// Default number of rows per record batch.
+const DEFAULT_BATCH_LEN: usize = 64 * 1024;
+
+/// Converts `[from, to)` by raw index of `vec` to an Arrow array, see [`ArrowValue`].
+pub fn to_arrow_array<I, T>(vec: &impl ReadableVec<I, T>, from: usize, to: usize) -> ArrayRef
+where
+    I: VecIndex,
+    T: ArrowValue,
+{
+    T::into_array(vec.collect_range_at(from, to))
+}
+
+/// Pushes the values of `array` to `vec` and returns how many. Fails without pushing
+/// anything if `array` isn't of `T::data_type()` or has nulls.
+pub fn append_arrow_column<I, T>(
+    vec: &mut impl WritableVec<I, T>,
+    array: &dyn Array,
+) -> Result<usize>
+where
+    I: VecIndex,
+    T: ArrowValue,
+{
+    let mut values = vec![];
+    T::extend_from_array(array, &mut values)?;
+    let len = values.len();
+    values.into_iter().for_each(|value| vec.push(value));
+    Ok(len)
+}
+
+/// Type-erased vec that can be read as Arrow arrays.
+pub trait AnyArrowVec: AnyVec {
+    /// Arrow type of the values.
+    fn arrow_data_type(&self) -> DataType;
+
+    /// Converts `[from, to)` by raw index to an Arrow array.
+    fn arrow_array(&self, from: usize, to: usize) -> ArrayRef;
+}
+
+impl<V> AnyArrowVec for V
+where
+    V: TypedVec + ReadableVec<V::I, V::T>,
+    V::T: ArrowValue,
+{
+    fn arrow_data_type(&self) -> DataType {
+        V::T::data_type()
+    }
+
+    fn arrow_array(&self, from: usize, to: usize) -> ArrayRef {
+        to_arrow_array(self, from, to)
+    }
+}
+
+/// Fails if `columns` is empty, mixes index types or repeats a name.
+fn check_columns<'a>(columns: impl Iterator<Item = &'a dyn AnyVec> + Clone) -> Result<()> {
+    let Some(first) = columns.clone().next() else {
+        return Err(ArrowError::InvalidArgumentError("a table needs a column".into()).into());
+    };
+    let index = first.index_type_to_string();
+    for (i, column) in columns.clone().enumerate() {
+        if column.index_type_to_string() != index {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "{} is indexed by {}, {} by {index}",
+                column.name(),
+                column.index_type_to_string(),
+                first.name(),
+            ))
+            .into());
+        }
+        if columns.clone().take(i).any(|c| c.name() == column.name()) {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "{} is in the table twice",
+                column.name()
+            ))
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Vecs sharing an index type, exported as Arrow record batches: row `i` holds index
+/// `from + i` of every vec, up to the length of the shortest one. Each vec is a column
+/// named after it.
+///
+/// ```ignore
+/// let table = ArrowTable::new(vec![&prices, &volumes])?
+///     .with_range(1_000, None)
+///     .with_index_column("height");
+/// table.write_ipc_file(File::create("prices.arrow")?)?;
+/// ```
+pub struct ArrowTable<'a> {
+    columns: Vec<&'a dyn AnyArrowVec>,
+    from: usize,
+    to: Option<usize>,
+    batch_len: usize,
+    index_column: Option<String>,
+}
+
+impl<'a> ArrowTable<'a> {
+    /// Fails if `columns` is empty, mixes index types or repeats a name.
+    pub fn new(columns: Vec<&'a dyn AnyArrowVec>) -> Result<Self> {
+        check_columns(columns.iter().map(|&c| c as &dyn AnyVec))?;
+        Ok(Self {
+            columns,
+            from: 0,
+            to: None,
+            batch_len: DEFAULT_BATCH_LEN,
+            index_column: None,
+        })
+    }
+
+    /// Exports indices `[from, to)` only, `to` defaulting to the end.
+    pub fn with_range(mut self, from: usize, to: Option<usize>) -> Self {
+        self.from = from;
+        self.to = to;
+        self
+    }
+
+    /// Rows per record batch, 65,536 by default.
+    pub fn with_batch_len(mut self, batch_len: usize) -> Self {
+        self.batch_len = batch_len.max(1);
+        self
+    }
+
+    /// Adds a leading `UInt64` column named `name` holding the index of each row.
+    pub fn with_index_column(mut self, name: &str) -> Self {
+        self.index_column = Some(name.to_string());
+        self
+    }
+
+    /// Exported index range.
+    pub fn range(&self) -> (usize, usize) {
+        let len = self.columns.iter().map(|c| c.len()).min().unwrap_or(0);
+        let to = self.to.unwrap_or(len).min(len);
+        (self.from.min(to), to)
+    }
+
+    pub fn schema(&self) -> SchemaRef {
+        let index = self
+            .index_column
+            .iter()
+            .map(|name| Field::new(name, DataType::UInt64, false));
+        let columns = self
+            .columns
+            .iter()
+            .map(|c| Field::new(c.name(), c.arrow_data_type(), false));
+        Arc::new(Schema::new(index.chain(columns).collect::<Vec<_>>()))
+    }
+
+    /// Record batches of the table, read lazily.
+    pub fn record_batches(&self) -> impl Iterator<Item = Result<RecordBatch>> + '_ {
+        let schema = self.schema();
+        let (from, to) = self.range();
+        (from..to).step_by(self.batch_len).map(move |start| {
+            let end = (start + self.batch_len).min(to);
+            let index = self.index_column.as_ref().map(|_| {
+                Arc::new(UInt64Array::from_iter_values(start as u64..end as u64)) as ArrayRef
+            });
+            let columns = index
+                .into_iter()
+                .chain(self.columns.iter().map(|c| c.arrow_array(start, end)))
+                .collect();
+            Ok(RecordBatch::try_new(schema.clone(), columns)?)
+        })
+    }
+
+    /// Writes the table as an Arrow IPC file.
+    pub fn write_ipc_file<W: Write>(&self, writer: W) -> Result<()> {
+        let mut writer = FileWriter::try_new(writer, &self.schema())?;
+        for batch in self.record_batches() {
+            writer.write(&batch?)?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+
+    /// Writes the table as an Arrow IPC stream.
+    pub fn write_ipc_stream<W: Write>(&self, writer: W) -> Result<()> {
+        let mut writer = StreamWriter::try_new(writer, &self.schema())?;
+        for batch in self.record_batches() {
+            writer.write(&batch?)?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,290 @@
+use std::{io::Write, str::FromStr, sync::Arc};
+
+use arrow_array::{
+    ArrayRef, ArrowPrimitiveType, RecordBatch, UInt64Array,
+    builder::{BooleanBuilder, PrimitiveBuilder, StringBuilder},
+    types::{
+        Float32Type, Float64Type, Int8Type, Int16Type, Int32Type, Int64Type, UInt8Type, UInt16Type,
+        UInt32Type, UInt64Type,
+    },
+};
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef};
+use parquet::{arrow::ArrowWriter, basic::Compression, file::properties::WriterProperties};
+
+use crate::{AnyExportableVec, AnyVec, Error, Result, ValueWriter};
+
+use super::check_columns;
+
+/// Default number of indices per row group.
+const DEFAULT_ROW_GROUP_LEN: usize = 1024 * 1024;
+
+/// Arrow type and nullability of a column, from the value type name and size: numbers
+/// and `bool` keep their type, anything else is written as its CSV cell text. A value
+/// larger than its named type is an `Option`, nullable with empty cells as nulls.
+fn column_type(vec: &dyn AnyVec) -> (DataType, bool) {
+    let (data_type, size) = match vec.value_type_to_string() {
+        "bool" => (DataType::Boolean, 1),
+        "u8" => (DataType::UInt8, 1),
+        "u16" => (DataType::UInt16, 2),
+        "u32" => (DataType::UInt32, 4),
+        "u64" => (DataType::UInt64, 8),
+        "usize" => (DataType::UInt64, size_of::<usize>()),
+        "i8" => (DataType::Int8, 1),
+        "i16" => (DataType::Int16, 2),
+        "i32" => (DataType::Int32, 4),
+        "i64" => (DataType::Int64, 8),
+        "isize" => (DataType::Int64, size_of::<isize>()),
+        "f32" => (DataType::Float32, 4),
+        "f64" => (DataType::Float64, 8),
+        _ => return (DataType::Utf8, false),
+    };
+    (data_type, vec.value_type_to_size_of() != size)
+}
+
+struct ParquetColumn<'a> {
+    vec: &'a dyn AnyExportableVec,
+    data_type: DataType,
+    nullable: bool,
+}
+
+/// Vecs sharing an index type, exported as a Parquet file: row `i` holds index `from + i`
+/// of every vec, up to the length of the shortest one. Each vec is a column named after
+/// it, typed as described in [`ParquetTable::with_column_type`].
+///
+/// Values are read through [`AnyVecWithWriter::create_writer`](crate::AnyVecWithWriter)
+/// one row group at a time, so memory stays bounded by the row group length whatever the
+/// size of the range. Row groups start at multiples of the row group length, the first and
+/// last one being shorter when the range isn't aligned.
+///
+/// ```ignore
+/// let rows = ParquetTable::new(vec![&prices, &volumes])?
+///     .with_row_group_len(100_000)
+///     .with_index_column("height")
+///     .write(File::create("prices.parquet")?)?;
+/// ```
+pub struct ParquetTable<'a> {
+    columns: Vec<ParquetColumn<'a>>,
+    from: usize,
+    to: Option<usize>,
+    row_group_len: usize,
+    index_column: Option<String>,
+    compression: Compression,
+}
+
+impl<'a> ParquetTable<'a> {
+    /// Fails if `columns` is empty, mixes index types or repeats a name.
+    pub fn new(columns: Vec<&'a dyn AnyExportableVec>) -> Result<Self> {
+        check_columns(columns.iter().map(|&c| c as &dyn AnyVec))?;
+        let columns = columns
+            .into_iter()
+            .map(|vec| {
+                let (data_type, nullable) = column_type(vec);
+                ParquetColumn {
+                    vec,
+                    data_type,
+                    nullable,
+                }
+            })
+            .collect();
+        Ok(Self {
+            columns,
+            from: 0,
+            to: None,
+            row_group_len: DEFAULT_ROW_GROUP_LEN,
+            index_column: None,
+            compression: Compression::SNAPPY,
+        })
+    }
+
+    /// Exports indices `[from, to)` only, `to` defaulting to the end.
+    pub fn with_range(mut self, from: usize, to: Option<usize>) -> Self {
+        self.from = from;
+        self.to = to;
+        self
+    }
+
+    /// Indices per row group, 1,048,576 by default.
+    pub fn with_row_group_len(mut self, row_group_len: usize) -> Self {
+        self.row_group_len = row_group_len.max(1);
+        self
+    }
+
+    /// Adds a leading `UInt64` column named `name` holding the index of each row.
+    pub fn with_index_column(mut self, name: &str) -> Self {
+        self.index_column = Some(name.to_string());
+        self
+    }
+
+    /// Compression of the column chunks, Snappy by default.
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Overrides the type of the column named `name`, parsing its cells as integers,
+    /// floats or booleans (physical types `INT32`, `INT64`, `FLOAT`, `DOUBLE` and
+    /// `BOOLEAN`) or keeping them as `Utf8` strings. Defaults to the type matching the
+    /// value type name, or `Utf8` for other types.
+    ///
+    /// Fails if there's no such column or `data_type` is none of those.
+    pub fn with_column_type(mut self, name: &str, data_type: DataType) -> Result<Self> {
+        if !matches!(
+            data_type,
+            DataType::Boolean
+                | DataType::UInt8
+                | DataType::UInt16
+                | DataType::UInt32
+                | DataType::UInt64
+                | DataType::Int8
+                | DataType::Int16
+                | DataType::Int32
+                | DataType::Int64
+                | DataType::Float32
+                | DataType::Float64
+                | DataType::Utf8
+        ) {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "can't write {name} as {data_type}"
+            ))
+            .into());
+        }
+        let Some(column) = self.columns.iter_mut().find(|c| c.vec.name() == name) else {
+            return Err(ArrowError::InvalidArgumentError(format!("no column named {name}")).into());
+        };
+        column.data_type = data_type;
+        Ok(self)
+    }
+
+    /// Exported index range.
+    pub fn range(&self) -> (usize, usize) {
+        let len = self.columns.iter().map(|c| c.vec.len()).min().unwrap_or(0);
+        let to = self.to.unwrap_or(len).min(len);
+        (self.from.min(to), to)
+    }
+
+    pub fn schema(&self) -> SchemaRef {
+        let index = self
+            .index_column
+            .iter()
+            .map(|name| Field::new(name, DataType::UInt64, false));
+        let columns = self
+            .columns
+            .iter()
+            .map(|c| Field::new(c.vec.name(), c.data_type.clone(), c.nullable));
+        Arc::new(Schema::new(index.chain(columns).collect::<Vec<_>>()))
+    }
+
+    /// Writes the table as a Parquet file and returns the number of rows.
+    pub fn write<W: Write + Send>(&self, writer: W) -> Result<usize> {
+        let schema = self.schema();
+        let properties = WriterProperties::builder()
+            .set_max_row_group_size(self.row_group_len)
+            .set_compression(self.compression)
+            .build();
+        let mut writer = ArrowWriter::try_new(writer, schema.clone(), Some(properties))?;
+
+        let (from, to) = self.range();
+        let mut start = from;
+        while start < to {
+            let end = ((start / self.row_group_len + 1) * self.row_group_len).min(to);
+            let index = self.index_column.as_ref().map(|_| {
+                Arc::new(UInt64Array::from_iter_values(start as u64..end as u64)) as ArrayRef
+            });
+            let columns = index
+                .into_iter()
+                .map(Ok)
+                .chain(self.columns.iter().map(|c| c.read(start, end)))
+                .collect::<Result<Vec<_>>>()?;
+            writer.write(&RecordBatch::try_new(schema.clone(), columns)?)?;
+            // Closes the row group, so that it ends exactly at `end`
+            writer.flush()?;
+            start = end;
+        }
+
+        writer.close()?;
+        Ok(to - from)
+    }
+}
+
+impl ParquetColumn<'_> {
+    /// Parses the cells of `[from, to)` into an array of the column type.
+    fn read(&self, from: usize, to: usize) -> Result<ArrayRef> {
+        let mut cells = Cells {
+            writer: self.vec.create_writer(Some(from as i64), Some(to as i64)),
+            buf: String::new(),
+            name: self.vec.name(),
+            nullable: self.nullable,
+        };
+        let len = to - from;
+        Ok(match self.data_type {
+            DataType::Boolean => {
+                let mut builder = BooleanBuilder::with_capacity(len);
+                for _ in 0..len {
+                    builder.append_option(cells.parse()?);
+                }
+                Arc::new(builder.finish()) as ArrayRef
+            }
+            DataType::UInt8 => cells.primitive::<UInt8Type>(len)?,
+            DataType::UInt16 => cells.primitive::<UInt16Type>(len)?,
+            DataType::UInt32 => cells.primitive::<UInt32Type>(len)?,
+            DataType::UInt64 => cells.primitive::<UInt64Type>(len)?,
+            DataType::Int8 => cells.primitive::<Int8Type>(len)?,
+            DataType::Int16 => cells.primitive::<Int16Type>(len)?,
+            DataType::Int32 => cells.primitive::<Int32Type>(len)?,
+            DataType::Int64 => cells.primitive::<Int64Type>(len)?,
+            DataType::Float32 => cells.primitive::<Float32Type>(len)?,
+            DataType::Float64 => cells.primitive::<Float64Type>(len)?,
+            _ => {
+                let mut builder = StringBuilder::with_capacity(len, len * 8);
+                for _ in 0..len {
+                    builder.append_value(cells.next()?);
+                }
+                Arc::new(builder.finish()) as ArrayRef
+            }
+        })
+    }
+}
+
+/// CSV cells of a column, one value at a time.
+struct Cells<'a> {
+    writer: Box<dyn ValueWriter + 'a>,
+    buf: String,
+    name: &'a str,
+    nullable: bool,
+}
+
+impl Cells<'_> {
+    fn next(&mut self) -> Result<&str> {
+        self.buf.clear();
+        self.writer.write_next(&mut self.buf)?;
+        Ok(&self.buf)
+    }
+
+    /// Parses the next cell, empty cells being nulls in nullable columns.
+    fn parse<T: FromStr>(&mut self) -> Result<Option<T>> {
+        let (name, nullable) = (self.name, self.nullable);
+        let cell = self.next()?;
+        if cell.is_empty() && nullable {
+            return Ok(None);
+        }
+        match cell.parse() {
+            Ok(value) => Ok(Some(value)),
+            Err(_) => Err(Error::from(ArrowError::ParseError(format!(
+                "can't parse {cell:?} in {name} as {}",
+                std::any::type_name::<T>()
+            )))),
+        }
+    }
+
+    fn primitive<A>(&mut self, len: usize) -> Result<ArrayRef>
+    where
+        A: ArrowPrimitiveType,
+        A::Native: FromStr,
+    {
+        let mut builder = PrimitiveBuilder::<A>::with_capacity(len);
+        for _ in 0..len {
+            builder.append_option(self.parse()?);
+        }
+        Ok(Arc::new(builder.finish()))
+    }
+}
@@ -0,0 +1,143 @@
+use std::sync::Arc;
+
+use arrow_array::{
+    Array, ArrayRef, ArrowPrimitiveType, BooleanArray, FixedSizeBinaryArray, PrimitiveArray,
+    types::{
+        Float32Type, Float64Type, Int8Type, Int16Type, Int32Type, Int64Type, UInt8Type, UInt16Type,
+        UInt32Type, UInt64Type,
+    },
+};
+use arrow_buffer::{Buffer, ScalarBuffer};
+use arrow_schema::{ArrowError, DataType};
+
+use crate::{Bytes, Result, VecValue};
+
+/// Values with an Arrow representation.
+///
+/// Primitive numbers map to the matching primitive array, moving the collected values
+/// into the array without copying them. Any other `Bytes` type can opt in with an empty
+/// impl and is stored as a fixed-size binary array of its `to_bytes`, copied in one go
+/// when the type has `IS_NATIVE_LAYOUT`:
+///
+/// ```ignore
+/// impl ArrowValue for TxId {}
+/// ```
+pub trait ArrowValue: VecValue + Bytes {
+    /// Arrow type of a column of these values.
+    fn data_type() -> DataType {
+        DataType::FixedSizeBinary(size_of::<Self>() as i32)
+    }
+
+    /// Converts `values` to an array of [`Self::data_type`].
+    fn into_array(values: Vec<Self>) -> ArrayRef {
+        let size = size_of::<Self>();
+        let byte_len = values.len() * size;
+        let bytes = if Self::IS_NATIVE_LAYOUT {
+            // Same layout as `to_bytes`: one copy of the whole slice
+            unsafe { std::slice::from_raw_parts(values.as_ptr() as *const u8, byte_len) }.to_vec()
+        } else {
+            let mut bytes = Vec::with_capacity(byte_len);
+            for value in &values {
+                bytes.extend_from_slice(value.to_bytes().as_ref());
+            }
+            bytes
+        };
+        Arc::new(FixedSizeBinaryArray::new(
+            size as i32,
+            Buffer::from_vec(bytes),
+            None,
+        ))
+    }
+
+    /// Appends the values of `array` to `out`. Fails if `array` isn't of
+    /// [`Self::data_type`] or has nulls.
+    fn extend_from_array(array: &dyn Array, out: &mut Vec<Self>) -> Result<()> {
+        let array = downcast::<FixedSizeBinaryArray, Self>(array)?;
+        out.reserve(array.len());
+        for bytes in array.value_data().chunks_exact(size_of::<Self>()) {
+            out.push(Self::from_bytes(bytes)?);
+        }
+        Ok(())
+    }
+}
+
+/// Checks the type and nulls of `array` before downcasting it.
+fn downcast<A: Array + 'static, T: ArrowValue>(array: &dyn Array) -> Result<&A> {
+    if array.data_type() != &T::data_type() {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "expected an array of {}, got {}",
+            T::data_type(),
+            array.data_type()
+        ))
+        .into());
+    }
+    if array.null_count() != 0 {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "{} nulls in an array of {}, vecs can't hold nulls",
+            array.null_count(),
+            array.data_type()
+        ))
+        .into());
+    }
+    Ok(array
+        .as_any()
+        .downcast_ref::<A>()
+        .expect("data type checked above"))
+}
+
+macro_rules! impl_arrow_primitive {
+    ($($t:ty => $arrow:ty),* $(,)?) => {
+        $(
+            impl ArrowValue for $t {
+                #[inline]
+                fn data_type() -> DataType {
+                    <$arrow as ArrowPrimitiveType>::DATA_TYPE
+                }
+
+                #[inline]
+                fn into_array(values: Vec<Self>) -> ArrayRef {
+                    Arc::new(PrimitiveArray::<$arrow>::new(ScalarBuffer::from(values), None))
+                }
+
+                #[inline]
+                fn extend_from_array(array: &dyn Array, out: &mut Vec<Self>) -> Result<()> {
+                    let array = downcast::<PrimitiveArray<$arrow>, Self>(array)?;
+                    out.extend_from_slice(array.values());
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_arrow_primitive!(
+    u8 => UInt8Type,
+    u16 => UInt16Type,
+    u32 => UInt32Type,
+    u64 => UInt64Type,
+    i8 => Int8Type,
+    i16 => Int16Type,
+    i32 => Int32Type,
+    i64 => Int64Type,
+    f32 => Float32Type,
+    f64 => Float64Type,
+);
+
+impl ArrowValue for bool {
+    #[inline]
+    fn data_type() -> DataType {
+        DataType::Boolean
+    }
+
+    fn into_array(values: Vec<Self>) -> ArrayRef {
+        Arc::new(BooleanArray::from(values))
+    }
+
+    fn extend_from_array(array: &dyn Array, out: &mut Vec<Self>) -> Result<()> {
+        let array = downcast::<BooleanArray, Self>(array)?;
+        out.extend(array.values().iter());
+        Ok(())
+    }
+}
+
+impl<const N: usize> ArrowValue for [u8; N] {}
@@ -47,6 +47,11 @@ impl<'a> ChangeCursor<'a> {
         Ok(vals)
     }
 
+    /// Whether every byte was read.
+    pub fn is_empty(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
     fn check_remaining(&self, len: usize) -> Result<()> {
         let end = self.pos.checked_add(len).ok_or(Error::Overflow)?;
         if end > self.bytes.len() {
@@ -26,6 +26,7 @@ impl Bytes for Format {
             64 => Ok(Self::Pco),
             65 => Ok(Self::LZ4),
             66 => Ok(Self::Zstd),
+            67 => Ok(Self::Monotonic),
             b => Err(Error::InvalidFormat(b)),
         }
     }
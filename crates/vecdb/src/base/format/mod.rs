@@ -1,3 +1,7 @@
+use std::{fmt, str::FromStr};
+
+use crate::{Error, Version};
+
 mod bytes;
 
 /// Storage format selection for stored vectors.
@@ -15,9 +19,30 @@ pub enum Format {
     LZ4 = 65,
     /// Zstd compression (highest compression ratio, slower).
     Zstd = 66,
+    /// Elias-Fano encoding for monotonically non-decreasing integer sequences.
+    Monotonic = 67,
 }
 
 impl Format {
+    /// Version the storage layout of this format adds to the version a vec is imported with.
+    /// Headers store the sum (twice the layout version for forced imports), which is what
+    /// [`AnyVec::version`](crate::AnyVec::version) returns.
+    #[inline]
+    pub const fn layout_version(&self) -> Version {
+        match self {
+            Self::Bytes | Self::ZeroCopy => Version::ONE,
+            Self::Pco | Self::LZ4 | Self::Zstd | Self::Monotonic => Version::new(3),
+        }
+    }
+
+    /// Version a vec stored as `vec_version` is imported with, `None` if it's lower than
+    /// the layout version, counted twice for forced imports.
+    #[inline]
+    pub(crate) fn import_version(&self, vec_version: Version, forced: bool) -> Option<Version> {
+        let layout = u32::from(self.layout_version()) * if forced { 2 } else { 1 };
+        u32::from(vec_version).checked_sub(layout).map(Version::new)
+    }
+
     #[inline]
     pub fn is_raw(&self) -> bool {
         matches!(self, Self::ZeroCopy | Self::Bytes)
@@ -25,7 +50,7 @@ impl Format {
 
     #[inline]
     pub fn is_compressed(&self) -> bool {
-        matches!(self, Self::Pco | Self::LZ4 | Self::Zstd)
+        matches!(self, Self::Pco | Self::LZ4 | Self::Zstd | Self::Monotonic)
     }
 
     #[inline]
@@ -52,4 +77,39 @@ impl Format {
     pub fn is_zstd(&self) -> bool {
         *self == Self::Zstd
     }
+
+    #[inline]
+    pub fn is_monotonic(&self) -> bool {
+        *self == Self::Monotonic
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Bytes => "bytes",
+            Self::ZeroCopy => "zerocopy",
+            Self::Pco => "pco",
+            Self::LZ4 => "lz4",
+            Self::Zstd => "zstd",
+            Self::Monotonic => "monotonic",
+        })
+    }
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    /// Parses the names written by [`Display`](fmt::Display), ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_ascii_lowercase().as_str() {
+            "bytes" => Self::Bytes,
+            "zerocopy" => Self::ZeroCopy,
+            "pco" => Self::Pco,
+            "lz4" => Self::LZ4,
+            "zstd" => Self::Zstd,
+            "monotonic" => Self::Monotonic,
+            _ => return Err(Error::InvalidArgument("unknown format")),
+        })
+    }
 }
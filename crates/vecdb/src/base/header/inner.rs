@@ -2,7 +2,7 @@ use rawdb::Region;
 
 use crate::{Bytes, Error, Result, Stamp, Version};
 
-use super::{super::Format, HEADER_OFFSET, HEADER_VERSION};
+use super::{super::Format, HEADER_MAGIC, HEADER_OFFSET, HEADER_VERSION};
 
 #[derive(Debug, Clone)]
 #[repr(C)]
@@ -12,16 +12,47 @@ pub(super) struct HeaderInner {
     pub computed_version: Version,
     pub stamp: Stamp,
     pub format: Format,
+    /// log2 of the maximum uncompressed page size of compressed vecs, 0 if unset.
+    pub page_size_log2: u8,
+    /// Page metadata flags of compressed vecs, see [`Self::PAGE_CHECKSUMS`] and [`Self::PAGE_STATS`],
+    /// and [`Self::FORCED_LAYOUT`].
+    pub flags: u8,
+    /// Fingerprint of the value and index types, see [`super::value_type_fingerprint`].
+    /// 0 for vecs created before it was stored.
+    pub value_type: u32,
 }
 
+/// Byte holding the header version, after the fields.
+const HEADER_VERSION_AT: usize = 27;
+/// CRC32C of every byte before it.
+const CHECKSUM_AT: usize = 28;
+const _: () = assert!(CHECKSUM_AT + size_of::<u32>() == HEADER_OFFSET);
+
 impl HeaderInner {
-    pub fn create_and_write(region: &Region, vec_version: Version, format: Format) -> Result<Self> {
+    /// Pages store a CRC32C checksum of their data.
+    pub const PAGE_CHECKSUMS: u8 = 1;
+    /// Sealed pages store their min, max and sum.
+    pub const PAGE_STATS: u8 = 1 << 1;
+    /// The vec was imported by a forced import, which adds the layout version twice.
+    pub const FORCED_LAYOUT: u8 = 1 << 2;
+
+    pub fn create_and_write(
+        region: &Region,
+        vec_version: Version,
+        format: Format,
+        page_size: Option<usize>,
+        flags: u8,
+        value_type: u32,
+    ) -> Result<Self> {
         let header = Self {
             header_version: HEADER_VERSION,
             vec_version,
             computed_version: Version::default(),
             stamp: Stamp::default(),
             format,
+            page_size_log2: page_size.map_or(0, |size| size.ilog2() as u8),
+            flags,
+            value_type,
         };
         header.write(region)?;
         Ok(header)
@@ -32,11 +63,8 @@ impl HeaderInner {
         Ok(())
     }
 
-    pub fn import_and_verify(
-        region: &Region,
-        vec_version: Version,
-        format: Format,
-    ) -> Result<Self> {
+    /// Reads the header of `region` without checking it against a vec type.
+    pub fn read(region: &Region) -> Result<Self> {
         let len = region.meta().len();
 
         if len < HEADER_OFFSET {
@@ -48,14 +76,31 @@ impl HeaderInner {
 
         let reader = region.create_reader();
         let vec = reader.unchecked_read(0, HEADER_OFFSET);
-        let header = HeaderInner::from_bytes(vec)?;
+        let name = || region.meta().id().to_string();
+        let header = HeaderInner::from_bytes(vec).map_err(|e| match e {
+            Error::NotAVec { .. } => Error::NotAVec { name: name() },
+            Error::CorruptedHeader { .. } => Error::CorruptedHeader { name: name() },
+            e => e,
+        })?;
 
-        if header.header_version != HEADER_VERSION {
+        if header.header_version > HEADER_VERSION {
             return Err(Error::DifferentVersion {
                 received: header.header_version,
                 expected: HEADER_VERSION,
             });
         }
+
+        Ok(header)
+    }
+
+    pub fn import_and_verify(
+        region: &Region,
+        vec_version: Version,
+        format: Format,
+        value_type: u32,
+    ) -> Result<Self> {
+        let header = Self::read(region)?;
+
         if header.vec_version != vec_version {
             return Err(Error::DifferentVersion {
                 received: header.vec_version,
@@ -70,15 +115,22 @@ impl HeaderInner {
             });
         }
 
+        // 0 for vecs created before it was stored, they take it in `Header`
+        if header.value_type != 0 && header.value_type != value_type {
+            return Err(Error::DifferentValueType {
+                received: header.value_type,
+                expected: value_type,
+            });
+        }
+
         Ok(header)
     }
 
     fn to_bytes(&self) -> [u8; HEADER_OFFSET] {
         let mut buf = [0u8; HEADER_OFFSET];
         let mut pos = 0;
-        let hv = self.header_version.to_bytes();
-        buf[pos..pos + hv.len()].copy_from_slice(&hv);
-        pos += hv.len();
+        buf[pos..pos + HEADER_MAGIC.len()].copy_from_slice(&HEADER_MAGIC);
+        pos += HEADER_MAGIC.len();
         let vv = self.vec_version.to_bytes();
         buf[pos..pos + vv.len()].copy_from_slice(&vv);
         pos += vv.len();
@@ -90,7 +142,14 @@ impl HeaderInner {
         pos += s.len();
         let f = self.format.to_bytes();
         buf[pos..pos + f.len()].copy_from_slice(&f);
-        // remaining bytes are already zero (padding)
+        pos += f.len();
+        buf[pos] = self.page_size_log2;
+        buf[pos + 1] = self.flags;
+        pos += 2;
+        buf[pos..pos + 4].copy_from_slice(&self.value_type.to_le_bytes());
+        buf[HEADER_VERSION_AT] = u32::from(self.header_version) as u8;
+        let checksum = crc32c::crc32c(&buf[..CHECKSUM_AT]);
+        buf[CHECKSUM_AT..].copy_from_slice(&checksum.to_le_bytes());
         buf
     }
 
@@ -102,17 +161,51 @@ impl HeaderInner {
                 received: len,
             });
         }
-        let header_version = Version::from_bytes(&bytes[0..4])?;
+
+        // Headers before THREE start with their version instead of the magic
+        let header_version = if bytes[..HEADER_MAGIC.len()] == HEADER_MAGIC {
+            let checksum =
+                u32::from_le_bytes(bytes[CHECKSUM_AT..HEADER_OFFSET].try_into().unwrap());
+            if crc32c::crc32c(&bytes[..CHECKSUM_AT]) != checksum {
+                return Err(Error::CorruptedHeader {
+                    name: String::new(),
+                });
+            }
+            Version::new(u32::from(bytes[HEADER_VERSION_AT]))
+        } else {
+            match Version::from_bytes(&bytes[0..4])? {
+                // Same fields, without magic and checksum
+                Version::TWO => Version::TWO,
+                Version::ONE => {
+                    return Err(Error::OlderHeaderVersion {
+                        received: Version::ONE,
+                        expected: HEADER_VERSION,
+                    });
+                }
+                _ => {
+                    return Err(Error::NotAVec {
+                        name: String::new(),
+                    });
+                }
+            }
+        };
+
         let vec_version = Version::from_bytes(&bytes[4..8])?;
         let computed_version = Version::from_bytes(&bytes[8..12])?;
         let stamp = Stamp::from_bytes(&bytes[12..20])?;
         let format = Format::from_bytes(&bytes[20..21])?;
+        let page_size_log2 = bytes[21];
+        let flags = bytes[22];
+        let value_type = u32::from_le_bytes(bytes[23..27].try_into().unwrap());
         Ok(Self {
             header_version,
             vec_version,
             computed_version,
             stamp,
             format,
+            page_size_log2,
+            flags,
+            value_type,
         })
     }
 }
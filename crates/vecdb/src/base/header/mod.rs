@@ -4,16 +4,40 @@ use parking_lot::RwLock;
 use rawdb::Region;
 
 mod inner;
+mod v1;
 
 use inner::HeaderInner;
 
-use crate::{Result, Stamp, Version};
+pub use v1::*;
+
+use crate::{Result, Stamp, VecIndex, VecValue, Version, short_type_name};
 
 use super::Format;
 
-const HEADER_VERSION: Version = Version::TWO;
+const HEADER_VERSION: Version = Version::new(3);
+/// First bytes of every vec header since version THREE.
+const HEADER_MAGIC: [u8; 4] = *b"vecd";
 pub const HEADER_OFFSET: usize = size_of::<HeaderInner>();
 
+/// CRC32C of the value type name, its size and the index type name, never 0.
+///
+/// Stored in vec headers, where it can be compared to candidate types with [`Header::value_type`].
+pub fn value_type_fingerprint<I: VecIndex, T: VecValue>() -> u32 {
+    value_type_fingerprint_with(
+        short_type_name::<T>(),
+        size_of::<T>(),
+        short_type_name::<I>(),
+    )
+}
+
+/// [`value_type_fingerprint`] from type names, for types only known at runtime.
+pub fn value_type_fingerprint_with(value_type: &str, value_size: usize, index_type: &str) -> u32 {
+    let mut crc = crc32c::crc32c(value_type.as_bytes());
+    crc = crc32c::crc32c_append(crc, &(value_size as u64).to_le_bytes());
+    crc = crc32c::crc32c_append(crc, index_type.as_bytes());
+    crc.max(1)
+}
+
 #[derive(Debug, Clone)]
 pub struct Header {
     inner: Arc<RwLock<HeaderInner>>,
@@ -21,8 +45,30 @@ pub struct Header {
 }
 
 impl Header {
-    pub fn create_and_write(region: &Region, vec_version: Version, format: Format) -> Result<Self> {
-        let inner = HeaderInner::create_and_write(region, vec_version, format)?;
+    pub fn create_and_write(
+        region: &Region,
+        vec_version: Version,
+        format: Format,
+        page_size: Option<usize>,
+        page_checksums: bool,
+        page_stats: bool,
+        value_type: u32,
+    ) -> Result<Self> {
+        let mut flags = 0;
+        if page_checksums {
+            flags |= HeaderInner::PAGE_CHECKSUMS;
+        }
+        if page_stats {
+            flags |= HeaderInner::PAGE_STATS;
+        }
+        let inner = HeaderInner::create_and_write(
+            region,
+            vec_version,
+            format,
+            page_size,
+            flags,
+            value_type,
+        )?;
         Ok(Self {
             inner: Arc::new(RwLock::new(inner)),
             modified: false,
@@ -33,10 +79,25 @@ impl Header {
         region: &Region,
         vec_version: Version,
         format: Format,
+        value_type: u32,
     ) -> Result<Self> {
-        let inner = HeaderInner::import_and_verify(region, vec_version, format)?;
+        let mut inner = HeaderInner::import_and_verify(region, vec_version, format, value_type)?;
+        // Older headers and vecs created before the fingerprint was stored
+        // are upgraded on their next write
+        let modified = inner.header_version != HEADER_VERSION || inner.value_type == 0;
+        inner.header_version = HEADER_VERSION;
+        inner.value_type = value_type;
         Ok(Self {
             inner: Arc::new(RwLock::new(inner)),
+            modified,
+        })
+    }
+
+    /// Reads the header of `region` as is, for tools that inspect vecs without knowing their
+    /// types. Fails like an import on regions that aren't vecs or have a damaged header.
+    pub fn read(region: &Region) -> Result<Self> {
+        Ok(Self {
+            inner: Arc::new(RwLock::new(HeaderInner::read(region)?)),
             modified: false,
         })
     }
@@ -77,6 +138,82 @@ impl Header {
         self.inner.read().stamp
     }
 
+    #[inline(always)]
+    pub fn format(&self) -> Format {
+        self.inner.read().format
+    }
+
+    /// Fingerprint of the value and index types, see [`value_type_fingerprint`].
+    /// 0 for vecs created before it was stored.
+    #[inline(always)]
+    pub fn value_type(&self) -> u32 {
+        self.inner.read().value_type
+    }
+
+    /// Maximum uncompressed page size persisted by compressed vecs.
+    /// `None` for raw vecs and for compressed vecs created before it was configurable.
+    #[inline(always)]
+    pub fn page_size(&self) -> Option<usize> {
+        match self.inner.read().page_size_log2 {
+            0 => None,
+            log2 => Some(1 << log2),
+        }
+    }
+
+    /// Whether pages of compressed vecs store a checksum of their data.
+    #[inline(always)]
+    pub fn page_checksums(&self) -> bool {
+        self.inner.read().flags & HeaderInner::PAGE_CHECKSUMS != 0
+    }
+
+    /// Whether sealed pages of compressed vecs store their min, max and sum.
+    #[inline(always)]
+    pub fn page_stats(&self) -> bool {
+        self.inner.read().flags & HeaderInner::PAGE_STATS != 0
+    }
+
+    /// Whether the vec was imported by a forced import, whose stored version adds the layout
+    /// version twice. Unset for vecs created by forced imports before it was stored, until
+    /// they are forced imported again.
+    #[inline(always)]
+    pub fn forced_layout(&self) -> bool {
+        self.inner.read().flags & HeaderInner::FORCED_LAYOUT != 0
+    }
+
+    /// Version the vec is imported with: the stored one minus the
+    /// [layout version](Format::layout_version), twice if [`Self::forced_layout`].
+    /// `None` if the stored version is lower.
+    pub fn import_version(&self) -> Option<Version> {
+        let inner = self.inner.read();
+        inner.format.import_version(
+            inner.vec_version,
+            inner.flags & HeaderInner::FORCED_LAYOUT != 0,
+        )
+    }
+
+    /// Marks pages as storing checksums, once every existing page has one.
+    pub fn enable_page_checksums(&mut self) {
+        self.add_flag(HeaderInner::PAGE_CHECKSUMS);
+    }
+
+    /// Marks sealed pages as storing their stats, once every existing one has them.
+    pub fn enable_page_stats(&mut self) {
+        self.add_flag(HeaderInner::PAGE_STATS);
+    }
+
+    /// Marks the vec as imported by a forced import.
+    pub fn enable_forced_layout(&mut self) {
+        self.add_flag(HeaderInner::FORCED_LAYOUT);
+    }
+
+    fn add_flag(&mut self, flag: u8) {
+        let mut inner = self.inner.write();
+        if inner.flags & flag == 0 {
+            self.modified = true;
+            inner.flags |= flag;
+        }
+    }
+
     pub fn write(&mut self, region: &Region) -> Result<()> {
         self.inner.read().write(region)?;
         self.modified = false;
@@ -0,0 +1,57 @@
+use rawdb::Region;
+
+use crate::{Bytes, Error, Result, Stamp, Version};
+
+use super::super::Format;
+
+/// Header of vecs written with header version ONE, before versions were stored as `u32`.
+///
+/// Such vecs can't be imported, their values start at [`Self::OFFSET`] instead of
+/// [`HEADER_OFFSET`](super::HEADER_OFFSET). This is meant for upgrade tooling that
+/// reads their values and writes them to a new vec.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeaderV1 {
+    pub vec_version: Version,
+    pub computed_version: Version,
+    pub stamp: Stamp,
+    pub format: Format,
+}
+
+impl HeaderV1 {
+    /// Size of the header, including its padding. Values are stored right after it.
+    pub const OFFSET: usize = 64;
+
+    pub fn read(region: &Region) -> Result<Self> {
+        let len = region.meta().len();
+        if len < Self::OFFSET {
+            return Err(Error::WrongLength {
+                expected: Self::OFFSET,
+                received: len,
+            });
+        }
+
+        let reader = region.create_reader();
+        let bytes = reader.unchecked_read(0, Self::OFFSET);
+
+        let header_version = u64::from_bytes(&bytes[0..8])?;
+        if header_version != 1 {
+            return Err(Error::DifferentVersion {
+                received: Self::version(header_version)?,
+                expected: Version::ONE,
+            });
+        }
+
+        Ok(Self {
+            vec_version: Self::version(u64::from_bytes(&bytes[8..16])?)?,
+            computed_version: Self::version(u64::from_bytes(&bytes[16..24])?)?,
+            stamp: Stamp::from_bytes(&bytes[24..32])?,
+            format: Format::from_bytes(&bytes[32..33])?,
+        })
+    }
+
+    fn version(v: u64) -> Result<Version> {
+        u32::try_from(v)
+            .map(Version::new)
+            .map_err(|_| Error::Overflow)
+    }
+}
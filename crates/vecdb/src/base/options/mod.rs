@@ -15,6 +15,19 @@ pub struct ImportOptions<'a> {
     pub version: Version,
     /// Number of stamped change files to keep for rollback support (0 to disable).
     pub saved_stamped_changes: u16,
+    /// Compression level for compressed vecs (`None` uses the format's default).
+    /// Only affects writes; pages decode the same regardless of level.
+    pub compression_level: Option<i32>,
+    /// Maximum uncompressed page size in bytes for compressed vecs (`None` keeps the
+    /// persisted size, or 16 KiB for new vecs). Must be a power of two.
+    pub page_size: Option<usize>,
+    /// Store a CRC32C checksum per page of compressed vecs, verified on every page read.
+    /// Reopening an existing vec without them adds them to its pages.
+    pub page_checksums: bool,
+    /// Store the min, max and sum of every sealed page of compressed vecs, letting range
+    /// aggregates and predicate scans skip decoding whole pages. Only supported by `PcoVec`.
+    /// Reopening an existing vec without them computes them for its sealed pages.
+    pub page_stats: bool,
 }
 
 impl<'a> ImportOptions<'a> {
@@ -24,6 +37,10 @@ impl<'a> ImportOptions<'a> {
             name,
             version,
             saved_stamped_changes: 0,
+            compression_level: None,
+            page_size: None,
+            page_checksums: false,
+            page_stats: false,
         }
     }
 
@@ -31,4 +48,24 @@ impl<'a> ImportOptions<'a> {
         self.saved_stamped_changes = num;
         self
     }
+
+    pub fn with_compression_level(mut self, level: i32) -> Self {
+        self.compression_level = Some(level);
+        self
+    }
+
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    pub fn with_page_checksums(mut self, enabled: bool) -> Self {
+        self.page_checksums = enabled;
+        self
+    }
+
+    pub fn with_page_stats(mut self, enabled: bool) -> Self {
+        self.page_stats = enabled;
+        self
+    }
 }
@@ -2,9 +2,12 @@ use std::{fs, marker::PhantomData, ops::Deref, path::PathBuf, sync::Arc};
 
 use rawdb::Database;
 
-use crate::{Error, Result, Stamp, VecIndex, VecValue};
+use crate::{Error, Result, Stamp, VecIndex, VecValue, convert::finish_interrupted_swap};
 
-use super::{Format, HEADER_OFFSET, Header, ImportOptions, ReadOnlyBaseVec, SharedLen, WithPrev};
+use super::{
+    Format, HEADER_OFFSET, Header, ImportOptions, ReadOnlyBaseVec, SharedLen, WithPrev,
+    value_type_fingerprint,
+};
 
 /// Base storage vector with fields common to all stored vector implementations.
 ///
@@ -33,9 +36,10 @@ where
     T: VecValue,
 {
     pub fn import(options: ImportOptions, format: Format) -> Result<Self> {
-        let region = options
-            .db
-            .create_region_if_needed(&vec_region_name_with::<I>(options.name))?;
+        let id = vec_region_name_with::<I>(options.name);
+        // A conversion stopped while swapping regions is finished before anything is created
+        finish_interrupted_swap(options.db, &id)?;
+        let region = options.db.create_region_if_needed(&id)?;
 
         let region_len = region.meta().len();
         if region_len > 0 && region_len < HEADER_OFFSET {
@@ -45,10 +49,22 @@ where
             });
         }
 
+        let value_type = value_type_fingerprint::<I, T>();
         let header = if region_len == 0 {
-            Header::create_and_write(&region, options.version, format)?
+            let page_size = options.page_size.filter(|_| format.is_compressed());
+            let page_checksums = options.page_checksums && format.is_compressed();
+            let page_stats = options.page_stats && format.is_compressed();
+            Header::create_and_write(
+                &region,
+                options.version,
+                format,
+                page_size,
+                page_checksums,
+                page_stats,
+                value_type,
+            )?
         } else {
-            Header::import_and_verify(&region, options.version, format)?
+            Header::import_and_verify(&region, options.version, format, value_type)?
         };
 
         Ok(Self {
@@ -138,7 +138,12 @@ where
     }
 
     pub fn find_rollback_files(&self) -> Result<BTreeMap<Stamp, PathBuf>> {
-        Ok(fs::read_dir(self.changes_path())?
+        let path = self.changes_path();
+        // Vecs that never saved changes have no directory
+        if !path.is_dir() {
+            return Ok(BTreeMap::new());
+        }
+        Ok(fs::read_dir(path)?
             .filter_map(|entry| {
                 let path = entry.ok()?.path();
                 let stamp = Stamp::from(path.file_name()?.to_str()?.parse::<u64>().ok()?);
@@ -1,32 +1,58 @@
+use std::{array, slice};
+
 use crate::{Error, Result};
 
-use super::Bytes;
+use super::{Bytes, Niche, SizedBytes};
 
-macro_rules! impl_bytes_for_array {
-    ($($n:expr),*) => {
-        $(
-            impl Bytes for [u8; $n] {
-                type Array = [u8; $n];
-                const IS_NATIVE_LAYOUT: bool = true;
+impl<T: Bytes, const N: usize> Bytes for [T; N] {
+    type Array = SizedBytes<Self>;
+    const IS_NATIVE_LAYOUT: bool = T::IS_NATIVE_LAYOUT;
+    const NICHE: Option<Niche> = if N > 0 { T::NICHE } else { None };
 
-                #[inline]
-                fn to_bytes(&self) -> Self::Array {
-                    *self
-                }
+    #[inline]
+    fn to_bytes(&self) -> Self::Array {
+        let mut array = SizedBytes::zeroed();
+        self.write_bytes_to(array.as_mut_slice());
+        array
+    }
 
-                #[inline]
-                fn from_bytes(bytes: &[u8]) -> Result<Self> {
-                    bytes.try_into().map_err(|_| Error::WrongLength {
-                        expected: $n,
-                        received: bytes.len(),
-                    })
-                }
+    /// Copies the whole array at once when its values have a native layout.
+    #[inline]
+    fn write_bytes_to(&self, dst: &mut [u8]) {
+        if T::IS_NATIVE_LAYOUT {
+            // SAFETY: native values are their own bytes and arrays have no padding
+            let bytes =
+                unsafe { slice::from_raw_parts(self.as_ptr().cast::<u8>(), size_of::<Self>()) };
+            dst.copy_from_slice(bytes);
+        } else {
+            for (value, dst) in self.iter().zip(dst.chunks_exact_mut(size_of::<T>())) {
+                value.write_bytes_to(dst);
             }
-        )*
-    };
-}
+        }
+    }
 
-impl_bytes_for_array!(
-    1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26,
-    27, 28, 29, 30, 31, 32, 33, 64, 65
-);
+    #[inline]
+    fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != size_of::<Self>() {
+            return Err(Error::WrongLength {
+                expected: size_of::<Self>(),
+                received: bytes.len(),
+            });
+        }
+        if T::IS_NATIVE_LAYOUT {
+            // SAFETY: the length is checked and native values are their own bytes
+            return Ok(unsafe { bytes.as_ptr().cast::<Self>().read_unaligned() });
+        }
+        let len = size_of::<T>();
+        let mut error = None;
+        let values = array::from_fn(|i| {
+            T::from_bytes(&bytes[i * len..(i + 1) * len])
+                .map_err(|e| error = Some(e))
+                .ok()
+        });
+        match error {
+            Some(e) => Err(e),
+            None => Ok(values.map(|value| value.unwrap())),
+        }
+    }
+}
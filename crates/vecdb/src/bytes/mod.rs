@@ -1,5 +1,14 @@
 mod array;
+mod niche;
+mod non_zero;
 mod numeric;
+mod option;
+mod primitive;
+mod sized;
+mod tuple;
+
+pub use niche::*;
+pub use sized::*;
 
 use crate::Result;
 
@@ -28,6 +37,10 @@ pub trait Bytes: Sized {
     /// `to_bytes`/`from_bytes` use little-endian format which matches native).
     const IS_NATIVE_LAYOUT: bool = false;
 
+    /// Bytes that no value serializes to, used to store `None` in `Option<Self>`
+    /// when it has no room for a tag byte (`NonZero*`, `bool`, `char`, ...).
+    const NICHE: Option<Niche> = None;
+
     /// Serializes this value to bytes.
     ///
     /// For numeric types, this uses little-endian byte order (via `to_le_bytes`).
@@ -37,4 +50,13 @@ pub trait Bytes: Sized {
     ///
     /// For numeric types, this uses little-endian byte order (via `from_le_bytes`).
     fn from_bytes(bytes: &[u8]) -> Result<Self>;
+
+    /// Serializes this value into `dst`, which is exactly `size_of::<Self>()` bytes long.
+    ///
+    /// Defaults to copying the bytes from `to_bytes`, overridden where building
+    /// `Self::Array` costs more than writing in place (`[T; N]`).
+    #[inline]
+    fn write_bytes_to(&self, dst: &mut [u8]) {
+        dst.copy_from_slice(self.to_bytes().as_ref());
+    }
 }
@@ -0,0 +1,33 @@
+/// Bytes that no value of a type has at `offset` of its serialized form.
+///
+/// Used to store `None` in `Option<T>` when it's no larger than `T`, so there's
+/// no room for a tag byte. The remaining bytes of `None` are zeros.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Niche {
+    pub offset: usize,
+    pub bytes: &'static [u8],
+}
+
+impl Niche {
+    pub const fn new(bytes: &'static [u8]) -> Self {
+        Self { offset: 0, bytes }
+    }
+
+    /// The same niche, for a value serialized `offset` bytes further.
+    pub const fn at(self, offset: usize) -> Self {
+        Self {
+            offset: self.offset + offset,
+            bytes: self.bytes,
+        }
+    }
+
+    #[inline]
+    pub fn write(&self, buf: &mut [u8]) {
+        buf[self.offset..self.offset + self.bytes.len()].copy_from_slice(self.bytes);
+    }
+
+    #[inline]
+    pub fn matches(&self, bytes: &[u8]) -> bool {
+        bytes[self.offset..self.offset + self.bytes.len()] == *self.bytes
+    }
+}
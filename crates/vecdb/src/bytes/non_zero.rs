@@ -0,0 +1,47 @@
+use std::num::{
+    NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize, NonZeroU8,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize,
+};
+
+use crate::{Error, Result};
+
+use super::{Bytes, Niche};
+
+macro_rules! impl_bytes_for_non_zero {
+    ($($t:ty => $inner:ty),*) => {
+        $(
+            impl Bytes for $t {
+                type Array = [u8; size_of::<$t>()];
+                const NICHE: Option<Niche> = Some(Niche::new(&[0; size_of::<$t>()]));
+
+                #[inline]
+                fn to_bytes(&self) -> Self::Array {
+                    self.get().to_le_bytes()
+                }
+
+                #[inline]
+                fn from_bytes(bytes: &[u8]) -> Result<Self> {
+                    <$t>::new(<$inner>::from_bytes(bytes)?).ok_or(Error::InvalidValue {
+                        name: stringify!($t),
+                        value: 0,
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_bytes_for_non_zero!(
+    NonZeroU8 => u8,
+    NonZeroU16 => u16,
+    NonZeroU32 => u32,
+    NonZeroU64 => u64,
+    NonZeroU128 => u128,
+    NonZeroUsize => usize,
+    NonZeroI8 => i8,
+    NonZeroI16 => i16,
+    NonZeroI32 => i32,
+    NonZeroI64 => i64,
+    NonZeroI128 => i128,
+    NonZeroIsize => isize
+);
@@ -0,0 +1,81 @@
+use crate::{Error, Result};
+
+use super::{Bytes, Niche, SizedBytes};
+
+/// `None` is stored as `T::NICHE` when `Option<T>` is no larger than `T`,
+/// otherwise values are prefixed with a tag byte: 0 for `None`, 1 for `Some`.
+impl<T: Bytes> Bytes for Option<T> {
+    type Array = SizedBytes<Self>;
+    // Unused tag, `Option<T>` already fills `T`'s niche otherwise
+    const NICHE: Option<Niche> = if size_of::<Option<T>>() > size_of::<T>() {
+        Some(Niche::new(&[2]))
+    } else {
+        None
+    };
+
+    #[inline]
+    fn to_bytes(&self) -> Self::Array {
+        let mut array = SizedBytes::zeroed();
+        self.write_bytes_to(array.as_mut_slice());
+        array
+    }
+
+    #[inline]
+    fn write_bytes_to(&self, dst: &mut [u8]) {
+        match (self, niche::<T>()) {
+            (Some(value), Some(_)) => value.write_bytes_to(dst),
+            (None, Some(niche)) => {
+                dst.fill(0);
+                niche.write(dst);
+            }
+            (Some(value), None) => {
+                let len = size_of::<T>();
+                dst[0] = 1;
+                value.write_bytes_to(&mut dst[1..1 + len]);
+                dst[1 + len..].fill(0);
+            }
+            (None, None) => dst.fill(0),
+        }
+    }
+
+    #[inline]
+    fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != size_of::<Self>() {
+            return Err(Error::WrongLength {
+                expected: size_of::<Self>(),
+                received: bytes.len(),
+            });
+        }
+        if let Some(niche) = niche::<T>() {
+            return if niche.matches(bytes) {
+                Ok(None)
+            } else {
+                T::from_bytes(bytes).map(Some)
+            };
+        }
+        match bytes[0] {
+            0 => Ok(None),
+            1 => T::from_bytes(&bytes[1..1 + size_of::<T>()]).map(Some),
+            value => Err(Error::InvalidValue {
+                name: "Option",
+                value: u64::from(value),
+            }),
+        }
+    }
+}
+
+/// Bytes of `None` when there's no room for a tag byte.
+#[inline(always)]
+fn niche<T: Bytes>() -> Option<Niche> {
+    const {
+        assert!(
+            size_of::<Option<T>>() > size_of::<T>() || T::NICHE.is_some(),
+            "Option<T> has no room for a tag byte and T has no Bytes::NICHE"
+        );
+    }
+    if size_of::<Option<T>>() == size_of::<T>() {
+        T::NICHE
+    } else {
+        None
+    }
+}
@@ -0,0 +1,45 @@
+use crate::{Error, Result};
+
+use super::{Bytes, Niche};
+
+impl Bytes for bool {
+    type Array = [u8; 1];
+    const NICHE: Option<Niche> = Some(Niche::new(&[2]));
+
+    #[inline]
+    fn to_bytes(&self) -> Self::Array {
+        [u8::from(*self)]
+    }
+
+    #[inline]
+    fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        match u8::from_bytes(bytes)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            value => Err(Error::InvalidValue {
+                name: "bool",
+                value: u64::from(value),
+            }),
+        }
+    }
+}
+
+impl Bytes for char {
+    type Array = [u8; 4];
+    // First value past the last scalar value
+    const NICHE: Option<Niche> = Some(Niche::new(&0x110000u32.to_le_bytes()));
+
+    #[inline]
+    fn to_bytes(&self) -> Self::Array {
+        u32::from(*self).to_le_bytes()
+    }
+
+    #[inline]
+    fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let value = u32::from_bytes(bytes)?;
+        char::from_u32(value).ok_or(Error::InvalidValue {
+            name: "char",
+            value: u64::from(value),
+        })
+    }
+}
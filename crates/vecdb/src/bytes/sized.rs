@@ -0,0 +1,52 @@
+use std::marker::PhantomData;
+
+/// Values up to this size are kept inline, larger ones on the heap.
+const INLINE: usize = 64;
+
+/// Zero-initialized bytes with the size of `T`.
+///
+/// `Array` of the generic `Bytes` impls (`Option<T>`, tuples, `[T; N]`), whose size
+/// can't be written as `[u8; N]` on stable. Values are written at the start and
+/// the remaining bytes stay zero.
+pub struct SizedBytes<T> {
+    buf: Buf,
+    _marker: PhantomData<fn() -> T>,
+}
+
+enum Buf {
+    Inline([u8; INLINE]),
+    Heap(Box<[u8]>),
+}
+
+impl<T> SizedBytes<T> {
+    #[inline]
+    pub fn zeroed() -> Self {
+        let buf = if size_of::<T>() <= INLINE {
+            Buf::Inline([0; INLINE])
+        } else {
+            Buf::Heap(vec![0; size_of::<T>()].into_boxed_slice())
+        };
+        Self {
+            buf,
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        match &mut self.buf {
+            Buf::Inline(bytes) => &mut bytes[..size_of::<T>()],
+            Buf::Heap(bytes) => bytes,
+        }
+    }
+}
+
+impl<T> AsRef<[u8]> for SizedBytes<T> {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        match &self.buf {
+            Buf::Inline(bytes) => &bytes[..size_of::<T>()],
+            Buf::Heap(bytes) => bytes,
+        }
+    }
+}
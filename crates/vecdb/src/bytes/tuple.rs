@@ -0,0 +1,80 @@
+use std::mem::offset_of;
+
+use crate::{Error, Result};
+
+use super::{Bytes, Niche, SizedBytes};
+
+/// Elements are concatenated in order, then zero-padded to the size of the tuple.
+macro_rules! impl_bytes_for_tuple {
+    ($($t:ident $v:ident $i:tt),+) => {
+        impl<$($t: Bytes),+> Bytes for ($($t,)+) {
+            type Array = SizedBytes<Self>;
+            // Tuples may reorder their elements, so check they're in order without padding
+            const IS_NATIVE_LAYOUT: bool = {
+                let mut native = true;
+                let mut pos = 0;
+                $(
+                    native &= $t::IS_NATIVE_LAYOUT && offset_of!(Self, $i) == pos;
+                    pos += size_of::<$t>();
+                )+
+                native && pos == size_of::<Self>()
+            };
+            // Niche of the first element that has one
+            const NICHE: Option<Niche> = {
+                let mut niche = None;
+                let mut pos = 0;
+                $(
+                    if let (None, Some(inner)) = (niche, $t::NICHE) {
+                        niche = Some(inner.at(pos));
+                    }
+                    pos += size_of::<$t>();
+                )+
+                let _ = pos;
+                niche
+            };
+
+            #[inline]
+            fn to_bytes(&self) -> Self::Array {
+                let mut array = SizedBytes::zeroed();
+                self.write_bytes_to(array.as_mut_slice());
+                array
+            }
+
+            #[inline]
+            fn write_bytes_to(&self, dst: &mut [u8]) {
+                let mut pos = 0;
+                $(
+                    let len = size_of::<$t>();
+                    self.$i.write_bytes_to(&mut dst[pos..pos + len]);
+                    pos += len;
+                )+
+                dst[pos..].fill(0);
+            }
+
+            #[inline]
+            fn from_bytes(bytes: &[u8]) -> Result<Self> {
+                if bytes.len() != size_of::<Self>() {
+                    return Err(Error::WrongLength {
+                        expected: size_of::<Self>(),
+                        received: bytes.len(),
+                    });
+                }
+                let mut pos = 0;
+                $(
+                    let len = size_of::<$t>();
+                    let $v = $t::from_bytes(&bytes[pos..pos + len])?;
+                    pos += len;
+                )+
+                let _ = pos;
+                Ok(($($v,)+))
+            }
+        }
+    };
+}
+
+impl_bytes_for_tuple!(A a 0);
+impl_bytes_for_tuple!(A a 0, B b 1);
+impl_bytes_for_tuple!(A a 0, B b 1, C c 2);
+impl_bytes_for_tuple!(A a 0, B b 1, C c 2, D d 3);
+impl_bytes_for_tuple!(A a 0, B b 1, C c 2, D d 3, E e 4);
+impl_bytes_for_tuple!(A a 0, B b 1, C c 2, D d 3, E e 4, F f 5);
@@ -0,0 +1,179 @@
+use crate::{Error, Formattable, Result};
+
+/// Value type of a [`DynVec`](super::DynVec), given at runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DynType {
+    /// Unsigned integer of 1, 2, 4 or 8 bytes.
+    Unsigned(usize),
+    /// Signed integer of 1, 2, 4 or 8 bytes.
+    Signed(usize),
+    /// Float of 4 or 8 bytes.
+    Float(usize),
+    Bool,
+    /// Values of a fixed number of bytes, read as is.
+    Bytes(usize),
+}
+
+impl DynType {
+    /// The type of a primitive from its name, e.g. `"u32"`, `"usize"` or `"f64"`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "u8" => Self::Unsigned(1),
+            "u16" => Self::Unsigned(2),
+            "u32" => Self::Unsigned(4),
+            "u64" => Self::Unsigned(8),
+            "usize" => Self::Unsigned(size_of::<usize>()),
+            "i8" => Self::Signed(1),
+            "i16" => Self::Signed(2),
+            "i32" => Self::Signed(4),
+            "i64" => Self::Signed(8),
+            "isize" => Self::Signed(size_of::<isize>()),
+            "f32" => Self::Float(4),
+            "f64" => Self::Float(8),
+            "bool" => Self::Bool,
+            _ => return None,
+        })
+    }
+
+    /// Size of a value in bytes.
+    pub fn size(&self) -> usize {
+        match *self {
+            Self::Unsigned(size) | Self::Signed(size) | Self::Float(size) | Self::Bytes(size) => {
+                size
+            }
+            Self::Bool => 1,
+        }
+    }
+
+    /// Name of the primitive, the fixed size one for integers of 8 bytes. `None` for bytes and
+    /// invalid sizes.
+    pub fn name(&self) -> Option<&'static str> {
+        Some(match *self {
+            Self::Unsigned(1) => "u8",
+            Self::Unsigned(2) => "u16",
+            Self::Unsigned(4) => "u32",
+            Self::Unsigned(8) => "u64",
+            Self::Signed(1) => "i8",
+            Self::Signed(2) => "i16",
+            Self::Signed(4) => "i32",
+            Self::Signed(8) => "i64",
+            Self::Float(4) => "f32",
+            Self::Float(8) => "f64",
+            Self::Bool => "bool",
+            _ => return None,
+        })
+    }
+
+    /// Whether [`Self::from_name`] returns this type for `name`.
+    pub fn is_named(&self, name: &str) -> bool {
+        Self::from_name(name) == Some(*self)
+    }
+
+    pub(super) fn validate(&self) -> Result<()> {
+        let valid = match *self {
+            Self::Unsigned(size) | Self::Signed(size) => matches!(size, 1 | 2 | 4 | 8),
+            Self::Float(size) => matches!(size, 4 | 8),
+            Self::Bool => true,
+            Self::Bytes(size) => size != 0,
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(Error::InvalidArgument("invalid value size for this type"))
+        }
+    }
+
+    /// Decodes one value of [`Self::size`] bytes, little-endian unless `native`.
+    pub(super) fn decode(&self, bytes: &[u8], native: bool) -> DynValue {
+        macro_rules! read {
+            ($t:ty) => {{
+                let array = bytes.try_into().unwrap();
+                if native {
+                    <$t>::from_ne_bytes(array)
+                } else {
+                    <$t>::from_le_bytes(array)
+                }
+            }};
+        }
+        match *self {
+            Self::Unsigned(1) => DynValue::Unsigned(read!(u8) as u64),
+            Self::Unsigned(2) => DynValue::Unsigned(read!(u16) as u64),
+            Self::Unsigned(4) => DynValue::Unsigned(read!(u32) as u64),
+            Self::Unsigned(_) => DynValue::Unsigned(read!(u64)),
+            Self::Signed(1) => DynValue::Signed(read!(i8) as i64),
+            Self::Signed(2) => DynValue::Signed(read!(i16) as i64),
+            Self::Signed(4) => DynValue::Signed(read!(i32) as i64),
+            Self::Signed(_) => DynValue::Signed(read!(i64)),
+            Self::Float(4) => DynValue::F32(read!(f32)),
+            Self::Float(_) => DynValue::F64(read!(f64)),
+            Self::Bool => DynValue::Bool(bytes[0] != 0),
+            Self::Bytes(_) => DynValue::Bytes(bytes.to_vec()),
+        }
+    }
+}
+
+/// A value read by a [`DynVec`](super::DynVec).
+#[derive(Debug, Clone, PartialEq)]
+pub enum DynValue {
+    Unsigned(u64),
+    Signed(i64),
+    /// Kept apart from [`Self::F64`] to format with the shortest `f32` representation.
+    F32(f32),
+    F64(f64),
+    Bool(bool),
+    Bytes(Vec<u8>),
+}
+
+impl DynValue {
+    /// The value as a float, `None` for bytes. Large integers lose precision.
+    pub fn to_f64(&self) -> Option<f64> {
+        Some(match *self {
+            Self::Unsigned(v) => v as f64,
+            Self::Signed(v) => v as f64,
+            Self::F32(v) => v as f64,
+            Self::F64(v) => v,
+            Self::Bool(v) => v as u8 as f64,
+            Self::Bytes(_) => return None,
+        })
+    }
+
+    /// The value as an integer, `None` for floats, bytes and unsigned values above `i64::MAX`.
+    pub fn to_i64(&self) -> Option<i64> {
+        match *self {
+            Self::Unsigned(v) => i64::try_from(v).ok(),
+            Self::Signed(v) => Some(v),
+            Self::Bool(v) => Some(v as i64),
+            Self::F32(_) | Self::F64(_) | Self::Bytes(_) => None,
+        }
+    }
+}
+
+impl Formattable for DynValue {
+    /// Bytes are written as lowercase hex.
+    fn write_to(&self, buf: &mut Vec<u8>) {
+        match self {
+            Self::Unsigned(v) => v.write_to(buf),
+            Self::Signed(v) => v.write_to(buf),
+            Self::F32(v) => v.write_to(buf),
+            Self::F64(v) => v.write_to(buf),
+            Self::Bool(v) => v.write_to(buf),
+            Self::Bytes(bytes) => {
+                const HEX: &[u8; 16] = b"0123456789abcdef";
+                for b in bytes {
+                    buf.push(HEX[(b >> 4) as usize]);
+                    buf.push(HEX[(b & 0xF) as usize]);
+                }
+            }
+        }
+    }
+
+    fn fmt_json(&self, buf: &mut Vec<u8>) {
+        if let Self::Bytes(_) = self {
+            buf.push(b'"');
+            self.write_to(buf);
+            buf.push(b'"');
+        } else {
+            self.write_to(buf);
+        }
+    }
+}
@@ -0,0 +1,391 @@
+use std::collections::BTreeSet;
+
+use rawdb::{Database, Reader, Region};
+
+#[cfg(feature = "lz4")]
+use crate::LZ4Strategy;
+#[cfg(feature = "pco")]
+use crate::PcodecStrategy;
+#[cfg(feature = "zstd")]
+use crate::ZstdStrategy;
+use crate::{
+    Bytes, CompressionStrategy, Dictionaries, EliasFanoStrategy, Error, Format, HEADER_OFFSET,
+    Header, MAX_UNCOMPRESSED_PAGE_SIZE, Page, Pages, Result, value_type_fingerprint_with,
+};
+
+use super::{CatalogEntry, DynType, DynValue};
+
+/// A vec whose value type is only known at runtime, opened from a [`CatalogEntry`] and a
+/// [`DynType`] for tools that read any vec.
+///
+/// Raw vecs are read from their region, compressed vecs page by page: LZ4 and Zstd pages
+/// are decoded as bytes whatever the type, Pco and Monotonic pages need a primitive of a
+/// type they support. Values at holes of raw vecs are read as stored.
+///
+/// Reads see the vec as it was when opened.
+#[derive(Debug, Clone)]
+pub struct DynVec {
+    name: String,
+    index: String,
+    format: Format,
+    value_type: DynType,
+    region: Region,
+    len: usize,
+    /// Holes region of raw vecs
+    holes: Option<Region>,
+    pages: Option<DynPages>,
+}
+
+#[derive(Debug, Clone)]
+struct DynPages {
+    region_name: String,
+    vec: Vec<Page>,
+    /// Number of values in every page but the last
+    per_page: usize,
+    /// Index of the first value of every page, and the length last
+    starts: Vec<usize>,
+    checksums: bool,
+    dictionaries: Dictionaries,
+}
+
+impl DynVec {
+    /// Opens the vec of `entry`, reading its values as `value_type`. Fails if the fingerprint
+    /// is the one of another primitive, unless reading bytes, or if the value size is known to
+    /// differ.
+    pub fn open(db: &Database, entry: &CatalogEntry, value_type: DynType) -> Result<Self> {
+        value_type.validate()?;
+        let size = value_type.size();
+
+        let name = entry.region_name();
+        let region = db
+            .get_region(&name)
+            .ok_or_else(|| Error::NotAVec { name: name.clone() })?;
+        let header = Header::read(&region)?;
+
+        // Any value can be read as bytes
+        if let Some(type_name) = entry.value_type_name
+            && !matches!(value_type, DynType::Bytes(_))
+            && !value_type.is_named(type_name)
+        {
+            return Err(Error::DifferentValueType {
+                received: header.value_type(),
+                expected: value_type.name().map_or(0, |type_name| {
+                    value_type_fingerprint_with(type_name, size, &entry.index)
+                }),
+            });
+        }
+        if let Some(expected) = entry.value_size
+            && expected != size
+        {
+            return Err(Error::WrongLength {
+                received: size,
+                expected,
+            });
+        }
+
+        let format = header.format();
+        let (len, holes, pages) = if format.is_raw() {
+            let len = region.meta().len().saturating_sub(HEADER_OFFSET) / size;
+            (len, db.get_region(&format!("{name}_holes")), None)
+        } else {
+            let checksums = header.page_checksums();
+            let stats_size = if header.page_stats() {
+                Pages::stats_size(size)
+            } else {
+                0
+            };
+            let region_name = format!("{name}_pages");
+            let vec = match db.get_region(&region_name) {
+                Some(region) => Pages::read_entries(&region, checksums, stats_size)?.0,
+                None => vec![],
+            };
+            let mut starts = Vec::with_capacity(vec.len() + 1);
+            starts.push(0);
+            for page in &vec {
+                starts.push(starts.last().unwrap() + page.values_count() as usize);
+            }
+            let page_size = header.page_size().unwrap_or(MAX_UNCOMPRESSED_PAGE_SIZE);
+            let pages = DynPages {
+                region_name,
+                vec,
+                per_page: page_size / size,
+                starts,
+                checksums,
+                dictionaries: Dictionaries::import(db, &format!("{name}_dict"))?,
+            };
+            (*pages.starts.last().unwrap(), None, Some(pages))
+        };
+
+        Ok(Self {
+            name: entry.name.clone(),
+            index: entry.index.clone(),
+            format,
+            value_type,
+            region,
+            len,
+            holes,
+            pages,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn index(&self) -> &str {
+        &self.index
+    }
+
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    pub fn value_type(&self) -> DynType {
+        self.value_type
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Deleted indices of raw vecs, whose values are still read as stored.
+    pub fn holes(&self) -> Result<BTreeSet<usize>> {
+        let Some(region) = &self.holes else {
+            return Ok(BTreeSet::new());
+        };
+        region
+            .create_reader()
+            .read_all()
+            .chunks(size_of::<usize>())
+            .map(usize::from_bytes)
+            .collect()
+    }
+
+    /// Checks the layout of the vec without decoding values: raw data aligned on values and
+    /// holes within the length, or pages within the region, of the page size, with their
+    /// dictionaries and, if the vec was created with them, matching checksums.
+    pub fn verify(&self) -> Result<()> {
+        let region_len = self.region.meta().len();
+        let corrupted = |name: &str, region_len| Error::CorruptedRegion {
+            name: name.to_string(),
+            region_len,
+        };
+
+        let Some(pages) = &self.pages else {
+            let name = self.region.meta().id().to_string();
+            if region_len < HEADER_OFFSET
+                || !(region_len - HEADER_OFFSET).is_multiple_of(self.value_type.size())
+            {
+                return Err(corrupted(&name, region_len));
+            }
+            if let Some(region) = &self.holes {
+                let holes_len = region.meta().len();
+                if !holes_len.is_multiple_of(size_of::<usize>())
+                    || self.holes()?.last().is_some_and(|&hole| hole >= self.len)
+                {
+                    return Err(corrupted(&format!("{name}_holes"), holes_len));
+                }
+            }
+            return Ok(());
+        };
+
+        let reader = self.region.create_reader();
+        for (page_index, page) in pages.vec.iter().enumerate() {
+            let is_last = page_index + 1 == pages.vec.len();
+            let values_count = page.values_count() as usize;
+            if page.start < HEADER_OFFSET as u64
+                || page.end() > region_len as u64
+                || values_count > pages.per_page
+                || (!is_last && values_count != pages.per_page)
+            {
+                return Err(corrupted(&pages.region_name, region_len));
+            }
+            if let Some(id) = page.dictionary_id()
+                && pages.dictionaries.get(id).is_none()
+            {
+                return Err(Error::MissingDictionary(id));
+            }
+            let data = reader.unchecked_read(page.start as usize, page.bytes as usize);
+            if pages.checksums && !page.checksum_matches(data) {
+                return Err(Error::ChecksumMismatch {
+                    name: pages.region_name.clone(),
+                    page: page_index,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Value bytes of `from..to`, clamped to the length, as stored: little-endian for every
+    /// format but ZeroCopy, which stores them in native order.
+    pub fn read_bytes_range(&self, from: usize, to: usize) -> Result<Vec<u8>> {
+        let to = to.min(self.len);
+        let from = from.min(to);
+        let size = self.value_type.size();
+        let reader = self.region.create_reader();
+
+        let Some(pages) = &self.pages else {
+            return Ok(reader
+                .unchecked_read(HEADER_OFFSET + from * size, (to - from) * size)
+                .to_vec());
+        };
+
+        let mut bytes = Vec::with_capacity((to - from) * size);
+        let mut index = from;
+        while index < to {
+            let page_index = pages.starts.partition_point(|&start| start <= index) - 1;
+            let start = pages.starts[page_index];
+            let end = pages.starts[page_index + 1].min(to);
+            let page = self.decode_page(pages, page_index, &reader)?;
+            bytes.extend_from_slice(&page[(index - start) * size..(end - start) * size]);
+            index = end;
+        }
+        Ok(bytes)
+    }
+
+    /// Values of `from..to`, clamped to the length.
+    pub fn read_range(&self, from: usize, to: usize) -> Result<Vec<DynValue>> {
+        let native = self.format.is_zerocopy();
+        Ok(self
+            .read_bytes_range(from, to)?
+            .chunks_exact(self.value_type.size())
+            .map(|bytes| self.value_type.decode(bytes, native))
+            .collect())
+    }
+
+    /// The value at `index`, `None` past the end.
+    pub fn get(&self, index: usize) -> Result<Option<DynValue>> {
+        Ok(self.read_range(index, index + 1)?.pop())
+    }
+
+    /// Values of `from..to` as floats, booleans as 0 and 1. Fails for bytes.
+    pub fn read_f64_range(&self, from: usize, to: usize) -> Result<Vec<f64>> {
+        if let DynType::Bytes(_) = self.value_type {
+            return Err(Error::InvalidArgument("bytes can't be read as numbers"));
+        }
+        Ok(self
+            .read_range(from, to)?
+            .iter()
+            .filter_map(DynValue::to_f64)
+            .collect())
+    }
+
+    /// Values of `from..to` as integers, booleans as 0 and 1. Fails for floats and bytes, and
+    /// on unsigned values above `i64::MAX`.
+    pub fn read_i64_range(&self, from: usize, to: usize) -> Result<Vec<i64>> {
+        if let DynType::Float(_) | DynType::Bytes(_) = self.value_type {
+            return Err(Error::InvalidArgument("only integers can be read as i64"));
+        }
+        self.read_range(from, to)?
+            .iter()
+            .map(|value| value.to_i64().ok_or(Error::Overflow))
+            .collect()
+    }
+
+    /// Value bytes of page `page_index`, checked against its checksum.
+    fn decode_page(&self, pages: &DynPages, page_index: usize, reader: &Reader) -> Result<Vec<u8>> {
+        let page = &pages.vec[page_index];
+        if page.end() as usize > reader.len() {
+            return Err(Error::CorruptedRegion {
+                name: self.region.meta().id().to_string(),
+                region_len: reader.len(),
+            });
+        }
+        let data = reader.unchecked_read(page.start as usize, page.bytes as usize);
+        if pages.checksums && !page.checksum_matches(data) {
+            return Err(Error::ChecksumMismatch {
+                name: pages.region_name.clone(),
+                page: page_index,
+            });
+        }
+
+        let values = page.values_count() as usize;
+        let expected_len = values * self.value_type.size();
+        let bytes = if page.is_raw() {
+            data.to_vec()
+        } else {
+            let dictionary = match page.dictionary_id() {
+                Some(id) => Some(
+                    pages
+                        .dictionaries
+                        .get(id)
+                        .ok_or(Error::MissingDictionary(id))?
+                        .as_ref(),
+                ),
+                None => None,
+            };
+            self.decompress(data, values, dictionary)?
+        };
+
+        if bytes.len() != expected_len {
+            return Err(Error::DecompressionMismatch {
+                expected_len: values,
+                actual_len: bytes.len() / self.value_type.size(),
+            });
+        }
+        Ok(bytes)
+    }
+
+    #[allow(unused_variables)]
+    fn decompress(&self, data: &[u8], values: usize, dictionary: Option<&[u8]>) -> Result<Vec<u8>> {
+        let len = values * self.value_type.size();
+        match self.format {
+            // Serialized values, decoded as bytes
+            #[cfg(feature = "lz4")]
+            Format::LZ4 => LZ4Strategy::<u8>::decompress(data, len),
+            #[cfg(feature = "zstd")]
+            Format::Zstd => match dictionary {
+                Some(dictionary) => {
+                    let mut bytes = Vec::with_capacity(len);
+                    ZstdStrategy::<u8>::decompress_with_dictionary_into(
+                        data, len, dictionary, &mut bytes,
+                    )?;
+                    Ok(bytes)
+                }
+                None => ZstdStrategy::<u8>::decompress(data, len),
+            },
+            #[cfg(feature = "pco")]
+            Format::Pco => match self.value_type {
+                DynType::Unsigned(1) => decompress_as::<u8, PcodecStrategy<u8>>(data, values),
+                DynType::Unsigned(2) => decompress_as::<u16, PcodecStrategy<u16>>(data, values),
+                DynType::Unsigned(4) => decompress_as::<u32, PcodecStrategy<u32>>(data, values),
+                DynType::Unsigned(8) => decompress_as::<u64, PcodecStrategy<u64>>(data, values),
+                DynType::Signed(1) => decompress_as::<i8, PcodecStrategy<i8>>(data, values),
+                DynType::Signed(2) => decompress_as::<i16, PcodecStrategy<i16>>(data, values),
+                DynType::Signed(4) => decompress_as::<i32, PcodecStrategy<i32>>(data, values),
+                DynType::Signed(8) => decompress_as::<i64, PcodecStrategy<i64>>(data, values),
+                DynType::Float(4) => decompress_as::<f32, PcodecStrategy<f32>>(data, values),
+                DynType::Float(8) => decompress_as::<f64, PcodecStrategy<f64>>(data, values),
+                _ => Err(Error::InvalidArgument("value type not supported by pco")),
+            },
+            Format::Monotonic => match self.value_type {
+                DynType::Unsigned(1) => decompress_as::<u8, EliasFanoStrategy<u8>>(data, values),
+                DynType::Unsigned(2) => decompress_as::<u16, EliasFanoStrategy<u16>>(data, values),
+                DynType::Unsigned(4) => decompress_as::<u32, EliasFanoStrategy<u32>>(data, values),
+                DynType::Unsigned(8) => decompress_as::<u64, EliasFanoStrategy<u64>>(data, values),
+                _ => Err(Error::InvalidArgument(
+                    "value type not supported by monotonic",
+                )),
+            },
+            _ => Err(Error::InvalidArgument("format not enabled")),
+        }
+    }
+}
+
+/// Decompresses `values` values of `T` and serializes them back to little-endian bytes.
+fn decompress_as<T, S>(data: &[u8], values: usize) -> Result<Vec<u8>>
+where
+    T: Bytes,
+    S: CompressionStrategy<T>,
+{
+    let values = S::decompress(data, values)?;
+    let mut bytes = Vec::with_capacity(size_of_val(values.as_slice()));
+    for value in &values {
+        bytes.extend_from_slice(value.to_bytes().as_ref());
+    }
+    Ok(bytes)
+}
@@ -0,0 +1,183 @@
+use std::collections::BTreeSet;
+
+use rawdb::{Database, Region};
+
+use crate::{
+    Format, HEADER_OFFSET, Header, Page, Pages, Stamp, Version, short_type_name,
+    value_type_fingerprint_with, vec_region_name,
+};
+
+use super::DynType;
+
+/// Suffixes of the regions a vec stores next to its own: pages and dictionaries of compressed
+/// vecs, holes of raw vecs.
+pub const COMPANION_SUFFIXES: [&str; 3] = ["_pages", "_dict", "_holes"];
+
+/// A vec found by a [`Catalog`](super::Catalog), described from its header and regions.
+#[derive(Debug, Clone)]
+pub struct CatalogEntry {
+    pub name: String,
+    /// Name of the index type, the part of the region name after the last `/`.
+    pub index: String,
+    pub format: Format,
+    /// Stored version, the import version plus the [layout version](Format::layout_version).
+    pub vec_version: Version,
+    /// Whether the vec was imported by a forced import, see [`Header::forced_layout`].
+    pub forced_layout: bool,
+    pub computed_version: Version,
+    pub stamp: Stamp,
+    /// Fingerprint of the value and index types, see [`Header::value_type`].
+    pub value_type: u32,
+    /// Name of the value type, when the fingerprint is the one of a primitive.
+    pub value_type_name: Option<&'static str>,
+    /// Size of a value in bytes, from the value type or from a raw page of a compressed vec.
+    pub value_size: Option<usize>,
+    /// Number of stored values. `None` for raw vecs of unknown value size, and for compressed
+    /// vecs whose pages couldn't be read.
+    pub len: Option<usize>,
+    /// Number of pages of compressed vecs.
+    pub pages: Option<usize>,
+    /// Regions of the vec, its own first.
+    pub regions: Vec<String>,
+    /// Sum of the lengths of [`Self::regions`].
+    pub stored_bytes: usize,
+    /// Whether stamped change files for rollbacks exist.
+    pub has_rollback_files: bool,
+}
+
+impl CatalogEntry {
+    pub(super) fn read(
+        db: &Database,
+        ids: &BTreeSet<String>,
+        region: &Region,
+        header: &Header,
+    ) -> Option<Self> {
+        let id = region.meta().id().to_string();
+        let (name, index) = id.rsplit_once('/')?;
+        let format = header.format();
+
+        let (value_type_name, mut value_size) = match primitive_type(header.value_type(), index) {
+            Some((name, size)) => (Some(name), Some(size)),
+            None => (None, None),
+        };
+
+        let mut regions = vec![id.clone()];
+        let mut stored_bytes = region.meta().len();
+        for suffix in COMPANION_SUFFIXES {
+            let companion = format!("{id}{suffix}");
+            if ids.contains(&companion)
+                && let Some(region) = db.get_region(&companion)
+            {
+                stored_bytes += region.meta().len();
+                regions.push(companion);
+            }
+        }
+
+        let (len, pages) = if format.is_raw() {
+            let data = region.meta().len().saturating_sub(HEADER_OFFSET);
+            (value_size.map(|size| data / size), None)
+        } else {
+            match read_pages(db, &id, header, value_size) {
+                Some(pages) => {
+                    // Raw pages store values as is
+                    if value_size.is_none() {
+                        value_size = pages
+                            .iter()
+                            .find(|page| page.is_raw() && page.values_count() != 0)
+                            .map(|page| page.bytes as usize / page.values_count() as usize);
+                    }
+                    let len = pages.iter().map(|page| page.values_count() as usize).sum();
+                    (Some(len), Some(pages.len()))
+                }
+                None => (None, None),
+            }
+        };
+
+        let has_rollback_files = db
+            .path()
+            .join("changes")
+            .join(&id)
+            .read_dir()
+            .is_ok_and(|mut entries| entries.next().is_some());
+
+        Some(Self {
+            name: name.to_string(),
+            index: index.to_string(),
+            format,
+            vec_version: header.vec_version(),
+            forced_layout: header.forced_layout(),
+            computed_version: header.computed_version(),
+            stamp: header.stamp(),
+            value_type: header.value_type(),
+            value_type_name,
+            value_size,
+            len,
+            pages,
+            regions,
+            stored_bytes,
+            has_rollback_files,
+        })
+    }
+
+    /// Value type for a [`DynVec`](super::DynVec), when the value type is a known primitive.
+    pub fn dyn_type(&self) -> Option<DynType> {
+        DynType::from_name(self.value_type_name?)
+    }
+
+    /// Version the vec is imported with, see [`Header::import_version`].
+    pub fn import_version(&self) -> Option<Version> {
+        self.format
+            .import_version(self.vec_version, self.forced_layout)
+    }
+
+    /// Name of the vec's region, `{name}/{index}`.
+    pub fn region_name(&self) -> String {
+        vec_region_name(&self.name, &self.index)
+    }
+
+    /// Size of the values once decoded, when the length and value size are known.
+    pub fn uncompressed_bytes(&self) -> Option<usize> {
+        Some(self.len? * self.value_size?)
+    }
+
+    /// [`Self::uncompressed_bytes`] divided by [`Self::stored_bytes`], headers and page
+    /// metadata included.
+    pub fn compression_ratio(&self) -> Option<f64> {
+        let uncompressed = self.uncompressed_bytes()?;
+        (self.stored_bytes != 0).then(|| uncompressed as f64 / self.stored_bytes as f64)
+    }
+}
+
+/// Pages of the compressed vec in region `id`, `None` if they can't be parsed: damaged, or
+/// storing stats of a value size that isn't known.
+fn read_pages(
+    db: &Database,
+    id: &str,
+    header: &Header,
+    value_size: Option<usize>,
+) -> Option<Vec<Page>> {
+    let Some(region) = db.get_region(&format!("{id}_pages")) else {
+        // Created on the first write
+        return Some(vec![]);
+    };
+    let stats_size = if header.page_stats() {
+        Pages::stats_size(value_size?)
+    } else {
+        0
+    };
+    Pages::read_entries(&region, header.page_checksums(), stats_size)
+        .ok()
+        .map(|(pages, _)| pages)
+}
+
+/// Name and size of the primitive value type matching `fingerprint` with index `index`.
+pub(crate) fn primitive_type(fingerprint: u32, index: &str) -> Option<(&'static str, usize)> {
+    macro_rules! primitives {
+        ($($t:ty),*) => {
+            [$((short_type_name::<$t>(), size_of::<$t>())),*]
+        };
+    }
+    primitives!(u8, u16, u32, u64, usize, i8, i16, i32, i64, f32, f64, bool)
+        .into_iter()
+        .find(|&(name, size)| value_type_fingerprint_with(name, size, index) == fingerprint)
+}
@@ -0,0 +1,140 @@
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use log::debug;
+use rawdb::Database;
+
+use crate::{Header, Result};
+
+mod dyn_type;
+mod dyn_vec;
+mod entry;
+
+pub use dyn_type::*;
+pub use dyn_vec::*;
+pub use entry::*;
+
+/// The vecs of a database, found from their headers without knowing their types.
+///
+/// Every region named `{name}/{index}` with a valid vec header is a vec, the regions named after
+/// it with a `_pages`, `_dict` or `_holes` suffix belong to it. Other regions, whether left
+/// behind by a removed vec or written directly through rawdb, are listed apart, like change
+/// directories of vecs that don't exist anymore.
+///
+/// Reading a catalog doesn't modify the database.
+#[derive(Debug, Clone)]
+pub struct Catalog {
+    entries: Vec<CatalogEntry>,
+    other_regions: Vec<String>,
+    orphaned_changes: Vec<PathBuf>,
+}
+
+impl Catalog {
+    pub fn open(db: &Database) -> Result<Self> {
+        let ids = db
+            .regions()
+            .id_to_index()
+            .keys()
+            .cloned()
+            .collect::<BTreeSet<_>>();
+
+        let mut entries = vec![];
+        for id in &ids {
+            let is_companion = COMPANION_SUFFIXES.iter().any(|suffix| {
+                id.strip_suffix(suffix)
+                    .is_some_and(|vec_id| ids.contains(vec_id))
+            });
+            if is_companion {
+                continue;
+            }
+            let Some(region) = db.get_region(id) else {
+                continue;
+            };
+            match Header::read(&region) {
+                Ok(header) => entries.extend(CatalogEntry::read(db, &ids, &region, &header)),
+                Err(e) => debug!("{id} isn't a vec: {e}"),
+            }
+        }
+
+        let claimed = entries
+            .iter()
+            .flat_map(|entry| entry.regions.iter())
+            .collect::<BTreeSet<_>>();
+        let other_regions = ids
+            .iter()
+            .filter(|id| !claimed.contains(id))
+            .cloned()
+            .collect();
+
+        let changes = db.path().join("changes");
+        let mut orphaned_changes = vec![];
+        for path in change_dirs(&changes)? {
+            let id = path
+                .strip_prefix(&changes)
+                .ok()
+                .and_then(Path::to_str)
+                .map(|id| id.replace(std::path::MAIN_SEPARATOR, "/"));
+            if !id.is_some_and(|id| entries.iter().any(|entry| entry.region_name() == id)) {
+                orphaned_changes.push(path);
+            }
+        }
+
+        Ok(Self {
+            entries,
+            other_regions,
+            orphaned_changes,
+        })
+    }
+
+    /// Vecs sorted by region name.
+    pub fn entries(&self) -> &[CatalogEntry] {
+        &self.entries
+    }
+
+    /// The vec `name` indexed by `index`.
+    pub fn get(&self, name: &str, index: &str) -> Option<&CatalogEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.name == name && entry.index == index)
+    }
+
+    /// Vecs named `name`, one per index type.
+    pub fn find(&self, name: &str) -> impl Iterator<Item = &CatalogEntry> {
+        self.entries.iter().filter(move |entry| entry.name == name)
+    }
+
+    /// Regions that are neither a vec nor one of its companion regions, sorted.
+    pub fn other_regions(&self) -> &[String] {
+        &self.other_regions
+    }
+
+    /// Change directories of vecs that aren't in the database.
+    pub fn orphaned_changes(&self) -> &[PathBuf] {
+        &self.orphaned_changes
+    }
+}
+
+/// Directories under `root` holding change files, `root/{name}/{index}` for a vec.
+fn change_dirs(root: &Path) -> Result<Vec<PathBuf>> {
+    let mut dirs = vec![];
+    if !root.is_dir() {
+        return Ok(dirs);
+    }
+    let mut has_files = false;
+    for entry in fs::read_dir(root)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            dirs.extend(change_dirs(&path)?);
+        } else {
+            has_files = true;
+        }
+    }
+    if has_files {
+        dirs.push(root.to_path_buf());
+    }
+    dirs.sort();
+    Ok(dirs)
+}
@@ -0,0 +1,240 @@
+use log::info;
+use rawdb::Database;
+
+use crate::{
+    COMPANION_SUFFIXES, Error, Format, Header, ImportOptions, PrintableIndex, Result, StoredVec,
+    VecIndex, vec_region_name, vec_region_name_with,
+};
+
+mod value;
+
+pub use value::*;
+
+/// Appended to the name of a vec while it's being converted.
+const CONVERTING_SUFFIX: &str = ".converting";
+
+/// Appended to the name of a vec while its old regions are swapped out.
+const REPLACED_SUFFIX: &str = ".replaced";
+
+/// Values are copied in chunks of this many bytes, written between chunks.
+const CHUNK_BYTES: usize = 16 * 1024 * 1024;
+
+/// Converts the vec `name`, stored as `from`, to the format `to`.
+///
+/// The version is read from the stored header, so only the value and index types
+/// are needed, see [`convert_format_with`] for the rest. Vecs created by forced
+/// imports are told apart by their [header](Header::forced_layout), set since they
+/// were last forced imported. Errors with [`Error::UnsupportedFormat`] if either
+/// format can't hold `T`.
+pub fn convert_format<I, T>(db: &Database, name: &str, from: Format, to: Format) -> Result<()>
+where
+    I: VecIndex,
+    T: FormatValue,
+{
+    let id = vec_region_name_with::<I>(name);
+    finish_interrupted_swap(db, &id)?;
+
+    let region = db.get_region(&id).ok_or(rawdb::Error::RegionNotFound)?;
+    let version = Header::read(&region)?
+        .import_version()
+        .ok_or(Error::Underflow)?;
+    drop(region);
+
+    T::with_format::<I, _>(
+        from,
+        ConvertFrom {
+            options: ImportOptions::new(db, name, version),
+            to,
+        },
+    )
+}
+
+/// Converts the vec `options.name`, stored as `Src`, to the layout of `Dst` and returns it.
+///
+/// Values are streamed into regions under a temporary name, then the old regions
+/// are swapped out and the new ones in. If anything fails before the swap, the
+/// temporary regions are dropped and the old data is left intact. A swap that
+/// was interrupted is finished the next time the vec is imported.
+///
+/// `options` apply to the converted vec (page size, compression level, checksums,
+/// ...), the source is imported with only the name and version. A source created
+/// by a forced import is converted to the forced layout of `Dst`, so that it opens
+/// with `Dst::forced_import_with`. The stamp and computed version are carried over.
+/// Stamped change files of the source are deleted since they can't be applied to
+/// the new layout, and holes of raw vecs aren't carried over.
+pub fn convert_format_with<Src, Dst>(options: ImportOptions) -> Result<Dst>
+where
+    Src: StoredVec,
+    Dst: StoredVec<I = Src::I, T = Src::T>,
+{
+    let db = options.db;
+    let name = options.name;
+
+    let id = vec_region_name_with::<Src::I>(name);
+    finish_interrupted_swap(db, &id)?;
+    if db.get_region(&id).is_none() {
+        return Err(rawdb::Error::RegionNotFound.into());
+    }
+
+    // Both layouts can't match the same stored version
+    let src_options = ImportOptions::new(db, name, options.version);
+    let (src, import): (Src, fn(ImportOptions) -> Result<Dst>) = match Src::import_with(src_options)
+    {
+        Err(Error::DifferentVersion { .. }) => (
+            Src::import_forced_layout_with(src_options)?,
+            Dst::import_forced_layout_with,
+        ),
+        res => (res?, Dst::import_with),
+    };
+    rewrite(src, options, import, |v| v)?;
+
+    import(options)
+}
+
+/// Streams every value of `src` through `f` into a new vec created by `create`
+/// under a temporary name, then swaps it in place of `src`.
+///
+/// `options` are those of the new vec under its final name. Nothing is removed
+/// if copying fails.
+pub(crate) fn rewrite<Src, Dst>(
+    src: Src,
+    options: ImportOptions,
+    create: fn(ImportOptions) -> Result<Dst>,
+    f: impl FnMut(Src::T) -> Dst::T,
+) -> Result<()>
+where
+    Src: StoredVec,
+    Dst: StoredVec<I = Src::I>,
+{
+    let db = options.db;
+    let name = options.name;
+    let converting = format!("{name}{CONVERTING_SUFFIX}");
+    let swap = Swap::new(name, <Src::I as PrintableIndex>::to_string());
+
+    // Leftovers of an interrupted conversion
+    swap.remove_converting(db)?;
+
+    let copied = copy_into(
+        &src,
+        create,
+        ImportOptions {
+            name: &converting,
+            saved_stamped_changes: 0,
+            ..options
+        },
+        f,
+    );
+    if let Err(e) = copied {
+        swap.remove_converting(db)?;
+        return Err(e);
+    }
+
+    info!("Swapping {name} to its new layout...");
+
+    let change_files = src.find_rollback_files()?;
+    drop(src);
+    for path in change_files.into_values() {
+        std::fs::remove_file(path)?;
+    }
+
+    swap.run(db)
+}
+
+/// Copies every value of `src` through `f` into a new vec, flushed to disk.
+fn copy_into<Src, Dst>(
+    src: &Src,
+    create: fn(ImportOptions) -> Result<Dst>,
+    options: ImportOptions,
+    mut f: impl FnMut(Src::T) -> Dst::T,
+) -> Result<()>
+where
+    Src: StoredVec,
+    Dst: StoredVec<I = Src::I>,
+{
+    let mut dst = create(options)?;
+
+    let len = src.len();
+    let chunk_len = (CHUNK_BYTES / size_of::<Src::T>()).max(1);
+    for from in (0..len).step_by(chunk_len) {
+        let to = (from + chunk_len).min(len);
+        src.for_each_range_dyn_at(from, to, &mut |v| dst.push(f(v)));
+        dst.write()?;
+    }
+
+    dst.mut_header()
+        .update_computed_version(src.header().computed_version());
+    dst.stamped_write(src.stamp())?;
+    dst.db().flush()?;
+
+    Ok(())
+}
+
+/// Finishes the swap of the `{name}/{index}` region `id` if one was interrupted after
+/// it started, leaving it untouched otherwise.
+pub(crate) fn finish_interrupted_swap(db: &Database, id: &str) -> Result<()> {
+    let Some((name, index)) = id.rsplit_once('/') else {
+        return Ok(());
+    };
+    let swap = Swap::new(name, index);
+    if db.get_region(&swap.replaced).is_none() {
+        return Ok(());
+    }
+    info!("Finishing the interrupted swap of {id}...");
+    swap.run(db)
+}
+
+/// Region ids of a vec being swapped for its converted copy: its own, the copy's and
+/// the ones the old regions are moved to before being removed. Companion regions
+/// follow their vec's.
+///
+/// Each step of [`Self::run`] resumes where an interrupted one stopped, the vec
+/// regions telling which steps are done: the old one is moved out first and removed
+/// last, the copy moved in first.
+struct Swap {
+    id: String,
+    converting: String,
+    replaced: String,
+}
+
+impl Swap {
+    fn new(name: &str, index: &str) -> Self {
+        Self {
+            id: vec_region_name(name, index),
+            converting: vec_region_name(&format!("{name}{CONVERTING_SUFFIX}"), index),
+            replaced: vec_region_name(&format!("{name}{REPLACED_SUFFIX}"), index),
+        }
+    }
+
+    /// Moves the old regions out of the way, the copy in, then removes the old ones.
+    fn run(&self, db: &Database) -> Result<()> {
+        // Until the copy starts moving in, regions under the vec's id are old
+        if db.get_region(&self.converting).is_some() {
+            Self::rename_all(db, &self.id, &self.replaced)?;
+        }
+        Self::rename_all(db, &self.converting, &self.id)?;
+        for suffix in COMPANION_SUFFIXES {
+            db.remove_region_if_exists(&format!("{}{suffix}", self.replaced))?;
+        }
+        db.remove_region_if_exists(&self.replaced)?;
+        db.flush()?;
+        Ok(())
+    }
+
+    /// Renames the vec region `from` and its companions to `to`, the vec region first.
+    fn rename_all(db: &Database, from: &str, to: &str) -> Result<()> {
+        for suffix in std::iter::once("").chain(COMPANION_SUFFIXES) {
+            if let Some(region) = db.get_region(&format!("{from}{suffix}")) {
+                region.rename(&format!("{to}{suffix}"))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes a copy left by a conversion that stopped before swapping.
+    fn remove_converting(&self, db: &Database) -> Result<()> {
+        for suffix in std::iter::once("").chain(COMPANION_SUFFIXES) {
+            db.remove_region_if_exists(&format!("{}{suffix}", self.converting))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,182 @@
+use std::marker::PhantomData;
+
+#[cfg(feature = "lz4")]
+use crate::LZ4Vec;
+#[cfg(feature = "pco")]
+use crate::PcoVec;
+#[cfg(feature = "zerocopy")]
+use crate::ZeroCopyVec;
+#[cfg(feature = "zstd")]
+use crate::ZstdVec;
+use crate::{
+    BytesVec, Error, Format, ImportOptions, MonotonicVec, Result, StoredVec, VecIndex, VecValue,
+};
+
+use super::convert_format_with;
+
+/// Work done with a vec of `T` values indexed by `I`, once its type is picked from a
+/// [`Format`] by [`FormatValue::with_format`].
+pub trait FormatAction<I: VecIndex, T: VecValue> {
+    type Output;
+
+    fn run<V: StoredVec<I = I, T = T>>(self) -> Result<Self::Output>;
+}
+
+/// Value types whose vec type can be picked from a [`Format`] at runtime, see
+/// [`convert_format`](super::convert_format).
+pub trait FormatValue: VecValue {
+    /// Runs `action` with the vec type storing `Self` as `format`. Errors with
+    /// [`Error::UnsupportedFormat`] if it can't hold `Self` or its feature is disabled.
+    fn with_format<I: VecIndex, A: FormatAction<I, Self>>(
+        format: Format,
+        action: A,
+    ) -> Result<A::Output>;
+}
+
+macro_rules! impl_format_value {
+    ($($t:ty => [$($(#[$cfg:meta])* $format:ident => $vec:ident),*];)*) => {
+        $(
+            impl FormatValue for $t {
+                fn with_format<I: VecIndex, A: FormatAction<I, Self>>(
+                    format: Format,
+                    action: A,
+                ) -> Result<A::Output> {
+                    match format {
+                        $($(#[$cfg])* Format::$format => action.run::<$vec<I, $t>>(),)*
+                        #[allow(unreachable_patterns)]
+                        _ => Err(Error::UnsupportedFormat {
+                            format,
+                            value_type: stringify!($t),
+                        }),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_format_value! {
+    u8 => [
+        Bytes => BytesVec,
+        #[cfg(feature = "zerocopy")] ZeroCopy => ZeroCopyVec,
+        #[cfg(feature = "pco")] Pco => PcoVec,
+        #[cfg(feature = "lz4")] LZ4 => LZ4Vec,
+        #[cfg(feature = "zstd")] Zstd => ZstdVec,
+        Monotonic => MonotonicVec
+    ];
+    u16 => [
+        Bytes => BytesVec,
+        #[cfg(feature = "zerocopy")] ZeroCopy => ZeroCopyVec,
+        #[cfg(feature = "pco")] Pco => PcoVec,
+        #[cfg(feature = "lz4")] LZ4 => LZ4Vec,
+        #[cfg(feature = "zstd")] Zstd => ZstdVec,
+        Monotonic => MonotonicVec
+    ];
+    u32 => [
+        Bytes => BytesVec,
+        #[cfg(feature = "zerocopy")] ZeroCopy => ZeroCopyVec,
+        #[cfg(feature = "pco")] Pco => PcoVec,
+        #[cfg(feature = "lz4")] LZ4 => LZ4Vec,
+        #[cfg(feature = "zstd")] Zstd => ZstdVec,
+        Monotonic => MonotonicVec
+    ];
+    u64 => [
+        Bytes => BytesVec,
+        #[cfg(feature = "zerocopy")] ZeroCopy => ZeroCopyVec,
+        #[cfg(feature = "pco")] Pco => PcoVec,
+        #[cfg(feature = "lz4")] LZ4 => LZ4Vec,
+        #[cfg(feature = "zstd")] Zstd => ZstdVec,
+        Monotonic => MonotonicVec
+    ];
+    usize => [
+        Bytes => BytesVec,
+        #[cfg(feature = "zerocopy")] ZeroCopy => ZeroCopyVec,
+        #[cfg(feature = "lz4")] LZ4 => LZ4Vec,
+        #[cfg(feature = "zstd")] Zstd => ZstdVec,
+        Monotonic => MonotonicVec
+    ];
+    i8 => [
+        Bytes => BytesVec,
+        #[cfg(feature = "zerocopy")] ZeroCopy => ZeroCopyVec,
+        #[cfg(feature = "pco")] Pco => PcoVec,
+        #[cfg(feature = "lz4")] LZ4 => LZ4Vec,
+        #[cfg(feature = "zstd")] Zstd => ZstdVec
+    ];
+    i16 => [
+        Bytes => BytesVec,
+        #[cfg(feature = "zerocopy")] ZeroCopy => ZeroCopyVec,
+        #[cfg(feature = "pco")] Pco => PcoVec,
+        #[cfg(feature = "lz4")] LZ4 => LZ4Vec,
+        #[cfg(feature = "zstd")] Zstd => ZstdVec
+    ];
+    i32 => [
+        Bytes => BytesVec,
+        #[cfg(feature = "zerocopy")] ZeroCopy => ZeroCopyVec,
+        #[cfg(feature = "pco")] Pco => PcoVec,
+        #[cfg(feature = "lz4")] LZ4 => LZ4Vec,
+        #[cfg(feature = "zstd")] Zstd => ZstdVec
+    ];
+    i64 => [
+        Bytes => BytesVec,
+        #[cfg(feature = "zerocopy")] ZeroCopy => ZeroCopyVec,
+        #[cfg(feature = "pco")] Pco => PcoVec,
+        #[cfg(feature = "lz4")] LZ4 => LZ4Vec,
+        #[cfg(feature = "zstd")] Zstd => ZstdVec
+    ];
+    f32 => [
+        Bytes => BytesVec,
+        #[cfg(feature = "zerocopy")] ZeroCopy => ZeroCopyVec,
+        #[cfg(feature = "pco")] Pco => PcoVec,
+        #[cfg(feature = "lz4")] LZ4 => LZ4Vec,
+        #[cfg(feature = "zstd")] Zstd => ZstdVec
+    ];
+    f64 => [
+        Bytes => BytesVec,
+        #[cfg(feature = "zerocopy")] ZeroCopy => ZeroCopyVec,
+        #[cfg(feature = "pco")] Pco => PcoVec,
+        #[cfg(feature = "lz4")] LZ4 => LZ4Vec,
+        #[cfg(feature = "zstd")] Zstd => ZstdVec
+    ];
+    bool => [
+        Bytes => BytesVec,
+        #[cfg(feature = "lz4")] LZ4 => LZ4Vec,
+        #[cfg(feature = "zstd")] Zstd => ZstdVec
+    ];
+}
+
+/// Picks the source vec type of [`convert_format`](super::convert_format).
+pub(super) struct ConvertFrom<'a> {
+    pub(super) options: ImportOptions<'a>,
+    pub(super) to: Format,
+}
+
+impl<I: VecIndex, T: FormatValue> FormatAction<I, T> for ConvertFrom<'_> {
+    type Output = ();
+
+    fn run<Src: StoredVec<I = I, T = T>>(self) -> Result<()> {
+        T::with_format(
+            self.to,
+            ConvertTo::<Src> {
+                options: self.options,
+                _src: PhantomData,
+            },
+        )
+    }
+}
+
+/// [`ConvertFrom`] once the source vec type is known.
+struct ConvertTo<'a, Src> {
+    options: ImportOptions<'a>,
+    _src: PhantomData<Src>,
+}
+
+impl<I: VecIndex, T: VecValue, Src: StoredVec<I = I, T = T>> FormatAction<I, T>
+    for ConvertTo<'_, Src>
+{
+    type Output = ();
+
+    fn run<Dst: StoredVec<I = I, T = T>>(self) -> Result<()> {
+        convert_format_with::<Src, Dst>(self.options)?;
+        Ok(())
+    }
+}
@@ -23,6 +23,12 @@ pub enum Error {
     ZeroCopyError,
     #[error(transparent)]
     SystemTimeError(#[from] time::SystemTimeError),
+    #[cfg(feature = "arrow")]
+    #[error(transparent)]
+    Arrow(#[from] arrow_schema::ArrowError),
+    #[cfg(feature = "parquet")]
+    #[error(transparent)]
+    Parquet(#[from] parquet::errors::ParquetError),
     #[cfg(feature = "pco")]
     #[error(transparent)]
     PCO(#[from] pco::errors::PcoError),
@@ -40,6 +46,15 @@ pub enum Error {
 
     #[error("Wrong length: received: {received:?}, expected: {expected:?}")]
     WrongLength { received: usize, expected: usize },
+    #[error("{name} is not a vecdb vec: unknown header")]
+    NotAVec { name: String },
+    #[error("Corrupted header in {name}: checksum mismatch")]
+    CorruptedHeader { name: String },
+    #[error("Older header version received: {received:?}, expected: {expected:?}")]
+    OlderHeaderVersion {
+        received: Version,
+        expected: Version,
+    },
     #[error("Wrong endian")]
     WrongEndian,
     #[error("Iterator ended")]
@@ -67,6 +82,12 @@ pub enum Error {
     FailedKeyTryIntoUsize,
     #[error("Different format received: {received:?}, expected: {expected:?}")]
     DifferentFormat { received: Format, expected: Format },
+    #[error(
+        "Different value type fingerprint received: {received:#010x}, expected: {expected:#010x}"
+    )]
+    DifferentValueType { received: u32, expected: u32 },
+    #[error("Different page size received: {received}, expected: {expected}")]
+    DifferentPageSize { received: usize, expected: usize },
     #[error("Version cannot be zero, can't verify endianness otherwise")]
     VersionCannotBeZero,
     #[error("Stamp mismatch: file stamp {file:?} != vec stamp {vec:?}")]
@@ -78,12 +99,33 @@ pub enum Error {
         expected_len: usize,
         actual_len: usize,
     },
+    #[error("Checksum mismatch in {name} at page {page}")]
+    ChecksumMismatch { name: String, page: usize },
+    #[error("Column {name}: {source}")]
+    Column {
+        name: &'static str,
+        #[source]
+        source: Box<Error>,
+    },
+    #[error("Missing compression dictionary {0}")]
+    MissingDictionary(u8),
     #[error("Cannot remove PcodecVec: pages still referenced")]
     PagesStillReferenced,
     #[error("Invalid format byte: {0}")]
     InvalidFormat(u8),
+    #[error("Invalid discriminant {value} for {name}")]
+    InvalidDiscriminant { name: &'static str, value: u64 },
+    #[error("Invalid value {value} for {name}")]
+    InvalidValue { name: &'static str, value: u64 },
+    #[error("Couldn't parse line {line}: {reason}")]
+    ParseError { line: usize, reason: String },
     #[error("Invalid argument: {0}")]
     InvalidArgument(&'static str),
+    #[error("{format} vecs can't hold {value_type} values")]
+    UnsupportedFormat {
+        format: Format,
+        value_type: &'static str,
+    },
     #[error("Arithmetic overflow")]
     Overflow,
     #[error("Arithmetic underflow")]
@@ -102,18 +144,27 @@ impl Error {
     pub fn is_data_error(&self) -> bool {
         match self {
             Error::IO(io_err) => is_io_data_error(io_err),
+            Error::Column { source, .. } => source.is_data_error(),
             Error::RawDB(rawdb::Error::IO(io_err)) => is_io_data_error(io_err),
             Error::RawDB(rawdb::Error::CorruptedMetadata(_)) => true,
             Error::RawDB(rawdb::Error::InvalidMetadataSize { .. }) => true,
             Error::RawDB(rawdb::Error::EmptyMetadata) => true,
             Error::DifferentVersion { .. }
             | Error::DifferentFormat { .. }
+            | Error::DifferentValueType { .. }
+            | Error::NotAVec { .. }
+            | Error::CorruptedHeader { .. }
+            | Error::OlderHeaderVersion { .. }
             | Error::StampMismatch { .. }
             | Error::CorruptedRegion { .. }
             | Error::DecompressionMismatch { .. }
+            | Error::ChecksumMismatch { .. }
+            | Error::MissingDictionary(_)
             | Error::WrongEndian
             | Error::WrongLength { .. }
-            | Error::InvalidFormat(_) => true,
+            | Error::InvalidFormat(_)
+            | Error::InvalidDiscriminant { .. }
+            | Error::InvalidValue { .. } => true,
             _ => false,
         }
     }
@@ -0,0 +1,110 @@
+use std::io;
+
+use crate::{
+    Cursor, Error, Formattable, READ_CHUNK_SIZE, ReadableVec, Result, ValueWriter, VecIndex,
+    VecValue, WriterFormat,
+};
+
+/// Cursor-backed writer over `[from, to)` of a vec.
+///
+/// Values are read in [`READ_CHUNK_SIZE`] batches aligned like [`Cursor`] reads, so each
+/// page is decoded once and memory stays bounded whatever the size of the range.
+/// [`write_all`](ValueWriter::write_all) flushes to the output after every batch, and in
+/// [`WriterFormat::Json`] wraps the values in a JSON array. Values that can't be read,
+/// like those of a page failing its checksum, end both writes with
+/// [`Error::WrongLength`] instead of a truncated output.
+pub struct CursorWriter<'a, I, T, V>
+where
+    I: VecIndex,
+    T: VecValue,
+    V: ReadableVec<I, T> + ?Sized,
+{
+    cursor: Cursor<'a, I, T, V>,
+    to: usize,
+    format: WriterFormat,
+}
+
+impl<'a, I, T, V> CursorWriter<'a, I, T, V>
+where
+    I: VecIndex,
+    T: VecValue,
+    V: ReadableVec<I, T> + ?Sized,
+{
+    /// Writer over `[from, to)` by raw index, `to` being capped at the length of `source`.
+    pub fn new(source: &'a V, from: usize, to: usize, format: WriterFormat) -> Self {
+        let mut cursor = Cursor::new(source);
+        cursor.advance(from);
+        Self {
+            to: to.min(source.len()),
+            cursor,
+            format,
+        }
+    }
+
+    /// Number of values left to write.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.to.saturating_sub(self.cursor.position())
+    }
+}
+
+impl<I, T, V> ValueWriter for CursorWriter<'_, I, T, V>
+where
+    I: VecIndex,
+    T: VecValue + Formattable,
+    V: ReadableVec<I, T> + ?Sized,
+{
+    fn write_next(&mut self, buf: &mut String) -> Result<()> {
+        if self.remaining() == 0 {
+            return Err(Error::IteratorEnded);
+        }
+        // Values in range stop early at a page that can't be read
+        let value = self.cursor.next().ok_or(Error::WrongLength {
+            received: self.cursor.position(),
+            expected: self.to,
+        })?;
+        self.format.write_value(&value, buf);
+        Ok(())
+    }
+
+    fn write_all(&mut self, out: &mut dyn io::Write) -> Result<usize> {
+        let format = self.format;
+        let json = format == WriterFormat::Json;
+        let mut buf = String::with_capacity(READ_CHUNK_SIZE.min(self.remaining()) * 20 + 2);
+        let mut count = 0;
+        let expected = self.remaining();
+
+        if json {
+            buf.push('[');
+        }
+        while self.remaining() > 0 {
+            let n = self.remaining().min(READ_CHUNK_SIZE);
+            let position = self.cursor.position();
+            self.cursor.for_each(n, |value| {
+                if json && count > 0 {
+                    buf.push(',');
+                }
+                format.write_value(&value, &mut buf);
+                if !json {
+                    buf.push('\n');
+                }
+                count += 1;
+            });
+            out.write_all(buf.as_bytes())?;
+            buf.clear();
+            // Stopped at a page that can't be read, don't close a truncated output
+            if self.cursor.position() == position {
+                return Err(Error::WrongLength {
+                    received: count,
+                    expected,
+                });
+            }
+        }
+        if json {
+            buf.push(']');
+        }
+        out.write_all(buf.as_bytes())?;
+
+        Ok(count)
+    }
+}
@@ -0,0 +1,352 @@
+use std::{borrow::Cow, io};
+
+use crate::{Error, Parseable, Result, Stamp, TypedVec, VecValue, WritableVec};
+
+/// What a [`ColumnLoader`] does with a line whose value is missing or can't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OnParseError<T> {
+    /// Fails with [`Error::ParseError`], naming the line. Values pushed before it stay pushed.
+    Abort,
+    /// Drops the line.
+    Skip,
+    /// Pushes the given value instead, e.g. `OnParseError::Fill(T::default())`.
+    Fill(T),
+}
+
+/// Counts of a [`ColumnLoader::load`] run.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LoadSummary {
+    /// Values pushed, fills included.
+    pub pushed: usize,
+    /// Lines dropped by [`OnParseError::Skip`].
+    pub skipped: usize,
+    /// Lines replaced by [`OnParseError::Fill`].
+    pub filled: usize,
+}
+
+#[derive(Debug, Clone)]
+enum Source {
+    CsvNamed(String),
+    CsvAt(usize),
+    NdJson(String),
+}
+
+/// Appends one column of a CSV file, or one field of an NDJSON file, to a vec.
+///
+/// Input is read line by line, values are parsed with [`Parseable`] and pushed, and the
+/// vec is flushed whenever [`batch_limit_reached`](WritableVec::batch_limit_reached), so
+/// memory stays bounded. A final [`stamped_write`](crate::AnyStoredVec::stamped_write)
+/// ends the load. Blank NDJSON lines are ignored, while a blank CSV line is an empty
+/// cell, as written for `None` values. Line numbers in errors start at 1.
+///
+/// ```ignore
+/// let summary = ColumnLoader::csv("price")
+///     .on_parse_error(OnParseError::Fill(0.0))
+///     .load(BufReader::new(File::open("prices.csv")?), &mut prices, Stamp::new(1))?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct ColumnLoader<T> {
+    source: Source,
+    on_parse_error: OnParseError<T>,
+}
+
+impl<T> ColumnLoader<T>
+where
+    T: VecValue + Parseable,
+{
+    /// Loads the column named `name` in the header line of a CSV file.
+    pub fn csv(name: &str) -> Self {
+        Self::new(Source::CsvNamed(name.to_string()))
+    }
+
+    /// Loads the column at `position` of a CSV file without header, such as the output of
+    /// [`write_csv_column`](crate::AnySerializableVec::write_csv_column).
+    pub fn csv_at(position: usize) -> Self {
+        Self::new(Source::CsvAt(position))
+    }
+
+    /// Loads the field `name` of every object of an NDJSON file.
+    pub fn ndjson(name: &str) -> Self {
+        Self::new(Source::NdJson(name.to_string()))
+    }
+
+    fn new(source: Source) -> Self {
+        Self {
+            source,
+            on_parse_error: OnParseError::Abort,
+        }
+    }
+
+    /// What to do with invalid lines, [`OnParseError::Abort`] by default.
+    pub fn on_parse_error(mut self, on_parse_error: OnParseError<T>) -> Self {
+        self.on_parse_error = on_parse_error;
+        self
+    }
+
+    /// Appends the values read from `reader` to `vec`, then writes it with `stamp`.
+    pub fn load<V>(
+        &self,
+        mut reader: impl io::BufRead,
+        vec: &mut V,
+        stamp: Stamp,
+    ) -> Result<LoadSummary>
+    where
+        V: TypedVec<T = T> + WritableVec<V::I, T>,
+    {
+        let mut summary = LoadSummary::default();
+        let mut record = String::new();
+        let mut line = 0;
+
+        let position = match &self.source {
+            Source::CsvNamed(name) => {
+                if read_csv_record(&mut reader, &mut record, &mut line)?.is_none() {
+                    return Err(Error::InvalidArgument("CSV input has no header"));
+                }
+                (0..)
+                    .map_while(|i| csv_cell(&record, i))
+                    .position(|cell| cell.trim() == name.as_str())
+                    .ok_or(Error::InvalidArgument("column not found in CSV header"))?
+            }
+            Source::CsvAt(position) => *position,
+            Source::NdJson(_) => 0,
+        };
+
+        loop {
+            let (start, parsed) = match &self.source {
+                Source::CsvNamed(_) | Source::CsvAt(_) => {
+                    let Some(start) = read_csv_record(&mut reader, &mut record, &mut line)? else {
+                        break;
+                    };
+                    let parsed = match csv_cell(&record, position) {
+                        Some(cell) => T::parse_csv(&cell).ok_or_else(|| invalid_value(&cell)),
+                        None => Err(format!("missing column {position}")),
+                    };
+                    (start, parsed)
+                }
+                Source::NdJson(name) => {
+                    record.clear();
+                    if reader.read_line(&mut record)? == 0 {
+                        break;
+                    }
+                    line += 1;
+                    let object = record.trim();
+                    if object.is_empty() {
+                        continue;
+                    }
+                    let parsed = match json_field(object, name) {
+                        Some(Some(value)) => {
+                            T::parse_json(value).ok_or_else(|| invalid_value(value))
+                        }
+                        Some(None) => Err(format!("missing field {name:?}")),
+                        None => Err("invalid JSON object".to_string()),
+                    };
+                    (line, parsed)
+                }
+            };
+
+            match parsed {
+                Ok(value) => vec.push(value),
+                Err(reason) => match &self.on_parse_error {
+                    OnParseError::Abort => {
+                        return Err(Error::ParseError {
+                            line: start,
+                            reason,
+                        });
+                    }
+                    OnParseError::Skip => {
+                        summary.skipped += 1;
+                        continue;
+                    }
+                    OnParseError::Fill(value) => {
+                        vec.push(value.clone());
+                        summary.filled += 1;
+                    }
+                },
+            }
+            summary.pushed += 1;
+
+            if vec.batch_limit_reached() {
+                vec.write()?;
+            }
+        }
+
+        vec.stamped_write(stamp)?;
+        Ok(summary)
+    }
+}
+
+fn invalid_value(text: &str) -> String {
+    format!("invalid value {:?}", text.trim())
+}
+
+/// Reads the next CSV record into `record`, joining lines while a quoted cell is open, and
+/// returns the line it starts on, or `None` at the end of the input.
+fn read_csv_record(
+    reader: &mut impl io::BufRead,
+    record: &mut String,
+    line: &mut usize,
+) -> Result<Option<usize>> {
+    record.clear();
+    if reader.read_line(record)? == 0 {
+        return Ok(None);
+    }
+    *line += 1;
+    let start = *line;
+    while record.bytes().filter(|&b| b == b'"').count() % 2 == 1 {
+        if reader.read_line(record)? == 0 {
+            break;
+        }
+        *line += 1;
+    }
+    let len = record.trim_end_matches(['\n', '\r']).len();
+    record.truncate(len);
+    Ok(Some(start))
+}
+
+/// Returns the cell at `position` of a CSV record, unquoted.
+fn csv_cell(record: &str, position: usize) -> Option<Cow<'_, str>> {
+    let mut rest = record;
+    for i in 0.. {
+        let (cell, next) = if let Some(quoted) = rest.strip_prefix('"') {
+            let mut cell = String::new();
+            let mut chars = quoted.char_indices();
+            let end = loop {
+                match chars.next()? {
+                    (j, '"') if quoted[j + 1..].starts_with('"') => {
+                        cell.push('"');
+                        chars.next();
+                    }
+                    (j, '"') => break j + 1,
+                    (_, c) => cell.push(c),
+                }
+            };
+            let next = &quoted[end..];
+            (Cow::Owned(cell), next.find(',').map(|j| &next[j + 1..]))
+        } else {
+            match rest.find(',') {
+                Some(j) => (Cow::Borrowed(&rest[..j]), Some(&rest[j + 1..])),
+                None => (Cow::Borrowed(rest), None),
+            }
+        };
+        if i == position {
+            return Some(cell);
+        }
+        rest = next?;
+    }
+    None
+}
+
+/// Returns the raw value of the top-level field `name` of a JSON object: `None` if the
+/// object is malformed, `Some(None)` if it has no such field.
+fn json_field<'a>(object: &'a str, name: &str) -> Option<Option<&'a str>> {
+    let bytes = object.as_bytes();
+    let mut pos = skip_whitespace(bytes, 0);
+    if bytes.get(pos) != Some(&b'{') {
+        return None;
+    }
+    pos = skip_whitespace(bytes, pos + 1);
+    let mut field = None;
+    if bytes.get(pos) == Some(&b'}') {
+        pos += 1;
+    } else {
+        loop {
+            if bytes.get(pos) != Some(&b'"') {
+                return None;
+            }
+            let key_end = json_value_end(bytes, pos)?;
+            let key = &object[pos + 1..key_end - 1];
+            pos = skip_whitespace(bytes, key_end);
+            if bytes.get(pos) != Some(&b':') {
+                return None;
+            }
+            let start = skip_whitespace(bytes, pos + 1);
+            let end = json_value_end(bytes, start)?;
+            if field.is_none() && unescape_json(key)? == name {
+                field = Some(&object[start..end]);
+            }
+            pos = skip_whitespace(bytes, end);
+            match bytes.get(pos) {
+                Some(b',') => pos = skip_whitespace(bytes, pos + 1),
+                Some(b'}') => {
+                    pos += 1;
+                    break;
+                }
+                _ => return None,
+            }
+        }
+    }
+    (skip_whitespace(bytes, pos) == bytes.len()).then_some(field)
+}
+
+fn skip_whitespace(bytes: &[u8], mut pos: usize) -> usize {
+    while bytes.get(pos).is_some_and(u8::is_ascii_whitespace) {
+        pos += 1;
+    }
+    pos
+}
+
+/// Returns the end of the JSON value starting at `start`: a string, an array or object
+/// (nested values included), or a scalar.
+fn json_value_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, &b) in bytes.iter().enumerate().skip(start) {
+        if in_string {
+            match b {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => {
+                    in_string = false;
+                    if depth == 0 {
+                        return Some(i + 1);
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+        match b {
+            b'"' => in_string = true,
+            b'[' | b'{' => depth += 1,
+            b']' | b'}' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            b',' | b']' | b'}' if depth == 0 => return (i > start).then_some(i),
+            _ if depth == 0 && b.is_ascii_whitespace() => return (i > start).then_some(i),
+            _ => {}
+        }
+    }
+    (!in_string && depth == 0 && bytes.len() > start).then_some(bytes.len())
+}
+
+/// Unescapes the inside of a JSON string.
+fn unescape_json(s: &str) -> Option<Cow<'_, str>> {
+    if !s.contains('\\') {
+        return Some(Cow::Borrowed(s));
+    }
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        out.push(match chars.next()? {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'b' => '\u{8}',
+            'f' => '\u{c}',
+            'u' => {
+                let hex = chars.by_ref().take(4).collect::<String>();
+                char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?
+            }
+            c => c,
+        });
+    }
+    Some(Cow::Owned(out))
+}
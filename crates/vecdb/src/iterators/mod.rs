@@ -1,3 +1,9 @@
+mod cursor_writer;
+mod loader;
+mod table;
 mod writer;
 
+pub use cursor_writer::*;
+pub use loader::*;
+pub use table::*;
 pub use writer::*;
@@ -0,0 +1,184 @@
+use std::io;
+
+use crate::{AnyExportableVec, BUFFER_SIZE, Error, Formattable, Result, WriterFormat};
+
+/// Vecs sharing an index type, exported row by row: row `i` holds index `from + i` of
+/// every vec, up to the length of the shortest one. Each vec is a column named after it.
+///
+/// Every column streams through its own cursor-backed [`ValueWriter`](crate::ValueWriter), so memory stays
+/// bounded whatever the size of the range, and output is written in 512 KiB batches.
+///
+/// - [`WriterFormat::Csv`]: a header line with the column names, then one line per row
+/// - [`WriterFormat::Json`]: an array of objects keyed by column name
+/// - [`WriterFormat::NdJson`]: one object per line
+///
+/// ```ignore
+/// let rows = TableExporter::new(vec![&prices, &volumes])?
+///     .with_range(1_000, None)
+///     .with_index_column("height")
+///     .with_format(WriterFormat::NdJson)
+///     .write(File::create("prices.ndjson")?)?;
+/// ```
+pub struct TableExporter<'a> {
+    columns: Vec<&'a dyn AnyExportableVec>,
+    from: usize,
+    to: Option<usize>,
+    index_column: Option<String>,
+    format: WriterFormat,
+}
+
+impl<'a> TableExporter<'a> {
+    /// Fails if `columns` is empty, mixes index types or repeats a name.
+    pub fn new(columns: Vec<&'a dyn AnyExportableVec>) -> Result<Self> {
+        let Some(first) = columns.first() else {
+            return Err(Error::InvalidArgument("a table needs a column"));
+        };
+        for (i, column) in columns.iter().enumerate() {
+            if column.index_type_to_string() != first.index_type_to_string() {
+                return Err(Error::InvalidArgument("columns must share an index type"));
+            }
+            if columns[..i].iter().any(|c| c.name() == column.name()) {
+                return Err(Error::InvalidArgument("column names must be unique"));
+            }
+        }
+        Ok(Self {
+            columns,
+            from: 0,
+            to: None,
+            index_column: None,
+            format: WriterFormat::Csv,
+        })
+    }
+
+    /// Exports indices `[from, to)` only, `to` defaulting to the end.
+    pub fn with_range(mut self, from: usize, to: Option<usize>) -> Self {
+        self.from = from;
+        self.to = to;
+        self
+    }
+
+    /// Adds a leading column named `name` holding the index of each row.
+    pub fn with_index_column(mut self, name: &str) -> Self {
+        self.index_column = Some(name.to_string());
+        self
+    }
+
+    /// Output format, CSV by default.
+    pub fn with_format(mut self, format: WriterFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Exported index range.
+    pub fn range(&self) -> (usize, usize) {
+        let len = self.columns.iter().map(|c| c.len()).min().unwrap_or(0);
+        let to = self.to.unwrap_or(len).min(len);
+        (self.from.min(to), to)
+    }
+
+    /// Writes the table to `out` and returns the number of rows.
+    pub fn write<W: io::Write>(&self, mut out: W) -> Result<usize> {
+        let (from, to) = self.range();
+        let mut writers = self
+            .columns
+            .iter()
+            .map(|c| c.create_writer_with_format(Some(from as i64), Some(to as i64), self.format))
+            .collect::<Vec<_>>();
+        let names = self
+            .index_column
+            .iter()
+            .map(String::as_str)
+            .chain(self.columns.iter().map(|c| c.name()))
+            .collect::<Vec<_>>();
+
+        // What goes before each cell: a comma, or for JSON the key after `{` or a comma
+        let prefixes = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let mut prefix = if i > 0 { "," } else { "" }.to_string();
+                if self.format != WriterFormat::Csv {
+                    if i == 0 {
+                        prefix.push('{');
+                    }
+                    write_json_string(name, &mut prefix);
+                    prefix.push(':');
+                }
+                prefix
+            })
+            .collect::<Vec<_>>();
+
+        let mut buf = String::with_capacity(BUFFER_SIZE.min((to - from + 1) * 64));
+        match self.format {
+            WriterFormat::Csv => {
+                for (name, prefix) in names.iter().zip(&prefixes) {
+                    buf.push_str(prefix);
+                    write_csv_name(name, &mut buf);
+                }
+                buf.push('\n');
+            }
+            WriterFormat::Json => buf.push('['),
+            WriterFormat::NdJson => {}
+        }
+
+        for index in from..to {
+            if self.format == WriterFormat::Json && index > from {
+                buf.push(',');
+            }
+            let mut prefixes = prefixes.iter();
+            if self.index_column.is_some() {
+                buf.push_str(prefixes.next().unwrap());
+                index.fmt_into(&mut buf);
+            }
+            for (writer, prefix) in writers.iter_mut().zip(prefixes) {
+                buf.push_str(prefix);
+                writer.write_next(&mut buf)?;
+            }
+            match self.format {
+                WriterFormat::Csv => buf.push('\n'),
+                WriterFormat::Json => buf.push('}'),
+                WriterFormat::NdJson => buf.push_str("}\n"),
+            }
+
+            if buf.len() >= BUFFER_SIZE {
+                out.write_all(buf.as_bytes())?;
+                buf.clear();
+            }
+        }
+
+        if self.format == WriterFormat::Json {
+            buf.push(']');
+        }
+        out.write_all(buf.as_bytes())?;
+        out.flush()?;
+        Ok(to - from)
+    }
+}
+
+/// Writes a column name as a CSV header cell, quoted when needed.
+fn write_csv_name(name: &str, buf: &mut String) {
+    if name.contains([',', '"', '\n', '\r']) {
+        buf.push('"');
+        buf.push_str(&name.replace('"', "\"\""));
+        buf.push('"');
+    } else {
+        buf.push_str(name);
+    }
+}
+
+/// Writes a column name as a JSON string.
+fn write_json_string(name: &str, buf: &mut String) {
+    buf.push('"');
+    for c in name.chars() {
+        match c {
+            '"' => buf.push_str("\\\""),
+            '\\' => buf.push_str("\\\\"),
+            '\n' => buf.push_str("\\n"),
+            '\r' => buf.push_str("\\r"),
+            '\t' => buf.push_str("\\t"),
+            c if (c as u32) < 0x20 => buf.push_str(&format!("\\u{:04x}", c as u32)),
+            c => buf.push(c),
+        }
+    }
+    buf.push('"');
+}
@@ -1,15 +1,61 @@
+use std::io;
+
 use crate::{Error, Formattable, Result, VecValue};
 
+/// Output format of a [`ValueWriter`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum WriterFormat {
+    /// CSV cells, quoted when needed, one per line.
+    #[default]
+    Csv,
+    /// JSON values, written whole as a JSON array.
+    Json,
+    /// JSON values, one per line.
+    NdJson,
+}
+
+impl WriterFormat {
+    /// Writes `value` as a single cell or JSON value.
+    #[inline]
+    pub fn write_value<T: Formattable>(self, value: &T, buf: &mut String) {
+        match self {
+            Self::Csv => value.fmt_csv(buf).expect("csv formatting failed"),
+            // SAFETY: fmt_json produces valid UTF-8, like write_to.
+            Self::Json | Self::NdJson => unsafe { value.fmt_json(buf.as_mut_vec()) },
+        }
+    }
+}
+
 /// Stateful writer for streaming values one at a time to a string buffer.
 ///
 /// Useful for incremental serialization when memory constraints prevent
 /// materializing entire collections.
 pub trait ValueWriter {
-    /// Writes the next value to the buffer in CSV format.
+    /// Writes the next value to the buffer, as a CSV cell unless the writer was
+    /// created with another [`WriterFormat`].
     ///
     /// # Errors
-    /// Returns `Error::WrongLength` when no more values are available.
+    /// Returns `Error::IteratorEnded` when no more values are available, and another
+    /// error if a value in range can't be read.
     fn write_next(&mut self, buf: &mut String) -> Result<()>;
+
+    /// Writes the remaining values to `out`, one per line, and returns how many.
+    /// Errors if one can't be read, the values before it being written already.
+    fn write_all(&mut self, out: &mut dyn io::Write) -> Result<usize> {
+        let mut buf = String::new();
+        let mut count = 0;
+        loop {
+            buf.clear();
+            match self.write_next(&mut buf) {
+                Ok(()) => {}
+                Err(Error::IteratorEnded) => return Ok(count),
+                Err(e) => return Err(e),
+            }
+            buf.push('\n');
+            out.write_all(buf.as_bytes())?;
+            count += 1;
+        }
+    }
 }
 
 /// Iterator-backed writer that formats values as CSV.
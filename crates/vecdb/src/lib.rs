@@ -3,14 +3,19 @@
 pub use rawdb::{Database, Error as RawDBError, PAGE_SIZE, Reader, likely, unlikely};
 
 #[cfg(feature = "derive")]
-pub use vecdb_derive::{Bytes, Pco};
+pub use vecdb_derive::{Bytes, Columnar, Pco};
 
+#[cfg(feature = "arrow")]
+mod arrow;
 mod base;
 mod bytes;
+mod catalog;
+mod convert;
 mod cursor;
 mod error;
 mod exit;
 mod iterators;
+mod migrate;
 mod ops;
 mod stamp;
 mod traits;
@@ -19,12 +24,17 @@ mod version;
 
 use variants::*;
 
+#[cfg(feature = "arrow")]
+pub use arrow::*;
 pub use base::*;
 pub use bytes::*;
+pub use catalog::*;
+pub use convert::*;
 pub use cursor::*;
 pub use error::*;
 pub use exit::*;
 pub use iterators::*;
+pub use migrate::*;
 pub use ops::*;
 pub use stamp::*;
 pub use traits::*;
@@ -0,0 +1,160 @@
+use std::marker::PhantomData;
+
+use log::info;
+
+use crate::{Error, ImportOptions, Result, StoredVec, Version, convert::rewrite};
+
+type Probe = Box<dyn Fn(ImportOptions, bool) -> bool>;
+type Run = Box<dyn Fn(ImportOptions, bool) -> Result<()>>;
+
+struct Step {
+    from: Version,
+    to: Version,
+    /// Whether the stored vec opens as the old type of this step.
+    probe: Probe,
+    /// Rewrites the stored vec from the old type to the new one.
+    run: Run,
+}
+
+/// Imports a vec, migrating data stored with an older version instead of discarding it.
+///
+/// Each step maps the values of a vec stored as `Old` at version `from` to a vec
+/// stored as `New` at version `to`. When the stored version doesn't match
+/// `options.version`, the steps are chained from the stored version up to it and
+/// the data is rewritten through each of them, streaming, like [`convert_format_with`].
+/// The stamp and computed version are kept, stamped change files are deleted.
+///
+/// Vecs created with [`ImportableVec::forced_import`](crate::ImportableVec::forced_import)
+/// must be opened with [`Self::forced_import`], which also resets the vec when no
+/// path leads to `options.version`. Vecs created with
+/// [`ImportableVec::import`](crate::ImportableVec::import) use [`Self::import`],
+/// which returns the version error instead.
+///
+/// [`convert_format_with`]: crate::convert_format_with
+pub struct Migrations<'a, V> {
+    options: ImportOptions<'a>,
+    steps: Vec<Step>,
+    _vec: PhantomData<V>,
+}
+
+impl<'a, V> Migrations<'a, V>
+where
+    V: StoredVec,
+{
+    pub fn new(options: ImportOptions<'a>) -> Self {
+        Self {
+            options,
+            steps: vec![],
+            _vec: PhantomData,
+        }
+    }
+
+    /// Registers a step from `Old` at version `from` to `New` at version `to`.
+    pub fn step<Old, New>(mut self, from: Version, to: Version, f: fn(Old::T) -> New::T) -> Self
+    where
+        Old: StoredVec + 'static,
+        New: StoredVec<I = Old::I> + 'static,
+    {
+        self.steps.push(Step {
+            from,
+            to,
+            probe: Box::new(move |options, forced| {
+                open::<Old>(ImportOptions::new(options.db, options.name, from), forced).is_ok()
+            }),
+            run: Box::new(move |options, forced| {
+                let old = open::<Old>(ImportOptions::new(options.db, options.name, from), forced)?;
+                let create = if forced {
+                    New::forced_import_with
+                } else {
+                    New::import_with
+                };
+                rewrite(
+                    old,
+                    ImportOptions {
+                        version: to,
+                        ..options
+                    },
+                    create,
+                    f,
+                )
+            }),
+        });
+        self
+    }
+
+    /// Imports the vec, migrating it if needed.
+    pub fn import(self) -> Result<V> {
+        self.import_(false)
+    }
+
+    /// Imports a vec created with forced import, migrating it if needed.
+    ///
+    /// # Warning
+    ///
+    /// This will DELETE all existing data when no migration path exists. Use with caution.
+    pub fn forced_import(self) -> Result<V> {
+        self.import_(true)
+    }
+
+    fn import_(self, forced: bool) -> Result<V> {
+        let options = self.options;
+
+        match open::<V>(options, forced) {
+            Err(
+                Error::DifferentVersion { .. }
+                | Error::DifferentFormat { .. }
+                | Error::DifferentValueType { .. },
+            ) => {}
+            Err(_) if forced => return V::forced_import_with(options),
+            res => return res,
+        }
+
+        let Some(path) = self.path(forced) else {
+            return if forced {
+                V::forced_import_with(options)
+            } else {
+                open::<V>(options, forced)
+            };
+        };
+
+        for step in path {
+            info!(
+                "Migrating {} from {:?} to {:?}...",
+                options.name, step.from, step.to
+            );
+            (step.run)(options, forced)?;
+        }
+
+        open::<V>(options, forced)
+    }
+
+    /// Steps leading from the stored version to `options.version`, if any.
+    fn path(&self, forced: bool) -> Option<Vec<&Step>> {
+        let target = self.options.version;
+        let first = self
+            .steps
+            .iter()
+            .find(|step| (step.probe)(self.options, forced))?;
+
+        let mut path = vec![first];
+        let mut version = first.to;
+        while version != target {
+            // Bounded so that cycles can't loop forever
+            if path.len() == self.steps.len() {
+                return None;
+            }
+            let step = self.steps.iter().find(|step| step.from == version)?;
+            version = step.to;
+            path.push(step);
+        }
+        Some(path)
+    }
+}
+
+fn open<V: StoredVec>(options: ImportOptions, forced: bool) -> Result<V> {
+    if forced {
+        V::import_forced_layout_with(options)
+    } else {
+        V::import_with(options)
+    }
+}
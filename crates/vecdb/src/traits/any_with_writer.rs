@@ -1,9 +1,21 @@
-use crate::{AnyReadableVec, Formattable, ReadableVec, TypedVec, ValueWriter, VecIteratorWriter};
+use crate::{
+    AnyReadableVec, CursorWriter, Formattable, ReadableVec, TypedVec, ValueWriter, WriterFormat,
+};
 
 /// Type-erased trait for vecs that can produce a boxed row-by-row [`ValueWriter`].
 pub trait AnyVecWithWriter: AnyReadableVec {
-    /// Create a value writer that can be advanced row by row
-    fn create_writer(&self, from: Option<i64>, to: Option<i64>) -> Box<dyn ValueWriter + '_>;
+    /// Create a value writer that can be advanced row by row, writing CSV cells
+    fn create_writer(&self, from: Option<i64>, to: Option<i64>) -> Box<dyn ValueWriter + '_> {
+        self.create_writer_with_format(from, to, WriterFormat::Csv)
+    }
+
+    /// Create a value writer in `format`, streaming values through a cursor
+    fn create_writer_with_format(
+        &self,
+        from: Option<i64>,
+        to: Option<i64>,
+        format: WriterFormat,
+    ) -> Box<dyn ValueWriter + '_>;
 }
 
 impl<V> AnyVecWithWriter for V
@@ -12,15 +24,17 @@ where
     V: ReadableVec<V::I, V::T>,
     V::T: Formattable,
 {
-    fn create_writer(&self, from: Option<i64>, to: Option<i64>) -> Box<dyn ValueWriter + '_> {
+    fn create_writer_with_format(
+        &self,
+        from: Option<i64>,
+        to: Option<i64>,
+        format: WriterFormat,
+    ) -> Box<dyn ValueWriter + '_> {
         let from_usize = from.map(|i| self.i64_to_usize(i)).unwrap_or(0);
         let to_usize = to
             .map(|i| self.i64_to_usize(i))
             .unwrap_or_else(|| self.len());
 
-        let values = self.collect_range_at(from_usize, to_usize);
-        Box::new(VecIteratorWriter {
-            iter: values.into_iter(),
-        })
+        Box::new(CursorWriter::new(self, from_usize, to_usize, format))
     }
 }
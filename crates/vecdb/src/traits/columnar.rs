@@ -0,0 +1,54 @@
+use crate::{AnyStoredVec, ImportOptions, Result, Stamp, VecIndex, VecValue};
+
+/// Record types stored as one vec per field in a [`ColumnarVec`](crate::ColumnarVec).
+///
+/// Implemented by `#[derive(Columnar)]`, which also generates the struct of columns.
+pub trait Columnar: VecValue {
+    /// One stored vec per field.
+    type Columns<I: VecIndex>: Columns<I, Self>;
+}
+
+/// Stored vecs holding the fields of `R`, one per field.
+///
+/// Every method applies to all columns, keeping them the same length.
+pub trait Columns<I: VecIndex, R>: Send + Sync + Sized {
+    /// Imports every column, each named `{name}_{field}`.
+    fn import_with(options: ImportOptions) -> Result<Self>;
+
+    /// Imports every column, resetting the ones with a version or format mismatch.
+    fn forced_import_with(options: ImportOptions) -> Result<Self>;
+
+    /// Columns in field order.
+    fn vecs(&self) -> Vec<&dyn AnyStoredVec>;
+
+    /// Columns in field order.
+    fn vecs_mut(&mut self) -> Vec<&mut dyn AnyStoredVec>;
+
+    /// Pushes each field to its column.
+    fn push(&mut self, value: R);
+
+    /// Reads every column over `[from, to)` and zips them into records.
+    ///
+    /// Errors without appending anything with [`Error::Column`](crate::Error::Column),
+    /// holding the field name and the error of its column, if a value can't be read, or
+    /// with [`Error::WrongLength`](crate::Error::WrongLength) if a column doesn't return
+    /// `to - from` values because `[from, to)` isn't within it.
+    fn read_into_at(&self, from: usize, to: usize, buf: &mut Vec<R>) -> Result<()>;
+
+    fn reset_unsaved(&mut self);
+
+    fn is_dirty(&self) -> bool;
+
+    /// Rolls back the most recent change set of each column, in field order.
+    ///
+    /// Columns aren't checked up front: if one fails, the columns before it stay
+    /// rolled back and the others don't, leaving them out of sync.
+    fn rollback(&mut self) -> Result<()>;
+
+    /// Rolls back each column to before `stamp`, in field order, returning the
+    /// oldest stamp reached. Fails halfway like [`Self::rollback`].
+    fn rollback_before(&mut self, stamp: Stamp) -> Result<Stamp>;
+
+    /// Removes every column from the database.
+    fn remove(self) -> Result<()>;
+}
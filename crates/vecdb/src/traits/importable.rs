@@ -18,4 +18,14 @@ pub trait ImportableVec: Sized {
 
     /// Forced import with custom options.
     fn forced_import_with(options: ImportOptions) -> Result<Self>;
+
+    /// Import a vec created by [`Self::forced_import_with`] without resetting it on mismatch.
+    ///
+    /// Defaults to [`Self::import_with`], for vecs whose forced imports store the same
+    /// version as plain ones.
+    #[doc(hidden)]
+    #[inline]
+    fn import_forced_layout_with(options: ImportOptions) -> Result<Self> {
+        Self::import_with(options)
+    }
 }
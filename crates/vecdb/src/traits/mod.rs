@@ -6,9 +6,11 @@ mod any_stored;
 #[cfg(feature = "schemars")]
 mod any_with_schema;
 mod any_with_writer;
+mod columnar;
 mod formattable;
 mod importable;
 mod index;
+mod parseable;
 mod printable;
 mod read_only_clone;
 mod readable;
@@ -28,9 +30,11 @@ pub use any_stored::*;
 #[cfg(feature = "schemars")]
 pub use any_with_schema::*;
 pub use any_with_writer::*;
+pub use columnar::*;
 pub use formattable::*;
 pub use importable::*;
 pub use index::*;
+pub use parseable::*;
 pub use printable::*;
 pub use read_only_clone::*;
 pub use readable::*;
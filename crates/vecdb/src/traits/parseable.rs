@@ -0,0 +1,122 @@
+/// Parsing counterpart of [`Formattable`](crate::Formattable): reads a value back from
+/// the text of a CSV cell or JSON value.
+///
+/// Newtypes implement it by delegating to their inner type:
+///
+/// ```ignore
+/// impl Parseable for Height {
+///     fn parse_str(s: &str) -> Option<Self> {
+///         u32::parse_str(s).map(Self)
+///     }
+/// }
+/// ```
+pub trait Parseable: Sized {
+    /// Parses trimmed text. Primary method — all others derive from it.
+    fn parse_str(s: &str) -> Option<Self>;
+
+    /// Parses an unquoted CSV cell. Override for types with a CSV-only form (e.g., empty cells).
+    #[inline(always)]
+    fn parse_csv(s: &str) -> Option<Self> {
+        Self::parse_str(s.trim())
+    }
+
+    /// Parses a raw JSON value. Override for types with a JSON-only form (e.g., `null`).
+    #[inline(always)]
+    fn parse_json(s: &str) -> Option<Self> {
+        Self::parse_str(s.trim())
+    }
+}
+
+macro_rules! impl_parseable_from_str {
+    ($($t:ty),*) => {
+        $(
+            impl Parseable for $t {
+                #[inline(always)]
+                fn parse_str(s: &str) -> Option<Self> {
+                    s.parse().ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_parseable_from_str!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool
+);
+
+impl<T: Parseable> Parseable for Option<T> {
+    #[inline]
+    fn parse_str(s: &str) -> Option<Self> {
+        if s.is_empty() {
+            Some(None)
+        } else {
+            T::parse_str(s).map(Some)
+        }
+    }
+
+    #[inline]
+    fn parse_csv(s: &str) -> Option<Self> {
+        if s.trim().is_empty() {
+            Some(None)
+        } else {
+            T::parse_csv(s).map(Some)
+        }
+    }
+
+    #[inline]
+    fn parse_json(s: &str) -> Option<Self> {
+        match s.trim() {
+            "null" => Some(None),
+            s => T::parse_json(s).map(Some),
+        }
+    }
+}
+
+/// Arrays are read as JSON arrays (`[1,2,3]`), in CSV cells too.
+impl<T: Parseable, const N: usize> Parseable for [T; N] {
+    fn parse_str(s: &str) -> Option<Self> {
+        let inner = s.strip_prefix('[')?.strip_suffix(']')?;
+        let mut values = Vec::with_capacity(N);
+        if !inner.trim().is_empty() {
+            for element in split_json_elements(inner)? {
+                values.push(T::parse_json(element)?);
+            }
+        }
+        values.try_into().ok()
+    }
+}
+
+/// Splits the inside of a JSON array on its top-level commas.
+fn split_json_elements(s: &str) -> Option<Vec<&str>> {
+    let mut elements = vec![];
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut start = 0;
+    for (i, b) in s.bytes().enumerate() {
+        if in_string {
+            match b {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match b {
+            b'"' => in_string = true,
+            b'[' | b'{' => depth += 1,
+            b']' | b'}' => depth = depth.checked_sub(1)?,
+            b',' if depth == 0 => {
+                elements.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if in_string || depth != 0 {
+        return None;
+    }
+    elements.push(&s[start..]);
+    Some(elements)
+}
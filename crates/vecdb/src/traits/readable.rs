@@ -1,6 +1,6 @@
 use std::ops::AddAssign;
 
-use crate::{AnyVec, VecIndex, VecValue, cursor::Cursor};
+use crate::{AnyVec, Error, Result, VecIndex, VecValue, cursor::Cursor};
 
 /// Default chunk size for chunked iteration (matches PcoVec page size).
 pub const READ_CHUNK_SIZE: usize = 4096;
@@ -254,6 +254,22 @@ pub trait ReadableVec<I: VecIndex, T: VecValue>: AnyVec {
         buf
     }
 
+    /// Like [`Self::read_into_at`], but errors if a value in `[from, to)` (clamped to
+    /// `len()`) can't be read instead of stopping before it. Defaults to
+    /// [`Error::WrongLength`] when fewer values were appended, compressed vecs override it
+    /// to return why a page can't be read (object-safe).
+    #[inline]
+    fn try_read_into_at(&self, from: usize, to: usize, buf: &mut Vec<T>) -> Result<()> {
+        let expected = to.min(self.len()).saturating_sub(from);
+        let start = buf.len();
+        self.read_into_at(from, to, buf);
+        let received = buf.len() - start;
+        if received != expected {
+            return Err(Error::WrongLength { received, expected });
+        }
+        Ok(())
+    }
+
     /// Collects all values into a `Vec<T>`.
     #[inline]
     fn collect(&self) -> Vec<T>
@@ -453,6 +469,293 @@ pub trait ReadableVec<I: VecIndex, T: VecValue>: AnyVec {
         });
         has_values.then_some(result)
     }
+
+    // ── Searches ───────────────────────────────────────────────────
+
+    /// Returns the first index in `[from, to)` by raw index whose value fails `pred`, or `to`
+    /// (clamped to `len()`) if none does. Like [`slice::partition_point`], `pred` must hold
+    /// for a (possibly empty) prefix of the range and fail for the rest.
+    ///
+    /// Bisects with `collect_one_at`, values that can't be read counting as failing `pred`.
+    /// Compressed vecs override it to bisect pages by their first value, then decode a
+    /// single page.
+    #[inline]
+    fn partition_point_range_at<P: FnMut(&T) -> bool>(
+        &self,
+        from: usize,
+        to: usize,
+        mut pred: P,
+    ) -> usize
+    where
+        Self: Sized,
+    {
+        let to = to.min(self.len());
+        partition_point_by(from.min(to), to, |i| {
+            self.collect_one_at(i).is_some_and(|v| pred(&v))
+        })
+    }
+
+    /// Returns the first index whose value fails `pred`, or `len()` if none does,
+    /// see [`Self::partition_point_range_at`].
+    #[inline]
+    fn partition_point<P: FnMut(&T) -> bool>(&self, pred: P) -> I
+    where
+        Self: Sized,
+    {
+        I::from(self.partition_point_range_at(0, self.len(), pred))
+    }
+
+    /// Returns the first index in `[from, to)` whose value is `>= value` in a vec sorted in
+    /// non-decreasing order, or `to` (clamped to `len()`) if there's none.
+    #[inline]
+    fn search_sorted_range(&self, from: I, to: I, value: &T) -> I
+    where
+        Self: Sized,
+        T: PartialOrd,
+    {
+        I::from(self.search_sorted_range_at(from.to_usize(), to.to_usize(), value))
+    }
+
+    /// Returns the first index in `[from, to)` by raw index whose value is `>= value`,
+    /// see [`Self::search_sorted_range`].
+    #[inline]
+    fn search_sorted_range_at(&self, from: usize, to: usize, value: &T) -> usize
+    where
+        Self: Sized,
+        T: PartialOrd,
+    {
+        self.partition_point_range_at(from, to, |v| v < value)
+    }
+
+    /// Returns the first index whose value is `>= value` in a vec sorted in non-decreasing
+    /// order, or `len()` if there's none. This is where `value` would be inserted to keep
+    /// the vec sorted.
+    #[inline]
+    fn search_sorted(&self, value: &T) -> I
+    where
+        Self: Sized,
+        T: PartialOrd,
+    {
+        I::from(self.search_sorted_range_at(0, self.len(), value))
+    }
+
+    // ── Parallel reads ─────────────────────────────────────────────
+
+    /// Number of values parallel reads align their chunks on: a page for compressed vecs,
+    /// [`PAGE_SIZE`](crate::PAGE_SIZE) bytes of values otherwise.
+    #[inline]
+    fn read_chunk_len(&self) -> usize {
+        (crate::PAGE_SIZE / size_of::<T>().max(1)).max(1)
+    }
+
+    /// Folds `[from, to)` by raw index in parallel: each chunk is folded from `identity()`
+    /// with `fold` on a rayon worker, with its own reader, and the chunk results are
+    /// combined in order with `reduce`.
+    #[cfg(feature = "rayon")]
+    fn par_fold_range_at<B, ID, F, R>(
+        &self,
+        from: usize,
+        to: usize,
+        identity: ID,
+        fold: F,
+        reduce: R,
+    ) -> B
+    where
+        Self: Sized + Sync,
+        B: Send,
+        ID: Fn() -> B + Sync + Send,
+        F: Fn(B, T) -> B + Sync + Send,
+        R: Fn(B, B) -> B + Sync + Send,
+    {
+        use rayon::prelude::*;
+        par_chunks(from, to.min(self.len()), self.read_chunk_len())
+            .into_par_iter()
+            .map(|(from, to)| self.fold_range_at(from, to, identity(), &fold))
+            .reduce(&identity, reduce)
+    }
+
+    /// Folds `[from, to)` in parallel, see [`Self::par_fold_range_at`].
+    #[cfg(feature = "rayon")]
+    #[inline]
+    fn par_fold_range<B, ID, F, R>(&self, from: I, to: I, identity: ID, fold: F, reduce: R) -> B
+    where
+        Self: Sized + Sync,
+        B: Send,
+        ID: Fn() -> B + Sync + Send,
+        F: Fn(B, T) -> B + Sync + Send,
+        R: Fn(B, B) -> B + Sync + Send,
+    {
+        self.par_fold_range_at(from.to_usize(), to.to_usize(), identity, fold, reduce)
+    }
+
+    /// Calls `f` in parallel with the first raw index and the values of each chunk of
+    /// `[from, to)`. Chunks are visited in no particular order; each worker reuses its
+    /// own buffer.
+    #[cfg(feature = "rayon")]
+    fn par_for_each_chunk_at<F>(&self, from: usize, to: usize, f: F)
+    where
+        Self: Sized + Sync,
+        F: Fn(usize, &[T]) + Sync + Send,
+    {
+        use rayon::prelude::*;
+        par_chunks(from, to.min(self.len()), self.read_chunk_len())
+            .into_par_iter()
+            .for_each_init(Vec::new, |buf, (from, to)| {
+                buf.clear();
+                self.read_into_at(from, to, buf);
+                f(from, buf);
+            });
+    }
+
+    /// Calls `f` in parallel with the first index and the values of each chunk of
+    /// `[from, to)`, see [`Self::par_for_each_chunk_at`].
+    #[cfg(feature = "rayon")]
+    #[inline]
+    fn par_for_each_chunk<F>(&self, from: I, to: I, f: F)
+    where
+        Self: Sized + Sync,
+        F: Fn(I, &[T]) + Sync + Send,
+    {
+        self.par_for_each_chunk_at(from.to_usize(), to.to_usize(), |i, values| {
+            f(I::from(i), values)
+        })
+    }
+
+    /// Parallel [`Self::min_at`], running it on each chunk so page stats are still used.
+    #[cfg(feature = "rayon")]
+    fn par_min_at(&self, from: usize, to: usize) -> Option<T>
+    where
+        Self: Sized + Sync,
+        T: PartialOrd,
+    {
+        use rayon::prelude::*;
+        par_chunks(from, to.min(self.len()), self.read_chunk_len())
+            .into_par_iter()
+            .map(|(from, to)| self.min_at(from, to))
+            .reduce(
+                || None,
+                |acc, v| match v {
+                    Some(v) => crate::fold_min(acc, v),
+                    None => acc,
+                },
+            )
+    }
+
+    /// Parallel [`Self::min`].
+    #[cfg(feature = "rayon")]
+    #[inline]
+    fn par_min(&self, from: I, to: I) -> Option<T>
+    where
+        Self: Sized + Sync,
+        T: PartialOrd,
+    {
+        self.par_min_at(from.to_usize(), to.to_usize())
+    }
+
+    /// Parallel [`Self::max_at`], running it on each chunk so page stats are still used.
+    #[cfg(feature = "rayon")]
+    fn par_max_at(&self, from: usize, to: usize) -> Option<T>
+    where
+        Self: Sized + Sync,
+        T: PartialOrd,
+    {
+        use rayon::prelude::*;
+        par_chunks(from, to.min(self.len()), self.read_chunk_len())
+            .into_par_iter()
+            .map(|(from, to)| self.max_at(from, to))
+            .reduce(
+                || None,
+                |acc, v| match v {
+                    Some(v) => crate::fold_max(acc, v),
+                    None => acc,
+                },
+            )
+    }
+
+    /// Parallel [`Self::max`].
+    #[cfg(feature = "rayon")]
+    #[inline]
+    fn par_max(&self, from: I, to: I) -> Option<T>
+    where
+        Self: Sized + Sync,
+        T: PartialOrd,
+    {
+        self.par_max_at(from.to_usize(), to.to_usize())
+    }
+
+    /// Parallel [`Self::sum_at`], running it on each chunk so page stats are still used.
+    #[cfg(feature = "rayon")]
+    fn par_sum_at(&self, from: usize, to: usize) -> Option<T>
+    where
+        Self: Sized + Sync,
+        T: AddAssign + From<u8>,
+    {
+        use rayon::prelude::*;
+        par_chunks(from, to.min(self.len()), self.read_chunk_len())
+            .into_par_iter()
+            .map(|(from, to)| self.sum_at(from, to))
+            .reduce(
+                || None,
+                |acc, v| match (acc, v) {
+                    (Some(mut acc), Some(v)) => {
+                        acc += v;
+                        Some(acc)
+                    }
+                    (acc, v) => acc.or(v),
+                },
+            )
+    }
+
+    /// Parallel [`Self::sum`].
+    #[cfg(feature = "rayon")]
+    #[inline]
+    fn par_sum(&self, from: I, to: I) -> Option<T>
+    where
+        Self: Sized + Sync,
+        T: AddAssign + From<u8>,
+    {
+        self.par_sum_at(from.to_usize(), to.to_usize())
+    }
+}
+
+/// Splits `[from, to)` into chunks for parallel reads, a few per rayon thread, whose
+/// bounds are multiples of `unit` (besides `from` and `to`).
+#[cfg(feature = "rayon")]
+fn par_chunks(from: usize, to: usize, unit: usize) -> Vec<(usize, usize)> {
+    if from >= to {
+        return vec![];
+    }
+    let per_chunk = (to - from)
+        .div_ceil(rayon::current_num_threads() * 4)
+        .next_multiple_of(unit)
+        .max(unit);
+    let mut chunks = Vec::with_capacity((to - from).div_ceil(per_chunk) + 1);
+    let mut start = from;
+    while start < to {
+        let end = ((start / per_chunk + 1) * per_chunk).min(to);
+        chunks.push((start, end));
+        start = end;
+    }
+    chunks
+}
+
+/// Returns the first index in `[lo, hi)` for which `pred` fails, or `hi`, assuming
+/// `pred` holds for a (possibly empty) prefix.
+#[inline]
+pub(crate) fn partition_point_by(
+    mut lo: usize,
+    mut hi: usize,
+    mut pred: impl FnMut(usize) -> bool,
+) -> usize {
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if pred(mid) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
 }
 
 /// Trait for readable vectors that can be cloned as trait objects.
@@ -18,7 +18,8 @@ pub trait ValueStrategy<T>: Send + Sync + Clone {
 }
 
 /// Implements `ValueStrategy` for a Bytes-based strategy type.
-/// Used by all Bytes-based strategies (BytesStrategy, LZ4Strategy, ZstdStrategy, PcodecStrategy).
+/// Used by all Bytes-based strategies (BytesStrategy, LZ4Strategy, ZstdStrategy, PcodecStrategy,
+/// EliasFanoStrategy).
 macro_rules! impl_bytes_value_strategy {
     ($strategy:ident, $value_trait:path) => {
         impl<T> $crate::ValueStrategy<T> for $strategy<T>
@@ -34,12 +35,14 @@ macro_rules! impl_bytes_value_strategy {
 
             #[inline(always)]
             fn write_to_vec(value: &T, buf: &mut Vec<u8>) {
-                buf.extend_from_slice(value.to_bytes().as_ref());
+                let start = buf.len();
+                buf.resize(start + size_of::<T>(), 0);
+                value.write_bytes_to(&mut buf[start..]);
             }
 
             #[inline(always)]
             fn write_to_slice(value: &T, dst: &mut [u8]) {
-                dst.copy_from_slice(value.to_bytes().as_ref());
+                value.write_bytes_to(dst);
             }
         }
     };
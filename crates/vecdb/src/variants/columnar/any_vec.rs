@@ -0,0 +1,54 @@
+use crate::{AnyVec, Columnar, Columns, VecIndex, Version, short_type_name};
+
+use super::ColumnarVec;
+
+impl<I, R> AnyVec for ColumnarVec<I, R>
+where
+    I: VecIndex,
+    R: Columnar,
+{
+    #[inline]
+    fn version(&self) -> Version {
+        self.version
+    }
+
+    #[inline]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Length of the shortest column, they only differ if one was modified on its own.
+    #[inline]
+    fn len(&self) -> usize {
+        self.columns
+            .vecs()
+            .into_iter()
+            .map(|vec| vec.len())
+            .min()
+            .unwrap_or_default()
+    }
+
+    #[inline]
+    fn index_type_to_string(&self) -> &'static str {
+        I::to_string()
+    }
+
+    #[inline]
+    fn value_type_to_size_of(&self) -> usize {
+        size_of::<R>()
+    }
+
+    #[inline]
+    fn value_type_to_string(&self) -> &'static str {
+        short_type_name::<R>()
+    }
+
+    #[inline]
+    fn region_names(&self) -> Vec<String> {
+        self.columns
+            .vecs()
+            .into_iter()
+            .flat_map(|vec| vec.region_names())
+            .collect()
+    }
+}
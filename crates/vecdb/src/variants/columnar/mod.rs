@@ -0,0 +1,215 @@
+use std::marker::PhantomData;
+
+use rawdb::Database;
+
+mod any_vec;
+mod readable;
+mod typed;
+
+use crate::{AnyVec, Columnar, Columns, Error, ImportOptions, Result, Stamp, VecIndex, Version};
+
+/// Records stored as one vec per field, kept in sync.
+///
+/// The columns are generated by `#[derive(Columnar)]` on the record type, each field
+/// picking its own vec type with `#[column(...)]` (`BytesVec` by default). Pushes,
+/// truncations, writes and rollbacks apply to every column, and reads zip them back
+/// into records. Single columns can still be scanned on their own through
+/// [`Self::columns`].
+///
+/// # Example
+/// ```rust,ignore
+/// #[derive(Debug, Clone, Columnar)]
+/// struct Trade {
+///     #[column(PcoVec)]
+///     price: f64,
+///     #[column(PcoVec)]
+///     amount: u64,
+///     side: Side,
+/// }
+///
+/// let mut trades: ColumnarVec<usize, Trade> = ColumnarVec::import(&db, "trades", Version::ONE)?;
+/// trades.push(trade);
+/// trades.write()?;
+///
+/// let volume = trades.columns().amount.fold(0, |acc, amount| acc + amount);
+/// ```
+#[derive(Debug)]
+#[must_use = "Vector should be stored to keep data accessible"]
+pub struct ColumnarVec<I, R>
+where
+    I: VecIndex,
+    R: Columnar,
+{
+    name: String,
+    version: Version,
+    columns: R::Columns<I>,
+    _index: PhantomData<fn(I)>,
+}
+
+impl<I, R> ColumnarVec<I, R>
+where
+    I: VecIndex,
+    R: Columnar,
+{
+    pub fn import(db: &Database, name: &str, version: Version) -> Result<Self> {
+        Self::import_with(ImportOptions::new(db, name, version))
+    }
+
+    pub fn import_with(options: ImportOptions) -> Result<Self> {
+        Ok(Self::new(options, R::Columns::import_with(options)?))
+    }
+
+    pub fn forced_import(db: &Database, name: &str, version: Version) -> Result<Self> {
+        Self::forced_import_with(ImportOptions::new(db, name, version))
+    }
+
+    pub fn forced_import_with(options: ImportOptions) -> Result<Self> {
+        Ok(Self::new(options, R::Columns::forced_import_with(options)?))
+    }
+
+    fn new(options: ImportOptions, columns: R::Columns<I>) -> Self {
+        Self {
+            name: options.name.to_string(),
+            version: options.version,
+            columns,
+            _index: PhantomData,
+        }
+    }
+
+    /// The vecs holding each field, for single-column reads.
+    #[inline]
+    pub fn columns(&self) -> &R::Columns<I> {
+        &self.columns
+    }
+
+    /// The vecs holding each field.
+    ///
+    /// Pushing to or truncating a single column leaves the others out of sync.
+    #[inline]
+    pub fn columns_mut(&mut self) -> &mut R::Columns<I> {
+        &mut self.columns
+    }
+
+    #[inline]
+    pub fn push(&mut self, value: R) {
+        self.columns.push(value)
+    }
+
+    /// Pushes a value at the given index, erroring if index != current length.
+    #[inline]
+    pub fn checked_push(&mut self, index: I, value: R) -> Result<()> {
+        let len = self.len();
+        if index.to_usize() != len {
+            return Err(Error::UnexpectedIndex {
+                expected: len,
+                got: index.to_usize(),
+                name: self.name.clone(),
+            });
+        }
+        self.push(value);
+        Ok(())
+    }
+
+    pub fn truncate_if_needed(&mut self, index: I) -> Result<()> {
+        self.truncate_if_needed_at(index.to_usize())
+    }
+
+    pub fn truncate_if_needed_at(&mut self, index: usize) -> Result<()> {
+        self.columns
+            .vecs_mut()
+            .into_iter()
+            .try_for_each(|vec| vec.any_truncate_if_needed_at(index))
+    }
+
+    /// Resets every column, clearing all data.
+    pub fn reset(&mut self) -> Result<()> {
+        self.columns
+            .vecs_mut()
+            .into_iter()
+            .try_for_each(|vec| vec.any_reset())
+    }
+
+    /// Resets uncommitted changes.
+    pub fn reset_unsaved(&mut self) {
+        self.columns.reset_unsaved()
+    }
+
+    /// Returns true if any column has uncommitted changes.
+    pub fn is_dirty(&self) -> bool {
+        self.columns.is_dirty()
+    }
+
+    /// Writes pending changes of every column.
+    /// Returns `Ok(true)` if any data was written.
+    pub fn write(&mut self) -> Result<bool> {
+        let mut written = false;
+        for vec in self.columns.vecs_mut() {
+            written |= vec.write()?;
+        }
+        Ok(written)
+    }
+
+    pub fn flush(&mut self) -> Result<()> {
+        self.columns
+            .vecs_mut()
+            .into_iter()
+            .try_for_each(|vec| vec.flush())
+    }
+
+    #[inline]
+    pub fn stamp(&self) -> Stamp {
+        self.columns
+            .vecs()
+            .into_iter()
+            .map(|vec| vec.stamp())
+            .min()
+            .unwrap_or_default()
+    }
+
+    pub fn stamped_write(&mut self, stamp: Stamp) -> Result<()> {
+        self.columns
+            .vecs_mut()
+            .into_iter()
+            .try_for_each(|vec| vec.stamped_write(stamp))
+    }
+
+    /// Flushes with the given stamp, saving changes to enable rollback.
+    pub fn stamped_write_with_changes(&mut self, stamp: Stamp) -> Result<()> {
+        self.columns
+            .vecs_mut()
+            .into_iter()
+            .try_for_each(|vec| vec.any_stamped_write_with_changes(stamp))
+    }
+
+    /// Rolls back the most recent change set of every column.
+    ///
+    /// Columns are rolled back one after the other, so if one fails the earlier ones
+    /// stay rolled back, see [`Columns::rollback`]. The rest can still be rolled back
+    /// one by one through [`Self::columns_mut`].
+    pub fn rollback(&mut self) -> Result<()> {
+        self.columns.rollback()
+    }
+
+    /// Rolls back every column to before the given stamp, with the same partial
+    /// failures as [`Self::rollback`].
+    pub fn rollback_before(&mut self, stamp: Stamp) -> Result<Stamp> {
+        self.columns.rollback_before(stamp)
+    }
+
+    /// Appends the records in `[from, to)` to `buf` like
+    /// [`ReadableVec::read_into_at`](crate::ReadableVec::read_into_at), but errors if
+    /// a value of a column can't be read or a column returns fewer values than the others,
+    /// see [`Columns::read_into_at`].
+    pub fn try_read_into_at(&self, from: usize, to: usize, buf: &mut Vec<R>) -> Result<()> {
+        let to = to.min(self.len());
+        if from < to {
+            self.columns.read_into_at(from, to, buf)?;
+        }
+        Ok(())
+    }
+
+    /// Removes every column from the database.
+    pub fn remove(self) -> Result<()> {
+        self.columns.remove()
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{AnyVec, Columnar, Columns, READ_CHUNK_SIZE, ReadableVec, Result, VecIndex};
+
+use super::ColumnarVec;
+
+impl<I, R> ReadableVec<I, R> for ColumnarVec<I, R>
+where
+    I: VecIndex,
+    R: Columnar,
+{
+    /// Reads nothing if a value of a column can't be read or a column returns fewer
+    /// values than the others, see [`ColumnarVec::try_read_into_at`] to get the error.
+    #[inline]
+    fn read_into_at(&self, from: usize, to: usize, buf: &mut Vec<R>) {
+        let _ = self.try_read_into_at(from, to, buf);
+    }
+
+    #[inline]
+    fn try_read_into_at(&self, from: usize, to: usize, buf: &mut Vec<R>) -> Result<()> {
+        Self::try_read_into_at(self, from, to, buf)
+    }
+
+    #[inline]
+    fn for_each_range_dyn_at(&self, from: usize, to: usize, f: &mut dyn FnMut(R)) {
+        self.fold_range_at(from, to, (), |(), v| f(v));
+    }
+
+    #[inline]
+    fn fold_range_at<B, F: FnMut(B, R) -> B>(&self, from: usize, to: usize, init: B, mut f: F) -> B
+    where
+        Self: Sized,
+    {
+        self.try_fold_range_at(from, to, init, |acc, v| {
+            Ok::<_, std::convert::Infallible>(f(acc, v))
+        })
+        .unwrap_or_else(|e: std::convert::Infallible| match e {})
+    }
+
+    /// Zips the columns chunk by chunk, so only `READ_CHUNK_SIZE` records are in memory.
+    /// Stops at the first chunk whose columns don't have the same length.
+    fn try_fold_range_at<B, E, F: FnMut(B, R) -> std::result::Result<B, E>>(
+        &self,
+        from: usize,
+        to: usize,
+        init: B,
+        mut f: F,
+    ) -> std::result::Result<B, E>
+    where
+        Self: Sized,
+    {
+        let to = to.min(self.len());
+        let mut buf = Vec::with_capacity(READ_CHUNK_SIZE.min(to.saturating_sub(from)));
+        let mut acc = init;
+        let mut pos = from;
+        while pos < to {
+            let end = (pos + READ_CHUNK_SIZE).min(to);
+            if self.columns.read_into_at(pos, end, &mut buf).is_err() {
+                break;
+            }
+            for v in buf.drain(..) {
+                acc = f(acc, v)?;
+            }
+            pos = end;
+        }
+        Ok(acc)
+    }
+}
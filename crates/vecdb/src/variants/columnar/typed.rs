@@ -0,0 +1,12 @@
+use crate::{Columnar, TypedVec, VecIndex};
+
+use super::ColumnarVec;
+
+impl<I, R> TypedVec for ColumnarVec<I, R>
+where
+    I: VecIndex,
+    R: Columnar,
+{
+    type I = I;
+    type T = R;
+}
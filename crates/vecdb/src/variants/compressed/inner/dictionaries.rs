@@ -0,0 +1,135 @@
+use std::sync::Arc;
+
+use rawdb::{Database, Region};
+
+use crate::{Error, Result};
+
+use super::Page;
+
+/// Compression dictionaries of a compressed vector.
+///
+/// Persisted in their own region as repeated `[id: u8][len: u32][bytes]` entries;
+/// the last entry is the current dictionary, used for newly sealed pages. Older
+/// entries are kept while pages still reference them by id.
+///
+/// The region is only created once a dictionary is trained.
+#[derive(Debug, Clone)]
+pub struct Dictionaries {
+    name: String,
+    region: Option<Region>,
+    vec: Vec<(u8, Arc<[u8]>)>,
+}
+
+impl Dictionaries {
+    const ENTRY_HEADER_SIZE: usize = 1 + size_of::<u32>();
+
+    pub fn import(db: &Database, name: &str) -> Result<Self> {
+        let region = db.get_region(name);
+
+        let mut vec = vec![];
+        if let Some(region) = region.as_ref() {
+            let bytes = region.create_reader().read_all().to_vec();
+            let mut pos = 0;
+            while pos < bytes.len() {
+                if pos + Self::ENTRY_HEADER_SIZE > bytes.len() {
+                    return Err(Self::corrupted(region, bytes.len()));
+                }
+                let id = bytes[pos];
+                let len = u32::from_le_bytes(bytes[pos + 1..pos + 5].try_into().unwrap()) as usize;
+                pos += Self::ENTRY_HEADER_SIZE;
+                if id == 0 || pos + len > bytes.len() {
+                    return Err(Self::corrupted(region, bytes.len()));
+                }
+                vec.push((id, Arc::from(&bytes[pos..pos + len])));
+                pos += len;
+            }
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            region,
+            vec,
+        })
+    }
+
+    fn corrupted(region: &Region, region_len: usize) -> Error {
+        Error::CorruptedRegion {
+            name: region.meta().id().to_string(),
+            region_len,
+        }
+    }
+
+    #[inline]
+    pub fn get(&self, id: u8) -> Option<&Arc<[u8]>> {
+        self.vec
+            .iter()
+            .find(|(i, _)| *i == id)
+            .map(|(_, dict)| dict)
+    }
+
+    /// Dictionary used for newly compressed pages.
+    #[inline]
+    pub fn current(&self) -> Option<(u8, &Arc<[u8]>)> {
+        self.vec.last().map(|(id, dict)| (*id, dict))
+    }
+
+    /// Stores `dict` as the new current dictionary and returns its id.
+    /// `in_use` tells which ids are still referenced by pages and can't be reused.
+    pub fn push(
+        &mut self,
+        db: &Database,
+        dict: Vec<u8>,
+        in_use: impl Fn(u8) -> bool,
+    ) -> Result<u8> {
+        let id = (1..=Page::MAX_DICTIONARY_ID)
+            .find(|id| self.get(*id).is_none() && !in_use(*id))
+            .ok_or(Error::InvalidArgument(
+                "too many dictionaries in use, recompress pages before training again",
+            ))?;
+        if self.region.is_none() {
+            self.region = Some(db.create_region_if_needed(&self.name)?);
+        }
+        self.vec.push((id, Arc::from(dict)));
+        self.flush()?;
+        Ok(id)
+    }
+
+    /// Drops every dictionary but the current one for which `in_use` is false.
+    pub fn retain_in_use(&mut self, in_use: impl Fn(u8) -> bool) -> Result<()> {
+        let current = self.current().map(|(id, _)| id);
+        let len = self.vec.len();
+        self.vec
+            .retain(|(id, _)| Some(*id) == current || in_use(*id));
+        if self.vec.len() != len {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn flush(&self) -> Result<()> {
+        let mut bytes = vec![];
+        for (id, dict) in &self.vec {
+            bytes.push(*id);
+            bytes.extend((dict.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(dict);
+        }
+
+        if let Some(region) = self.region.as_ref() {
+            region.truncate_write(0, &bytes)?;
+        }
+
+        Ok(())
+    }
+
+    /// Region name, if a dictionary was ever stored.
+    pub fn region_name(&self) -> Option<&str> {
+        self.region.as_ref().map(|_| self.name.as_str())
+    }
+
+    pub fn remove(self) -> Result<()> {
+        if let Some(region) = self.region {
+            region.remove()?;
+        }
+        Ok(())
+    }
+}
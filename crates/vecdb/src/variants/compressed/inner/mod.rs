@@ -1,9 +1,11 @@
+mod dictionaries;
 mod page;
 mod pages;
 mod read_only;
 mod read_write;
 mod strategy;
 
+pub use dictionaries::*;
 pub use page::*;
 pub use pages::*;
 pub use read_only::*;
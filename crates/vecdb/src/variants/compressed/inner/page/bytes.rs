@@ -3,20 +3,23 @@ use crate::{Bytes, Error, Result};
 use super::Page;
 
 impl Bytes for Page {
-    type Array = [u8; size_of::<Self>()];
+    type Array = [u8; Page::SIZE_WITH_CHECKSUM];
 
+    /// Checksum in the last 4 bytes, dropped by pages without checksums.
     fn to_bytes(&self) -> Self::Array {
-        let mut bytes = [0u8; 16];
+        let mut bytes = [0u8; Page::SIZE_WITH_CHECKSUM];
         bytes[0..8].copy_from_slice(&self.start.to_bytes());
         bytes[8..12].copy_from_slice(&self.bytes.to_bytes());
         bytes[12..16].copy_from_slice(&self.values.to_bytes());
+        bytes[16..20].copy_from_slice(&self.checksum.to_bytes());
         bytes
     }
 
+    /// Reads the checksum only if `bytes` holds one.
     fn from_bytes(bytes: &[u8]) -> Result<Self> {
-        if bytes.len() < size_of::<Page>() {
+        if bytes.len() < Page::SIZE {
             return Err(Error::WrongLength {
-                expected: size_of::<Page>(),
+                expected: Page::SIZE,
                 received: bytes.len(),
             });
         }
@@ -24,11 +27,17 @@ impl Bytes for Page {
         let start = u64::from_bytes(&bytes[0..8])?;
         let bytes_val = u32::from_bytes(&bytes[8..12])?;
         let values = u32::from_bytes(&bytes[12..16])?;
+        let checksum = if bytes.len() >= Page::SIZE_WITH_CHECKSUM {
+            u32::from_bytes(&bytes[16..20])?
+        } else {
+            0
+        };
 
         Ok(Self {
             start,
             bytes: bytes_val,
             values,
+            checksum,
         })
     }
 }
@@ -1,10 +1,15 @@
 mod bytes;
+mod stats;
+
+pub use stats::*;
 
 /// Metadata for a page in a CompressedVec.
 ///
 /// Each page stores a chunk of values, either compressed or raw.
 /// The high bit of `values` encodes whether the page is raw (uncompressed).
 /// Raw pages are used for the last partial page to avoid recompression on every write.
+/// The next 7 bits hold the id of the dictionary a compressed page was encoded with (0 if none).
+/// `checksum` is only persisted by vecs created with page checksums, it is 0 otherwise.
 #[derive(Debug, Clone, Copy)]
 #[repr(C)]
 pub struct Page {
@@ -12,28 +17,64 @@ pub struct Page {
     pub start: u64,
     /// Number of bytes on disk (compressed or raw)
     pub bytes: u32,
-    /// Number of values in this page. High bit encodes raw flag.
+    /// Number of values in this page. High bit encodes raw flag, next 7 bits the dictionary id.
     pub(super) values: u32,
+    /// CRC32C of the page data on disk
+    pub checksum: u32,
 }
 
 impl Page {
     const RAW_FLAG: u32 = 1 << 31;
+    const DICTIONARY_SHIFT: u32 = 24;
+    const DICTIONARY_MASK: u32 = 0x7F << Self::DICTIONARY_SHIFT;
+
+    /// Serialized size without and with checksum.
+    pub const SIZE: usize = 16;
+    pub const SIZE_WITH_CHECKSUM: usize = Self::SIZE + size_of::<u32>();
+
+    /// Maximum number of values a single page can hold.
+    pub const MAX_VALUES: usize = (1 << Self::DICTIONARY_SHIFT) - 1;
+
+    /// Largest dictionary id that fits in the page metadata.
+    pub const MAX_DICTIONARY_ID: u8 = 0x7F;
 
     pub fn compressed(start: u64, bytes: u32, values: u32) -> Self {
-        debug_assert!(values & Self::RAW_FLAG == 0, "values too large");
+        debug_assert!(values as usize <= Self::MAX_VALUES, "values too large");
         Self {
             start,
             bytes,
             values,
+            checksum: 0,
+        }
+    }
+
+    /// A compressed page encoded with dictionary `dictionary_id` (1..=127).
+    pub fn compressed_with_dictionary(
+        start: u64,
+        bytes: u32,
+        values: u32,
+        dictionary_id: u8,
+    ) -> Self {
+        debug_assert!(values as usize <= Self::MAX_VALUES, "values too large");
+        debug_assert!(
+            (1..=Self::MAX_DICTIONARY_ID).contains(&dictionary_id),
+            "invalid dictionary id"
+        );
+        Self {
+            start,
+            bytes,
+            values: values | ((dictionary_id as u32) << Self::DICTIONARY_SHIFT),
+            checksum: 0,
         }
     }
 
     pub fn raw(start: u64, bytes: u32, values: u32) -> Self {
-        debug_assert!(values & Self::RAW_FLAG == 0, "values too large");
+        debug_assert!(values as usize <= Self::MAX_VALUES, "values too large");
         Self {
             start,
             bytes,
             values: values | Self::RAW_FLAG,
+            checksum: 0,
         }
     }
 
@@ -44,7 +85,36 @@ impl Page {
 
     #[inline]
     pub fn values_count(&self) -> u32 {
-        self.values & !Self::RAW_FLAG
+        self.values & !(Self::RAW_FLAG | Self::DICTIONARY_MASK)
+    }
+
+    /// Id of the dictionary this page was compressed with, if any.
+    #[inline]
+    pub fn dictionary_id(&self) -> Option<u8> {
+        match (self.values & Self::DICTIONARY_MASK) >> Self::DICTIONARY_SHIFT {
+            0 => None,
+            id => Some(id as u8),
+        }
+    }
+
+    /// Sets the checksum of the page data.
+    #[inline]
+    pub fn with_checksum(mut self, data: &[u8]) -> Self {
+        self.checksum = crc32c::crc32c(data);
+        self
+    }
+
+    /// Extends the checksum with `data` appended to the page.
+    #[inline]
+    pub fn with_appended_checksum(mut self, data: &[u8]) -> Self {
+        self.checksum = crc32c::crc32c_append(self.checksum, data);
+        self
+    }
+
+    /// Whether `data` matches the stored checksum.
+    #[inline]
+    pub fn checksum_matches(&self, data: &[u8]) -> bool {
+        crc32c::crc32c(data) == self.checksum
     }
 
     #[inline]
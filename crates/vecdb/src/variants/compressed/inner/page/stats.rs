@@ -0,0 +1,15 @@
+/// Statistics of a sealed page, stored by compressed vecs created with
+/// [`ImportOptions::with_page_stats`](crate::ImportOptions::with_page_stats).
+///
+/// `min`, `max` and `sum` skip nulls (NaN floats), which are counted in `nulls`.
+/// A page of only nulls has its first value as `min` and `max`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageStats<T> {
+    pub min: T,
+    pub max: T,
+    /// Wrapping for integers.
+    pub sum: T,
+    /// Number of values in the page, nulls included.
+    pub count: u32,
+    pub nulls: u32,
+}
@@ -2,7 +2,7 @@ use rawdb::{Database, Region, unlikely};
 
 use crate::{Bytes, Error, HEADER_OFFSET, Result};
 
-use super::Page;
+use super::{Dictionaries, Page};
 
 /// Manages page metadata for compressed vectors.
 ///
@@ -15,41 +15,90 @@ use super::Page;
 pub struct Pages {
     region: Region,
     vec: Vec<Page>,
+    /// Number of values in every page but the last
+    per_page: usize,
+    /// Whether pages store a checksum of their data
+    checksums: bool,
+    /// Size of the serialized stats after each page entry, 0 if pages don't store them
+    stats_size: usize,
+    /// Serialized stats of every page, `stats_size` bytes each. Zeroed for raw pages.
+    stats: Vec<u8>,
+    dictionaries: Dictionaries,
     /// Index of first changed page, or None if no changes
     change_at: Option<usize>,
 }
 
 impl Pages {
-    const SIZE_OF_PAGE: usize = size_of::<Page>();
-
-    pub fn import(db: &Database, name: &str) -> Result<Self> {
+    pub fn import(
+        db: &Database,
+        name: &str,
+        dictionaries_name: &str,
+        per_page: usize,
+        checksums: bool,
+        stats_size: usize,
+    ) -> Result<Self> {
         let region = db.create_region_if_needed(name)?;
-
-        let vec = region
-            .create_reader()
-            .read_all()
-            .chunks(Self::SIZE_OF_PAGE)
-            .map(Page::from_bytes)
-            .collect::<Result<_>>()?;
+        let (vec, stats) = Self::read_entries(&region, checksums, stats_size)?;
 
         Ok(Self {
             region,
             vec,
+            per_page,
+            checksums,
+            stats_size,
+            stats,
+            dictionaries: Dictionaries::import(db, dictionaries_name)?,
             change_at: None,
         })
     }
 
+    /// Reads the pages and their serialized stats from `region`, without importing the vec.
+    pub fn read_entries(
+        region: &Region,
+        checksums: bool,
+        stats_size: usize,
+    ) -> Result<(Vec<Page>, Vec<u8>)> {
+        let page_size = Self::entry_size(checksums);
+        let mut stats = vec![];
+        let vec = region
+            .create_reader()
+            .read_all()
+            .chunks(page_size + stats_size)
+            .map(|entry| {
+                let (page, page_stats) = entry.split_at(page_size.min(entry.len()));
+                stats.extend_from_slice(page_stats);
+                Page::from_bytes(page)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if stats.len() != vec.len() * stats_size {
+            return Err(Error::CorruptedRegion {
+                name: region.meta().id().to_string(),
+                region_len: region.meta().len(),
+            });
+        }
+        Ok((vec, stats))
+    }
+
+    /// Size of the serialized stats of a page of values of `value_size` bytes.
+    #[inline(always)]
+    pub const fn stats_size(value_size: usize) -> usize {
+        3 * value_size + size_of::<u32>()
+    }
+
     pub fn flush(&mut self) -> Result<()> {
         let Some(change_at) = self.change_at.take() else {
             return Ok(());
         };
 
-        let at = change_at * Self::SIZE_OF_PAGE;
+        let page_size = Self::entry_size(self.checksums);
+        let entry_size = page_size + self.stats_size;
+        let at = change_at * entry_size;
         let pages_to_write = self.vec.len() - change_at;
 
-        let mut bytes = Vec::with_capacity(pages_to_write * Self::SIZE_OF_PAGE);
-        for page in &self.vec[change_at..] {
-            bytes.extend_from_slice(&page.to_bytes());
+        let mut bytes = Vec::with_capacity(pages_to_write * entry_size);
+        for (page_index, page) in self.vec.iter().enumerate().skip(change_at) {
+            bytes.extend_from_slice(&page.to_bytes()[..page_size]);
+            bytes.extend_from_slice(self.stats_bytes(page_index));
         }
 
         self.region.truncate_write(at, &bytes)?;
@@ -61,6 +110,131 @@ impl Pages {
         self.vec.len()
     }
 
+    #[inline(always)]
+    pub fn per_page(&self) -> usize {
+        self.per_page
+    }
+
+    #[inline(always)]
+    fn entry_size(checksums: bool) -> usize {
+        if checksums {
+            Page::SIZE_WITH_CHECKSUM
+        } else {
+            Page::SIZE
+        }
+    }
+
+    #[inline(always)]
+    pub fn has_checksums(&self) -> bool {
+        self.checksums
+    }
+
+    #[inline(always)]
+    pub fn has_stats(&self) -> bool {
+        self.stats_size != 0
+    }
+
+    /// Serialized stats of page `page_index`, empty if pages don't store them.
+    #[inline]
+    pub fn stats_bytes(&self, page_index: usize) -> &[u8] {
+        let at = page_index * self.stats_size;
+        &self.stats[at..at + self.stats_size]
+    }
+
+    /// Replaces the serialized stats of page `page_index`, ignored if pages don't store them.
+    pub fn set_stats(&mut self, page_index: usize, bytes: &[u8]) {
+        if self.stats_size == 0 {
+            return;
+        }
+        debug_assert_eq!(bytes.len(), self.stats_size);
+        let at = page_index * self.stats_size;
+        self.stats[at..at + self.stats_size].copy_from_slice(bytes);
+        self.set_changed_at(page_index);
+    }
+
+    /// Sets the checksum of `page` to the one of `data` if pages store them.
+    #[inline]
+    pub fn checksummed(&self, page: Page, data: &[u8]) -> Page {
+        if self.checksums {
+            page.with_checksum(data)
+        } else {
+            page
+        }
+    }
+
+    /// Switches pages to storing checksums, computing the one of every existing page from
+    /// `data`, its stored bytes. Written on the next flush.
+    pub fn add_checksums<'a>(&mut self, data: impl Fn(&Page) -> &'a [u8]) {
+        if self.checksums {
+            return;
+        }
+        self.checksums = true;
+        self.vec
+            .iter_mut()
+            .for_each(|page| *page = page.with_checksum(data(page)));
+        self.set_changed_at(0);
+    }
+
+    /// Switches pages to storing `stats_size` bytes of stats, zeroed until set with
+    /// [`Self::set_stats`]. Written on the next flush.
+    pub fn add_stats(&mut self, stats_size: usize) {
+        if self.has_stats() {
+            return;
+        }
+        self.stats_size = stats_size;
+        self.stats = vec![0; self.vec.len() * stats_size];
+        self.set_changed_at(0);
+    }
+
+    /// Checks `data` against the checksum of page `page_index`, if pages store them.
+    #[inline]
+    pub fn verify(&self, page_index: usize, page: &Page, data: &[u8]) -> Result<()> {
+        if self.checksums && unlikely(!page.checksum_matches(data)) {
+            return Err(self.checksum_mismatch(page_index));
+        }
+        Ok(())
+    }
+
+    fn checksum_mismatch(&self, page_index: usize) -> Error {
+        Error::ChecksumMismatch {
+            name: self.region.meta().id().to_string(),
+            page: page_index,
+        }
+    }
+
+    #[inline(always)]
+    pub fn dictionaries(&self) -> &Dictionaries {
+        &self.dictionaries
+    }
+
+    #[inline(always)]
+    pub fn mut_dictionaries(&mut self) -> &mut Dictionaries {
+        &mut self.dictionaries
+    }
+
+    /// Dictionary `page` was compressed with. Errors if the page references a missing one.
+    #[inline]
+    pub fn dictionary_for(&self, page: &Page) -> Result<Option<&[u8]>> {
+        match page.dictionary_id() {
+            None => Ok(None),
+            Some(id) => self
+                .dictionaries
+                .get(id)
+                .map(|dictionary| Some(dictionary.as_ref()))
+                .ok_or(Error::MissingDictionary(id)),
+        }
+    }
+
+    #[inline(always)]
+    pub fn index_to_page_index(&self, index: usize) -> usize {
+        index / self.per_page
+    }
+
+    #[inline(always)]
+    pub fn page_index_to_index(&self, page_index: usize) -> usize {
+        page_index * self.per_page
+    }
+
     pub fn get(&self, page_index: usize) -> Option<&Page> {
         self.vec.get(page_index)
     }
@@ -82,6 +256,7 @@ impl Pages {
         self.set_changed_at(page_index);
 
         self.vec.push(page);
+        self.stats.resize(self.vec.len() * self.stats_size, 0);
         Ok(())
     }
 
@@ -98,6 +273,7 @@ impl Pages {
     pub fn truncate(&mut self, page_index: usize) -> Option<Page> {
         let page = self.get(page_index).cloned();
         self.vec.truncate(page_index);
+        self.stats.truncate(self.vec.len() * self.stats_size);
         self.set_changed_at(page_index);
         page
     }
@@ -106,16 +282,23 @@ impl Pages {
         self.last().map_or(HEADER_OFFSET as u64, |page| page.end())
     }
 
-    pub fn stored_len(&self, per_page: usize) -> usize {
+    pub fn stored_len(&self) -> usize {
         if let Some(last) = self.last() {
-            (self.len() - 1) * per_page + last.values_count() as usize
+            (self.len() - 1) * self.per_page + last.values_count() as usize
         } else {
             0
         }
     }
 
+    /// Replaces the metadata of an existing page.
+    pub fn replace(&mut self, page_index: usize, page: Page) {
+        self.vec[page_index] = page;
+        self.set_changed_at(page_index);
+    }
+
     pub fn remove(self) -> Result<()> {
         self.region.remove()?;
+        self.dictionaries.remove()?;
         Ok(())
     }
 }
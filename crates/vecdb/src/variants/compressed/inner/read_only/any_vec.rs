@@ -42,6 +42,14 @@ where
     fn region_names(&self) -> Vec<String> {
         let base = vec_region_name(self.base.name(), I::to_string());
         let pages = format!("{base}_pages");
-        vec![base, pages]
+        let mut names = vec![base, pages];
+        names.extend(
+            self.pages
+                .read()
+                .dictionaries()
+                .region_name()
+                .map(str::to_string),
+        );
+        names
     }
 }
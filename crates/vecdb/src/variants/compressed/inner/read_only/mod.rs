@@ -1,17 +1,18 @@
 use std::{marker::PhantomData, sync::Arc};
 
 use parking_lot::RwLock;
+use rawdb::Reader;
 
 mod any_vec;
 mod readable;
 mod typed;
 
 use crate::{
-    CompressedIoSource, CompressedMmapSource, MMAP_CROSSOVER_BYTES, ReadOnlyBaseVec, VecIndex,
-    VecValue,
+    CompressedIoSource, CompressedMmapSource, Error, MMAP_CROSSOVER_BYTES, PageStats,
+    ReadOnlyBaseVec, Result, VecIndex, VecValue,
 };
 
-use super::{CompressionStrategy, Pages};
+use super::{CompressionStrategy, Pages, ReadWriteCompressedVec, StatsRead, StatsSegment};
 
 /// Lean read-only view of a compressed vector (~48 bytes).
 ///
@@ -42,6 +43,82 @@ where
     T: VecValue,
     S: CompressionStrategy<T>,
 {
+    #[inline]
+    pub(crate) fn create_reader(&self) -> Reader {
+        self.base.region().create_reader()
+    }
+
+    #[inline]
+    pub(crate) fn pages(&self) -> &Arc<RwLock<Pages>> {
+        &self.pages
+    }
+
+    /// Appends the values in `[from, to)` to `buf` like
+    /// [`ReadableVec::read_into_at`](crate::ReadableVec::read_into_at), but errors on the
+    /// first page that fails its checksum or doesn't decode, with the values before it
+    /// appended.
+    pub fn try_read_into_at(&self, from: usize, to: usize, buf: &mut Vec<T>) -> Result<()> {
+        let len = self.base.len();
+        let from = from.min(len);
+        let to = to.min(len);
+        if from >= to {
+            return Ok(());
+        }
+        buf.reserve(to - from);
+
+        let reader = self.create_reader();
+        let pages = self.pages.read();
+        ReadWriteCompressedVec::<I, T, S>::read_stored_pages_into(&reader, &pages, from, to, buf)
+    }
+
+    /// Like [`ReadableVec::partition_point_range_at`](crate::ReadableVec::partition_point_range_at),
+    /// but errors on the first page read by the search that fails its checksum or doesn't
+    /// decode.
+    pub fn try_partition_point_range_at(
+        &self,
+        from: usize,
+        to: usize,
+        pred: impl FnMut(&T) -> bool,
+    ) -> Result<usize> {
+        self.partition_point_range_with(from, to, pred, Err)
+    }
+
+    fn partition_point_range_with(
+        &self,
+        from: usize,
+        to: usize,
+        pred: impl FnMut(&T) -> bool,
+        on_error: impl FnMut(Error) -> Result<()>,
+    ) -> Result<usize> {
+        let to = to.min(self.base.len());
+        ReadWriteCompressedVec::<I, T, S>::partition_point_stored(
+            &self.create_reader(),
+            &self.pages.read(),
+            from.min(to),
+            to,
+            pred,
+            on_error,
+        )
+    }
+
+    /// Stats of sealed page `page_index`, `None` for raw pages or if pages don't store them.
+    pub fn page_stats(&self, page_index: usize) -> Option<PageStats<T>> {
+        ReadWriteCompressedVec::<I, T, S>::page_stats_with(&self.pages.read(), page_index)
+    }
+
+    /// Calls `f` with the index and value of every value in `[from, to)`, skipping the
+    /// sealed pages for which `may_match` returns false. Pages without stats or with
+    /// nulls are always visited.
+    pub fn for_each_where_at(
+        &self,
+        from: usize,
+        to: usize,
+        may_match: impl FnMut(&PageStats<T>) -> bool,
+        f: impl FnMut(usize, T),
+    ) {
+        self.stats_for_each_where_at(from, to, may_match, f);
+    }
+
     #[inline(always)]
     pub(super) fn fold_source<B, F: FnMut(B, T) -> B>(
         &self,
@@ -104,3 +181,26 @@ where
         }
     }
 }
+
+impl<I, T, S> StatsRead<I, T> for ReadOnlyCompressedVec<I, T, S>
+where
+    I: VecIndex,
+    T: VecValue,
+    S: CompressionStrategy<T>,
+{
+    fn stats_segments_at(&self, from: usize, to: usize) -> Vec<StatsSegment<T>> {
+        let len = self.base.len();
+        let from = from.min(len);
+        let to = to.min(len);
+        if from >= to {
+            return vec![];
+        }
+        ReadWriteCompressedVec::<I, T, S>::stats_segments(
+            &self.pages.read(),
+            from,
+            to,
+            len,
+            |_, _| false,
+        )
+    }
+}
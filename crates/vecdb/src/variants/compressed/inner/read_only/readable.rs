@@ -1,7 +1,9 @@
-use crate::{ReadableVec, VecIndex, VecValue};
+use std::ops::AddAssign;
+
+use crate::{ReadableVec, Result, VecIndex, VecValue};
 
 use super::{
-    super::{CompressionStrategy, ReadWriteCompressedVec},
+    super::{CompressionStrategy, StatsRead},
     ReadOnlyCompressedVec,
 };
 
@@ -11,19 +13,16 @@ where
     T: VecValue,
     S: CompressionStrategy<T>,
 {
+    /// Stops before the first page that fails its checksum or doesn't decode, like folds,
+    /// see [`ReadOnlyCompressedVec::try_read_into_at`] to get the error.
     #[inline(always)]
     fn read_into_at(&self, from: usize, to: usize, buf: &mut Vec<T>) {
-        let len = self.base.len();
-        let from = from.min(len);
-        let to = to.min(len);
-        if from >= to {
-            return;
-        }
-        buf.reserve(to - from);
+        let _ = self.try_read_into_at(from, to, buf);
+    }
 
-        let reader = self.base.region().create_reader();
-        let pages = self.pages.read();
-        ReadWriteCompressedVec::<I, T, S>::read_stored_pages_into(&reader, &pages, from, to, buf);
+    #[inline]
+    fn try_read_into_at(&self, from: usize, to: usize, buf: &mut Vec<T>) -> Result<()> {
+        Self::try_read_into_at(self, from, to, buf)
     }
 
     #[inline]
@@ -64,4 +63,56 @@ where
         }
         self.try_fold_source(from, to, len, init, f)
     }
+
+    #[inline]
+    fn read_chunk_len(&self) -> usize {
+        self.pages.read().per_page()
+    }
+
+    /// Uses the stats of sealed pages, if stored, decoding only the values around them.
+    #[inline]
+    fn min_at(&self, from: usize, to: usize) -> Option<T>
+    where
+        Self: Sized,
+        T: PartialOrd,
+    {
+        self.stats_min_at(from, to)
+    }
+
+    /// Uses the stats of sealed pages, if stored, decoding only the values around them.
+    #[inline]
+    fn max_at(&self, from: usize, to: usize) -> Option<T>
+    where
+        Self: Sized,
+        T: PartialOrd,
+    {
+        self.stats_max_at(from, to)
+    }
+
+    /// Uses the stats of sealed pages, if stored, decoding only the values around them.
+    /// Float sums may differ in the last bits from adding every value in order.
+    #[inline]
+    fn sum_at(&self, from: usize, to: usize) -> Option<T>
+    where
+        Self: Sized,
+        T: AddAssign + From<u8>,
+    {
+        self.stats_sum_at(from, to)
+    }
+
+    /// Bisects the pages by their first value, then decodes a single page. Pages that
+    /// fail their checksum or don't decode count as failing `pred`, like the end of folds,
+    /// see [`ReadOnlyCompressedVec::try_partition_point_range_at`] to get the error.
+    fn partition_point_range_at<P: FnMut(&T) -> bool>(
+        &self,
+        from: usize,
+        to: usize,
+        pred: P,
+    ) -> usize
+    where
+        Self: Sized,
+    {
+        self.partition_point_range_with(from, to, pred, |_| Ok(()))
+            .unwrap_or(to)
+    }
 }
@@ -1,8 +1,11 @@
-use std::{mem, path::PathBuf};
+use std::{mem, path::PathBuf, sync::Arc};
 
 use rawdb::{Database, Region};
 
-use crate::{AnyStoredVec, AnyVec, Error, Header, Result, Stamp, VecIndex, VecValue, WritableVec};
+use crate::{
+    AnyStoredVec, AnyVec, Error, Header, ReadableVec, Result, Stamp, VecIndex, VecValue,
+    WritableVec,
+};
 
 use super::super::{CompressionStrategy, Page};
 use super::ReadWriteCompressedVec;
@@ -45,19 +48,34 @@ where
 
     #[inline]
     fn real_stored_len(&self) -> usize {
-        self.pages.read().stored_len(Self::PER_PAGE)
+        self.pages.read().stored_len()
     }
 
     fn write(&mut self) -> Result<bool> {
         self.base.write_header_if_needed()?;
+        let wrote_updates = self.write_updated()?;
 
         let stored_len = self.stored_len();
         let pushed_len = self.base.pushed().len();
-
-        let (truncate_at, starting_page_index, partial_page) = {
+        S::validate_sequence(
+            || {
+                stored_len
+                    .checked_sub(1)
+                    .and_then(|index| self.collect_one_at(index))
+            },
+            self.base.pushed(),
+            || None,
+        )?;
+
+        let (truncate_at, starting_page_index, partial_page, per_page, dictionary) = {
             let pages = self.pages.read();
+            let per_page = pages.per_page();
+            let dictionary = pages
+                .dictionaries()
+                .current()
+                .map(|(id, dictionary)| (id, Arc::clone(dictionary)));
 
-            let real_stored_len = pages.stored_len(Self::PER_PAGE);
+            let real_stored_len = pages.stored_len();
             if stored_len > real_stored_len {
                 return Err(Error::CorruptedRegion {
                     name: self.name().to_string(),
@@ -66,10 +84,10 @@ where
             }
 
             if pushed_len == 0 && stored_len == real_stored_len {
-                return Ok(false);
+                return Ok(wrote_updates);
             }
 
-            let starting_page_index = Self::index_to_page_index(stored_len);
+            let starting_page_index = pages.index_to_page_index(stored_len);
             if starting_page_index > pages.len() {
                 return Err(Error::CorruptedRegion {
                     name: self.name().to_string(),
@@ -78,7 +96,7 @@ where
             }
 
             if starting_page_index < pages.len() {
-                let partial_len = stored_len % Self::PER_PAGE;
+                let partial_len = stored_len % per_page;
                 let page = *pages
                     .get(starting_page_index)
                     .ok_or(Error::ExpectVecToHaveIndex)?;
@@ -86,13 +104,22 @@ where
                     page.start,
                     starting_page_index,
                     if partial_len != 0 {
-                        Some((page, partial_len))
+                        let page_dictionary = pages.dictionary_for(&page)?.map(Arc::<[u8]>::from);
+                        Some((page, partial_len, page_dictionary))
                     } else {
                         None
                     },
+                    per_page,
+                    dictionary,
                 )
             } else {
-                (pages.next_start(), starting_page_index, None)
+                (
+                    pages.next_start(),
+                    starting_page_index,
+                    None,
+                    per_page,
+                    dictionary,
+                )
             }
         };
         // Pages lock released — decompression happens without blocking readers
@@ -100,10 +127,10 @@ where
         // Fast path: append to existing raw page without reading it back.
         // When the last page is raw, not truncated, and won't overflow, just
         // write the new pushed bytes at the end of the existing page data.
-        if let Some((page, partial_len)) = partial_page
+        if let Some((page, partial_len, _)) = partial_page
             && page.is_raw()
             && partial_len == page.values_count() as usize
-            && partial_len + pushed_len < Self::PER_PAGE
+            && partial_len + pushed_len < per_page
         {
             let taken = mem::take(self.base.mut_pushed());
             let raw = S::values_to_bytes(&taken);
@@ -111,25 +138,28 @@ where
             self.region().truncate_write(append_at, &raw)?;
 
             let mut pages = self.pages.write();
+            let mut appended = Page::raw(
+                page.start,
+                page.bytes + raw.len() as u32,
+                (partial_len + pushed_len) as u32,
+            );
+            if pages.has_checksums() {
+                appended.checksum = page.checksum;
+                appended = appended.with_appended_checksum(&raw);
+            }
             pages.truncate(starting_page_index);
-            pages.checked_push(
-                starting_page_index,
-                Page::raw(
-                    page.start,
-                    page.bytes + raw.len() as u32,
-                    (partial_len + pushed_len) as u32,
-                ),
-            )?;
+            pages.checked_push(starting_page_index, appended)?;
             self.base.update_stored_len(stored_len + pushed_len);
             pages.flush()?;
             return Ok(true);
         }
 
         // Decompress the partial page outside the pages lock.
-        let mut values = if let Some((page, partial_len)) = partial_page {
+        let mut values = if let Some((page, partial_len, page_dictionary)) = partial_page {
             let reader = self.create_reader();
             let data = reader.unchecked_read(page.start as usize, page.bytes as usize);
-            let mut page_values = S::decode_page(data, &page)?;
+            self.pages.read().verify(starting_page_index, &page, data)?;
+            let mut page_values = S::decode_page(data, &page, page_dictionary.as_deref())?;
             page_values.truncate(partial_len);
             page_values
         } else {
@@ -145,14 +175,26 @@ where
             values.extend_from_slice(&taken);
         }
 
-        let num_pages = values.len().div_ceil(Self::PER_PAGE);
+        let num_pages = values.len().div_ceil(per_page);
         let mut buf = Vec::with_capacity(values.len() * Self::SIZE_OF_T);
+        let dictionary_id = dictionary.as_ref().map(|(id, _)| *id);
+        let has_stats = self.pages.read().has_stats();
         let mut page_sizes: Vec<(usize, usize, bool)> = Vec::with_capacity(num_pages);
-        for chunk in values.chunks(Self::PER_PAGE) {
-            if chunk.len() == Self::PER_PAGE {
-                let compressed = Self::compress_page(chunk)?;
+        let mut page_stats = Vec::with_capacity(if has_stats { num_pages } else { 0 });
+        for chunk in values.chunks(per_page) {
+            if chunk.len() == per_page {
+                let compressed = self.compress_page(
+                    chunk,
+                    per_page,
+                    dictionary
+                        .as_ref()
+                        .map(|(_, dictionary)| dictionary.as_ref()),
+                )?;
                 page_sizes.push((compressed.len(), chunk.len(), false));
                 buf.extend_from_slice(&compressed);
+                if has_stats {
+                    page_stats.push(Self::encode_page_stats(chunk)?);
+                }
             } else {
                 let raw = S::values_to_bytes(chunk);
                 page_sizes.push((raw.len(), chunk.len(), true));
@@ -166,14 +208,22 @@ where
         let mut pages = self.pages.write();
         pages.truncate(starting_page_index);
 
+        let mut offset = 0;
         for (i, &(byte_len, values_len, is_raw)) in page_sizes.iter().enumerate() {
             let start = pages.next_start();
             let page = if is_raw {
                 Page::raw(start, byte_len as u32, values_len as u32)
+            } else if let Some(id) = dictionary_id {
+                Page::compressed_with_dictionary(start, byte_len as u32, values_len as u32, id)
             } else {
                 Page::compressed(start, byte_len as u32, values_len as u32)
             };
+            let page = pages.checksummed(page, &buf[offset..offset + byte_len]);
+            offset += byte_len;
             pages.checked_push(starting_page_index + i, page)?;
+            if let Some(stats) = page_stats.get(i) {
+                pages.set_stats(starting_page_index + i, stats);
+            }
         }
 
         self.base.update_stored_len(stored_len + pushed_len);
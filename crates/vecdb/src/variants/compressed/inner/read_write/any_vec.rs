@@ -40,6 +40,14 @@ where
 
     #[inline]
     fn region_names(&self) -> Vec<String> {
-        vec![self.base.index_to_name(), self.pages_region_name()]
+        let mut names = vec![self.base.index_to_name(), self.pages_region_name()];
+        names.extend(
+            self.pages
+                .read()
+                .dictionaries()
+                .region_name()
+                .map(str::to_string),
+        );
+        names
     }
 }
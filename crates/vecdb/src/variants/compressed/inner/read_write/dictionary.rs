@@ -0,0 +1,147 @@
+use crate::{AnyStoredVec, Error, Result, VecIndex, VecValue};
+
+use super::super::{CompressionStrategy, Page, Pages};
+use super::{ReadWriteCompressedVec, update::EncodedPage};
+
+/// Maximum number of stored pages sampled when training a dictionary.
+const MAX_SAMPLED_PAGES: usize = 128;
+
+/// Pages are split into samples of this size, zstd trains best on many small samples.
+const SAMPLE_SIZE: usize = 4 * 1024;
+
+impl<I, T, S> ReadWriteCompressedVec<I, T, S>
+where
+    I: VecIndex,
+    T: VecValue,
+    S: CompressionStrategy<T>,
+{
+    /// Trains a dictionary of at most `max_size` bytes from a sample of the stored
+    /// compressed pages and makes it the current one. Returns its id.
+    ///
+    /// Pages sealed from now on are compressed with it, existing pages keep the
+    /// dictionary they were written with until [`Self::recompress_pages`] is called.
+    ///
+    /// Errors if the format doesn't support dictionaries or if there are no
+    /// compressed pages to sample yet.
+    pub fn train_dictionary(&mut self, max_size: usize) -> Result<u8> {
+        if !S::SUPPORTS_DICTIONARY {
+            return Err(Error::InvalidArgument(
+                "dictionaries are not supported by this format",
+            ));
+        }
+
+        let (samples, sample_sizes) = {
+            let reader = self.create_reader();
+            let pages = self.pages.read();
+            let stored_len = self.stored_len();
+
+            let sealed = (0..pages.len())
+                .filter(|&i| {
+                    pages.page_index_to_index(i + 1) <= stored_len
+                        && pages.get(i).is_some_and(|page| !page.is_raw())
+                })
+                .collect::<Vec<_>>();
+            if sealed.is_empty() {
+                return Err(Error::InvalidArgument(
+                    "no compressed pages to train a dictionary from",
+                ));
+            }
+
+            let step = sealed.len().div_ceil(MAX_SAMPLED_PAGES);
+            let mut samples = vec![];
+            let mut sample_sizes = vec![];
+            for &page_index in sealed.iter().step_by(step) {
+                let values = Self::decode_page_with(stored_len, page_index, &reader, &pages)?;
+                let bytes = S::values_to_bytes(&values);
+                for sample in bytes.chunks(SAMPLE_SIZE) {
+                    sample_sizes.push(sample.len());
+                    samples.extend_from_slice(sample);
+                }
+            }
+            (samples, sample_sizes)
+        };
+
+        let dictionary = S::train_dictionary(&samples, &sample_sizes, max_size)?;
+
+        let db = self.db();
+        let mut pages = self.pages.write();
+        let in_use = Self::dictionaries_in_use(&pages);
+        let dictionaries = pages.mut_dictionaries();
+        let id = dictionaries.push(&db, dictionary, |id| in_use[id as usize])?;
+        dictionaries.retain_in_use(|id| in_use[id as usize])?;
+        Ok(id)
+    }
+
+    /// Id of the dictionary used for newly sealed pages, if one was trained.
+    pub fn dictionary_id(&self) -> Option<u8> {
+        self.pages.read().dictionaries().current().map(|(id, _)| id)
+    }
+
+    /// Recompresses up to `max_pages` stored pages that weren't compressed with the
+    /// current dictionary, oldest first. Returns how many pages were recompressed,
+    /// 0 once every page uses it (or if no dictionary was trained).
+    ///
+    /// Meant to be called repeatedly in small batches, between writes. Dictionaries
+    /// no longer referenced by any page are dropped.
+    pub fn recompress_pages(&mut self, max_pages: usize) -> Result<usize> {
+        let encoded = {
+            let reader = self.create_reader();
+            let pages = self.pages.read();
+            let Some((dictionary_id, dictionary)) = pages.dictionaries().current() else {
+                return Ok(0);
+            };
+            let stored_len = self.stored_len();
+
+            let stale = |i: usize| {
+                pages.page_index_to_index(i + 1) <= stored_len
+                    && pages.get(i).is_some_and(|page| {
+                        !page.is_raw() && page.dictionary_id() != Some(dictionary_id)
+                    })
+            };
+            let Some(first) = (0..pages.len()).find(|&i| stale(i)) else {
+                return Ok(0);
+            };
+
+            (first..pages.len())
+                .take(max_pages)
+                .take_while(|&i| stale(i))
+                .map(|page_index| {
+                    let values = Self::decode_page_with(stored_len, page_index, &reader, &pages)?;
+                    Ok(EncodedPage {
+                        index: page_index,
+                        bytes: self.compress_page(
+                            &values,
+                            pages.per_page(),
+                            Some(dictionary.as_ref()),
+                        )?,
+                        values: values.len() as u32,
+                        raw: false,
+                        dictionary_id: Some(dictionary_id),
+                        stats: None,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?
+        };
+
+        let recompressed = encoded.len();
+        self.rewrite_pages(encoded)?;
+
+        let mut pages = self.pages.write();
+        let in_use = Self::dictionaries_in_use(&pages);
+        pages
+            .mut_dictionaries()
+            .retain_in_use(|id| in_use[id as usize])?;
+
+        Ok(recompressed)
+    }
+
+    fn dictionaries_in_use(pages: &Pages) -> [bool; Page::MAX_DICTIONARY_ID as usize + 1] {
+        let mut in_use = [false; Page::MAX_DICTIONARY_ID as usize + 1];
+        for page_index in 0..pages.len() {
+            if let Some(id) = pages.get(page_index).and_then(Page::dictionary_id) {
+                in_use[id as usize] = true;
+            }
+        }
+        in_use
+    }
+}
@@ -1,4 +1,4 @@
-use std::{marker::PhantomData, sync::Arc};
+use std::{collections::BTreeMap, marker::PhantomData, sync::Arc};
 
 use log::info;
 use parking_lot::RwLock;
@@ -6,25 +6,33 @@ use rawdb::{Reader, likely, unlikely};
 
 mod any_stored_vec;
 mod any_vec;
+mod dictionary;
 mod readable;
 mod rollback;
+mod stats;
 mod typed;
+mod update;
 mod writable;
 
+pub(crate) use stats::*;
+
 use crate::{
-    AnyStoredVec, AnyVec, Error, Format, ImportOptions, MMAP_CROSSOVER_BYTES, ReadWriteBaseVec,
-    Result, VecIndex, VecValue, Version, WritableVec, vec_region_name_with,
+    AnyStoredVec, AnyVec, Error, Format, HEADER_OFFSET, ImportOptions, MMAP_CROSSOVER_BYTES,
+    ReadWriteBaseVec, Result, VecIndex, VecValue, WithPrev, WritableVec, partition_point_by,
+    vec_region_name_with,
 };
 
-use super::{CompressionStrategy, Pages, ReadOnlyCompressedVec};
+use super::{CompressionStrategy, Page, Pages, ReadOnlyCompressedVec};
 
-/// Maximum size in bytes of a single uncompressed page (16 KiB).
+/// Default maximum size in bytes of a single uncompressed page (16 KiB).
 /// Smaller pages reduce memory overhead during decompression and improve
 /// random access performance, while larger pages compress more efficiently.
 /// 16 KiB balances these trade-offs for typical workloads.
+/// Override per vec with [`ImportOptions::with_page_size`].
 pub const MAX_UNCOMPRESSED_PAGE_SIZE: usize = 16 * 1024;
 
-const VERSION: Version = Version::new(3);
+/// Largest configurable page size (1 GiB), keeping page byte lengths within `u32`.
+const MAX_PAGE_SIZE_LIMIT: usize = 1 << 30;
 
 /// Inner implementation for compressed storage vectors.
 /// Parameterized by compression strategy to support different compression algorithms.
@@ -33,6 +41,8 @@ const VERSION: Version = Version::new(3);
 pub struct ReadWriteCompressedVec<I, T, S> {
     pub(super) base: ReadWriteBaseVec<I, T>,
     pub(super) pages: Arc<RwLock<Pages>>,
+    pub(super) updated: WithPrev<BTreeMap<usize, T>>,
+    compression_level: Option<i32>,
     _strategy: PhantomData<S>,
 }
 
@@ -42,8 +52,6 @@ where
     T: VecValue,
     S: CompressionStrategy<T>,
 {
-    pub(super) const PER_PAGE: usize = MAX_UNCOMPRESSED_PAGE_SIZE / Self::SIZE_OF_T;
-
     pub fn read_only_clone(&self) -> ReadOnlyCompressedVec<I, T, S> {
         ReadOnlyCompressedVec::new(self.base.read_only_base(), Arc::clone(&self.pages))
     }
@@ -51,13 +59,19 @@ where
     /// # Warning
     ///
     /// This will DELETE all existing data on format/version errors. Use with caution.
-    pub fn forced_import_with(mut options: ImportOptions, format: Format) -> Result<Self> {
-        options.version = options.version + VERSION;
-        let res = Self::import_with(options, format);
+    ///
+    /// Regions that aren't vecs ([`Error::NotAVec`]), have a damaged header
+    /// ([`Error::CorruptedHeader`]) or an older header ([`Error::OlderHeaderVersion`], see
+    /// [`HeaderV1`](crate::HeaderV1)) are left untouched and the error is returned, like a
+    /// page size other than the stored one ([`Error::DifferentPageSize`], see
+    /// [`convert_format_with`](crate::convert_format_with) to re-page a vec).
+    pub fn forced_import_with(options: ImportOptions, format: Format) -> Result<Self> {
+        let res = Self::import_forced_layout_with(options, format);
         match res {
             Err(Error::WrongEndian)
             | Err(Error::WrongLength { .. })
             | Err(Error::DifferentFormat { .. })
+            | Err(Error::DifferentValueType { .. })
             | Err(Error::DifferentVersion { .. }) => {
                 info!("Resetting {}...", options.name);
                 options
@@ -66,25 +80,75 @@ where
                 options
                     .db
                     .remove_region_if_exists(&Self::pages_region_name_with(options.name))?;
-                Self::import_with(options, format)
+                options
+                    .db
+                    .remove_region_if_exists(&Self::dictionary_region_name_with(options.name))?;
+                Self::import_forced_layout_with(options, format)
             }
             _ => res,
         }
     }
 
+    /// Imports a vec created by [`Self::forced_import_with`], without resetting it, and
+    /// marks its header as [forced](crate::Header::forced_layout).
+    pub fn import_forced_layout_with(mut options: ImportOptions, format: Format) -> Result<Self> {
+        options.version = options.version + format.layout_version();
+        let mut this = Self::import_with(options, format)?;
+        this.base.mut_header().enable_forced_layout();
+        this.base.write_header_if_needed()?;
+        Ok(this)
+    }
+
     #[inline]
     pub fn import_with(mut options: ImportOptions, format: Format) -> Result<Self> {
-        options.version = options.version + VERSION;
+        options.version = options.version + format.layout_version();
         let db = options.db;
         let name = options.name;
 
+        if let Some(page_size) = options.page_size {
+            Self::validate_page_size(page_size)?;
+        }
+        if let Some(level) = options.compression_level {
+            S::validate_compression_level(level)?;
+        }
+        if options.page_stats && !S::SUPPORTS_PAGE_STATS {
+            return Err(Error::InvalidArgument(
+                "page stats are not supported by this format",
+            ));
+        }
+
         let base = ReadWriteBaseVec::import(options, format)?;
 
-        let pages = Pages::import(db, &Self::pages_region_name_with(name))?;
+        let page_size = base
+            .header()
+            .page_size()
+            .unwrap_or(MAX_UNCOMPRESSED_PAGE_SIZE);
+        if let Some(expected) = options.page_size
+            && expected != page_size
+        {
+            return Err(Error::DifferentPageSize {
+                received: page_size,
+                expected,
+            });
+        }
+
+        let checksums = base.header().page_checksums();
+        let stats = base.header().page_stats();
+
+        let pages = Pages::import(
+            db,
+            &Self::pages_region_name_with(name),
+            &Self::dictionary_region_name_with(name),
+            page_size / Self::SIZE_OF_T,
+            checksums,
+            if stats { Self::STATS_SIZE } else { 0 },
+        )?;
 
         let mut this = Self {
             base,
             pages: Arc::new(RwLock::new(pages)),
+            updated: WithPrev::default(),
+            compression_level: options.compression_level,
             _strategy: PhantomData,
         };
 
@@ -92,9 +156,55 @@ where
         *this.base.mut_prev_stored_len() = len;
         this.base.update_stored_len(len);
 
+        if options.page_checksums && !checksums {
+            this.add_page_checksums()?;
+        }
+        if options.page_stats && !stats {
+            this.add_page_stats()?;
+        }
+
         Ok(this)
     }
 
+    /// Adds checksums to the pages of a vec created without them, then writes the pages and
+    /// the header.
+    fn add_page_checksums(&mut self) -> Result<()> {
+        info!("Adding page checksums to {}...", self.name());
+        let reader = self.create_reader();
+        let mut pages = self.pages.write();
+        pages.add_checksums(|page| reader.unchecked_read(page.start as usize, page.bytes as usize));
+        pages.flush()?;
+        drop(pages);
+        drop(reader);
+        self.base.mut_header().enable_page_checksums();
+        self.base.write_header_if_needed()
+    }
+
+    /// Adds stats to the sealed pages of a vec created without them, decoding each once,
+    /// then writes the pages and the header.
+    fn add_page_stats(&mut self) -> Result<()> {
+        info!("Adding page stats to {}...", self.name());
+        let reader = self.create_reader();
+        let stored_len = self.stored_len();
+        let mut pages = self.pages.write();
+        let stats = (0..pages.len())
+            .filter(|&page_index| pages.get(page_index).is_some_and(|page| !page.is_raw()))
+            .map(|page_index| {
+                let values = Self::decode_page_with(stored_len, page_index, &reader, &pages)?;
+                Ok((page_index, Self::encode_page_stats(&values)?))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        pages.add_stats(Self::STATS_SIZE);
+        for (page_index, bytes) in stats {
+            pages.set_stats(page_index, &bytes);
+        }
+        pages.flush()?;
+        drop(pages);
+        drop(reader);
+        self.base.mut_header().enable_page_stats();
+        self.base.write_header_if_needed()
+    }
+
     #[inline]
     pub fn decode_page(&self, page_index: usize, reader: &Reader) -> Result<Vec<T>> {
         Self::decode_page_with(self.stored_len(), page_index, reader, &self.pages.read())
@@ -107,7 +217,7 @@ where
         reader: &Reader,
         pages: &Pages,
     ) -> Result<Vec<T>> {
-        let index = Self::page_index_to_index(page_index);
+        let index = pages.page_index_to_index(page_index);
 
         if unlikely(index >= stored_len) {
             return Err(Error::IndexTooHigh {
@@ -125,32 +235,115 @@ where
             .get(page_index)
             .expect("page should exist after bounds check");
         let data = reader.unchecked_read(page.start as usize, page.bytes as usize);
-        S::decode_page(data, page)
+        pages.verify(page_index, page, data)?;
+        S::decode_page(data, page, pages.dictionary_for(page)?)
+    }
+
+    /// Decodes only the first value of page `page_index`, after checking its checksum.
+    fn decode_first_with(
+        stored_len: usize,
+        page_index: usize,
+        reader: &Reader,
+        pages: &Pages,
+    ) -> Result<T> {
+        let index = pages.page_index_to_index(page_index);
+
+        if unlikely(index >= stored_len) {
+            return Err(Error::IndexTooHigh {
+                index,
+                len: stored_len,
+                name: "page".to_string(),
+            });
+        }
+        let page = pages.get(page_index).ok_or(Error::ExpectVecToHaveIndex)?;
+        let data = reader.unchecked_read(page.start as usize, page.bytes as usize);
+        pages.verify(page_index, page, data)?;
+        S::decode_first(data, page, pages.dictionary_for(page)?)
+    }
+
+    /// Checks that every stored page lies within the region and, if the vec was
+    /// created with page checksums, that its data matches. Doesn't decode values.
+    pub fn verify(&self) -> Result<()> {
+        let region_len = self.region().meta().len() as u64;
+        let reader = self.create_reader();
+        let pages = self.pages.read();
+
+        for page_index in 0..pages.len() {
+            let page = pages
+                .get(page_index)
+                .expect("page should exist within bounds");
+            let is_last = page_index + 1 == pages.len();
+            let values_count = page.values_count() as usize;
+            if page.start < HEADER_OFFSET as u64
+                || page.end() > region_len
+                || values_count > pages.per_page()
+                || (!is_last && values_count != pages.per_page())
+            {
+                return Err(Error::CorruptedRegion {
+                    name: self.pages_region_name(),
+                    region_len: region_len as usize,
+                });
+            }
+            pages.dictionary_for(page)?;
+
+            let data = reader.unchecked_read(page.start as usize, page.bytes as usize);
+            pages.verify(page_index, page, data)?;
+        }
+
+        Ok(())
     }
 
     #[inline]
-    pub(super) fn compress_page(chunk: &[T]) -> Result<Vec<u8>> {
+    pub(super) fn compress_page(
+        &self,
+        chunk: &[T],
+        per_page: usize,
+        dictionary: Option<&[u8]>,
+    ) -> Result<Vec<u8>> {
         debug_assert!(
-            chunk.len() <= Self::PER_PAGE,
-            "chunk length {} exceeds PER_PAGE {}",
+            chunk.len() <= per_page,
+            "chunk length {} exceeds per_page {}",
             chunk.len(),
-            Self::PER_PAGE
+            per_page
         );
 
-        S::compress(chunk)
+        match dictionary {
+            Some(dictionary) => {
+                S::compress_with_dictionary(chunk, self.compression_level, dictionary)
+            }
+            None => S::compress(chunk, self.compression_level),
+        }
     }
 
-    #[inline(always)]
-    pub(crate) fn index_to_page_index(index: usize) -> usize {
-        index / Self::PER_PAGE
+    /// Maximum uncompressed page size in bytes.
+    #[inline]
+    pub fn page_size(&self) -> usize {
+        self.pages.read().per_page() * Self::SIZE_OF_T
     }
 
-    #[inline(always)]
-    pub(crate) fn page_index_to_index(page_index: usize) -> usize {
-        page_index * Self::PER_PAGE
+    #[inline]
+    pub fn compression_level(&self) -> Option<i32> {
+        self.compression_level
+    }
+
+    fn validate_page_size(page_size: usize) -> Result<()> {
+        if !page_size.is_power_of_two() {
+            return Err(Error::InvalidArgument("page size must be a power of two"));
+        }
+        if page_size < Self::SIZE_OF_T || page_size > MAX_PAGE_SIZE_LIMIT {
+            return Err(Error::InvalidArgument(
+                "page size must hold at least one value and be at most 1 GiB",
+            ));
+        }
+        if page_size / Self::SIZE_OF_T > Page::MAX_VALUES {
+            return Err(Error::InvalidArgument("page size holds too many values"));
+        }
+        Ok(())
     }
 
     /// Reads stored page data into a buffer. Used by both ReadWrite and ReadOnly read_into_at.
+    /// Errors on the first page that fails its checksum or doesn't decode, with the values
+    /// of the pages before it appended.
     #[inline(always)]
     pub(crate) fn read_stored_pages_into(
         reader: &Reader,
@@ -158,31 +351,197 @@ where
         from: usize,
         to: usize,
         buf: &mut Vec<T>,
-    ) {
-        let start_page = Self::index_to_page_index(from);
-        let end_page = Self::index_to_page_index(to - 1);
+    ) -> Result<()> {
+        let start_page = pages.index_to_page_index(from);
+        let end_page = pages.index_to_page_index(to - 1);
         for page_idx in start_page..=end_page {
-            let page_start = Self::page_index_to_index(page_idx);
-            let page = pages
-                .get(page_idx)
-                .expect("page should exist after bounds check");
+            let page_start = pages.page_index_to_index(page_idx);
+            let page = pages.get(page_idx).ok_or(Error::ExpectVecToHaveIndex)?;
             let data = reader.unchecked_read(page.start as usize, page.bytes as usize);
+            pages.verify(page_idx, page, data)?;
             let values_count = page.values_count() as usize;
             let local_from = from.saturating_sub(page_start);
             let local_to = (to - page_start).min(values_count);
 
-            if !page.is_raw() && likely(local_from == 0) {
+            let dictionary = pages.dictionary_for(page)?;
+
+            if !page.is_raw() && dictionary.is_none() && likely(local_from == 0) {
                 let before = buf.len();
-                S::decompress_append(data, values_count, buf)
-                    .expect("decompression failed in read_into_at");
+                let decoded = S::decompress_append(data, values_count, buf);
+                let actual_len = buf.len() - before;
+                if decoded.is_err() || actual_len != values_count {
+                    buf.truncate(before);
+                    decoded?;
+                    return Err(Error::DecompressionMismatch {
+                        expected_len: values_count,
+                        actual_len,
+                    });
+                }
                 buf.truncate(before + local_to);
             } else {
                 let mut page_buf = Vec::with_capacity(values_count);
-                S::decode_page_into(data, page, &mut page_buf)
-                    .expect("page decode failed in read_into_at");
+                S::decode_page_into(data, page, dictionary, &mut page_buf)?;
                 buf.extend_from_slice(&page_buf[local_from..local_to]);
             }
         }
+        Ok(())
+    }
+
+    /// Appends the values in `[from, to)` to `buf` like
+    /// [`ReadableVec::read_into_at`](crate::ReadableVec::read_into_at), but errors on the
+    /// first page that fails its checksum or doesn't decode, with the values before it
+    /// appended.
+    pub fn try_read_into_at(&self, from: usize, to: usize, buf: &mut Vec<T>) -> Result<()> {
+        let len = self.base.len();
+        let from = from.min(len);
+        let to = to.min(len);
+        if from >= to {
+            return Ok(());
+        }
+
+        buf.reserve(to - from);
+        let stored_len = self.stored_len();
+
+        if from < stored_len {
+            let stored_to = to.min(stored_len);
+            let reader = self.create_reader();
+            let pages = self.pages.read();
+            let start = buf.len();
+            let read = Self::read_stored_pages_into(&reader, &pages, from, stored_to, buf);
+            let read_to = from + (buf.len() - start);
+            for (&i, v) in self.updated().range(from..read_to) {
+                buf[start + i - from] = v.clone();
+            }
+            read?;
+        }
+
+        if to > stored_len {
+            let push_from = from.max(stored_len);
+            let pushed = self.base.pushed();
+            let start = push_from - stored_len;
+            let end = (to - stored_len).min(pushed.len());
+            buf.extend_from_slice(&pushed[start..end]);
+        }
+
+        Ok(())
+    }
+
+    /// Like [`ReadableVec::partition_point_range_at`](crate::ReadableVec::partition_point_range_at),
+    /// but errors on the first page read by the search that fails its checksum or doesn't
+    /// decode.
+    pub fn try_partition_point_range_at(
+        &self,
+        from: usize,
+        to: usize,
+        pred: impl FnMut(&T) -> bool,
+    ) -> Result<usize> {
+        self.partition_point_range_with(from, to, pred, Err)
+    }
+
+    /// First index in `[from, to)` whose value fails `pred`, or `to` clamped to the length.
+    /// Errors that `on_error` swallows count as failing `pred`, see
+    /// [`Self::partition_point_stored`].
+    pub(crate) fn partition_point_range_with(
+        &self,
+        from: usize,
+        to: usize,
+        mut pred: impl FnMut(&T) -> bool,
+        mut on_error: impl FnMut(Error) -> Result<()>,
+    ) -> Result<usize> {
+        let to = to.min(self.base.len());
+        let from = from.min(to);
+        if self.has_dirty_stored() {
+            // Unsaved updates aren't in the pages, so bisect value by value
+            let mut buf = Vec::with_capacity(1);
+            let mut error = None;
+            let index = partition_point_by(from, to, |i| {
+                buf.clear();
+                match self.try_read_into_at(i, i + 1, &mut buf) {
+                    Ok(()) => pred(&buf[0]),
+                    Err(e) => {
+                        if let Err(e) = on_error(e) {
+                            error.get_or_insert(e);
+                        }
+                        false
+                    }
+                }
+            });
+            return error.map_or(Ok(index), Err);
+        }
+
+        let stored_len = self.stored_len();
+        let stored_to = to.min(stored_len);
+        if from < stored_to {
+            let index = Self::partition_point_stored(
+                &self.create_reader(),
+                &self.pages.read(),
+                from,
+                stored_to,
+                &mut pred,
+                on_error,
+            )?;
+            if index < stored_to {
+                return Ok(index);
+            }
+        }
+        if to <= stored_len {
+            return Ok(to);
+        }
+
+        let push_from = from.max(stored_len);
+        let pushed = &self.base.pushed()[push_from - stored_len..to - stored_len];
+        Ok(push_from + pushed.partition_point(pred))
+    }
+
+    /// First index in `[from, to)` whose stored value fails `pred`, or `to`. `to` must be
+    /// within the stored pages. Used by both ReadWrite and ReadOnly searches.
+    ///
+    /// Bisects the pages by decoding only their first value, then decodes the one page
+    /// holding the partition point. A page that fails its checksum or doesn't decode is
+    /// passed to `on_error`: its error is returned if `on_error` returns it, otherwise the
+    /// page counts as failing `pred`.
+    pub(crate) fn partition_point_stored(
+        reader: &Reader,
+        pages: &Pages,
+        from: usize,
+        to: usize,
+        mut pred: impl FnMut(&T) -> bool,
+        mut on_error: impl FnMut(Error) -> Result<()>,
+    ) -> Result<usize> {
+        if from >= to {
+            return Ok(to);
+        }
+        let stored_len = pages.stored_len();
+
+        let first_page = pages.index_to_page_index(from);
+        let last_page = pages.index_to_page_index(to - 1);
+        let mut error = None;
+        let page_index = partition_point_by(first_page + 1, last_page + 1, |page_index| {
+            match Self::decode_first_with(stored_len, page_index, reader, pages) {
+                Ok(first) => pred(&first),
+                Err(e) => {
+                    if let Err(e) = on_error(e) {
+                        error.get_or_insert(e);
+                    }
+                    false
+                }
+            }
+        }) - 1;
+        if let Some(e) = error {
+            return Err(e);
+        }
+
+        let page_start = pages.page_index_to_index(page_index);
+        let local_from = from.saturating_sub(page_start);
+        let values = match Self::decode_page_with(stored_len, page_index, reader, pages) {
+            Ok(values) => values,
+            Err(e) => {
+                on_error(e)?;
+                return Ok(page_start + local_from);
+            }
+        };
+        let local_to = (to - page_start).min(values.len());
+        Ok(page_start + local_from + values[local_from..local_to].partition_point(pred))
     }
 
     pub(crate) fn pages_region_name(&self) -> String {
@@ -193,6 +552,10 @@ where
         format!("{}_pages", vec_region_name_with::<I>(name))
     }
 
+    fn dictionary_region_name_with(name: &str) -> String {
+        format!("{}_dict", vec_region_name_with::<I>(name))
+    }
+
     pub fn remove(self) -> Result<()> {
         self.base.remove()?;
 
@@ -224,24 +587,28 @@ where
 
         let reader = self.create_reader();
         let pages = self.pages.read();
-        let real_len = pages.stored_len(Self::PER_PAGE);
+        let real_len = pages.stored_len();
         let to = to.min(real_len);
         if from >= to {
             return Ok(vec![]);
         }
 
         let mut result = Vec::with_capacity(to - from);
-        let start_page = Self::index_to_page_index(from);
-        let end_page = Self::index_to_page_index(to - 1);
+        let start_page = pages.index_to_page_index(from);
+        let end_page = pages.index_to_page_index(to - 1);
 
         for page_idx in start_page..=end_page {
-            let page_start = Self::page_index_to_index(page_idx);
+            let page_start = pages.page_index_to_index(page_idx);
             let decoded = Self::decode_page_with(real_len, page_idx, &reader, &pages)?;
             let local_from = from.saturating_sub(page_start);
             let local_to = (to - page_start).min(decoded.len());
             result.extend_from_slice(&decoded[local_from..local_to]);
         }
 
+        for (&i, v) in self.prev_updated().range(from..to) {
+            result[i - from] = v.clone();
+        }
+
         Ok(result)
     }
 
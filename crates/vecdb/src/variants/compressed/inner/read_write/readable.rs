@@ -1,6 +1,8 @@
-use crate::{AnyStoredVec, ReadableVec, VecIndex, VecValue};
+use std::ops::AddAssign;
 
-use super::{super::CompressionStrategy, ReadWriteCompressedVec};
+use crate::{AnyStoredVec, ReadableVec, Result, VecIndex, VecValue};
+
+use super::{super::CompressionStrategy, ReadWriteCompressedVec, StatsRead};
 
 impl<I, T, S> ReadableVec<I, T> for ReadWriteCompressedVec<I, T, S>
 where
@@ -8,32 +10,16 @@ where
     T: VecValue,
     S: CompressionStrategy<T>,
 {
+    /// Stops before the first page that fails its checksum or doesn't decode, like folds,
+    /// see [`ReadWriteCompressedVec::try_read_into_at`] to get the error.
     #[inline(always)]
     fn read_into_at(&self, from: usize, to: usize, buf: &mut Vec<T>) {
-        let len = self.base.len();
-        let from = from.min(len);
-        let to = to.min(len);
-        if from >= to {
-            return;
-        }
-
-        buf.reserve(to - from);
-        let stored_len = self.stored_len();
-
-        if from < stored_len {
-            let stored_to = to.min(stored_len);
-            let reader = self.create_reader();
-            let pages = self.pages.read();
-            Self::read_stored_pages_into(&reader, &pages, from, stored_to, buf);
-        }
+        let _ = self.try_read_into_at(from, to, buf);
+    }
 
-        if to > stored_len {
-            let push_from = from.max(stored_len);
-            let pushed = self.base.pushed();
-            let start = push_from - stored_len;
-            let end = (to - stored_len).min(pushed.len());
-            buf.extend_from_slice(&pushed[start..end]);
-        }
+    #[inline]
+    fn try_read_into_at(&self, from: usize, to: usize, buf: &mut Vec<T>) -> Result<()> {
+        Self::try_read_into_at(self, from, to, buf)
     }
 
     #[inline]
@@ -53,6 +39,10 @@ where
             return init;
         }
 
+        if self.has_dirty_stored() {
+            return self.fold_dirty(from, to, init, f);
+        }
+
         let stored_len = self.stored_len();
 
         if to <= stored_len {
@@ -84,6 +74,10 @@ where
             return Ok(init);
         }
 
+        if self.has_dirty_stored() {
+            return self.try_fold_dirty(from, to, init, f);
+        }
+
         let stored_len = self.stored_len();
 
         if to <= stored_len {
@@ -96,4 +90,57 @@ where
         }
         self.base.try_fold_pushed(from, to, acc, f)
     }
+
+    #[inline]
+    fn read_chunk_len(&self) -> usize {
+        self.pages.read().per_page()
+    }
+
+    /// Uses the stats of sealed pages, if stored, decoding only the values around them.
+    #[inline]
+    fn min_at(&self, from: usize, to: usize) -> Option<T>
+    where
+        Self: Sized,
+        T: PartialOrd,
+    {
+        self.stats_min_at(from, to)
+    }
+
+    /// Uses the stats of sealed pages, if stored, decoding only the values around them.
+    #[inline]
+    fn max_at(&self, from: usize, to: usize) -> Option<T>
+    where
+        Self: Sized,
+        T: PartialOrd,
+    {
+        self.stats_max_at(from, to)
+    }
+
+    /// Uses the stats of sealed pages, if stored, decoding only the values around them.
+    /// Float sums may differ in the last bits from adding every value in order.
+    #[inline]
+    fn sum_at(&self, from: usize, to: usize) -> Option<T>
+    where
+        Self: Sized,
+        T: AddAssign + From<u8>,
+    {
+        self.stats_sum_at(from, to)
+    }
+
+    /// Bisects the stored pages by their first value, then decodes a single page.
+    /// Unsaved updates fall back to bisecting value by value. Pages that fail their
+    /// checksum or don't decode count as failing `pred`, like the end of folds, see
+    /// [`ReadWriteCompressedVec::try_partition_point_range_at`] to get the error.
+    fn partition_point_range_at<P: FnMut(&T) -> bool>(
+        &self,
+        from: usize,
+        to: usize,
+        pred: P,
+    ) -> usize
+    where
+        Self: Sized,
+    {
+        self.partition_point_range_with(from, to, pred, |_| Ok(()))
+            .unwrap_or(to)
+    }
 }
@@ -1,5 +1,8 @@
+use std::collections::BTreeSet;
+
 use crate::{
-    AnyStoredVec, ChangeCursor, ReadWriteBaseVec, Result, VecIndex, VecValue, WritableVec,
+    AnyStoredVec, Bytes, ChangeCursor, ReadWriteBaseVec, Result, SIZE_OF_U64, VecIndex, VecValue,
+    WritableVec,
 };
 
 use super::{super::CompressionStrategy, ReadWriteCompressedVec};
@@ -11,7 +14,7 @@ where
     S: CompressionStrategy<T>,
 {
     pub(super) fn serialize_compressed_changes(&self) -> Result<Vec<u8>> {
-        self.base.serialize_changes(
+        let mut bytes = self.base.serialize_changes(
             Self::SIZE_OF_T,
             |from, to| self.collect_stored_range(from, to),
             |vals, buf| {
@@ -19,7 +22,48 @@ where
                     S::write_to_vec(v, buf);
                 }
             },
-        )
+        )?;
+
+        let updated = self.updated();
+        let prev_updated = self.prev_updated();
+        if updated.is_empty() && prev_updated.is_empty() {
+            return Ok(bytes);
+        }
+
+        // Same section as raw vecs: indices, then the values they had before this write
+        let all_keys: BTreeSet<usize> =
+            updated.keys().chain(prev_updated.keys()).copied().collect();
+
+        bytes.extend(all_keys.len().to_bytes());
+        for &i in &all_keys {
+            bytes.extend(i.to_bytes());
+        }
+
+        let reader = self.create_reader();
+        let pages = self.pages.read();
+        let real_stored_len = pages.stored_len();
+        let mut decoded: Option<(usize, Vec<T>)> = None;
+        for &i in &all_keys {
+            if let Some(v) = prev_updated.get(&i) {
+                S::write_to_vec(v, &mut bytes);
+                continue;
+            }
+            let page_index = pages.index_to_page_index(i);
+            if decoded
+                .as_ref()
+                .is_none_or(|(index, _)| *index != page_index)
+            {
+                let values = Self::decode_page_with(real_stored_len, page_index, &reader, &pages)?;
+                decoded = Some((page_index, values));
+            }
+            let (_, values) = decoded.as_ref().unwrap();
+            S::write_to_vec(
+                &values[i - pages.page_index_to_index(page_index)],
+                &mut bytes,
+            );
+        }
+
+        Ok(bytes)
     }
 
     pub(super) fn deserialize_then_undo_changes(&mut self, bytes: &[u8]) -> Result<()> {
@@ -27,6 +71,16 @@ where
         let change =
             ReadWriteBaseVec::<I, T>::parse_change_data(&mut c, Self::SIZE_OF_T, |b| S::read(b))?;
 
+        // Only written when values were updated
+        let modifications = if c.is_empty() {
+            vec![]
+        } else {
+            let modified_len = c.read_u64()?;
+            let indices = c.read_values(modified_len, SIZE_OF_U64, usize::from_bytes)?;
+            let values = c.read_values(modified_len, Self::SIZE_OF_T, |b| S::read(b))?;
+            indices.into_iter().zip(values).collect::<Vec<_>>()
+        };
+
         // No overlay map: truncated values ride in `pushed` and `stored_len`
         // is clamped to where disk still agrees with the rolled-back state.
         let (stored_len, pushed) = if change.truncated_values.is_empty() {
@@ -37,9 +91,18 @@ where
             buf.extend(change.prev_pushed);
             (agree_at, buf)
         };
+        // Updates past the rolled-back stored length would shadow `pushed`
+        self.truncate_dirty_at(stored_len);
         self.base
             .apply_rollback(change.prev_stamp, stored_len, pushed);
 
+        // Restored values are only valid together, so neighbours aren't checked one by one
+        for (idx, val) in modifications {
+            self.set_at(idx, val)?;
+        }
+
+        self.updated.save();
+
         Ok(())
     }
 }
@@ -0,0 +1,262 @@
+use std::ops::AddAssign;
+
+use crate::{AnyStoredVec, Bytes, ReadableVec, Result, VecIndex, VecValue, WritableVec};
+
+use super::super::{CompressionStrategy, PageStats, Pages};
+use super::ReadWriteCompressedVec;
+
+/// Part of a range folded with page stats, see [`ReadWriteCompressedVec::stats_segments`].
+#[derive(Debug)]
+pub(crate) enum StatsSegment<T> {
+    /// A sealed page within the range, whose values are all summarized by its stats.
+    Page {
+        from: usize,
+        to: usize,
+        stats: PageStats<T>,
+    },
+    /// Values to read one by one.
+    Values { from: usize, to: usize },
+}
+
+impl<T> StatsSegment<T> {
+    /// Folds `segments` in order, passing the stats of pages to `page` and value ranges to `values`.
+    pub(crate) fn fold<B>(
+        segments: Vec<Self>,
+        init: B,
+        mut page: impl FnMut(B, PageStats<T>) -> B,
+        mut values: impl FnMut(B, usize, usize) -> B,
+    ) -> B {
+        segments
+            .into_iter()
+            .fold(init, |acc, segment| match segment {
+                Self::Page { stats, .. } => page(acc, stats),
+                Self::Values { from, to } => values(acc, from, to),
+            })
+    }
+}
+
+/// Range aggregates and predicate scans over the [`StatsSegment`]s of a range, shared by
+/// [`ReadWriteCompressedVec`] and [`ReadOnlyCompressedVec`](crate::ReadOnlyCompressedVec).
+pub(crate) trait StatsRead<I: VecIndex, T: VecValue>: ReadableVec<I, T> + Sized {
+    /// [`ReadWriteCompressedVec::stats_segments`] of `[from, to)`, clamped to the length.
+    fn stats_segments_at(&self, from: usize, to: usize) -> Vec<StatsSegment<T>>;
+
+    /// Same combination as the default [`ReadableVec::min_at`].
+    fn stats_min_at(&self, from: usize, to: usize) -> Option<T>
+    where
+        T: PartialOrd,
+    {
+        StatsSegment::fold(
+            self.stats_segments_at(from, to),
+            None,
+            |acc, stats| fold_min(acc, stats.min),
+            |acc, from, to| self.fold_range_at(from, to, acc, fold_min),
+        )
+    }
+
+    /// Same combination as the default [`ReadableVec::max_at`].
+    fn stats_max_at(&self, from: usize, to: usize) -> Option<T>
+    where
+        T: PartialOrd,
+    {
+        StatsSegment::fold(
+            self.stats_segments_at(from, to),
+            None,
+            |acc, stats| fold_max(acc, stats.max),
+            |acc, from, to| self.fold_range_at(from, to, acc, fold_max),
+        )
+    }
+
+    /// Same combination as the default [`ReadableVec::sum_at`].
+    fn stats_sum_at(&self, from: usize, to: usize) -> Option<T>
+    where
+        T: AddAssign + From<u8>,
+    {
+        let (sum, has_values) = StatsSegment::fold(
+            self.stats_segments_at(from, to),
+            (T::from(0), false),
+            |acc, stats| fold_sum(acc, stats.sum),
+            |acc, from, to| self.fold_range_at(from, to, acc, fold_sum),
+        );
+        has_values.then_some(sum)
+    }
+
+    /// Calls `f` with every value of the segments of `[from, to)` but the pages for which
+    /// `may_match` returns false.
+    fn stats_for_each_where_at(
+        &self,
+        from: usize,
+        to: usize,
+        mut may_match: impl FnMut(&PageStats<T>) -> bool,
+        mut f: impl FnMut(usize, T),
+    ) {
+        for segment in self.stats_segments_at(from, to) {
+            let (from, to) = match segment {
+                StatsSegment::Page { from, to, stats } if may_match(&stats) => (from, to),
+                StatsSegment::Page { .. } => continue,
+                StatsSegment::Values { from, to } => (from, to),
+            };
+            self.fold_range_at(from, to, from, |i, v| {
+                f(i, v);
+                i + 1
+            });
+        }
+    }
+}
+
+/// Same combination as the default [`ReadableVec::min_at`](crate::ReadableVec::min_at).
+#[inline]
+pub(crate) fn fold_min<T: PartialOrd>(acc: Option<T>, v: T) -> Option<T> {
+    match acc {
+        Some(cur) if cur <= v => Some(cur),
+        _ => Some(v),
+    }
+}
+
+/// Same combination as the default [`ReadableVec::max_at`](crate::ReadableVec::max_at).
+#[inline]
+pub(crate) fn fold_max<T: PartialOrd>(acc: Option<T>, v: T) -> Option<T> {
+    match acc {
+        Some(cur) if cur >= v => Some(cur),
+        _ => Some(v),
+    }
+}
+
+/// Same combination as the default [`ReadableVec::sum_at`](crate::ReadableVec::sum_at),
+/// tracking whether any value was added.
+#[inline]
+pub(crate) fn fold_sum<T: AddAssign>((mut sum, _): (T, bool), v: T) -> (T, bool) {
+    sum += v;
+    (sum, true)
+}
+
+impl<I, T, S> ReadWriteCompressedVec<I, T, S>
+where
+    I: VecIndex,
+    T: VecValue,
+    S: CompressionStrategy<T>,
+{
+    /// Serialized size of the stats stored after each page entry: min, max, sum and null count.
+    pub(super) const STATS_SIZE: usize = Pages::stats_size(Self::SIZE_OF_T);
+
+    pub(super) fn encode_page_stats(values: &[T]) -> Result<Vec<u8>> {
+        let stats = S::page_stats(values)?;
+        let mut bytes = Vec::with_capacity(Self::STATS_SIZE);
+        S::write_to_vec(&stats.min, &mut bytes);
+        S::write_to_vec(&stats.max, &mut bytes);
+        S::write_to_vec(&stats.sum, &mut bytes);
+        bytes.extend_from_slice(&stats.nulls.to_bytes());
+        Ok(bytes)
+    }
+
+    /// Stats of sealed page `page_index`, `None` for raw pages or if pages don't store them.
+    pub fn page_stats(&self, page_index: usize) -> Option<PageStats<T>> {
+        Self::page_stats_with(&self.pages.read(), page_index)
+    }
+
+    pub(crate) fn page_stats_with(pages: &Pages, page_index: usize) -> Option<PageStats<T>> {
+        let page = pages.get(page_index)?;
+        if !pages.has_stats() || page.is_raw() {
+            return None;
+        }
+        let bytes = pages.stats_bytes(page_index);
+        let (min, rest) = bytes.split_at(Self::SIZE_OF_T);
+        let (max, rest) = rest.split_at(Self::SIZE_OF_T);
+        let (sum, nulls) = rest.split_at(Self::SIZE_OF_T);
+        Some(PageStats {
+            min: S::read(min).ok()?,
+            max: S::read(max).ok()?,
+            sum: S::read(sum).ok()?,
+            count: page.values_count(),
+            nulls: u32::from_bytes(nulls).ok()?,
+        })
+    }
+
+    /// Calls `f` with the index and value of every value in `[from, to)`, skipping the
+    /// sealed pages for which `may_match` returns false. Pages without stats, with
+    /// nulls, or with unsaved updates are always visited.
+    pub fn for_each_where_at(
+        &self,
+        from: usize,
+        to: usize,
+        may_match: impl FnMut(&PageStats<T>) -> bool,
+        f: impl FnMut(usize, T),
+    ) {
+        self.stats_for_each_where_at(from, to, may_match, f);
+    }
+
+    /// Splits `[from, to)` into sealed pages without nulls, whose stats stand for their
+    /// values, and ranges of values to read. `stored_len` bounds the pages, `dirty` tells
+    /// whether a range holds unsaved updates, keeping it out of the stats.
+    pub(crate) fn stats_segments(
+        pages: &Pages,
+        from: usize,
+        to: usize,
+        stored_len: usize,
+        dirty: impl Fn(usize, usize) -> bool,
+    ) -> Vec<StatsSegment<T>> {
+        let mut segments = vec![];
+        let mut values_from = from;
+
+        if pages.has_stats() && from < to.min(stored_len) {
+            let per_page = pages.per_page();
+            let stored_to = to.min(stored_len);
+            for page_index in from.div_ceil(per_page)..stored_to / per_page {
+                let page_from = pages.page_index_to_index(page_index);
+                let page_to = page_from + per_page;
+                let Some(stats) = Self::page_stats_with(pages, page_index)
+                    .filter(|stats| stats.nulls == 0 && stats.count as usize == per_page)
+                else {
+                    continue;
+                };
+                if dirty(page_from, page_to) {
+                    continue;
+                }
+                if values_from < page_from {
+                    segments.push(StatsSegment::Values {
+                        from: values_from,
+                        to: page_from,
+                    });
+                }
+                segments.push(StatsSegment::Page {
+                    from: page_from,
+                    to: page_to,
+                    stats,
+                });
+                values_from = page_to;
+            }
+        }
+
+        if values_from < to {
+            segments.push(StatsSegment::Values {
+                from: values_from,
+                to,
+            });
+        }
+        segments
+    }
+}
+
+impl<I, T, S> StatsRead<I, T> for ReadWriteCompressedVec<I, T, S>
+where
+    I: VecIndex,
+    T: VecValue,
+    S: CompressionStrategy<T>,
+{
+    /// Unsaved updates are read value by value.
+    fn stats_segments_at(&self, from: usize, to: usize) -> Vec<StatsSegment<T>> {
+        let len = self.base.len();
+        let from = from.min(len);
+        let to = to.min(len);
+        if from >= to {
+            return vec![];
+        }
+        Self::stats_segments(
+            &self.pages.read(),
+            from,
+            to,
+            self.stored_len(),
+            |from, to| self.updated().range(from..to).next().is_some(),
+        )
+    }
+}
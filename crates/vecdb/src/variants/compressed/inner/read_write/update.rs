@@ -0,0 +1,363 @@
+use std::collections::BTreeMap;
+
+use rawdb::unlikely;
+
+use crate::{AnyStoredVec, AnyVec, Error, ReadableVec, Result, VecIndex, VecValue};
+
+use super::super::{CompressionStrategy, Page};
+use super::ReadWriteCompressedVec;
+
+/// Pages moved by a rewrite are written in chunks of at most this many bytes, or a
+/// single page if bigger.
+const MOVE_CHUNK_BYTES: usize = 1024 * 1024;
+
+/// A stored page re-encoded after some of its values changed.
+pub(super) struct EncodedPage {
+    pub index: usize,
+    pub bytes: Vec<u8>,
+    pub values: u32,
+    pub raw: bool,
+    pub dictionary_id: Option<u8>,
+    /// Serialized stats of the page if its values changed and pages store them.
+    pub stats: Option<Vec<u8>>,
+}
+
+impl EncodedPage {
+    fn to_page(&self, start: u64) -> Page {
+        let (bytes, values) = (self.bytes.len() as u32, self.values);
+        if self.raw {
+            Page::raw(start, bytes, values)
+        } else if let Some(id) = self.dictionary_id {
+            Page::compressed_with_dictionary(start, bytes, values, id)
+        } else {
+            Page::compressed(start, bytes, values)
+        }
+    }
+}
+
+impl<I, T, S> ReadWriteCompressedVec<I, T, S>
+where
+    I: VecIndex,
+    T: VecValue,
+    S: CompressionStrategy<T>,
+{
+    #[inline(always)]
+    pub fn updated(&self) -> &BTreeMap<usize, T> {
+        self.updated.current()
+    }
+
+    #[inline(always)]
+    pub fn mut_updated(&mut self) -> &mut BTreeMap<usize, T> {
+        self.updated.current_mut()
+    }
+
+    #[inline(always)]
+    pub fn prev_updated(&self) -> &BTreeMap<usize, T> {
+        self.updated.previous()
+    }
+
+    #[inline]
+    pub fn update(&mut self, index: I, value: T) -> Result<()> {
+        self.update_at(index.to_usize(), value)
+    }
+
+    /// Replaces the value at `index`. Stored values are overlaid until the next
+    /// `write()`, which recompresses only the pages that changed. Errors if the
+    /// format doesn't accept `value` between its neighbours.
+    #[inline]
+    pub fn update_at(&mut self, index: usize, value: T) -> Result<()> {
+        if index < self.len() {
+            S::validate_sequence(
+                || index.checked_sub(1).and_then(|i| self.collect_one_at(i)),
+                std::slice::from_ref(&value),
+                || self.collect_one_at(index + 1),
+            )?;
+        }
+        self.set_at(index, value)
+    }
+
+    /// Like [`Self::update_at`] without validating `value` against its neighbours, for
+    /// restoring values that were valid together.
+    pub(super) fn set_at(&mut self, index: usize, value: T) -> Result<()> {
+        let stored_len = self.stored_len();
+        if index >= stored_len {
+            let Some(slot) = self.base.mut_pushed().get_mut(index - stored_len) else {
+                return Err(Error::IndexTooHigh {
+                    index,
+                    len: stored_len,
+                    name: self.name().to_string(),
+                });
+            };
+            *slot = value;
+            return Ok(());
+        }
+
+        self.mut_updated().insert(index, value);
+
+        Ok(())
+    }
+
+    #[inline]
+    pub(super) fn has_dirty_stored(&self) -> bool {
+        !self.updated().is_empty()
+    }
+
+    pub(super) fn truncate_dirty_at(&mut self, index: usize) {
+        if self
+            .updated()
+            .last_key_value()
+            .is_some_and(|(&k, _)| k >= index)
+        {
+            self.mut_updated().split_off(&index);
+        }
+    }
+
+    /// Recompresses the pages holding updated values. Returns whether anything was written.
+    pub(super) fn write_updated(&mut self) -> Result<bool> {
+        if self.updated().is_empty() {
+            return Ok(false);
+        }
+        let updated = self.updated.take_current();
+
+        let encoded = {
+            let reader = self.create_reader();
+            let pages = self.pages.read();
+            let real_stored_len = pages.stored_len();
+            let per_page = pages.per_page();
+            let dictionary = pages.dictionaries().current();
+            let has_stats = pages.has_stats();
+
+            let mut encoded = vec![];
+            let mut updated = updated.into_iter().peekable();
+            while let Some(&(index, _)) = updated.peek() {
+                let page_index = pages.index_to_page_index(index);
+                let page_start = pages.page_index_to_index(page_index);
+                let page = *pages.get(page_index).ok_or(Error::ExpectVecToHaveIndex)?;
+
+                let mut values =
+                    Self::decode_page_with(real_stored_len, page_index, &reader, &pages)?;
+                while let Some((index, value)) =
+                    updated.next_if(|(index, _)| *index < page_start + per_page)
+                {
+                    values[index - page_start] = value;
+                }
+
+                encoded.push(if page.is_raw() {
+                    EncodedPage {
+                        index: page_index,
+                        bytes: S::values_to_bytes(&values),
+                        values: values.len() as u32,
+                        raw: true,
+                        dictionary_id: None,
+                        stats: None,
+                    }
+                } else {
+                    EncodedPage {
+                        index: page_index,
+                        bytes: self.compress_page(
+                            &values,
+                            per_page,
+                            dictionary.map(|(_, dictionary)| dictionary.as_ref()),
+                        )?,
+                        values: values.len() as u32,
+                        raw: false,
+                        dictionary_id: dictionary.map(|(id, _)| id),
+                        stats: has_stats
+                            .then(|| Self::encode_page_stats(&values))
+                            .transpose()?,
+                    }
+                });
+            }
+            encoded
+        };
+
+        self.rewrite_pages(encoded)?;
+        Ok(true)
+    }
+
+    /// Writes re-encoded pages, sorted by index, over the existing ones. Pages that
+    /// still fit are overwritten in place, leaving a gap if they shrank. From the
+    /// first page that grew, each page starts at its old start or right after the
+    /// previous one, whichever is later, so pages only move towards the end and are
+    /// moved back to front, a chunk of [`MOVE_CHUNK_BYTES`] at a time.
+    ///
+    /// The region is grown first, then pages are written under the pages lock so that
+    /// read-only clones never decode them through stale metadata.
+    pub(super) fn rewrite_pages(&mut self, encoded: Vec<EncodedPage>) -> Result<()> {
+        let Some(first_index) = encoded.first().map(|e| e.index) else {
+            return Ok(());
+        };
+        debug_assert!(encoded.is_sorted_by_key(|e| e.index));
+
+        let old = {
+            let pages = self.pages.read();
+            (first_index..pages.len())
+                .map(|i| *pages.get(i).expect("page should exist within bounds"))
+                .collect::<Vec<_>>()
+        };
+        let old_page = |index: usize| old[index - first_index];
+        let split = encoded
+            .iter()
+            .position(|e| e.bytes.len() > old_page(e.index).bytes as usize)
+            .unwrap_or(encoded.len());
+        let (in_place, grown) = encoded.split_at(split);
+
+        let mut moved = vec![];
+        if let Some(first_grown) = grown.first() {
+            let mut grown = grown.iter().peekable();
+            let mut end = old_page(first_grown.index).start;
+            for index in first_grown.index..first_index + old.len() {
+                let e = grown.next_if(|e| e.index == index);
+                let mut page = old_page(index);
+                // Once the growth is absorbed, later pages stay where they are
+                if e.is_none() && grown.peek().is_none() && end <= page.start {
+                    break;
+                }
+                page = match e {
+                    Some(e) => e.to_page(end.max(page.start)),
+                    None => Page {
+                        start: end.max(page.start),
+                        ..page
+                    },
+                };
+                end = page.end();
+                moved.push((index, page, e));
+            }
+        }
+
+        // Zeroes past the end aren't read through the old pages, and growing the region
+        // later could have to move it while readers hold it
+        if let Some((_, last, _)) = moved.last() {
+            let end = last.end() as usize;
+            let mut len = self.region().meta().len();
+            while len < end {
+                let zeros = vec![0; (end - len).min(MOVE_CHUNK_BYTES)];
+                self.region().write_at(&zeros, len)?;
+                len += zeros.len();
+            }
+        }
+
+        let mut pages = self.pages.write();
+        let mut new_pages = Vec::with_capacity(encoded.len());
+        for e in in_place {
+            let start = old_page(e.index).start;
+            self.region().write_at(&e.bytes, start as usize)?;
+            new_pages.push((e.index, e.to_page(start), Some(e)));
+        }
+        self.move_pages(&moved, old_page)?;
+        new_pages.extend(moved);
+
+        for (index, page, encoded) in new_pages {
+            let page = match encoded {
+                Some(e) => {
+                    if let Some(stats) = &e.stats {
+                        pages.set_stats(index, stats);
+                    }
+                    pages.checksummed(page, &e.bytes)
+                }
+                None => page,
+            };
+            pages.replace(index, page);
+        }
+        pages.flush()
+    }
+
+    /// Writes `moved` pages at their new starts, none of them before its old one, back
+    /// to front so that no page is overwritten before it's read. The region must already
+    /// reach the end of the last one.
+    fn move_pages(
+        &self,
+        moved: &[(usize, Page, Option<&EncodedPage>)],
+        old_page: impl Fn(usize) -> Page,
+    ) -> Result<()> {
+        let mut chunk_end = moved.len();
+        while chunk_end > 0 {
+            let end = moved[chunk_end - 1].1.end();
+            let mut chunk_start = chunk_end - 1;
+            while chunk_start > 0
+                && (end - moved[chunk_start - 1].1.start) as usize <= MOVE_CHUNK_BYTES
+            {
+                chunk_start -= 1;
+            }
+            let chunk = &moved[chunk_start..chunk_end];
+            let at = chunk[0].1.start;
+
+            // Zeroes the gaps before pages kept at their old start
+            let mut buf = vec![0; (end - at) as usize];
+            {
+                let reader = self.create_reader();
+                for (index, page, e) in chunk {
+                    let bytes = match e {
+                        Some(e) => &e.bytes[..],
+                        None => {
+                            let old = old_page(*index);
+                            reader.unchecked_read(old.start as usize, old.bytes as usize)
+                        }
+                    };
+                    let offset = (page.start - at) as usize;
+                    buf[offset..offset + bytes.len()].copy_from_slice(bytes);
+                }
+            }
+
+            self.region().write_at(&buf, at as usize)?;
+
+            chunk_end = chunk_start;
+        }
+        Ok(())
+    }
+
+    /// Folds stored values with updates overlaid, then pushed values.
+    pub(super) fn fold_dirty<B, F: FnMut(B, T) -> B>(
+        &self,
+        from: usize,
+        to: usize,
+        init: B,
+        mut f: F,
+    ) -> B {
+        let stored_to = to.min(self.stored_len());
+        let mut acc = init;
+
+        if from < stored_to {
+            let mut updates = self.updated().range(from..stored_to).peekable();
+            let mut i = from;
+            acc = self.fold_source(from, stored_to, acc, |acc, v| {
+                let v = if unlikely(updates.peek().is_some_and(|&(&k, _)| k == i)) {
+                    updates.next().unwrap().1.clone()
+                } else {
+                    v
+                };
+                i += 1;
+                f(acc, v)
+            });
+        }
+
+        self.base.fold_pushed(from, to, acc, f)
+    }
+
+    pub(super) fn try_fold_dirty<B, E, F: FnMut(B, T) -> std::result::Result<B, E>>(
+        &self,
+        from: usize,
+        to: usize,
+        init: B,
+        mut f: F,
+    ) -> std::result::Result<B, E> {
+        let stored_to = to.min(self.stored_len());
+        let mut acc = init;
+
+        if from < stored_to {
+            let mut updates = self.updated().range(from..stored_to).peekable();
+            let mut i = from;
+            acc = self.try_fold_source(from, stored_to, acc, |acc, v| {
+                let v = if unlikely(updates.peek().is_some_and(|&(&k, _)| k == i)) {
+                    updates.next().unwrap().1.clone()
+                } else {
+                    v
+                };
+                i += 1;
+                f(acc, v)
+            })?;
+        }
+
+        self.base.try_fold_pushed(from, to, acc, f)
+    }
+}
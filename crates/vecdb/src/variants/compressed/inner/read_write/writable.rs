@@ -21,6 +21,8 @@ where
     }
 
     fn truncate_if_needed_at(&mut self, index: usize) -> Result<()> {
+        self.truncate_dirty_at(index);
+
         if self.base.truncate_pushed(index) {
             self.base.update_stored_len(index);
         }
@@ -29,16 +31,18 @@ where
 
     fn reset(&mut self) -> Result<()> {
         self.pages.write().reset();
+        self.updated.clear();
         self.truncate_if_needed_at(0)?;
         self.base.reset_base()
     }
 
     fn reset_unsaved(&mut self) {
         self.base.reset_unsaved_base();
+        self.updated.clear();
     }
 
     fn is_dirty(&self) -> bool {
-        !self.base.pushed().is_empty()
+        !self.base.pushed().is_empty() || self.has_dirty_stored()
     }
 
     fn stamped_write_with_changes(&mut self, stamp: Stamp) -> Result<()> {
@@ -50,6 +54,7 @@ where
         self.base.save_change_file(stamp, &data)?;
         self.stamped_write(stamp)?;
         self.base.save_prev();
+        self.updated.clear_previous();
 
         Ok(())
     }
@@ -65,5 +70,6 @@ where
 
     fn save_rollback_state(&mut self) {
         self.base.save_prev_for_rollback();
+        self.updated.save();
     }
 }
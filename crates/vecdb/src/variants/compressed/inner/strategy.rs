@@ -2,12 +2,89 @@ use rawdb::likely;
 
 use crate::{Error, Result, ValueStrategy};
 
-use super::Page;
+use super::{Page, PageStats};
 
 /// Trait for compression strategies used by ReadWriteCompressedVec.
 pub trait CompressionStrategy<T>: ValueStrategy<T> {
-    /// Compress a slice of values into bytes.
-    fn compress(values: &[T]) -> Result<Vec<u8>>;
+    /// Compress a slice of values into bytes, using the strategy's default level if `None`.
+    fn compress(values: &[T], level: Option<i32>) -> Result<Vec<u8>>;
+
+    /// Whether this strategy can compress pages with a trained dictionary.
+    const SUPPORTS_DICTIONARY: bool = false;
+
+    /// Compress a slice of values into bytes using a trained dictionary.
+    /// Default implementation errors, see [`Self::SUPPORTS_DICTIONARY`].
+    #[inline]
+    fn compress_with_dictionary(
+        _values: &[T],
+        _level: Option<i32>,
+        _dictionary: &[u8],
+    ) -> Result<Vec<u8>> {
+        Err(Error::InvalidArgument(
+            "dictionaries are not supported by this format",
+        ))
+    }
+
+    /// Decompress bytes compressed with `dictionary` into an existing buffer (replace semantics).
+    /// Default implementation errors, see [`Self::SUPPORTS_DICTIONARY`].
+    #[inline]
+    fn decompress_with_dictionary_into(
+        _bytes: &[u8],
+        _expected_len: usize,
+        _dictionary: &[u8],
+        _dst: &mut Vec<T>,
+    ) -> Result<()> {
+        Err(Error::InvalidArgument(
+            "dictionaries are not supported by this format",
+        ))
+    }
+
+    /// Train a dictionary of at most `max_size` bytes from `samples`, the concatenation
+    /// of serialized samples whose lengths are `sample_sizes`.
+    /// Default implementation errors, see [`Self::SUPPORTS_DICTIONARY`].
+    #[inline]
+    fn train_dictionary(
+        _samples: &[u8],
+        _sample_sizes: &[usize],
+        _max_size: usize,
+    ) -> Result<Vec<u8>> {
+        Err(Error::InvalidArgument(
+            "dictionaries are not supported by this format",
+        ))
+    }
+
+    /// Whether this strategy can compute [`PageStats`] of sealed pages.
+    const SUPPORTS_PAGE_STATS: bool = false;
+
+    /// Statistics of a sealed page holding `values`.
+    /// Default implementation errors, see [`Self::SUPPORTS_PAGE_STATS`].
+    #[inline]
+    fn page_stats(_values: &[T]) -> Result<PageStats<T>> {
+        Err(Error::InvalidArgument(
+            "page stats are not supported by this format",
+        ))
+    }
+
+    /// Checks that `values` can be stored between `previous` and `next`, the values before
+    /// and after them, which are only fetched by strategies constraining neighbours.
+    /// Default implementation accepts every sequence.
+    #[inline]
+    fn validate_sequence(
+        _previous: impl FnOnce() -> Option<T>,
+        _values: &[T],
+        _next: impl FnOnce() -> Option<T>,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Checks that `level` is a valid compression level for this strategy.
+    /// Default implementation rejects every level.
+    #[inline]
+    fn validate_compression_level(_level: i32) -> Result<()> {
+        Err(Error::InvalidArgument(
+            "compression level is not supported by this format",
+        ))
+    }
 
     /// Decompress bytes into a vector of values.
     fn decompress(bytes: &[u8], expected_len: usize) -> Result<Vec<T>>;
@@ -31,11 +108,22 @@ pub trait CompressionStrategy<T>: ValueStrategy<T> {
     }
 
     /// Decode page data (raw or compressed) into a new Vec.
+    /// `dictionary` must be the one referenced by the page, if any.
     #[inline]
-    fn decode_page(data: &[u8], page: &Page) -> Result<Vec<T>> {
+    fn decode_page(data: &[u8], page: &Page, dictionary: Option<&[u8]>) -> Result<Vec<T>> {
         let n = page.values_count() as usize;
         if page.is_raw() {
             Self::bytes_to_values(data, n)
+        } else if let Some(dictionary) = dictionary {
+            let mut vec = Vec::with_capacity(n);
+            Self::decompress_with_dictionary_into(data, n, dictionary, &mut vec)?;
+            if likely(vec.len() == n) {
+                return Ok(vec);
+            }
+            Err(Error::DecompressionMismatch {
+                expected_len: n,
+                actual_len: vec.len(),
+            })
         } else {
             let vec = Self::decompress(data, n)?;
             if likely(vec.len() == n) {
@@ -48,12 +136,62 @@ pub trait CompressionStrategy<T>: ValueStrategy<T> {
         }
     }
 
+    /// Decode the first value of a page, reading raw pages in place.
+    /// `dictionary` must be the one referenced by the page, if any.
+    #[inline]
+    fn decode_first(data: &[u8], page: &Page, dictionary: Option<&[u8]>) -> Result<T> {
+        let n = page.values_count() as usize;
+        if n == 0 {
+            return Err(Error::DecompressionMismatch {
+                expected_len: 1,
+                actual_len: 0,
+            });
+        }
+        if page.is_raw() {
+            let bytes = data.get(..size_of::<T>()).ok_or(Error::WrongLength {
+                received: data.len(),
+                expected: size_of::<T>(),
+            })?;
+            Self::read(bytes)
+        } else {
+            Self::decompress_first(data, n, dictionary)
+        }
+    }
+
+    /// Decompress the first of `expected_len` values compressed with `dictionary`, if any.
+    /// Default implementation decompresses every value.
+    #[inline]
+    fn decompress_first(bytes: &[u8], expected_len: usize, dictionary: Option<&[u8]>) -> Result<T> {
+        let mut values = Vec::with_capacity(expected_len);
+        match dictionary {
+            Some(dictionary) => {
+                Self::decompress_with_dictionary_into(bytes, expected_len, dictionary, &mut values)?
+            }
+            None => Self::decompress_into(bytes, expected_len, &mut values)?,
+        }
+        values
+            .into_iter()
+            .next()
+            .ok_or(Error::DecompressionMismatch {
+                expected_len,
+                actual_len: 0,
+            })
+    }
+
     /// Decode page data (raw or compressed) into an existing buffer (replace semantics).
+    /// `dictionary` must be the one referenced by the page, if any.
     #[inline]
-    fn decode_page_into(data: &[u8], page: &Page, dst: &mut Vec<T>) -> Result<()> {
+    fn decode_page_into(
+        data: &[u8],
+        page: &Page,
+        dictionary: Option<&[u8]>,
+        dst: &mut Vec<T>,
+    ) -> Result<()> {
         let n = page.values_count() as usize;
         if page.is_raw() {
             Self::bytes_to_values_into(data, n, dst)
+        } else if let Some(dictionary) = dictionary {
+            Self::decompress_with_dictionary_into(data, n, dictionary, dst)
         } else {
             Self::decompress_into(data, n, dst)
         }
@@ -16,7 +16,8 @@ impl<T> CompressionStrategy<T> for LZ4Strategy<T>
 where
     T: LZ4VecValue,
 {
-    fn compress(values: &[T]) -> Result<Vec<u8>> {
+    /// lz4_flex has no acceleration setting, so `level` is rejected on import.
+    fn compress(values: &[T], _level: Option<i32>) -> Result<Vec<u8>> {
         Ok(compress_prepend_size(&Self::values_to_bytes(values)))
     }
 
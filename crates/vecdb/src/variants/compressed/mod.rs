@@ -1,6 +1,7 @@
 mod inner;
 #[cfg(feature = "lz4")]
 mod lz4;
+mod monotonic;
 #[cfg(feature = "pco")]
 mod pco;
 mod sources;
@@ -8,9 +9,12 @@ mod sources;
 mod zstd;
 
 pub(crate) use inner::*;
-pub use inner::{CompressionStrategy, ReadOnlyCompressedVec};
+pub use inner::{
+    CompressionStrategy, MAX_UNCOMPRESSED_PAGE_SIZE, PageStats, ReadOnlyCompressedVec,
+};
 #[cfg(feature = "lz4")]
 pub use lz4::*;
+pub use monotonic::*;
 #[cfg(feature = "pco")]
 pub use pco::*;
 pub(crate) use sources::*;
@@ -0,0 +1,228 @@
+use crate::{Error, Result};
+
+/// Number of set high bits between two select samples.
+const SAMPLE_RATE: usize = 256;
+
+/// `n` (u32) + low bit width (u8) + padding (3) + base (u64) + upper (u64).
+const HEADER_SIZE: usize = 24;
+
+const SIZE_OF_U32: usize = size_of::<u32>();
+const SIZE_OF_U64: usize = size_of::<u64>();
+
+/// Zero-copy view over one Elias-Fano encoded page.
+///
+/// Layout (little-endian):
+/// `[n: u32][low_bits: u8][pad: 3][base: u64][upper: u64][samples: u32 * ceil(n / 256)][low: u64 *][high: u64 *]`
+///
+/// Each value `v` is stored as `v - base`, split into `low_bits` low bits (packed
+/// densely in `low`) and the remaining high bits (unary-coded in `high`, where
+/// element `i` sets bit `(v_i - base) >> low_bits + i`). `samples[k]` is the bit
+/// position of the `k * 256`-th set bit, bounding every select to a few words.
+pub(super) struct EliasFano<'a> {
+    n: usize,
+    low_bits: u32,
+    base: u64,
+    samples: &'a [u8],
+    low: &'a [u8],
+    high: &'a [u8],
+}
+
+impl<'a> EliasFano<'a> {
+    /// Encodes a non-decreasing sequence. Errors if any value is smaller than its predecessor.
+    pub fn encode(values: &[u64]) -> Result<Vec<u8>> {
+        if values.windows(2).any(|w| w[1] < w[0]) {
+            return Err(Error::InvalidArgument(
+                "MonotonicVec values must be non-decreasing",
+            ));
+        }
+
+        let n = values.len();
+        let base = values.first().copied().unwrap_or_default();
+        let upper = values.last().map_or(0, |&last| last - base);
+        let low_bits = Self::low_bits_for(upper, n);
+
+        let low_words = (n * low_bits as usize).div_ceil(64);
+        let high_len = n + (upper >> low_bits) as usize + 1;
+        let high_words = high_len.div_ceil(64);
+        let sample_count = n.div_ceil(SAMPLE_RATE);
+
+        let mut low = vec![0u64; low_words];
+        let mut high = vec![0u64; high_words];
+        let mut samples = Vec::with_capacity(sample_count);
+        let low_mask = Self::mask(low_bits);
+
+        for (i, &v) in values.iter().enumerate() {
+            let delta = v - base;
+
+            if low_bits > 0 {
+                let bit = i * low_bits as usize;
+                let (word, offset) = (bit / 64, bit % 64);
+                let low_value = delta & low_mask;
+                low[word] |= low_value << offset;
+                if offset + low_bits as usize > 64 {
+                    low[word + 1] |= low_value >> (64 - offset);
+                }
+            }
+
+            let pos = (delta >> low_bits) as usize + i;
+            high[pos / 64] |= 1 << (pos % 64);
+
+            if i % SAMPLE_RATE == 0 {
+                samples.push(pos as u32);
+            }
+        }
+
+        let mut bytes = Vec::with_capacity(
+            HEADER_SIZE + sample_count * SIZE_OF_U32 + (low_words + high_words) * SIZE_OF_U64,
+        );
+        bytes.extend((n as u32).to_le_bytes());
+        bytes.push(low_bits as u8);
+        bytes.extend([0u8; 3]);
+        bytes.extend(base.to_le_bytes());
+        bytes.extend(upper.to_le_bytes());
+        samples.iter().for_each(|s| bytes.extend(s.to_le_bytes()));
+        low.iter().for_each(|w| bytes.extend(w.to_le_bytes()));
+        high.iter().for_each(|w| bytes.extend(w.to_le_bytes()));
+
+        Ok(bytes)
+    }
+
+    /// Parses a page without decoding any value.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self> {
+        if bytes.len() < HEADER_SIZE {
+            return Err(Error::WrongLength {
+                received: bytes.len(),
+                expected: HEADER_SIZE,
+            });
+        }
+
+        let n = u32::from_le_bytes(bytes[0..4].try_into().unwrap()) as usize;
+        let low_bits = bytes[4] as u32;
+        let base = u64::from_le_bytes(bytes[8..16].try_into().unwrap());
+        let upper = u64::from_le_bytes(bytes[16..24].try_into().unwrap());
+
+        if low_bits >= 64 {
+            return Err(Error::InvalidArgument("invalid Elias-Fano low bit width"));
+        }
+
+        let samples_len = n.div_ceil(SAMPLE_RATE) * SIZE_OF_U32;
+        let low_len = (n * low_bits as usize).div_ceil(64) * SIZE_OF_U64;
+        let high_len = (n + (upper >> low_bits) as usize + 1).div_ceil(64) * SIZE_OF_U64;
+        let expected = HEADER_SIZE + samples_len + low_len + high_len;
+
+        if bytes.len() < expected {
+            return Err(Error::WrongLength {
+                received: bytes.len(),
+                expected,
+            });
+        }
+
+        let (samples, rest) = bytes[HEADER_SIZE..].split_at(samples_len);
+        let (low, rest) = rest.split_at(low_len);
+        let high = &rest[..high_len];
+
+        Ok(Self {
+            n,
+            low_bits,
+            base,
+            samples,
+            low,
+            high,
+        })
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// Returns the `i`-th value. `i` must be `< len()`.
+    #[inline]
+    pub fn get(&self, i: usize) -> u64 {
+        debug_assert!(i < self.n);
+        let high = (self.select_high(i) - i) as u64;
+        self.base + ((high << self.low_bits) | self.low_at(i))
+    }
+
+    /// Calls `f` with every value in order, walking the high bits sequentially.
+    pub fn for_each(&self, mut f: impl FnMut(u64)) {
+        let mut i = 0;
+        let words = self.high.len() / SIZE_OF_U64;
+        for w in 0..words {
+            let mut word = self.word(self.high, w);
+            while word != 0 && i < self.n {
+                let pos = w * 64 + word.trailing_zeros() as usize;
+                let high = (pos - i) as u64;
+                f(self.base + ((high << self.low_bits) | self.low_at(i)));
+                i += 1;
+                word &= word - 1;
+            }
+        }
+    }
+
+    #[inline]
+    fn low_at(&self, i: usize) -> u64 {
+        if self.low_bits == 0 {
+            return 0;
+        }
+        let bit = i * self.low_bits as usize;
+        let (w, offset) = (bit / 64, bit % 64);
+        let mut value = self.word(self.low, w) >> offset;
+        if offset + self.low_bits as usize > 64 {
+            value |= self.word(self.low, w + 1) << (64 - offset);
+        }
+        value & Self::mask(self.low_bits)
+    }
+
+    /// Bit position of the `i`-th set bit in the high bits.
+    #[inline]
+    fn select_high(&self, i: usize) -> usize {
+        let sample = i / SAMPLE_RATE;
+        let at = sample * SIZE_OF_U32;
+        let pos =
+            u32::from_le_bytes(self.samples[at..at + SIZE_OF_U32].try_into().unwrap()) as usize;
+        let mut remaining = i - sample * SAMPLE_RATE;
+
+        let mut w = pos / 64;
+        let mut word = self.word(self.high, w) & (u64::MAX << (pos % 64));
+        loop {
+            let ones = word.count_ones() as usize;
+            if remaining < ones {
+                for _ in 0..remaining {
+                    word &= word - 1;
+                }
+                return w * 64 + word.trailing_zeros() as usize;
+            }
+            remaining -= ones;
+            w += 1;
+            word = self.word(self.high, w);
+        }
+    }
+
+    #[inline(always)]
+    fn word(&self, bytes: &[u8], w: usize) -> u64 {
+        let at = w * SIZE_OF_U64;
+        u64::from_le_bytes(bytes[at..at + SIZE_OF_U64].try_into().unwrap())
+    }
+
+    #[inline(always)]
+    fn mask(bits: u32) -> u64 {
+        if bits == 0 {
+            0
+        } else {
+            u64::MAX >> (64 - bits)
+        }
+    }
+
+    fn low_bits_for(upper: u64, n: usize) -> u32 {
+        if n == 0 {
+            return 0;
+        }
+        let ratio = upper / n as u64;
+        if ratio == 0 {
+            0
+        } else {
+            63 - ratio.leading_zeros()
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use std::{cell::Cell, collections::BTreeMap, marker::PhantomData};
+
+use parking_lot::RwLockReadGuard;
+use rawdb::Reader;
+
+use crate::{
+    AnyStoredVec, AnyVec, Error, Format, ReadOnlyCompressedVec, ReadWriteCompressedVec, Result,
+    VecIndex, WritableVec, impl_vec_wrapper,
+};
+
+use super::inner::{CompressionStrategy, Pages};
+
+mod elias_fano;
+mod strategy;
+mod value;
+
+use elias_fano::EliasFano;
+pub use strategy::*;
+pub use value::*;
+
+type Inner<I, T> = ReadWriteCompressedVec<I, T, EliasFanoStrategy<T>>;
+
+/// Compressed storage for monotonically non-decreasing integers using Elias-Fano encoding.
+///
+/// Each page is encoded as a succinct Elias-Fano sequence, taking roughly
+/// `2 + log2(range / len)` bits per value, and supports random access without
+/// decoding the page. Ideal for index mappings such as "first tx index of block".
+///
+/// # Performance Characteristics
+/// - Near-optimal size for sorted sequences
+/// - O(1)-ish `get` (no page decompression)
+/// - O(log n) `successor` / `predecessor` / `rank` lookups
+///
+/// # When to Use
+/// - Sorted index mappings where "which entry contains value N" is a common query
+/// - Values are non-decreasing; writing or updating values below the ones before them fails
+///   with [`crate::Error::InvalidArgument`], use [`Self::try_push`] to fail on push instead
+#[derive(Debug)]
+#[must_use = "Vector should be stored to keep data accessible"]
+pub struct MonotonicVec<I, T>(Inner<I, T>);
+
+impl_vec_wrapper!(
+    MonotonicVec,
+    ReadWriteCompressedVec<I, T, EliasFanoStrategy<T>>,
+    MonotonicVecValue,
+    Format::Monotonic,
+    ReadOnlyCompressedVec<I, T, EliasFanoStrategy<T>>
+);
+
+impl<I, T> Inner<I, T>
+where
+    I: VecIndex,
+    T: MonotonicVecValue,
+{
+    /// Returns the value at `index`, including pushed values, without decoding its page.
+    #[inline]
+    pub fn get(&self, index: I) -> Result<Option<T>> {
+        self.get_at(index.to_usize())
+    }
+
+    /// Returns the value at `index`, including pushed values, without decoding its page.
+    #[inline]
+    pub fn get_at(&self, index: usize) -> Result<Option<T>> {
+        self.lookup().get(index)
+    }
+
+    /// Number of values strictly less than `value`.
+    pub fn rank(&self, value: T) -> Result<usize> {
+        self.lookup().partition_point(|v| v < value)
+    }
+
+    /// First entry whose value is `>= value`.
+    pub fn successor(&self, value: T) -> Result<Option<(I, T)>> {
+        self.lookup().successor(value)
+    }
+
+    /// Last entry whose value is `<= value`, i.e. the entry "containing" `value`
+    /// when the vec maps each index to the first value of a range.
+    pub fn predecessor(&self, value: T) -> Result<Option<(I, T)>> {
+        self.lookup().predecessor(value)
+    }
+
+    /// Pushes `value`, erroring right away if it's smaller than the last value instead
+    /// of on the next write.
+    pub fn try_push(&mut self, value: T) -> Result<()> {
+        let last = match self.len().checked_sub(1) {
+            Some(index) => self.lookup().get(index)?,
+            None => None,
+        };
+        EliasFanoStrategy::<T>::validate_sequence(|| last, &[value], || None)?;
+        self.push(value);
+        Ok(())
+    }
+
+    fn lookup(&self) -> Lookup<'_, I, T> {
+        Lookup {
+            reader: self.create_reader(),
+            pages: self.pages().read(),
+            verified: Cell::new(None),
+            stored_len: self.stored_len(),
+            updated: Some(self.updated()).filter(|updated| !updated.is_empty()),
+            pushed: self.pushed(),
+            _index: PhantomData,
+        }
+    }
+}
+
+impl<I, T> ReadOnlyCompressedVec<I, T, EliasFanoStrategy<T>>
+where
+    I: VecIndex,
+    T: MonotonicVecValue,
+{
+    /// Returns the value at `index` without decoding its page.
+    #[inline]
+    pub fn get(&self, index: I) -> Result<Option<T>> {
+        self.get_at(index.to_usize())
+    }
+
+    /// Returns the value at `index` without decoding its page.
+    #[inline]
+    pub fn get_at(&self, index: usize) -> Result<Option<T>> {
+        self.lookup().get(index)
+    }
+
+    /// Number of values strictly less than `value`.
+    pub fn rank(&self, value: T) -> Result<usize> {
+        self.lookup().partition_point(|v| v < value)
+    }
+
+    /// First entry whose value is `>= value`.
+    pub fn successor(&self, value: T) -> Result<Option<(I, T)>> {
+        self.lookup().successor(value)
+    }
+
+    /// Last entry whose value is `<= value`.
+    pub fn predecessor(&self, value: T) -> Result<Option<(I, T)>> {
+        self.lookup().predecessor(value)
+    }
+
+    fn lookup(&self) -> Lookup<'_, I, T> {
+        Lookup {
+            reader: self.create_reader(),
+            pages: self.pages().read(),
+            verified: Cell::new(None),
+            stored_len: self.len(),
+            updated: None,
+            pushed: &[],
+            _index: PhantomData,
+        }
+    }
+}
+
+/// Point lookups over stored pages, with unsaved updates overlaid, followed by
+/// pushed values, holding the reader and pages lock for the duration of a search.
+struct Lookup<'a, I, T> {
+    reader: Reader,
+    pages: RwLockReadGuard<'a, Pages>,
+    /// Last page whose checksum matched, the probes of a search ending in one page.
+    verified: Cell<Option<usize>>,
+    stored_len: usize,
+    updated: Option<&'a BTreeMap<usize, T>>,
+    pushed: &'a [T],
+    _index: PhantomData<I>,
+}
+
+impl<I, T> Lookup<'_, I, T>
+where
+    I: VecIndex,
+    T: MonotonicVecValue,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.stored_len + self.pushed.len()
+    }
+
+    #[inline]
+    fn get(&self, index: usize) -> Result<Option<T>> {
+        (index < self.len()).then(|| self.at(index)).transpose()
+    }
+
+    /// `index` must be `< len()`.
+    fn at(&self, index: usize) -> Result<T> {
+        if index >= self.stored_len {
+            return Ok(self.pushed[index - self.stored_len]);
+        }
+        if let Some(&value) = self.updated.and_then(|updated| updated.get(&index)) {
+            return Ok(value);
+        }
+
+        let page_index = self.pages.index_to_page_index(index);
+        let local = index - self.pages.page_index_to_index(page_index);
+        let page = self
+            .pages
+            .get(page_index)
+            .ok_or(Error::ExpectVecToHaveIndex)?;
+        let data = self
+            .reader
+            .unchecked_read(page.start as usize, page.bytes as usize);
+        if self.verified.get() != Some(page_index) {
+            self.pages.verify(page_index, page, data)?;
+            self.verified.set(Some(page_index));
+        }
+
+        if page.is_raw() {
+            let size = size_of::<T>();
+            let bytes = data
+                .get(local * size..(local + 1) * size)
+                .ok_or(Error::WrongLength {
+                    received: data.len(),
+                    expected: (local + 1) * size,
+                })?;
+            T::from_bytes(bytes)
+        } else {
+            let ef = EliasFano::parse(data)?;
+            if local >= ef.len() {
+                return Err(Error::DecompressionMismatch {
+                    expected_len: page.values_count() as usize,
+                    actual_len: ef.len(),
+                });
+            }
+            Ok(T::from_u64(ef.get(local)))
+        }
+    }
+
+    /// Index of the first value for which `pred` is false, assuming `pred`
+    /// holds for a (possibly empty) prefix.
+    fn partition_point(&self, mut pred: impl FnMut(T) -> bool) -> Result<usize> {
+        let (mut lo, mut hi) = (0, self.len());
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if pred(self.at(mid)?) {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        Ok(lo)
+    }
+
+    fn successor(&self, value: T) -> Result<Option<(I, T)>> {
+        let index = self.partition_point(|v| v < value)?;
+        Ok(self.get(index)?.map(|v| (I::from(index), v)))
+    }
+
+    fn predecessor(&self, value: T) -> Result<Option<(I, T)>> {
+        let Some(index) = self.partition_point(|v| v <= value)?.checked_sub(1) else {
+            return Ok(None);
+        };
+        Ok(Some((I::from(index), self.at(index)?)))
+    }
+}
@@ -0,0 +1,81 @@
+use std::marker::PhantomData;
+
+use crate::{Error, Result, impl_bytes_value_strategy};
+
+use super::{super::inner::CompressionStrategy, elias_fano::EliasFano, value::MonotonicVecValue};
+
+/// Elias-Fano encoding strategy for non-decreasing integer sequences.
+#[derive(Debug, Clone, Copy)]
+pub struct EliasFanoStrategy<T>(PhantomData<T>);
+
+impl_bytes_value_strategy!(EliasFanoStrategy, MonotonicVecValue);
+
+impl<T> CompressionStrategy<T> for EliasFanoStrategy<T>
+where
+    T: MonotonicVecValue,
+{
+    fn compress(values: &[T], _level: Option<i32>) -> Result<Vec<u8>> {
+        let values = values.iter().map(|v| v.to_u64()).collect::<Vec<_>>();
+        EliasFano::encode(&values)
+    }
+
+    /// Values must not be smaller than the one before them, across pages too.
+    fn validate_sequence(
+        previous: impl FnOnce() -> Option<T>,
+        values: &[T],
+        next: impl FnOnce() -> Option<T>,
+    ) -> Result<()> {
+        let (Some(&first), Some(&last)) = (values.first(), values.last()) else {
+            return Ok(());
+        };
+        if values.windows(2).any(|w| w[1] < w[0])
+            || previous().is_some_and(|previous| first < previous)
+            || next().is_some_and(|next| next < last)
+        {
+            return Err(Error::InvalidArgument(
+                "MonotonicVec values must be non-decreasing",
+            ));
+        }
+        Ok(())
+    }
+
+    fn decompress(bytes: &[u8], expected_len: usize) -> Result<Vec<T>> {
+        let mut vec = Vec::with_capacity(expected_len);
+        Self::decompress_append(bytes, expected_len, &mut vec)?;
+        Ok(vec)
+    }
+
+    fn decompress_into(bytes: &[u8], expected_len: usize, dst: &mut Vec<T>) -> Result<()> {
+        dst.clear();
+        Self::decompress_append(bytes, expected_len, dst)
+    }
+
+    /// Reads the first value without decoding the others.
+    fn decompress_first(
+        bytes: &[u8],
+        expected_len: usize,
+        _dictionary: Option<&[u8]>,
+    ) -> Result<T> {
+        let ef = EliasFano::parse(bytes)?;
+        if ef.len() != expected_len || expected_len == 0 {
+            return Err(Error::DecompressionMismatch {
+                expected_len,
+                actual_len: ef.len(),
+            });
+        }
+        Ok(T::from_u64(ef.get(0)))
+    }
+
+    fn decompress_append(bytes: &[u8], expected_len: usize, dst: &mut Vec<T>) -> Result<()> {
+        let ef = EliasFano::parse(bytes)?;
+        if ef.len() != expected_len {
+            return Err(Error::DecompressionMismatch {
+                expected_len,
+                actual_len: ef.len(),
+            });
+        }
+        dst.reserve(expected_len);
+        ef.for_each(|v| dst.push(T::from_u64(v)));
+        Ok(())
+    }
+}
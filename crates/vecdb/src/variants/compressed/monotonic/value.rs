@@ -0,0 +1,33 @@
+use crate::BytesVecValue;
+
+/// Value trait for MonotonicVec.
+/// Unsigned integers that losslessly round-trip through `u64`, which is the
+/// domain Elias-Fano encodes.
+pub trait MonotonicVecValue
+where
+    Self: BytesVecValue + Copy + Ord,
+{
+    fn to_u64(self) -> u64;
+
+    fn from_u64(value: u64) -> Self;
+}
+
+macro_rules! impl_monotonic_value {
+    ($($t:ty),*) => {
+        $(
+            impl MonotonicVecValue for $t {
+                #[inline(always)]
+                fn to_u64(self) -> u64 {
+                    self as u64
+                }
+
+                #[inline(always)]
+                fn from_u64(value: u64) -> Self {
+                    value as Self
+                }
+            }
+        )*
+    };
+}
+
+impl_monotonic_value!(u8, u16, u32, u64, usize);
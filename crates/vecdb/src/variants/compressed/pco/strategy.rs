@@ -3,16 +3,23 @@ use std::marker::PhantomData;
 use pco::ChunkConfig;
 use pco::standalone::{simple_compress, simple_decompress, simple_decompress_into};
 
-use crate::{Error, Result, impl_bytes_value_strategy, likely};
+use crate::{Error, PageStats, PcoNumber, Result, impl_bytes_value_strategy, likely};
 
 use super::{
     super::inner::CompressionStrategy,
     value::{AsInnerSlice, FromInnerSlice, PcoVecValue},
 };
 
-/// Returns the default ChunkConfig for pcodec compression.
-fn chunk_config() -> ChunkConfig {
-    ChunkConfig::default().with_enable_8_bit(true)
+/// Highest compression level accepted by pcodec.
+const PCO_MAX_COMPRESSION_LEVEL: i32 = 12;
+
+/// Returns the ChunkConfig for pcodec compression, using pcodec's default level if `None`.
+fn chunk_config(level: Option<i32>) -> ChunkConfig {
+    let config = ChunkConfig::default().with_enable_8_bit(true);
+    match level {
+        Some(level) => config.with_compression_level(level as usize),
+        None => config,
+    }
 }
 
 /// Pcodec compression strategy for numerical data.
@@ -25,8 +32,56 @@ impl<T> CompressionStrategy<T> for PcodecStrategy<T>
 where
     T: PcoVecValue,
 {
-    fn compress(values: &[T]) -> Result<Vec<u8>> {
-        Ok(simple_compress(values.as_inner_slice(), &chunk_config())?)
+    fn compress(values: &[T], level: Option<i32>) -> Result<Vec<u8>> {
+        Ok(simple_compress(
+            values.as_inner_slice(),
+            &chunk_config(level),
+        )?)
+    }
+
+    const SUPPORTS_PAGE_STATS: bool = true;
+
+    /// Computed on the pco numbers, so `T` must order and add like them.
+    fn page_stats(values: &[T]) -> Result<PageStats<T>> {
+        let numbers = values.as_inner_slice();
+        let first = *numbers
+            .first()
+            .ok_or(Error::InvalidArgument("empty page"))?;
+        let (mut min, mut max, mut sum) = (first, first, T::NumberType::ZERO);
+        let mut nulls = 0;
+        let mut seen = false;
+        for &n in numbers {
+            if n.is_null() {
+                nulls += 1;
+                continue;
+            }
+            if !seen {
+                (min, max, seen) = (n, n, true);
+            } else if n < min {
+                min = n;
+            } else if n > max {
+                max = n;
+            }
+            sum = n.add_to_sum(sum);
+        }
+        let [min, max, sum] = T::from_inner_slice(vec![min, max, sum])[..] else {
+            unreachable!()
+        };
+        Ok(PageStats {
+            min,
+            max,
+            sum,
+            count: numbers.len() as u32,
+            nulls,
+        })
+    }
+
+    fn validate_compression_level(level: i32) -> Result<()> {
+        if (0..=PCO_MAX_COMPRESSION_LEVEL).contains(&level) {
+            Ok(())
+        } else {
+            Err(Error::InvalidArgument("pco compression level out of range"))
+        }
     }
 
     fn decompress(bytes: &[u8], expected_len: usize) -> Result<Vec<T>> {
@@ -9,5 +9,59 @@ pub trait Pco
 where
     Self: TransparentPco<Self::NumberType>,
 {
-    type NumberType: Number;
+    type NumberType: PcoNumber;
 }
+
+/// Arithmetic on pco numbers, used to compute [`PageStats`](crate::PageStats).
+pub trait PcoNumber: Number + PartialOrd {
+    const ZERO: Self;
+
+    /// NaN floats, never integers.
+    fn is_null(self) -> bool;
+
+    /// Wrapping for integers.
+    fn add_to_sum(self, sum: Self) -> Self;
+}
+
+macro_rules! impl_pco_integer {
+    ($($t:ty),*) => {
+        $(
+            impl PcoNumber for $t {
+                const ZERO: Self = 0;
+
+                #[inline(always)]
+                fn is_null(self) -> bool {
+                    false
+                }
+
+                #[inline(always)]
+                fn add_to_sum(self, sum: Self) -> Self {
+                    sum.wrapping_add(self)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_pco_float {
+    ($($t:ty),*) => {
+        $(
+            impl PcoNumber for $t {
+                const ZERO: Self = 0.0;
+
+                #[inline(always)]
+                fn is_null(self) -> bool {
+                    self.is_nan()
+                }
+
+                #[inline(always)]
+                fn add_to_sum(self, sum: Self) -> Self {
+                    sum + self
+                }
+            }
+        )*
+    };
+}
+
+impl_pco_integer!(u8, u16, u32, u64, i8, i16, i32, i64);
+impl_pco_float!(f32, f64);
@@ -9,9 +9,7 @@ use rawdb::{Region, RegionMetadata};
 
 use crate::{AnyStoredVec, BUFFER_SIZE, Pages, VecIndex, VecValue, unlikely};
 
-use super::super::inner::{
-    CompressionStrategy, MAX_UNCOMPRESSED_PAGE_SIZE, Page, ReadWriteCompressedVec,
-};
+use super::super::inner::{CompressionStrategy, Page, ReadWriteCompressedVec};
 
 /// Buffered file I/O source for reading stored compressed data.
 ///
@@ -39,8 +37,6 @@ where
     T: VecValue,
     S: CompressionStrategy<T>,
 {
-    const SIZE_OF_T: usize = size_of::<T>();
-    const PER_PAGE: usize = MAX_UNCOMPRESSED_PAGE_SIZE / Self::SIZE_OF_T;
     const NO_PAGE: usize = usize::MAX;
 
     pub(crate) fn new(vec: &'a ReadWriteCompressedVec<I, T, S>, from: usize, to: usize) -> Self {
@@ -68,7 +64,7 @@ where
             buffer: vec![0; BUFFER_SIZE],
             buffer_len: 0,
             buffer_start_offset: 0,
-            decoded_values: Vec::with_capacity(Self::PER_PAGE),
+            decoded_values: Vec::with_capacity(pages.per_page()),
             decoded_page_index: Self::NO_PAGE,
             pages,
             index: from,
@@ -93,18 +89,25 @@ where
         let last_needed_page = if self.end_index == 0 {
             0
         } else {
-            (self.end_index - 1) / Self::PER_PAGE
+            self.pages.index_to_page_index(self.end_index - 1)
         };
         let max_page = last_needed_page.min(self.pages.len().saturating_sub(1));
 
+        // Pages larger than BUFFER_SIZE (big configured page sizes) grow the buffer
+        let buffer_size = self.buffer.len().max(start_page.bytes as usize);
+        if buffer_size > self.buffer.len() {
+            self.buffer.resize(buffer_size, 0);
+        }
+
+        // Pages recompressed in place can leave gaps, so measure spans rather than sum sizes
         let mut total_bytes = 0usize;
         for i in starting_page_index..=max_page {
             let page = self.pages.get(i)?;
-            let page_bytes = page.bytes as usize;
-            if total_bytes + page_bytes > BUFFER_SIZE {
+            let span = (page.end() - start_offset) as usize;
+            if span > buffer_size {
                 break;
             }
-            total_bytes += page_bytes;
+            total_bytes = span;
         }
 
         if total_bytes == 0 {
@@ -131,7 +134,9 @@ where
         let in_buffer_offset = (page.start - self.buffer_start_offset) as usize;
         let data = &self.buffer[in_buffer_offset..in_buffer_offset + page.bytes as usize];
 
-        S::decode_page_into(data, &page, &mut self.decoded_values).ok()?;
+        self.pages.verify(page_index, &page, data).ok()?;
+        let dictionary = self.pages.dictionary_for(&page).ok()?;
+        S::decode_page_into(data, &page, dictionary, &mut self.decoded_values).ok()?;
         self.decoded_page_index = page_index;
 
         Some(())
@@ -160,7 +165,7 @@ where
     /// Fold all remaining elements — tight pointer loop per page so LLVM can vectorize.
     #[inline(always)]
     pub(crate) fn fold<B, F: FnMut(B, T) -> B>(mut self, init: B, mut f: F) -> B {
-        let per_page = Self::PER_PAGE;
+        let per_page = self.pages.per_page();
         let end_index = self.end_index;
         let mut page_index = self.index / per_page;
         let mut page_start = page_index * per_page;
@@ -192,7 +197,7 @@ where
         init: B,
         mut f: F,
     ) -> std::result::Result<B, E> {
-        let per_page = Self::PER_PAGE;
+        let per_page = self.pages.per_page();
         let end_index = self.end_index;
         let mut page_index = self.index / per_page;
         let mut page_start = page_index * per_page;
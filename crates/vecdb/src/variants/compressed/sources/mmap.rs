@@ -5,9 +5,7 @@ use rawdb::{Reader, Region};
 
 use crate::{AnyStoredVec, Pages, VecIndex, VecValue, unlikely};
 
-use super::super::inner::{
-    CompressionStrategy, MAX_UNCOMPRESSED_PAGE_SIZE, ReadWriteCompressedVec,
-};
+use super::super::inner::{CompressionStrategy, ReadWriteCompressedVec};
 
 /// Read-only mmap-backed source over a compressed vector.
 ///
@@ -29,8 +27,6 @@ where
     T: VecValue,
     S: CompressionStrategy<T>,
 {
-    const SIZE_OF_T: usize = size_of::<T>();
-    const PER_PAGE: usize = MAX_UNCOMPRESSED_PAGE_SIZE / Self::SIZE_OF_T;
     const NO_PAGE: usize = usize::MAX;
 
     pub(crate) fn new(vec: &'a ReadWriteCompressedVec<I, T, S>, from: usize, to: usize) -> Self {
@@ -46,10 +42,11 @@ where
     ) -> Self {
         let from = from.min(stored_len);
         let to = to.min(stored_len);
+        let pages = pages.read();
         Self {
             reader: region.create_reader(),
-            pages: pages.read(),
-            page_buf: Vec::with_capacity(Self::PER_PAGE),
+            page_buf: Vec::with_capacity(pages.per_page()),
+            pages,
             page_buf_idx: Self::NO_PAGE,
             pos: from,
             end: to,
@@ -73,7 +70,9 @@ where
         let data = self
             .reader
             .unchecked_read(page.start as usize, page.bytes as usize);
-        S::decode_page_into(data, page, &mut self.page_buf).ok()?;
+        self.pages.verify(page_index, page, data).ok()?;
+        let dictionary = self.pages.dictionary_for(page).ok()?;
+        S::decode_page_into(data, page, dictionary, &mut self.page_buf).ok()?;
         self.page_buf_idx = page_index;
         Some(())
     }
@@ -81,7 +80,7 @@ where
     /// Fold all remaining elements — tight pointer loop per page so LLVM can vectorize.
     #[inline(always)]
     pub(crate) fn fold<B, F: FnMut(B, T) -> B>(mut self, init: B, mut f: F) -> B {
-        let per_page = Self::PER_PAGE;
+        let per_page = self.pages.per_page();
         let end = self.end;
         let mut page_index = self.pos / per_page;
         let mut page_start = page_index * per_page;
@@ -113,7 +112,7 @@ where
         init: B,
         mut f: F,
     ) -> std::result::Result<B, E> {
-        let per_page = Self::PER_PAGE;
+        let per_page = self.pages.per_page();
         let end = self.end;
         let mut page_index = self.pos / per_page;
         let mut page_start = page_index * per_page;
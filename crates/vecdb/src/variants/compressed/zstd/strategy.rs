@@ -1,12 +1,18 @@
-use std::marker::PhantomData;
+use std::{io::Read, marker::PhantomData};
 
-use zstd::{decode_all, encode_all};
+use zstd::{
+    bulk::{Compressor, Decompressor},
+    compression_level_range, decode_all,
+    dict::from_continuous,
+    encode_all,
+    stream::read::Decoder,
+};
 
-use crate::{Result, impl_bytes_value_strategy};
+use crate::{Error, Result, ValueStrategy, impl_bytes_value_strategy};
 
 use super::{super::inner::CompressionStrategy, value::ZstdVecValue};
 
-/// Zstd compression level (1-22). Level 3 provides a good balance
+/// Default Zstd compression level (1-22). Level 3 provides a good balance
 /// between compression ratio and speed for most workloads.
 const ZSTD_COMPRESSION_LEVEL: i32 = 3;
 
@@ -20,9 +26,54 @@ impl<T> CompressionStrategy<T> for ZstdStrategy<T>
 where
     T: ZstdVecValue,
 {
-    fn compress(values: &[T]) -> Result<Vec<u8>> {
+    fn compress(values: &[T], level: Option<i32>) -> Result<Vec<u8>> {
         let bytes = Self::values_to_bytes(values);
-        Ok(encode_all(bytes.as_slice(), ZSTD_COMPRESSION_LEVEL)?)
+        Ok(encode_all(
+            bytes.as_slice(),
+            level.unwrap_or(ZSTD_COMPRESSION_LEVEL),
+        )?)
+    }
+
+    const SUPPORTS_DICTIONARY: bool = true;
+
+    fn compress_with_dictionary(
+        values: &[T],
+        level: Option<i32>,
+        dictionary: &[u8],
+    ) -> Result<Vec<u8>> {
+        let bytes = Self::values_to_bytes(values);
+        let mut compressor =
+            Compressor::with_dictionary(level.unwrap_or(ZSTD_COMPRESSION_LEVEL), dictionary)?;
+        Ok(compressor.compress(&bytes)?)
+    }
+
+    fn decompress_with_dictionary_into(
+        bytes: &[u8],
+        expected_len: usize,
+        dictionary: &[u8],
+        dst: &mut Vec<T>,
+    ) -> Result<()> {
+        let mut decompressor = Decompressor::with_dictionary(dictionary)?;
+        let decompressed = decompressor.decompress(bytes, expected_len * size_of::<T>())?;
+        Self::bytes_to_values_into(&decompressed, expected_len, dst)
+    }
+
+    fn train_dictionary(
+        samples: &[u8],
+        sample_sizes: &[usize],
+        max_size: usize,
+    ) -> Result<Vec<u8>> {
+        Ok(from_continuous(samples, sample_sizes, max_size)?)
+    }
+
+    fn validate_compression_level(level: i32) -> Result<()> {
+        if compression_level_range().contains(&level) {
+            Ok(())
+        } else {
+            Err(Error::InvalidArgument(
+                "zstd compression level out of range",
+            ))
+        }
     }
 
     fn decompress(bytes: &[u8], expected_len: usize) -> Result<Vec<T>> {
@@ -34,4 +85,20 @@ where
         let decompressed = decode_all(bytes)?;
         Self::bytes_to_values_into(&decompressed, expected_len, dst)
     }
+
+    /// Streams only the bytes of the first value out of the frame.
+    fn decompress_first(
+        bytes: &[u8],
+        _expected_len: usize,
+        dictionary: Option<&[u8]>,
+    ) -> Result<T> {
+        let mut first = vec![0; size_of::<T>()];
+        match dictionary {
+            Some(dictionary) => {
+                Decoder::with_dictionary(bytes, dictionary)?.read_exact(&mut first)?
+            }
+            None => Decoder::with_buffer(bytes)?.read_exact(&mut first)?,
+        }
+        <Self as ValueStrategy<T>>::read(&first)
+    }
 }
@@ -20,4 +20,8 @@ impl<V: ImportableVec> ImportableVec for EagerVec<V> {
     fn forced_import_with(options: ImportOptions) -> Result<Self> {
         Ok(Self(V::forced_import_with(options)?))
     }
+
+    fn import_forced_layout_with(options: ImportOptions) -> Result<Self> {
+        Ok(Self(V::import_forced_layout_with(options)?))
+    }
 }
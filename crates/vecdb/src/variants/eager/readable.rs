@@ -1,4 +1,4 @@
-use crate::{ReadableVec, StoredVec};
+use crate::{ReadableVec, Result, StoredVec};
 
 use super::EagerVec;
 
@@ -11,11 +11,21 @@ where
         self.0.collect_one_at(index)
     }
 
+    #[inline]
+    fn read_chunk_len(&self) -> usize {
+        self.0.read_chunk_len()
+    }
+
     #[inline(always)]
     fn read_into_at(&self, from: usize, to: usize, buf: &mut Vec<V::T>) {
         self.0.read_into_at(from, to, buf)
     }
 
+    #[inline]
+    fn try_read_into_at(&self, from: usize, to: usize, buf: &mut Vec<V::T>) -> Result<()> {
+        self.0.try_read_into_at(from, to, buf)
+    }
+
     #[inline]
     fn for_each_range_dyn_at(&self, from: usize, to: usize, f: &mut dyn FnMut(V::T)) {
         self.0.for_each_range_dyn_at(from, to, f)
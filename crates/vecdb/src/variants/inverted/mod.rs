@@ -0,0 +1,327 @@
+use std::marker::PhantomData;
+
+use rawdb::{Database, Region};
+
+mod segment;
+
+use segment::{Segment, SegmentView, encode};
+
+use crate::{
+    AnyStoredVec, Bytes, BytesVecValue, Error, Exit, ReadableVec, Result, Stamp, VecIndex, Version,
+    vec_region_name_with,
+};
+
+/// Source indices covered by a sealed segment.
+const SEGMENT_LEN: usize = 1 << 16;
+
+/// Persistent inverted index from the values of a source vec to their indices.
+///
+/// Maintained incrementally with [`compute`](Self::compute), like the computes of
+/// [`EagerVec`](crate::EagerVec): the source is indexed in segments of up to 65,536
+/// indices, each holding a sorted directory of its distinct values followed by their
+/// postings, delta and varint encoded. Segments are persisted in a `_postings` region
+/// and listed in a `_segments` region along with the stamp of the source they were
+/// indexed at, so the index follows truncations and rollbacks of its source.
+///
+/// ```ignore
+/// let mut by_address: InvertedIndex<TxIndex, AddressId> =
+///     InvertedIndex::forced_import(&db, "txindex_by_addressid", Version::ONE)?;
+/// by_address.compute(starting_txindex, &txindex_to_addressid, &exit)?;
+/// let txindexes = by_address.indices_of(&address_id)?;
+/// ```
+#[derive(Debug)]
+pub struct InvertedIndex<I, T> {
+    name: String,
+    version: Version,
+    computed_version: Version,
+    len: usize,
+    stamp: Stamp,
+    segments: Vec<Segment>,
+    meta: Region,
+    postings: Region,
+    _marker: PhantomData<fn() -> (I, T)>,
+}
+
+impl<I, T> InvertedIndex<I, T>
+where
+    I: VecIndex,
+    T: BytesVecValue + Ord,
+{
+    /// Size of the `[computed version][len: u64][stamp]` header of the segments region.
+    const HEADER_SIZE: usize = size_of::<Version>() + size_of::<u64>() + size_of::<Stamp>();
+
+    pub fn import(db: &Database, name: &str, version: Version) -> Result<Self> {
+        let region_name = vec_region_name_with::<I>(name);
+        let meta = db.create_region_if_needed(&format!("{region_name}_segments"))?;
+        let postings = db.create_region_if_needed(&format!("{region_name}_postings"))?;
+
+        let mut index = Self {
+            name: name.to_string(),
+            version,
+            computed_version: Version::ZERO,
+            len: 0,
+            stamp: Stamp::default(),
+            segments: vec![],
+            meta,
+            postings,
+            _marker: PhantomData,
+        };
+
+        let bytes = index.meta.create_reader().read_all().to_vec();
+        if bytes.is_empty() {
+            return Ok(index);
+        }
+        let corrupted = || Error::CorruptedRegion {
+            name: index.meta.meta().id().to_string(),
+            region_len: bytes.len(),
+        };
+        if bytes.len() < Self::HEADER_SIZE
+            || !(bytes.len() - Self::HEADER_SIZE).is_multiple_of(Segment::SIZE)
+        {
+            return Err(corrupted());
+        }
+
+        let (header, entries) = bytes.split_at(Self::HEADER_SIZE);
+        let computed_version = Version::from_bytes(&header[..4])?;
+        let len = u64::from_bytes(&header[4..12])? as usize;
+        let stamp = Stamp::from_bytes(&header[12..])?;
+        let segments = entries
+            .chunks_exact(Segment::SIZE)
+            .map(Segment::from_bytes)
+            .collect::<Result<Vec<_>>>()?;
+
+        let postings_len = index.postings.meta().len() as u64;
+        let mut expected_from = 0;
+        for segment in &segments {
+            if segment.from != expected_from
+                || segment.to <= segment.from
+                || segment.end() > postings_len
+            {
+                return Err(corrupted());
+            }
+            expected_from = segment.to;
+        }
+        if len as u64 > expected_from || segments.last().is_some_and(|s| len as u64 <= s.from) {
+            return Err(corrupted());
+        }
+
+        index.computed_version = computed_version;
+        index.len = len;
+        index.stamp = stamp;
+        index.segments = segments;
+        Ok(index)
+    }
+
+    /// Imports the index, recreating it empty if its regions are corrupted.
+    pub fn forced_import(db: &Database, name: &str, version: Version) -> Result<Self> {
+        match Self::import(db, name, version) {
+            Err(err) if err.is_data_error() => {
+                let region_name = vec_region_name_with::<I>(name);
+                db.remove_region_if_exists(&format!("{region_name}_segments"))?;
+                db.remove_region_if_exists(&format!("{region_name}_postings"))?;
+                Self::import(db, name, version)
+            }
+            result => result,
+        }
+    }
+
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[inline]
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    /// Number of source indices indexed.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Stamp of the source when it was last indexed.
+    #[inline]
+    pub fn stamp(&self) -> Stamp {
+        self.stamp
+    }
+
+    /// Indexes `source` from `max_from` on.
+    ///
+    /// Resets the index if its version or the source's changed, and rolls it back if
+    /// the source's stamp went back, then indexes everything up to `source.len()`.
+    /// Each segment is written under the `exit` lock.
+    pub fn compute<V>(&mut self, max_from: I, source: &V, exit: &Exit) -> Result<()>
+    where
+        V: ReadableVec<I, T> + AnyStoredVec,
+    {
+        let version = self.version + source.version();
+        if version != self.computed_version {
+            self.computed_version = version;
+            self.reset()?;
+        }
+
+        let source_stamp = source.stamp();
+        if source_stamp < self.stamp {
+            self.rollback_to(source_stamp)?;
+        }
+
+        let source_len = source.len();
+        self.truncate_if_needed_at(max_from.to_usize().min(source_len))?;
+
+        // A partial or truncated last segment is indexed again with what follows
+        if let Some(last) = self.segments.last().copied()
+            && source_len > self.len
+            && (last.to - last.from < SEGMENT_LEN as u64 || last.to as usize > self.len)
+        {
+            self.segments.pop();
+            self.len = last.from as usize;
+        }
+
+        while self.len < source_len {
+            let from = self.len;
+            let to = (from + SEGMENT_LEN).min(source_len);
+            let bytes = encode(&source.collect_range_at(from, to));
+            let start = self.segments.last().map_or(0, Segment::end);
+
+            let _lock = exit.lock();
+            self.postings.truncate_write(start as usize, &bytes)?;
+            self.segments.push(Segment {
+                from: from as u64,
+                to: to as u64,
+                stamp: source_stamp,
+                start,
+                bytes: bytes.len() as u64,
+            });
+            self.len = to;
+            self.stamp = source_stamp;
+            self.flush_meta()?;
+        }
+
+        if self.stamp != source_stamp {
+            let _lock = exit.lock();
+            self.stamp = source_stamp;
+            self.flush_meta()?;
+        }
+        Ok(())
+    }
+
+    /// Forgets source indices from `index` on.
+    pub fn truncate_if_needed(&mut self, index: I) -> Result<()> {
+        self.truncate_if_needed_at(index.to_usize())
+    }
+
+    /// Forgets source indices from `index` on. A segment straddling `index` is kept and
+    /// its postings past the end are skipped until the next compute rebuilds it.
+    pub fn truncate_if_needed_at(&mut self, index: usize) -> Result<()> {
+        if index >= self.len {
+            return Ok(());
+        }
+        let keep = self.segments.partition_point(|s| (s.from as usize) < index);
+        self.segments.truncate(keep);
+        self.len = index;
+        self.postings
+            .truncate(self.segments.last().map_or(0, Segment::end) as usize)?;
+        self.flush_meta()
+    }
+
+    /// Drops the segments indexed after the source had `stamp`, for a source rolled
+    /// back to it.
+    pub fn rollback_to(&mut self, stamp: Stamp) -> Result<()> {
+        if let Some(segment) = self.segments.iter().find(|s| s.stamp > stamp) {
+            self.truncate_if_needed_at(segment.from as usize)?;
+        }
+        self.stamp = stamp;
+        self.flush_meta()
+    }
+
+    /// Empties the index.
+    pub fn reset(&mut self) -> Result<()> {
+        self.segments.clear();
+        self.len = 0;
+        self.stamp = Stamp::default();
+        self.postings.truncate(0)?;
+        self.flush_meta()
+    }
+
+    /// Indices of `value` in the source, in order. Errors if a segment it's looked up
+    /// in doesn't decode.
+    pub fn indices_of(&self, value: &T) -> Result<Vec<I>> {
+        let mut indices = vec![];
+        self.for_each_segment(|view, from, to, _| {
+            view.for_each_index_of(value, |i| {
+                if from + i < to {
+                    indices.push(I::from(from + i));
+                }
+            })
+        })?;
+        Ok(indices)
+    }
+
+    /// Number of occurrences of `value` in the source. Errors if a segment it's looked
+    /// up in doesn't decode.
+    pub fn count_of(&self, value: &T) -> Result<usize> {
+        let mut count = 0;
+        self.for_each_segment(|view, from, to, truncated| {
+            if truncated {
+                view.for_each_index_of(value, |i| count += usize::from(from + i < to))
+            } else {
+                count += view.count_of(value)?;
+                Ok(())
+            }
+        })?;
+        Ok(count)
+    }
+
+    /// Calls `f` with the view of each segment, the source range it covers and whether
+    /// it holds postings past the end, stopping at the first error.
+    fn for_each_segment(
+        &self,
+        mut f: impl FnMut(&SegmentView<'_, T>, usize, usize, bool) -> Result<()>,
+    ) -> Result<()> {
+        let reader = self.postings.create_reader();
+        for segment in &self.segments {
+            if segment.end() > reader.len() as u64 {
+                return Err(Error::CorruptedRegion {
+                    name: self.postings.meta().id().to_string(),
+                    region_len: reader.len(),
+                });
+            }
+            let bytes = reader.unchecked_read(segment.start as usize, segment.bytes as usize);
+            let view = SegmentView::parse(bytes)?;
+            let to = segment.to as usize;
+            f(
+                &view,
+                segment.from as usize,
+                to.min(self.len),
+                to > self.len,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Removes the regions of the index.
+    pub fn remove(self) -> Result<()> {
+        self.meta.remove()?;
+        self.postings.remove()?;
+        Ok(())
+    }
+
+    fn flush_meta(&self) -> Result<()> {
+        let mut bytes = Vec::with_capacity(Self::HEADER_SIZE + self.segments.len() * Segment::SIZE);
+        bytes.extend_from_slice(self.computed_version.to_bytes().as_ref());
+        bytes.extend_from_slice((self.len as u64).to_bytes().as_ref());
+        bytes.extend_from_slice(self.stamp.to_bytes().as_ref());
+        for segment in &self.segments {
+            bytes.extend_from_slice(&segment.to_bytes());
+        }
+        self.meta.truncate_write(0, &bytes)?;
+        Ok(())
+    }
+}
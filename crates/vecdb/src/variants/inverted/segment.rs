@@ -0,0 +1,181 @@
+use std::{collections::BTreeMap, marker::PhantomData};
+
+use crate::{Bytes, Error, Result, Stamp};
+
+/// Metadata of a segment: the postings of source indices `[from, to)`, stored at
+/// `start..start + bytes` in the postings region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct Segment {
+    pub from: u64,
+    pub to: u64,
+    /// Stamp of the source when the segment was indexed
+    pub stamp: Stamp,
+    pub start: u64,
+    pub bytes: u64,
+}
+
+impl Segment {
+    pub const SIZE: usize = 5 * size_of::<u64>();
+
+    #[inline]
+    pub fn end(&self) -> u64 {
+        self.start + self.bytes
+    }
+
+    pub fn to_bytes(self) -> [u8; Self::SIZE] {
+        let mut bytes = [0; Self::SIZE];
+        bytes[0..8].copy_from_slice(&self.from.to_bytes());
+        bytes[8..16].copy_from_slice(&self.to.to_bytes());
+        bytes[16..24].copy_from_slice(&self.stamp.to_bytes());
+        bytes[24..32].copy_from_slice(&self.start.to_bytes());
+        bytes[32..40].copy_from_slice(&self.bytes.to_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Ok(Self {
+            from: u64::from_bytes(&bytes[0..8])?,
+            to: u64::from_bytes(&bytes[8..16])?,
+            stamp: Stamp::from_bytes(&bytes[16..24])?,
+            start: u64::from_bytes(&bytes[24..32])?,
+            bytes: u64::from_bytes(&bytes[32..40])?,
+        })
+    }
+}
+
+/// Size of a directory entry's count and postings offset, after the key.
+const ENTRY_TAIL: usize = 2 * size_of::<u32>();
+
+/// Encodes the postings of `values`, the source values from index `from`.
+///
+/// Layout: `[keys: u32][(key, count: u32, offset: u32) * keys][postings]`, keys sorted.
+/// The postings of a key are its indices relative to `from`, as varint deltas from
+/// the previous one (the first from 0), starting `offset` bytes into the postings.
+pub(super) fn encode<T>(values: &[T]) -> Vec<u8>
+where
+    T: Bytes + Ord + Clone,
+{
+    let mut postings: BTreeMap<&T, Vec<u32>> = BTreeMap::new();
+    for (i, value) in values.iter().enumerate() {
+        postings.entry(value).or_default().push(i as u32);
+    }
+
+    let entry_size = size_of::<T>() + ENTRY_TAIL;
+    let mut directory = Vec::with_capacity(size_of::<u32>() + postings.len() * entry_size);
+    directory.extend((postings.len() as u32).to_bytes());
+    let mut data = vec![];
+    for (key, indices) in postings {
+        directory.extend_from_slice(key.to_bytes().as_ref());
+        directory.extend((indices.len() as u32).to_bytes());
+        directory.extend((data.len() as u32).to_bytes());
+        let mut prev = 0;
+        for i in indices {
+            write_varint(&mut data, i - prev);
+            prev = i;
+        }
+    }
+
+    directory.extend(data);
+    directory
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut value: u32) {
+    while value >= 0x80 {
+        buf.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+fn read_varint(bytes: &[u8], pos: &mut usize) -> Result<u32> {
+    let mut value = 0u32;
+    for shift in (0..35).step_by(7) {
+        let byte = *bytes.get(*pos).ok_or(Error::InvalidArgument(
+            "postings end in the middle of a varint",
+        ))?;
+        *pos += 1;
+        value |= u32::from(byte & 0x7F) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(Error::InvalidArgument("varint is too long"))
+}
+
+/// Zero-copy view over the postings of one segment, see [`encode`].
+pub(super) struct SegmentView<'a, T> {
+    bytes: &'a [u8],
+    keys: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> SegmentView<'a, T>
+where
+    T: Bytes + Ord,
+{
+    const ENTRY_SIZE: usize = size_of::<T>() + ENTRY_TAIL;
+
+    pub fn parse(bytes: &'a [u8]) -> Result<Self> {
+        let keys = u32::from_bytes(bytes.get(..size_of::<u32>()).ok_or(Error::WrongLength {
+            expected: size_of::<u32>(),
+            received: bytes.len(),
+        })?)? as usize;
+        let directory_end = size_of::<u32>() + keys * Self::ENTRY_SIZE;
+        if directory_end > bytes.len() {
+            return Err(Error::WrongLength {
+                expected: directory_end,
+                received: bytes.len(),
+            });
+        }
+        Ok(Self {
+            bytes,
+            keys,
+            _marker: PhantomData,
+        })
+    }
+
+    fn entry(&self, i: usize) -> &'a [u8] {
+        let at = size_of::<u32>() + i * Self::ENTRY_SIZE;
+        &self.bytes[at..at + Self::ENTRY_SIZE]
+    }
+
+    /// Number of postings of `value` and the offset of the first one.
+    fn find(&self, value: &T) -> Result<Option<(usize, usize)>> {
+        let (mut lo, mut hi) = (0, self.keys);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            let entry = self.entry(mid);
+            match T::from_bytes(&entry[..size_of::<T>()])?.cmp(value) {
+                std::cmp::Ordering::Less => lo = mid + 1,
+                std::cmp::Ordering::Greater => hi = mid,
+                std::cmp::Ordering::Equal => {
+                    let tail = &entry[size_of::<T>()..];
+                    let count = u32::from_bytes(&tail[..4])? as usize;
+                    let offset = u32::from_bytes(&tail[4..])? as usize;
+                    return Ok(Some((count, offset)));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Number of postings of `value`.
+    pub fn count_of(&self, value: &T) -> Result<usize> {
+        Ok(self.find(value)?.map_or(0, |(count, _)| count))
+    }
+
+    /// Calls `f` with the indices of `value`, relative to the start of the segment, in order.
+    pub fn for_each_index_of(&self, value: &T, mut f: impl FnMut(usize)) -> Result<()> {
+        let Some((count, offset)) = self.find(value)? else {
+            return Ok(());
+        };
+        let postings = &self.bytes[size_of::<u32>() + self.keys * Self::ENTRY_SIZE..];
+        let mut pos = offset;
+        let mut index = 0;
+        for _ in 0..count {
+            index += read_varint(postings, &mut pos)? as usize;
+            f(index);
+        }
+        Ok(())
+    }
+}
@@ -17,7 +17,7 @@
 /// - `TypedVec`
 /// - `AnyStoredVec`
 /// - `WritableVec`
-/// - `ReadableVec` (delegates `for_each_range_dyn` / `fold_range` to inner)
+/// - `ReadableVec` (delegates `for_each_range_dyn` / `fold_range` aggregations and searches to inner)
 macro_rules! impl_vec_wrapper {
     ($wrapper:ident, $inner:ty, $value_trait:ident, $format:expr, $read_only:ty) => {
         impl<I, T> ::std::ops::Deref for $wrapper<I, T> {
@@ -64,6 +64,10 @@ macro_rules! impl_vec_wrapper {
             fn forced_import_with(options: $crate::ImportOptions) -> $crate::Result<Self> {
                 Ok(Self(<$inner>::forced_import_with(options, $format)?))
             }
+
+            fn import_forced_layout_with(options: $crate::ImportOptions) -> $crate::Result<Self> {
+                Ok(Self(<$inner>::import_forced_layout_with(options, $format)?))
+            }
         }
 
         impl<I, T> $crate::AnyVec for $wrapper<I, T>
@@ -288,6 +292,16 @@ macro_rules! impl_vec_wrapper {
                 $crate::ReadableVec::<I, T>::read_into_at(&self.0, from, to, buf)
             }
 
+            #[inline]
+            fn try_read_into_at(
+                &self,
+                from: usize,
+                to: usize,
+                buf: &mut Vec<T>,
+            ) -> $crate::Result<()> {
+                $crate::ReadableVec::<I, T>::try_read_into_at(&self.0, from, to, buf)
+            }
+
             #[inline]
             fn for_each_range_dyn_at(&self, from: usize, to: usize, f: &mut dyn FnMut(T)) {
                 $crate::ReadableVec::<I, T>::for_each_range_dyn_at(&self.0, from, to, f)
@@ -320,6 +334,51 @@ macro_rules! impl_vec_wrapper {
             {
                 $crate::ReadableVec::<I, T>::try_fold_range_at(&self.0, from, to, init, f)
             }
+
+            #[inline]
+            fn read_chunk_len(&self) -> usize {
+                $crate::ReadableVec::<I, T>::read_chunk_len(&self.0)
+            }
+
+            #[inline]
+            fn min_at(&self, from: usize, to: usize) -> Option<T>
+            where
+                Self: Sized,
+                T: PartialOrd,
+            {
+                $crate::ReadableVec::<I, T>::min_at(&self.0, from, to)
+            }
+
+            #[inline]
+            fn max_at(&self, from: usize, to: usize) -> Option<T>
+            where
+                Self: Sized,
+                T: PartialOrd,
+            {
+                $crate::ReadableVec::<I, T>::max_at(&self.0, from, to)
+            }
+
+            #[inline]
+            fn sum_at(&self, from: usize, to: usize) -> Option<T>
+            where
+                Self: Sized,
+                T: ::std::ops::AddAssign + From<u8>,
+            {
+                $crate::ReadableVec::<I, T>::sum_at(&self.0, from, to)
+            }
+
+            #[inline]
+            fn partition_point_range_at<P: FnMut(&T) -> bool>(
+                &self,
+                from: usize,
+                to: usize,
+                pred: P,
+            ) -> usize
+            where
+                Self: Sized,
+            {
+                $crate::ReadableVec::<I, T>::partition_point_range_at(&self.0, from, to, pred)
+            }
         }
     };
 }
@@ -1,13 +1,17 @@
 mod cached;
+mod columnar;
 mod compressed;
 mod eager;
+mod inverted;
 mod lazy;
 mod macros;
 mod raw;
 
 pub use cached::*;
+pub use columnar::*;
 pub use compressed::*;
 pub use eager::*;
+pub use inverted::*;
 pub use lazy::*;
 #[allow(unused_imports)]
 pub use macros::*;
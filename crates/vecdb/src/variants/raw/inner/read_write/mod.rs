@@ -16,14 +16,12 @@ mod writable;
 
 use crate::{
     AnyStoredVec, AnyVec, Bytes, Error, Format, HEADER_OFFSET, ImportOptions, MMAP_CROSSOVER_BYTES,
-    RawIoSource, RawMmapSource, ReadWriteBaseVec, Result, VecIndex, VecReader, VecValue, Version,
-    WithPrev, vec_region_name_with,
+    RawIoSource, RawMmapSource, ReadWriteBaseVec, Result, VecIndex, VecReader, VecValue, WithPrev,
+    vec_region_name_with,
 };
 
 use super::{RawStrategy, ReadOnlyRawVec};
 
-const VERSION: Version = Version::ONE;
-
 /// Core implementation for raw storage vectors shared by BytesVec and ZeroCopyVec.
 ///
 /// Parameterized by serialization strategy `S` to support different serialization approaches:
@@ -56,26 +54,40 @@ where
     /// # Warning
     ///
     /// This will DELETE all existing data on format/version errors. Use with caution.
-    pub fn forced_import_with(mut options: ImportOptions, format: Format) -> Result<Self> {
-        options.version = options.version + VERSION;
-        let res = Self::import_with(options, format);
+    ///
+    /// Regions that aren't vecs ([`Error::NotAVec`]), have a damaged header
+    /// ([`Error::CorruptedHeader`]) or an older header ([`Error::OlderHeaderVersion`], see
+    /// [`HeaderV1`](crate::HeaderV1)) are left untouched and the error is returned.
+    pub fn forced_import_with(options: ImportOptions, format: Format) -> Result<Self> {
+        let res = Self::import_forced_layout_with(options, format);
         match res {
             Err(Error::WrongEndian)
             | Err(Error::WrongLength { .. })
             | Err(Error::DifferentFormat { .. })
+            | Err(Error::DifferentValueType { .. })
             | Err(Error::DifferentVersion { .. }) => {
                 info!("Resetting {}...", options.name);
                 options
                     .db
                     .remove_region_if_exists(&vec_region_name_with::<I>(options.name))?;
-                Self::import_with(options, format)
+                Self::import_forced_layout_with(options, format)
             }
             _ => res,
         }
     }
 
+    /// Imports a vec created by [`Self::forced_import_with`], without resetting it, and
+    /// marks its header as [forced](crate::Header::forced_layout).
+    pub fn import_forced_layout_with(mut options: ImportOptions, format: Format) -> Result<Self> {
+        options.version = options.version + format.layout_version();
+        let mut this = Self::import_with(options, format)?;
+        this.base.mut_header().enable_forced_layout();
+        this.base.write_header_if_needed()?;
+        Ok(this)
+    }
+
     pub fn import_with(mut options: ImportOptions, format: Format) -> Result<Self> {
-        options.version = options.version + VERSION;
+        options.version = options.version + format.layout_version();
 
         let db = options.db;
         let name = options.name;
@@ -463,7 +475,10 @@ where
 
         let stored_to = to.min(stored_len);
         let mut hole_iter = self.holes().range(from..to).peekable();
-        let mut update_iter = self.updated().range(from..stored_to).peekable();
+        let mut update_iter = self
+            .updated()
+            .range(from.min(stored_to)..stored_to)
+            .peekable();
 
         let mut byte_off = from * Self::SIZE_OF_T;
         for i in from..stored_to {
@@ -512,7 +527,10 @@ where
 
         let stored_to = to.min(stored_len);
         let mut hole_iter = self.holes().range(from..to).peekable();
-        let mut update_iter = self.updated().range(from..stored_to).peekable();
+        let mut update_iter = self
+            .updated()
+            .range(from.min(stored_to)..stored_to)
+            .peekable();
 
         let mut byte_off = from * Self::SIZE_OF_T;
         for i in from..stored_to {
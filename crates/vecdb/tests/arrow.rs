@@ -0,0 +1,175 @@
+//! Tests for Apache Arrow import and export.
+//!
+//! These tests cover:
+//! - Primitive, boolean and fixed-size binary arrays from vec ranges, with pushed values
+//! - Appending Arrow columns to vecs, rejecting other types and nulls
+//! - Tables of several vecs written as IPC files and streams, read back batch by batch
+//! - Rejecting empty tables and repeated columns
+
+#![cfg(feature = "arrow")]
+
+use std::io::Cursor;
+
+use rawdb::Database;
+use tempfile::TempDir;
+use vecdb::{
+    AnyArrowVec, AnyStoredVec, AnyVec, ArrowTable, BytesVec, Error, ImportableVec, ReadableVec,
+    Result, Version, WritableVec, append_arrow_column,
+    arrow_array::{
+        Array, BooleanArray, FixedSizeBinaryArray, Float64Array, RecordBatch, UInt32Array,
+        UInt64Array, cast::AsArray, types::UInt64Type,
+    },
+    arrow_ipc::reader::{FileReader, StreamReader},
+    arrow_schema::DataType,
+    to_arrow_array,
+};
+
+fn setup_db() -> Result<(Database, TempDir)> {
+    let temp = TempDir::new()?;
+    let db = Database::open(temp.path())?;
+    Ok((db, temp))
+}
+
+#[test]
+fn primitive_round_trip() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let mut vec: BytesVec<usize, u64> = BytesVec::import(&db, "vec", Version::ONE)?;
+    (0..1_000u64).for_each(|i| vec.push(i * 3));
+    vec.write()?;
+    (1_000..1_100u64).for_each(|i| vec.push(i * 3));
+
+    let array = to_arrow_array(&vec, 990, 1_010);
+    assert_eq!(array.data_type(), &DataType::UInt64);
+    let expected = UInt64Array::from_iter_values((990..1_010).map(|i| i * 3));
+    assert_eq!(array.as_primitive::<UInt64Type>(), &expected);
+    assert_eq!(vec.arrow_array(1_095, 2_000).len(), 5);
+
+    let mut copy: BytesVec<usize, u64> = BytesVec::import(&db, "copy", Version::ONE)?;
+    assert_eq!(append_arrow_column(&mut copy, &array)?, 20);
+    assert_eq!(append_arrow_column(&mut copy, &array.slice(5, 3))?, 3);
+    assert_eq!(copy.collect()[20..], [995 * 3, 996 * 3, 997 * 3]);
+    Ok(())
+}
+
+#[test]
+fn bool_and_fixed_size_binary() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let mut flags: BytesVec<usize, bool> = BytesVec::import(&db, "flags", Version::ONE)?;
+    let mut hashes: BytesVec<usize, [u8; 4]> = BytesVec::import(&db, "hashes", Version::ONE)?;
+    for i in 0..100u32 {
+        flags.push(i % 3 == 0);
+        hashes.push((i * 7).to_le_bytes());
+    }
+
+    let array = to_arrow_array(&flags, 0, 100);
+    let expected = BooleanArray::from((0..100).map(|i| i % 3 == 0).collect::<Vec<_>>());
+    assert_eq!(array.as_boolean(), &expected);
+
+    let array = to_arrow_array(&hashes, 10, 20);
+    assert_eq!(array.data_type(), &DataType::FixedSizeBinary(4));
+    let binary = array
+        .as_any()
+        .downcast_ref::<FixedSizeBinaryArray>()
+        .unwrap();
+    assert_eq!(binary.value(0), 70u32.to_le_bytes());
+
+    let mut copy: BytesVec<usize, [u8; 4]> = BytesVec::import(&db, "copy", Version::ONE)?;
+    append_arrow_column(&mut copy, &array.slice(2, 3))?;
+    assert_eq!(copy.collect(), hashes.collect_range(12, 15));
+    Ok(())
+}
+
+#[test]
+fn rejected_columns() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let mut vec: BytesVec<usize, u64> = BytesVec::import(&db, "vec", Version::ONE)?;
+
+    let floats = Float64Array::from(vec![1.0, 2.0]);
+    assert!(matches!(
+        append_arrow_column(&mut vec, &floats),
+        Err(Error::Arrow(_))
+    ));
+    let with_nulls = UInt64Array::from(vec![Some(1), None, Some(3)]);
+    assert!(matches!(
+        append_arrow_column(&mut vec, &with_nulls),
+        Err(Error::Arrow(_))
+    ));
+    assert_eq!(vec.len(), 0);
+    Ok(())
+}
+
+fn check_batches(batches: Vec<RecordBatch>) {
+    assert_eq!(
+        batches
+            .iter()
+            .map(RecordBatch::num_rows)
+            .collect::<Vec<_>>(),
+        [40, 40, 20]
+    );
+    let schema = batches[0].schema();
+    let names = schema.fields().iter().map(|f| f.name().as_str());
+    assert_eq!(names.collect::<Vec<_>>(), ["height", "price", "count"]);
+
+    let batch = &batches[1];
+    let height = batch.column(0).as_primitive::<UInt64Type>();
+    let price = batch
+        .column(1)
+        .as_any()
+        .downcast_ref::<Float64Array>()
+        .unwrap();
+    let count = batch
+        .column(2)
+        .as_any()
+        .downcast_ref::<UInt32Array>()
+        .unwrap();
+    assert_eq!(height.value(0), 50);
+    assert_eq!(price.value(0), 25.0);
+    assert_eq!(count.value(39), 89);
+}
+
+#[test]
+fn ipc_table() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let mut price: BytesVec<usize, f64> = BytesVec::import(&db, "price", Version::ONE)?;
+    let mut count: BytesVec<usize, u32> = BytesVec::import(&db, "count", Version::ONE)?;
+    (0..120).for_each(|i| price.push(i as f64 / 2.0));
+    (0..110).for_each(|i| count.push(i));
+
+    // Rows stop at the shortest column
+    let table = ArrowTable::new(vec![&price, &count])?
+        .with_range(10, None)
+        .with_batch_len(40)
+        .with_index_column("height");
+    assert_eq!(table.range(), (10, 110));
+
+    let mut file = vec![];
+    table.write_ipc_file(&mut file)?;
+    let batches = FileReader::try_new(Cursor::new(file), None)?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    check_batches(batches);
+
+    let mut stream = vec![];
+    table.write_ipc_stream(&mut stream)?;
+    let batches = StreamReader::try_new(Cursor::new(stream), None)?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    check_batches(batches);
+
+    // An empty range still writes the schema
+    let mut file = vec![];
+    ArrowTable::new(vec![&price])?
+        .with_range(500, None)
+        .write_ipc_file(&mut file)?;
+    let reader = FileReader::try_new(Cursor::new(file), None)?;
+    assert_eq!(reader.schema().fields().len(), 1);
+    assert_eq!(reader.count(), 0);
+    Ok(())
+}
+
+#[test]
+fn rejected_tables() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let vec: BytesVec<usize, u64> = BytesVec::import(&db, "vec", Version::ONE)?;
+    assert!(ArrowTable::new(vec![]).is_err());
+    assert!(ArrowTable::new(vec![&vec, &vec]).is_err());
+    Ok(())
+}
@@ -236,6 +236,17 @@ mod zstd {
     }
 }
 
+mod monotonic {
+    use super::*;
+    use vecdb::MonotonicVec;
+    type V = MonotonicVec<usize, u32>;
+
+    #[test]
+    fn test_vec_operations() -> Result<(), Box<dyn std::error::Error>> {
+        run_vec_operations::<V>()
+    }
+}
+
 #[cfg(feature = "zerocopy")]
 mod eager_zerocopy {
     use super::*;
@@ -0,0 +1,227 @@
+//! Tests for the built-in `Bytes` implementations.
+//!
+//! These tests cover:
+//! - bool, char and NonZero values, rejecting invalid bytes
+//! - Option with a tag byte, or a niche when there's no room for one
+//! - Tuples and arrays of any `Bytes` type
+//! - Native layout flags
+//! - Storing them in vecs
+
+use std::{
+    fmt::Debug,
+    num::{NonZeroI64, NonZeroU8, NonZeroU32, NonZeroU64},
+};
+
+use rawdb::Database;
+use tempfile::TempDir;
+use vecdb::{
+    AnyStoredVec, Bytes, BytesVec, Error, ImportableVec, ReadableVec, Result, VecValue, Version,
+    WritableVec,
+};
+
+fn setup_db() -> Result<(Database, TempDir)> {
+    let temp = TempDir::new()?;
+    let db = Database::open(temp.path())?;
+    Ok((db, temp))
+}
+
+/// Checks that `value` round-trips through `size_of::<T>()` bytes, the same whether
+/// written in place or not.
+fn roundtrip<T: Bytes + PartialEq + Debug>(value: T) -> Result<()> {
+    let bytes = value.to_bytes();
+    assert_eq!(bytes.as_ref().len(), size_of::<T>());
+    assert_eq!(T::from_bytes(bytes.as_ref())?, value);
+
+    // Every byte is overwritten, padding included
+    let mut dst = vec![0xFF; size_of::<T>()];
+    value.write_bytes_to(&mut dst);
+    assert_eq!(dst, bytes.as_ref());
+    Ok(())
+}
+
+fn invalid_value<T: Bytes + Debug>(bytes: &[u8]) {
+    let err = T::from_bytes(bytes).unwrap_err();
+    assert!(matches!(err, Error::InvalidValue { .. }), "{err}");
+    assert!(err.is_data_error());
+}
+
+// ============================================================================
+// Generic tests
+// ============================================================================
+
+fn test_vec<T>(values: Vec<T>) -> Result<()>
+where
+    T: VecValue + Bytes + PartialEq,
+{
+    let (db, _temp) = setup_db()?;
+    {
+        let mut vec: BytesVec<usize, T> = BytesVec::import(&db, "vec", Version::ONE)?;
+        values.iter().for_each(|v| vec.push(v.clone()));
+        vec.write()?;
+    }
+
+    let vec: BytesVec<usize, T> = BytesVec::import(&db, "vec", Version::ONE)?;
+    assert_eq!(vec.collect(), values);
+    assert_eq!(vec.collect_range(1, 3), values[1..3]);
+    Ok(())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn primitives() -> Result<()> {
+    roundtrip(true)?;
+    roundtrip(false)?;
+    roundtrip('a')?;
+    roundtrip('🦀')?;
+    roundtrip(NonZeroU8::MAX)?;
+    roundtrip(NonZeroU64::new(42).unwrap())?;
+    roundtrip(NonZeroI64::new(-42).unwrap())?;
+
+    invalid_value::<bool>(&[2]);
+    invalid_value::<char>(&0xD800u32.to_le_bytes());
+    invalid_value::<char>(&0x110000u32.to_le_bytes());
+    invalid_value::<NonZeroU32>(&[0; 4]);
+    Ok(())
+}
+
+#[test]
+fn option_with_tag() -> Result<()> {
+    roundtrip(Some(7u64))?;
+    roundtrip(None::<u64>)?;
+    roundtrip(Some(u8::MAX))?;
+
+    let bytes = Some(7u32).to_bytes();
+    assert_eq!(bytes.as_ref(), [1, 7, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(None::<u32>.to_bytes().as_ref(), [0; 8]);
+
+    invalid_value::<Option<u32>>(&[2, 7, 0, 0, 0, 0, 0, 0]);
+    Ok(())
+}
+
+#[test]
+fn option_with_niche() -> Result<()> {
+    assert_eq!(size_of::<Option<NonZeroU32>>(), 4);
+    roundtrip(NonZeroU32::new(9))?;
+    roundtrip(None::<NonZeroU32>)?;
+    assert_eq!(None::<NonZeroU32>.to_bytes().as_ref(), [0; 4]);
+
+    roundtrip(Some(true))?;
+    roundtrip(Some(false))?;
+    roundtrip(None::<bool>)?;
+    roundtrip(Some('x'))?;
+    roundtrip(None::<char>)?;
+    Ok(())
+}
+
+#[test]
+fn nested_options() -> Result<()> {
+    roundtrip(Some(Some(1u16)))?;
+    roundtrip(Some(None::<u16>))?;
+    roundtrip(None::<Option<u16>>)?;
+    roundtrip(Some(NonZeroU64::new(3)))?;
+    roundtrip(Some(None::<NonZeroU64>))?;
+    roundtrip(None::<Option<NonZeroU64>>)?;
+    Ok(())
+}
+
+#[test]
+fn tuples() -> Result<()> {
+    roundtrip((1u8,))?;
+    roundtrip((1u32, 2u64))?;
+    roundtrip((1u8, 2u16, 3u32, 4u64, 5.0f32, 6.0f64))?;
+    roundtrip((true, 'z', Some(3i16)))?;
+
+    assert!(matches!(
+        <(u32, u32)>::from_bytes(&[0; 7]),
+        Err(Error::WrongLength {
+            expected: 8,
+            received: 7
+        })
+    ));
+    Ok(())
+}
+
+#[test]
+fn arrays() -> Result<()> {
+    roundtrip([1u8, 2, 3])?;
+    roundtrip([7u8; 65])?;
+    roundtrip([u64::MAX; 40])?;
+    roundtrip([(1u8, 2u32); 3])?;
+    roundtrip([Some(1u32), None, Some(3)])?;
+    roundtrip([(1u16, 2u16); 4])?;
+    roundtrip::<[u32; 0]>([])?;
+
+    invalid_value::<[bool; 2]>(&[1, 5]);
+    Ok(())
+}
+
+#[test]
+fn native_layout() {
+    let little = cfg!(target_endian = "little");
+    assert_eq!(<[u8; 32]>::IS_NATIVE_LAYOUT, little);
+    assert_eq!(<[u64; 4]>::IS_NATIVE_LAYOUT, little);
+    assert_eq!(<(u32, u32)>::IS_NATIVE_LAYOUT, little);
+    assert_eq!(<(u64, u64, u64)>::IS_NATIVE_LAYOUT, little);
+    // Padding
+    const { assert!(!<(u8, u32)>::IS_NATIVE_LAYOUT) };
+    // Invalid bit patterns
+    const { assert!(!bool::IS_NATIVE_LAYOUT) };
+    const { assert!(!NonZeroU32::IS_NATIVE_LAYOUT) };
+    const { assert!(!<Option<u32>>::IS_NATIVE_LAYOUT) };
+    const { assert!(!<[char; 2]>::IS_NATIVE_LAYOUT) };
+}
+
+#[test]
+fn vec_of_options() -> Result<()> {
+    test_vec((0..1000u64).map(|i| (i % 3 != 0).then_some(i)).collect())?;
+    test_vec((0..1000u32).map(NonZeroU32::new).collect())
+}
+
+#[test]
+fn vec_of_tuples() -> Result<()> {
+    test_vec(
+        (0..1000u32)
+            .map(|i| (i as u8, i, u64::from(i) * 2))
+            .collect(),
+    )
+}
+
+#[test]
+fn vec_of_arrays() -> Result<()> {
+    test_vec((0..1000u16).map(|i| [i, i + 1, i + 2]).collect())?;
+    test_vec((0..1000u32).map(|i| [i as u8; 100]).collect())?;
+    test_vec((0..1000u32).map(|i| [Some(i), None]).collect())
+}
+
+#[test]
+fn vec_of_primitives() -> Result<()> {
+    test_vec((0..1000u32).map(|i| i % 2 == 0).collect())?;
+    test_vec(('a'..='z').collect())
+}
+
+#[cfg(feature = "zstd")]
+mod compressed {
+    use super::*;
+    use vecdb::ZstdVec;
+
+    #[test]
+    fn zstd_vec_of_options() -> Result<()> {
+        let (db, _temp) = setup_db()?;
+        let values = (0..10_000u64)
+            .map(|i| (i % 5 != 0).then_some((i, i % 2 == 0)))
+            .collect::<Vec<_>>();
+
+        let mut vec: ZstdVec<usize, Option<(u64, bool)>> =
+            ZstdVec::import(&db, "vec", Version::ONE)?;
+        values.iter().for_each(|v| vec.push(*v));
+        vec.write()?;
+        drop(vec);
+
+        let vec: ZstdVec<usize, Option<(u64, bool)>> = ZstdVec::import(&db, "vec", Version::ONE)?;
+        assert_eq!(vec.collect(), values);
+        Ok(())
+    }
+}
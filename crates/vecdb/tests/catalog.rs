@@ -0,0 +1,126 @@
+//! Tests for the database catalog.
+//!
+//! These tests cover:
+//! - Raw vecs of primitive and unknown value types
+//! - Compressed vecs, with page stats and with a value size found from a raw page
+//! - Regions and change directories that don't belong to a vec
+
+use std::fs;
+
+use rawdb::Database;
+use tempfile::TempDir;
+use vecdb::{
+    AnyStoredVec, BytesVec, Catalog, Format, HEADER_OFFSET, ImportOptions, ImportableVec, Result,
+    Stamp, Version, WritableVec,
+};
+
+fn setup_db() -> Result<(Database, TempDir)> {
+    let temp = TempDir::new()?;
+    let db = Database::open(temp.path())?;
+    Ok((db, temp))
+}
+
+#[test]
+fn raw_vecs() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+
+    let options = ImportOptions::new(&db, "price", Version::TWO).with_saved_stamped_changes(2);
+    let mut price: BytesVec<usize, u64> = BytesVec::import_with(options)?;
+    (0..100).for_each(|v| price.push(v));
+    price.stamped_write_with_changes(Stamp::new(5))?;
+
+    let mut pair: BytesVec<usize, (u8, u16)> = BytesVec::import(&db, "pair", Version::ONE)?;
+    (0..10).for_each(|v| pair.push((v, 0)));
+    pair.write()?;
+
+    let catalog = Catalog::open(&db)?;
+    assert_eq!(catalog.entries().len(), 2);
+
+    let entry = catalog.get("price", "usize").unwrap();
+    assert_eq!(entry.region_name(), "price/usize");
+    assert_eq!(entry.format, Format::Bytes);
+    assert_eq!(entry.vec_version, Version::new(3));
+    assert_eq!(entry.stamp, Stamp::new(5));
+    assert_eq!(entry.value_type_name, Some("u64"));
+    assert_eq!(entry.value_size, Some(8));
+    assert_eq!(entry.len, Some(100));
+    assert_eq!(entry.pages, None);
+    assert_eq!(entry.stored_bytes, HEADER_OFFSET + 800);
+    assert_eq!(entry.uncompressed_bytes(), Some(800));
+    assert!(entry.compression_ratio().unwrap() < 1.0);
+    assert!(entry.has_rollback_files);
+
+    // The value size of raw vecs only comes from a primitive type
+    let entry = catalog.find("pair").next().unwrap();
+    assert_eq!(entry.value_type_name, None);
+    assert_eq!(entry.value_size, None);
+    assert_eq!(entry.len, None);
+    assert_eq!(entry.compression_ratio(), None);
+    assert!(!entry.has_rollback_files);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "pco")]
+fn compressed_vecs() -> Result<()> {
+    use vecdb::PcoVec;
+
+    let (db, _temp) = setup_db()?;
+    let options = ImportOptions::new(&db, "height", Version::ONE).with_page_stats(true);
+    let mut height: PcoVec<usize, u32> = PcoVec::import_with(options)?;
+    (0..10_000).for_each(|v| height.push(v));
+    height.stamped_write(Stamp::new(1))?;
+
+    let catalog = Catalog::open(&db)?;
+    let entry = catalog.get("height", "usize").unwrap();
+    assert_eq!(entry.format, Format::Pco);
+    assert_eq!(entry.value_type_name, Some("u32"));
+    assert_eq!(entry.len, Some(10_000));
+    assert_eq!(entry.pages, Some(3));
+    assert_eq!(entry.regions, ["height/usize", "height/usize_pages"]);
+    // The last page stays raw
+    assert!(entry.compression_ratio().unwrap() > 4.0);
+    assert!(catalog.other_regions().is_empty());
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "lz4")]
+fn compressed_value_size_from_raw_page() -> Result<()> {
+    use vecdb::LZ4Vec;
+
+    let (db, _temp) = setup_db()?;
+    let mut pair: LZ4Vec<usize, (u16, u16)> = LZ4Vec::import(&db, "pair", Version::ONE)?;
+    (0..10).for_each(|v| pair.push((v, v)));
+    pair.write()?;
+
+    let catalog = Catalog::open(&db)?;
+    let entry = catalog.get("pair", "usize").unwrap();
+    assert_eq!(entry.value_type_name, None);
+    assert_eq!(entry.value_size, Some(4));
+    assert_eq!(entry.len, Some(10));
+    assert_eq!(entry.pages, Some(1));
+    Ok(())
+}
+
+#[test]
+fn other_regions_and_orphaned_changes() -> Result<()> {
+    let (db, temp) = setup_db()?;
+    let mut vec: BytesVec<usize, u32> = BytesVec::import(&db, "vec", Version::ONE)?;
+    vec.push(1);
+    vec.write()?;
+
+    db.create_region_if_needed("custom")?
+        .write_at(b"not a vec", 0)?;
+    db.create_region_if_needed("removed/usize_pages")?
+        .write_at(&[0; 16], 0)?;
+    let orphaned = temp.path().join("changes").join("removed").join("usize");
+    fs::create_dir_all(&orphaned)?;
+    fs::write(orphaned.join("3"), [])?;
+
+    let catalog = Catalog::open(&db)?;
+    assert_eq!(catalog.entries().len(), 1);
+    assert_eq!(catalog.other_regions(), ["custom", "removed/usize_pages"]);
+    assert_eq!(catalog.orphaned_changes(), [orphaned]);
+    Ok(())
+}
@@ -0,0 +1,463 @@
+//! Tests for per-page checksums of compressed vectors.
+//!
+//! These tests cover:
+//! - Checksums kept up to date across compressed pages, fast raw appends and truncations
+//! - Corrupted page data reported as a checksum mismatch by reads, lookups and `verify()`
+//! - Infallible reads stopping before a corrupted page instead of panicking
+//! - Searches counting a corrupted page as failing the predicate instead of panicking,
+//!   including those of vecs bisecting by single values
+//! - Adding checksums to the pages of a vec created without them
+
+use rawdb::Database;
+use tempfile::TempDir;
+use vecdb::{
+    AnyStoredVec, Error, HEADER_OFFSET, ImportOptions, ImportableVec, LazyVecFrom1, MonotonicVec,
+    ReadableCloneableVec, ReadableVec, Result, StoredVec, Version, WritableVec,
+};
+
+const PER_PAGE_U64: usize = 16 * 1024 / size_of::<u64>(); // 2048
+
+type TryRead<V> = fn(&V, usize, usize, &mut Vec<u64>) -> Result<()>;
+type TrySearch<V> = fn(&V, u64) -> Result<usize>;
+
+fn setup_db() -> Result<(Database, TempDir)> {
+    let temp = TempDir::new()?;
+    let db = Database::open(temp.path())?;
+    Ok((db, temp))
+}
+
+fn options(db: &Database) -> ImportOptions<'_> {
+    ImportOptions::new(db, "vec", Version::ONE).with_page_checksums(true)
+}
+
+/// Flips one byte of the first page.
+fn corrupt_first_page<V>(vec: &V) -> Result<()>
+where
+    V: StoredVec,
+{
+    let at = HEADER_OFFSET + 3;
+    let byte = vec.region().create_reader().read(at, 1)[0];
+    vec.region().write_at(&[byte ^ 0xFF], at)?;
+    Ok(())
+}
+
+// ============================================================================
+// Generic tests
+// ============================================================================
+
+fn test_roundtrip<V>(verify: fn(&V) -> Result<()>) -> Result<()>
+where
+    V: StoredVec<I = usize, T = u64>,
+{
+    let (db, _temp) = setup_db()?;
+    let len = PER_PAGE_U64 * 3 + 100;
+
+    {
+        let mut vec: V = V::import_with(options(&db))?;
+        assert!(vec.header().page_checksums());
+
+        // Full pages, then several fast appends to the raw last page
+        (0..PER_PAGE_U64 as u64 * 3).for_each(|i| vec.push(i));
+        vec.write()?;
+        for chunk in (PER_PAGE_U64 as u64 * 3..len as u64)
+            .collect::<Vec<_>>()
+            .chunks(30)
+        {
+            chunk.iter().for_each(|&i| vec.push(i));
+            vec.write()?;
+        }
+        verify(&vec)?;
+    }
+
+    // Reopening without asking for checksums keeps them
+    let mut vec: V = V::import(&db, "vec", Version::ONE)?;
+    assert!(vec.header().page_checksums());
+    verify(&vec)?;
+    assert_eq!(vec.collect(), (0..len as u64).collect::<Vec<_>>());
+
+    // Truncating into a compressed page rewrites it
+    vec.truncate_if_needed_at(PER_PAGE_U64 + 10)?;
+    vec.push(1_000_000);
+    vec.write()?;
+    verify(&vec)?;
+    assert_eq!(vec.collect_one(PER_PAGE_U64 + 10), Some(1_000_000));
+
+    Ok(())
+}
+
+fn test_corruption_detected<V>(verify: fn(&V) -> Result<()>) -> Result<()>
+where
+    V: StoredVec<I = usize, T = u64>,
+{
+    let (db, _temp) = setup_db()?;
+
+    let mut vec: V = V::import_with(options(&db))?;
+    (0..PER_PAGE_U64 as u64 * 2 + 5).for_each(|i| vec.push(i));
+    vec.write()?;
+    verify(&vec)?;
+
+    corrupt_first_page(&vec)?;
+
+    let Err(err) = verify(&vec) else {
+        panic!("expected a checksum mismatch");
+    };
+    assert!(matches!(err, Error::ChecksumMismatch { page: 0, .. }));
+    assert!(err.is_data_error());
+
+    // Later pages are still readable
+    assert_eq!(
+        vec.collect_range(PER_PAGE_U64, PER_PAGE_U64 + 2),
+        vec![PER_PAGE_U64 as u64, PER_PAGE_U64 as u64 + 1]
+    );
+
+    Ok(())
+}
+
+fn test_corrupt_page_reads<V>(try_read: TryRead<V>) -> Result<()>
+where
+    V: StoredVec<I = usize, T = u64>,
+{
+    let (db, _temp) = setup_db()?;
+    let len = PER_PAGE_U64 * 2 + 5;
+
+    let mut vec: V = V::import_with(options(&db))?;
+    (0..len as u64).for_each(|i| vec.push(i));
+    vec.write()?;
+    vec.push(len as u64);
+    corrupt_first_page(&vec)?;
+
+    // Reads stop before the corrupted page, and don't reach pushed values
+    let mut buf = vec![];
+    vec.read_into_at(0, len + 1, &mut buf);
+    assert!(buf.is_empty());
+    assert!(matches!(
+        try_read(&vec, 0, len + 1, &mut buf),
+        Err(Error::ChecksumMismatch { page: 0, .. })
+    ));
+    assert!(buf.is_empty());
+
+    // Later pages and pushed values are still readable
+    try_read(&vec, PER_PAGE_U64, len + 1, &mut buf)?;
+    assert_eq!(buf, (PER_PAGE_U64 as u64..=len as u64).collect::<Vec<_>>());
+
+    Ok(())
+}
+
+fn test_corrupt_page_searches<V>(try_search: TrySearch<V>) -> Result<()>
+where
+    V: StoredVec<I = usize, T = u64>,
+{
+    let (db, _temp) = setup_db()?;
+    let len = PER_PAGE_U64 * 3 + 5;
+
+    let mut vec: V = V::import_with(options(&db))?;
+    (0..len as u64).for_each(|i| vec.push(i));
+    vec.write()?;
+    corrupt_first_page(&vec)?;
+
+    // Pages are bisected by their first value, the corrupted one is never probed
+    let target = PER_PAGE_U64 as u64 * 2 + 7;
+    assert_eq!(vec.partition_point(|&v| v < target), target as usize);
+    assert_eq!(try_search(&vec, target)?, target as usize);
+
+    // The partition point is in the corrupted page, which counts as failing the predicate
+    assert_eq!(vec.partition_point(|&v| v < 5), 0);
+    assert!(matches!(
+        try_search(&vec, 5),
+        Err(Error::ChecksumMismatch { page: 0, .. })
+    ));
+
+    Ok(())
+}
+
+fn test_added_checksums<V>(verify: fn(&V) -> Result<()>) -> Result<()>
+where
+    V: StoredVec<I = usize, T = u64>,
+{
+    let (db, _temp) = setup_db()?;
+    let plain = ImportOptions::new(&db, "vec", Version::ONE);
+
+    {
+        let mut vec: V = V::import_with(plain)?;
+        (0..PER_PAGE_U64 as u64 * 2).for_each(|i| vec.push(i));
+        vec.write()?;
+        assert!(!vec.header().page_checksums());
+        // Only the page layout is checked
+        verify(&vec)?;
+    }
+
+    // Reopening with checksums adds them to the existing pages
+    {
+        let mut vec: V = V::import_with(options(&db))?;
+        assert!(vec.header().page_checksums());
+        verify(&vec)?;
+        assert_eq!(
+            vec.collect(),
+            (0..PER_PAGE_U64 as u64 * 2).collect::<Vec<_>>()
+        );
+        vec.push(1_000_000);
+        vec.write()?;
+        verify(&vec)?;
+    }
+
+    let vec: V = V::import_with(plain)?;
+    assert!(vec.header().page_checksums());
+    assert_eq!(vec.len(), PER_PAGE_U64 * 2 + 1);
+    verify(&vec)?;
+
+    corrupt_first_page(&vec)?;
+    assert!(matches!(
+        verify(&vec),
+        Err(Error::ChecksumMismatch { page: 0, .. })
+    ));
+
+    Ok(())
+}
+
+// ============================================================================
+// Per-format modules
+// ============================================================================
+
+mod monotonic {
+    use super::*;
+    type V = MonotonicVec<usize, u64>;
+
+    #[test]
+    fn roundtrip() -> Result<()> {
+        test_roundtrip::<V>(|vec| vec.verify())
+    }
+    #[test]
+    fn corruption_detected() -> Result<()> {
+        test_corruption_detected::<V>(|vec| vec.verify())
+    }
+    #[test]
+    fn corrupt_page_reads() -> Result<()> {
+        test_corrupt_page_reads::<V>(|vec, from, to, buf| vec.try_read_into_at(from, to, buf))
+    }
+    #[test]
+    fn corrupt_page_searches() -> Result<()> {
+        test_corrupt_page_searches::<V>(|vec, value| {
+            vec.try_partition_point_range_at(0, usize::MAX, |&v| v < value)
+        })
+    }
+    #[test]
+    fn added_checksums() -> Result<()> {
+        test_added_checksums::<V>(|vec| vec.verify())
+    }
+
+    #[test]
+    fn read_only_corrupt_page_reads() -> Result<()> {
+        let (db, _temp) = setup_db()?;
+
+        let mut vec: V = V::import_with(options(&db))?;
+        (0..PER_PAGE_U64 as u64 * 2).for_each(|i| vec.push(i));
+        vec.write()?;
+        corrupt_first_page(&vec)?;
+
+        let ro = vec.read_only_clone();
+        let mut buf = vec![];
+        ro.read_into_at(0, PER_PAGE_U64 + 1, &mut buf);
+        assert!(buf.is_empty());
+        assert!(matches!(
+            ro.try_read_into_at(0, PER_PAGE_U64 + 1, &mut buf),
+            Err(Error::ChecksumMismatch { page: 0, .. })
+        ));
+        ro.try_read_into_at(PER_PAGE_U64, PER_PAGE_U64 + 1, &mut buf)?;
+        assert_eq!(buf, vec![PER_PAGE_U64 as u64]);
+
+        assert_eq!(ro.partition_point(|&v| v < 5), 0);
+        assert!(matches!(
+            ro.try_partition_point_range_at(0, usize::MAX, |&v| v < 5),
+            Err(Error::ChecksumMismatch { page: 0, .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn corrupt_page_lookups() -> Result<()> {
+        let (db, _temp) = setup_db()?;
+
+        let mut vec: V = V::import_with(options(&db))?;
+        (0..PER_PAGE_U64 as u64 * 2).for_each(|i| vec.push(i));
+        vec.write()?;
+        corrupt_first_page(&vec)?;
+
+        assert!(matches!(
+            vec.get_at(5),
+            Err(Error::ChecksumMismatch { page: 0, .. })
+        ));
+        assert!(matches!(
+            vec.successor(5),
+            Err(Error::ChecksumMismatch { page: 0, .. })
+        ));
+        assert!(matches!(
+            vec.rank(5),
+            Err(Error::ChecksumMismatch { page: 0, .. })
+        ));
+        assert!(matches!(
+            vec.read_only_clone().predecessor(5),
+            Err(Error::ChecksumMismatch { page: 0, .. })
+        ));
+        assert_eq!(vec.get_at(PER_PAGE_U64 + 5)?, Some(PER_PAGE_U64 as u64 + 5));
+        assert_eq!(
+            vec.successor(PER_PAGE_U64 as u64 + 5)?,
+            Some((PER_PAGE_U64 + 5, PER_PAGE_U64 as u64 + 5))
+        );
+
+        Ok(())
+    }
+
+    /// Vecs without their own search bisect by single values, some of them unreadable.
+    #[test]
+    fn lazy_corrupt_page_searches() -> Result<()> {
+        let (db, _temp) = setup_db()?;
+
+        let mut vec: V = V::import_with(options(&db))?;
+        (0..PER_PAGE_U64 as u64 * 2).for_each(|i| vec.push(i));
+        vec.write()?;
+        corrupt_first_page(&vec)?;
+
+        let lazy = LazyVecFrom1::<usize, u64, usize, u64>::init(
+            "lazy",
+            Version::ONE,
+            vec.read_only_boxed_clone(),
+            |_, v| v,
+        );
+        assert_eq!(lazy.partition_point(|&v| v < 5), 0);
+        assert_eq!(
+            lazy.partition_point(|&v| v < PER_PAGE_U64 as u64 + 5),
+            PER_PAGE_U64 + 5
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "pco")]
+mod pco {
+    use super::*;
+    use vecdb::PcoVec;
+    type V = PcoVec<usize, u64>;
+
+    #[test]
+    fn roundtrip() -> Result<()> {
+        test_roundtrip::<V>(|vec| vec.verify())
+    }
+    #[test]
+    fn corruption_detected() -> Result<()> {
+        test_corruption_detected::<V>(|vec| vec.verify())
+    }
+    #[test]
+    fn corrupt_page_reads() -> Result<()> {
+        test_corrupt_page_reads::<V>(|vec, from, to, buf| vec.try_read_into_at(from, to, buf))
+    }
+    #[test]
+    fn corrupt_page_searches() -> Result<()> {
+        test_corrupt_page_searches::<V>(|vec, value| {
+            vec.try_partition_point_range_at(0, usize::MAX, |&v| v < value)
+        })
+    }
+    #[test]
+    fn added_checksums() -> Result<()> {
+        test_added_checksums::<V>(|vec| vec.verify())
+    }
+
+    #[test]
+    fn decode_page_reports_mismatch() -> Result<()> {
+        let (db, _temp) = setup_db()?;
+
+        let mut vec: V = V::import_with(options(&db))?;
+        (0..PER_PAGE_U64 as u64 * 2).for_each(|i| vec.push(i));
+        vec.write()?;
+        corrupt_first_page(&vec)?;
+
+        let reader = vec.region().create_reader();
+        assert!(matches!(
+            vec.decode_page(0, &reader),
+            Err(Error::ChecksumMismatch { page: 0, .. })
+        ));
+        assert!(vec.decode_page(1, &reader).is_ok());
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "lz4")]
+mod lz4 {
+    use super::*;
+    use vecdb::LZ4Vec;
+    type V = LZ4Vec<usize, u64>;
+
+    #[test]
+    fn roundtrip() -> Result<()> {
+        test_roundtrip::<V>(|vec| vec.verify())
+    }
+    #[test]
+    fn corruption_detected() -> Result<()> {
+        test_corruption_detected::<V>(|vec| vec.verify())
+    }
+    #[test]
+    fn corrupt_page_reads() -> Result<()> {
+        test_corrupt_page_reads::<V>(|vec, from, to, buf| vec.try_read_into_at(from, to, buf))
+    }
+    #[test]
+    fn corrupt_page_searches() -> Result<()> {
+        test_corrupt_page_searches::<V>(|vec, value| {
+            vec.try_partition_point_range_at(0, usize::MAX, |&v| v < value)
+        })
+    }
+    #[test]
+    fn added_checksums() -> Result<()> {
+        test_added_checksums::<V>(|vec| vec.verify())
+    }
+}
+
+#[cfg(feature = "zstd")]
+mod zstd {
+    use super::*;
+    use vecdb::{ReadableVec, ZstdVec};
+    type V = ZstdVec<usize, u64>;
+
+    #[test]
+    fn roundtrip() -> Result<()> {
+        test_roundtrip::<V>(|vec| vec.verify())
+    }
+    #[test]
+    fn corruption_detected() -> Result<()> {
+        test_corruption_detected::<V>(|vec| vec.verify())
+    }
+    #[test]
+    fn corrupt_page_reads() -> Result<()> {
+        test_corrupt_page_reads::<V>(|vec, from, to, buf| vec.try_read_into_at(from, to, buf))
+    }
+    #[test]
+    fn corrupt_page_searches() -> Result<()> {
+        test_corrupt_page_searches::<V>(|vec, value| {
+            vec.try_partition_point_range_at(0, usize::MAX, |&v| v < value)
+        })
+    }
+    #[test]
+    fn added_checksums() -> Result<()> {
+        test_added_checksums::<V>(|vec| vec.verify())
+    }
+
+    #[test]
+    fn recompressed_pages_are_checksummed() -> Result<()> {
+        let (db, _temp) = setup_db()?;
+        let values = (0..PER_PAGE_U64 as u64 * 20)
+            .map(|i| i % 1000 * 7)
+            .collect::<Vec<_>>();
+
+        let mut vec: V = V::import_with(options(&db))?;
+        values.iter().for_each(|&v| vec.push(v));
+        vec.write()?;
+        vec.train_dictionary(4096)?;
+        while vec.recompress_pages(4)? != 0 {}
+
+        vec.verify()?;
+        assert_eq!(vec.collect(), values);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,168 @@
+//! Tests for loading vecs from CSV and NDJSON.
+//!
+//! These tests cover:
+//! - CSV columns picked by header name or position, with quoted and multi-line cells
+//! - NDJSON fields, escaped keys and nested arrays, `Option` values from empty cells and `null`
+//! - Aborting with the line number, skipping and filling invalid lines
+//! - Appending to stored values and stamping the final write
+
+use rawdb::Database;
+use tempfile::TempDir;
+use vecdb::{
+    AnyStoredVec, BytesVec, ColumnLoader, Error, ImportableVec, LoadSummary, OnParseError,
+    Parseable, ReadableVec, Result, Stamp, Version, WritableVec,
+};
+
+fn setup_db() -> Result<(Database, TempDir)> {
+    let temp = TempDir::new()?;
+    let db = Database::open(temp.path())?;
+    Ok((db, temp))
+}
+
+#[test]
+fn parseable() {
+    assert_eq!(u8::parse_csv(" 42 "), Some(42));
+    assert_eq!(u8::parse_csv("256"), None);
+    assert_eq!(i64::parse_json("-7"), Some(-7));
+    assert_eq!(f64::parse_str("1.5e3"), Some(1500.0));
+    assert_eq!(bool::parse_str("true"), Some(true));
+    assert_eq!(Option::<u32>::parse_csv(""), Some(None));
+    assert_eq!(Option::<u32>::parse_json("null"), Some(None));
+    assert_eq!(Option::<u32>::parse_json("3"), Some(Some(3)));
+    assert_eq!(<[u16; 3]>::parse_json("[1, 2,3]"), Some([1, 2, 3]));
+    assert_eq!(<[u16; 3]>::parse_json("[1,2]"), None);
+    assert_eq!(
+        <[[u8; 2]; 2]>::parse_csv("[[1,2],[3,4]]"),
+        Some([[1, 2], [3, 4]])
+    );
+    assert_eq!(
+        <[Option<u8>; 2]>::parse_json("[null,1]"),
+        Some([None, Some(1)])
+    );
+    assert_eq!(<[u8; 0]>::parse_json("[]"), Some([]));
+}
+
+#[test]
+fn csv() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let input = "height,price,\"fl\"\"ag\",note\n\
+                 0,1.5,true,plain\n\
+                 1,\"2.5\",false,\"multi\nline, quoted\"\n\
+                 2,3.5,true,x\n";
+
+    let mut price: BytesVec<usize, f64> = BytesVec::import(&db, "price", Version::ONE)?;
+    let summary = ColumnLoader::csv("price").load(input.as_bytes(), &mut price, Stamp::new(1))?;
+    assert_eq!(
+        summary,
+        LoadSummary {
+            pushed: 3,
+            skipped: 0,
+            filled: 0
+        }
+    );
+    assert_eq!(price.collect(), vec![1.5, 2.5, 3.5]);
+    assert_eq!(price.stamp(), Stamp::new(1));
+    assert_eq!(price.stored_len(), 3);
+
+    let mut flag: BytesVec<usize, bool> = BytesVec::import(&db, "flag", Version::ONE)?;
+    ColumnLoader::csv("fl\"ag").load(input.as_bytes(), &mut flag, Stamp::new(1))?;
+    assert_eq!(flag.collect(), vec![true, false, true]);
+
+    // Headerless, with empty lines as `None`, appended to stored values
+    let mut fee: BytesVec<usize, Option<u32>> = BytesVec::import(&db, "fee", Version::ONE)?;
+    fee.push(Some(9));
+    fee.write()?;
+    ColumnLoader::csv_at(1).load("a,1\nb,\nc,3\n".as_bytes(), &mut fee, Stamp::new(2))?;
+    ColumnLoader::csv_at(0).load("\n4\n".as_bytes(), &mut fee, Stamp::new(3))?;
+    assert_eq!(
+        fee.collect(),
+        vec![Some(9), Some(1), None, Some(3), None, Some(4)]
+    );
+    assert_eq!(fee.stamp(), Stamp::new(3));
+
+    assert!(matches!(
+        ColumnLoader::csv("volume").load(input.as_bytes(), &mut price, Stamp::new(2)),
+        Err(Error::InvalidArgument(_))
+    ));
+    assert!(matches!(
+        ColumnLoader::csv("price").load("".as_bytes(), &mut price, Stamp::new(2)),
+        Err(Error::InvalidArgument(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn ndjson() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let input = "{\"height\":0,\"pair\":[1,2],\"note\":\"a,}\\\"\",\"fl\\\"ag\":null}\n\
+                 \n\
+                 { \"fl\\\"ag\" : 7 , \"pair\" : [ 3 , 4 ] }\r\n\
+                 {\"pair\":[5,6],\"fl\\u0022ag\":8}";
+
+    let mut pair: BytesVec<usize, [u32; 2]> = BytesVec::import(&db, "pair", Version::ONE)?;
+    let summary = ColumnLoader::ndjson("pair").load(input.as_bytes(), &mut pair, Stamp::new(1))?;
+    assert_eq!(summary.pushed, 3);
+    assert_eq!(pair.collect(), vec![[1, 2], [3, 4], [5, 6]]);
+
+    let mut flag: BytesVec<usize, Option<u8>> = BytesVec::import(&db, "flag", Version::ONE)?;
+    ColumnLoader::ndjson("fl\"ag").load(input.as_bytes(), &mut flag, Stamp::new(1))?;
+    assert_eq!(flag.collect(), vec![None, Some(7), Some(8)]);
+    Ok(())
+}
+
+#[test]
+fn parse_errors() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let csv = "v,note\n1,\"a\nb\"\nx,c\n3\n4,d\n";
+    let ndjson = "{\"v\":1}\n{\"v\":\"2\"}\n{\"w\":3}\n{\"v\":4\n{\"v\":5}\n";
+
+    let mut vec: BytesVec<usize, u32> = BytesVec::import(&db, "vec", Version::ONE)?;
+    match ColumnLoader::csv("v").load(csv.as_bytes(), &mut vec, Stamp::new(1)) {
+        Err(Error::ParseError { line, reason }) => {
+            assert_eq!(line, 4);
+            assert!(reason.contains("\"x\""), "{reason}");
+        }
+        other => panic!("expected a parse error, got {other:?}"),
+    }
+    // The values before the error are pushed but not written
+    assert_eq!(vec.collect(), vec![1]);
+    vec.reset_unsaved();
+
+    match ColumnLoader::csv("note").load(csv.as_bytes(), &mut vec, Stamp::new(1)) {
+        Err(Error::ParseError { line, .. }) => assert_eq!(line, 2),
+        other => panic!("expected a parse error, got {other:?}"),
+    }
+    vec.reset_unsaved();
+
+    let summary = ColumnLoader::csv("v")
+        .on_parse_error(OnParseError::Skip)
+        .load(csv.as_bytes(), &mut vec, Stamp::new(1))?;
+    assert_eq!((summary.pushed, summary.skipped), (3, 1));
+    assert_eq!(vec.collect(), vec![1, 3, 4]);
+
+    vec.clear()?;
+    let summary = ColumnLoader::ndjson("v")
+        .on_parse_error(OnParseError::Fill(u32::default()))
+        .load(ndjson.as_bytes(), &mut vec, Stamp::new(2))?;
+    assert_eq!((summary.pushed, summary.filled), (5, 3));
+    assert_eq!(vec.collect(), vec![1, 0, 0, 0, 5]);
+
+    vec.clear()?;
+    for (line, message) in [
+        (2, "invalid value"),
+        (3, "missing field"),
+        (4, "invalid JSON"),
+    ] {
+        let input = ndjson.lines().take(line).collect::<Vec<_>>();
+        let input = [&input[..1], &input[line - 1..]].concat().join("\n");
+        match ColumnLoader::ndjson("v").load(input.as_bytes(), &mut vec, Stamp::new(3)) {
+            Err(Error::ParseError { line, reason }) => {
+                assert_eq!(line, 2);
+                assert!(reason.starts_with(message), "{reason}");
+            }
+            other => panic!("expected a parse error, got {other:?}"),
+        }
+        vec.reset_unsaved();
+    }
+    Ok(())
+}
@@ -0,0 +1,656 @@
+//! Tests for updating stored values of compressed vectors.
+//!
+//! These tests cover:
+//! - Updates overlaid on reads before `write()`, then persisted by recompressing their pages
+//! - Updates on the raw last page, mixed with pushes, and dropped by truncation
+//! - Pages growing after an update, moving later pages in chunks, and shrinking pages
+//!   absorbing the growth of earlier ones
+//! - Read-only clones reading while pages grow and move
+//! - Rollback of updates through stamped change files
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread,
+};
+
+use rawdb::Database;
+use tempfile::TempDir;
+use vecdb::{
+    AnyStoredVec, Error, ImportOptions, ImportableVec, MonotonicVec, ReadableVec, Result, Stamp,
+    StoredVec, Version, WritableVec,
+};
+
+const PER_PAGE_U64: usize = 16 * 1024 / size_of::<u64>(); // 2048
+
+type Update<V> = fn(&mut V, usize, u64) -> Result<()>;
+
+fn setup_db() -> Result<(Database, TempDir)> {
+    let temp = TempDir::new()?;
+    let db = Database::open(temp.path())?;
+    Ok((db, temp))
+}
+
+fn options(db: &Database) -> ImportOptions<'_> {
+    ImportOptions::new(db, "vec", Version::ONE)
+}
+
+/// Non-decreasing so the same values work for `MonotonicVec`.
+fn value(i: usize) -> u64 {
+    i as u64 * 3
+}
+
+/// Stays between its neighbours.
+fn updated_value(i: usize) -> u64 {
+    value(i) + 1
+}
+
+fn collect_io<V>(vec: &V) -> Vec<u64>
+where
+    V: StoredVec<I = usize, T = u64>,
+{
+    let mut values = vec![];
+    vec.for_each_range_dyn_at(0, vec.len(), &mut |v| values.push(v));
+    values
+}
+
+// ============================================================================
+// Generic tests
+// ============================================================================
+
+fn test_update_stored_values<V>(update: Update<V>) -> Result<()>
+where
+    V: StoredVec<I = usize, T = u64>,
+{
+    let (db, _temp) = setup_db()?;
+    let len = PER_PAGE_U64 * 3 + 100;
+    let updated_at = [5, PER_PAGE_U64 - 1, PER_PAGE_U64 * 2 + 1, len - 1];
+
+    let mut expected = (0..len).map(value).collect::<Vec<_>>();
+    {
+        let mut vec: V = V::import_with(options(&db))?;
+        expected.iter().for_each(|&v| vec.push(v));
+        vec.write()?;
+
+        for i in updated_at {
+            update(&mut vec, i, updated_value(i))?;
+            expected[i] = updated_value(i);
+        }
+        assert!(vec.is_dirty());
+
+        // Visible before writing
+        assert_eq!(vec.collect(), expected);
+        assert_eq!(collect_io(&vec), expected);
+        assert_eq!(
+            vec.collect_range(PER_PAGE_U64 * 2, PER_PAGE_U64 * 2 + 3),
+            expected[PER_PAGE_U64 * 2..PER_PAGE_U64 * 2 + 3]
+        );
+        assert_eq!(vec.collect_one(5), Some(updated_value(5)));
+
+        assert!(vec.write()?);
+        assert!(!vec.is_dirty());
+        assert!(!vec.write()?);
+        assert_eq!(vec.len(), len);
+        assert_eq!(vec.collect(), expected);
+    }
+
+    let mut vec: V = V::import_with(options(&db))?;
+    assert_eq!(vec.collect(), expected);
+    assert_eq!(collect_io(&vec), expected);
+
+    // Appending to the updated raw last page
+    vec.push(value(len));
+    vec.write()?;
+    expected.push(value(len));
+    assert_eq!(vec.collect(), expected);
+
+    Ok(())
+}
+
+fn test_updates_with_pushes<V>(update: Update<V>) -> Result<()>
+where
+    V: StoredVec<I = usize, T = u64>,
+{
+    let (db, _temp) = setup_db()?;
+    let len = PER_PAGE_U64 * 2 - 10;
+
+    let mut vec: V = V::import_with(options(&db))?;
+    (0..len).for_each(|i| vec.push(value(i)));
+    vec.write()?;
+
+    // The raw last page gets updated, then sealed by the pushes
+    update(&mut vec, len - 3, updated_value(len - 3))?;
+    (len..len + 20).for_each(|i| vec.push(value(i)));
+    // Pushed values are updated in place
+    update(&mut vec, len + 2, updated_value(len + 2))?;
+    vec.write()?;
+
+    let mut expected = (0..len + 20).map(value).collect::<Vec<_>>();
+    expected[len - 3] = updated_value(len - 3);
+    expected[len + 2] = updated_value(len + 2);
+    assert_eq!(vec.collect(), expected);
+    assert_eq!(collect_io(&vec), expected);
+
+    assert!(matches!(
+        update(&mut vec, len + 20, 0),
+        Err(Error::IndexTooHigh { .. })
+    ));
+
+    Ok(())
+}
+
+fn test_truncate_drops_updates<V>(update: Update<V>) -> Result<()>
+where
+    V: StoredVec<I = usize, T = u64>,
+{
+    let (db, _temp) = setup_db()?;
+    let len = PER_PAGE_U64 * 3;
+
+    let mut vec: V = V::import_with(options(&db))?;
+    (0..len).for_each(|i| vec.push(value(i)));
+    vec.write()?;
+
+    update(&mut vec, 10, updated_value(10))?;
+    update(
+        &mut vec,
+        PER_PAGE_U64 * 2 + 10,
+        updated_value(PER_PAGE_U64 * 2 + 10),
+    )?;
+    vec.truncate_if_needed_at(PER_PAGE_U64 * 2)?;
+    vec.write()?;
+
+    let mut expected = (0..PER_PAGE_U64 * 2).map(value).collect::<Vec<_>>();
+    expected[10] = updated_value(10);
+    assert_eq!(vec.collect(), expected);
+
+    // Pushing back over the truncated range doesn't resurrect the update
+    (PER_PAGE_U64 * 2..len).for_each(|i| vec.push(value(i)));
+    vec.write()?;
+    expected.extend((PER_PAGE_U64 * 2..len).map(value));
+    assert_eq!(vec.collect(), expected);
+
+    Ok(())
+}
+
+fn test_rollback_updates<V>(update: Update<V>) -> Result<()>
+where
+    V: StoredVec<I = usize, T = u64>,
+{
+    let (db, _temp) = setup_db()?;
+    let len = PER_PAGE_U64 * 2 + 50;
+    let original = (0..len).map(value).collect::<Vec<_>>();
+
+    let mut vec: V = V::forced_import_with(options(&db).with_saved_stamped_changes(10))?;
+    original.iter().for_each(|&v| vec.push(v));
+    vec.stamped_write_with_changes(Stamp::new(1))?;
+
+    // Stamp 2: updates in a compressed page and in the raw last page
+    let stamp_2 = |vec: &mut V| -> Result<()> {
+        update(vec, 7, updated_value(7))?;
+        update(vec, len - 1, updated_value(len - 1))?;
+        vec.stamped_write_with_changes(Stamp::new(2))
+    };
+    stamp_2(&mut vec)?;
+    let mut updated = original.clone();
+    updated[7] = updated_value(7);
+    updated[len - 1] = updated_value(len - 1);
+    assert_eq!(vec.collect(), updated);
+
+    vec.rollback()?;
+    assert_eq!(vec.stamp(), Stamp::new(1));
+    assert_eq!(vec.collect(), original);
+    assert_eq!(collect_io(&vec), original);
+
+    // The restored values are written back
+    vec.stamped_write_with_changes(Stamp::new(1))?;
+    drop(vec);
+    let mut vec: V = V::forced_import_with(options(&db).with_saved_stamped_changes(10))?;
+    assert_eq!(vec.collect(), original);
+
+    // Stamp 3: another update plus a truncation
+    stamp_2(&mut vec)?;
+    update(&mut vec, PER_PAGE_U64 + 1, updated_value(PER_PAGE_U64 + 1))?;
+    vec.truncate_if_needed_at(PER_PAGE_U64 * 2)?;
+    vec.stamped_write_with_changes(Stamp::new(3))?;
+
+    vec.rollback()?;
+    assert_eq!(vec.stamp(), Stamp::new(2));
+    assert_eq!(vec.collect(), updated);
+
+    Ok(())
+}
+
+/// Updates noisy values into a page of constant values, growing it past its
+/// old size so that every later page moves.
+fn test_page_grows<V>(update: Update<V>, verify: fn(&V) -> Result<()>) -> Result<()>
+where
+    V: StoredVec<I = usize, T = u64>,
+{
+    let (db, _temp) = setup_db()?;
+    let len = PER_PAGE_U64 * 4 + 10;
+    let options = options(&db).with_page_checksums(true);
+
+    let mut expected = (0..len)
+        .map(|i| (i / PER_PAGE_U64) as u64)
+        .collect::<Vec<_>>();
+    let mut vec: V = V::import_with(options)?;
+    expected.iter().for_each(|&v| vec.push(v));
+    vec.write()?;
+    let region_len = vec.region().meta().len();
+
+    for i in (PER_PAGE_U64..PER_PAGE_U64 * 2).step_by(3) {
+        let noise = (i as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        update(&mut vec, i, noise)?;
+        expected[i] = noise;
+    }
+    // A shrinking page is rewritten in place
+    update(&mut vec, PER_PAGE_U64 * 3, 3)?;
+    vec.write()?;
+
+    assert!(vec.region().meta().len() > region_len);
+    verify(&vec)?;
+    assert_eq!(vec.collect(), expected);
+    assert_eq!(collect_io(&vec), expected);
+    drop(vec);
+
+    let vec: V = V::import_with(options)?;
+    verify(&vec)?;
+    assert_eq!(vec.collect(), expected);
+
+    Ok(())
+}
+
+/// Barely compressible, unlike a multiple of `i` that delta encodings catch.
+fn noise(i: usize) -> u64 {
+    let x = (i as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    (x ^ (x >> 31)).wrapping_mul(0xBF58_476D_1CE4_E5B9)
+}
+
+/// Checks the values read back after a rewrite, then after reopening with `options`.
+fn check_rewritten<V>(
+    vec: V,
+    options: ImportOptions,
+    expected: &[u64],
+    verify: fn(&V) -> Result<()>,
+) -> Result<()>
+where
+    V: StoredVec<I = usize, T = u64>,
+{
+    verify(&vec)?;
+    assert_eq!(vec.collect(), expected);
+    assert_eq!(collect_io(&vec), expected);
+    drop(vec);
+
+    let vec: V = V::import_with(options)?;
+    verify(&vec)?;
+    assert_eq!(vec.collect(), expected);
+    Ok(())
+}
+
+/// Grows several pages in front of a tail bigger than the chunks later pages are
+/// moved in, while a page of the tail is updated too.
+fn test_pages_grow_before_big_tail<V>(update: Update<V>, verify: fn(&V) -> Result<()>) -> Result<()>
+where
+    V: StoredVec<I = usize, T = u64>,
+{
+    let (db, _temp) = setup_db()?;
+    let constant_pages = 5;
+    let len = PER_PAGE_U64 * 200 + 10;
+    let options = options(&db).with_page_checksums(true);
+
+    // Noisy pages barely compress, so the tail spans a few megabytes
+    let mut expected = (0..len)
+        .map(|i| {
+            if i < PER_PAGE_U64 * constant_pages {
+                (i / PER_PAGE_U64) as u64
+            } else {
+                noise(i)
+            }
+        })
+        .collect::<Vec<_>>();
+    let mut vec: V = V::import_with(options)?;
+    expected.iter().for_each(|&v| vec.push(v));
+    vec.write()?;
+    let region_len = vec.region().meta().len();
+
+    for page in [1, 3] {
+        for i in (PER_PAGE_U64 * page..PER_PAGE_U64 * (page + 1)).step_by(2) {
+            update(&mut vec, i, noise(i + len))?;
+            expected[i] = noise(i + len);
+        }
+    }
+    let i = PER_PAGE_U64 * 100 + 7;
+    update(&mut vec, i, 0)?;
+    expected[i] = 0;
+    vec.write()?;
+
+    assert!(vec.region().meta().len() > region_len);
+    check_rewritten(vec, options, &expected, verify)
+}
+
+/// Shrinks a page before and one after a page that grows, the second one making
+/// room for the growth so that later pages stay in place.
+fn test_page_shrinks_while_another_grows<V>(
+    update: Update<V>,
+    verify: fn(&V) -> Result<()>,
+) -> Result<()>
+where
+    V: StoredVec<I = usize, T = u64>,
+{
+    let (db, _temp) = setup_db()?;
+    let len = PER_PAGE_U64 * 6 + 10;
+    let options = options(&db).with_page_checksums(true);
+
+    let noisy_pages = [0, 2];
+    let mut expected = (0..len)
+        .map(|i| {
+            let page = i / PER_PAGE_U64;
+            if noisy_pages.contains(&page) {
+                noise(i)
+            } else {
+                page as u64
+            }
+        })
+        .collect::<Vec<_>>();
+    let mut vec: V = V::import_with(options)?;
+    expected.iter().for_each(|&v| vec.push(v));
+    vec.write()?;
+    let region_len = vec.region().meta().len();
+
+    for (i, expected) in expected.iter_mut().enumerate().take(PER_PAGE_U64 * 3) {
+        let value = match i / PER_PAGE_U64 {
+            // Grows, half noisy
+            1 if i % 2 == 0 => noise(i),
+            1 => continue,
+            // Shrink, constant
+            page => page as u64,
+        };
+        update(&mut vec, i, value)?;
+        *expected = value;
+    }
+    vec.write()?;
+
+    assert_eq!(vec.region().meta().len(), region_len);
+    check_rewritten(vec, options, &expected, verify)
+}
+
+/// Grows one page after another in front of a noisy tail while a read-only clone,
+/// taken before, keeps reading every value, each one either old or updated.
+fn test_clone_reads_while_pages_move<V>(update: Update<V>) -> Result<()>
+where
+    V: StoredVec<I = usize, T = u64>,
+    V::ReadOnly: Send,
+{
+    let (db, _temp) = setup_db()?;
+    let constant_pages = 8;
+    let len = PER_PAGE_U64 * 100 + 10;
+    let options = options(&db).with_page_checksums(true);
+
+    let old = move |i: usize| {
+        if i < PER_PAGE_U64 * constant_pages {
+            (i / PER_PAGE_U64) as u64
+        } else {
+            noise(i)
+        }
+    };
+    let new = move |i: usize| noise(i + len);
+
+    let mut vec: V = V::import_with(options)?;
+    (0..len).for_each(|i| vec.push(old(i)));
+    vec.write()?;
+
+    let clone = vec.read_only_clone();
+    let done = Arc::new(AtomicBool::new(false));
+    let reader = thread::spawn({
+        let done = Arc::clone(&done);
+        move || {
+            let mut reads = 0;
+            while !done.load(Ordering::Acquire) || reads == 0 {
+                let values = clone.collect();
+                assert_eq!(values.len(), len);
+                for (i, v) in values.into_iter().enumerate() {
+                    assert!(v == old(i) || v == new(i), "index {i}");
+                }
+                reads += 1;
+            }
+        }
+    });
+
+    for page in 0..constant_pages {
+        for i in (PER_PAGE_U64 * page..PER_PAGE_U64 * (page + 1)).step_by(2) {
+            update(&mut vec, i, new(i))?;
+        }
+        vec.write()?;
+    }
+    done.store(true, Ordering::Release);
+    reader.join().unwrap();
+
+    assert_eq!(
+        vec.collect(),
+        (0..len)
+            .map(|i| {
+                if i < PER_PAGE_U64 * constant_pages && i % 2 == 0 {
+                    new(i)
+                } else {
+                    old(i)
+                }
+            })
+            .collect::<Vec<_>>()
+    );
+    Ok(())
+}
+
+// ============================================================================
+// Per-format modules
+// ============================================================================
+
+mod monotonic {
+    use super::*;
+    type V = MonotonicVec<usize, u64>;
+
+    #[test]
+    fn update_stored_values() -> Result<()> {
+        test_update_stored_values::<V>(|vec, i, v| vec.update_at(i, v))
+    }
+    #[test]
+    fn updates_with_pushes() -> Result<()> {
+        test_updates_with_pushes::<V>(|vec, i, v| vec.update_at(i, v))
+    }
+    #[test]
+    fn truncate_drops_updates() -> Result<()> {
+        test_truncate_drops_updates::<V>(|vec, i, v| vec.update_at(i, v))
+    }
+    #[test]
+    fn rollback_updates() -> Result<()> {
+        test_rollback_updates::<V>(|vec, i, v| vec.update_at(i, v))
+    }
+
+    #[test]
+    fn lookups_see_updates() -> Result<()> {
+        let (db, _temp) = setup_db()?;
+
+        let mut vec: V = V::import_with(options(&db))?;
+        (0..PER_PAGE_U64 * 2).for_each(|i| vec.push(value(i)));
+        vec.write()?;
+
+        vec.update_at(10, updated_value(10))?;
+        assert_eq!(vec.get_at(10)?, Some(updated_value(10)));
+        assert_eq!(
+            vec.successor(updated_value(10))?,
+            Some((10, updated_value(10)))
+        );
+
+        // Breaking monotonicity fails right away, against stored and pushed neighbours
+        assert!(matches!(
+            vec.update_at(20, 0),
+            Err(Error::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            vec.update_at(9, updated_value(10) + 1),
+            Err(Error::InvalidArgument(_))
+        ));
+        vec.push(value(PER_PAGE_U64 * 2));
+        assert!(matches!(
+            vec.update_at(PER_PAGE_U64 * 2 - 1, u64::MAX),
+            Err(Error::InvalidArgument(_))
+        ));
+        vec.write()?;
+        assert_eq!(vec.get_at(20)?, Some(value(20)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn rollback_adjacent_updates() -> Result<()> {
+        let (db, _temp) = setup_db()?;
+        let original = (0..PER_PAGE_U64 * 2).map(value).collect::<Vec<_>>();
+
+        let mut vec: V = V::forced_import_with(options(&db).with_saved_stamped_changes(10))?;
+        original.iter().for_each(|&v| vec.push(v));
+        vec.stamped_write_with_changes(Stamp::new(1))?;
+
+        // Restoring either value alone would sit above its lowered neighbour
+        vec.update_at(10, value(10) - 2)?;
+        vec.update_at(11, value(10) - 1)?;
+        vec.stamped_write_with_changes(Stamp::new(2))?;
+
+        vec.rollback()?;
+        assert_eq!(vec.stamp(), Stamp::new(1));
+        assert_eq!(vec.collect(), original);
+        vec.stamped_write_with_changes(Stamp::new(1))?;
+        assert_eq!(vec.collect(), original);
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "pco")]
+mod pco {
+    use super::*;
+    use vecdb::PcoVec;
+    type V = PcoVec<usize, u64>;
+
+    #[test]
+    fn update_stored_values() -> Result<()> {
+        test_update_stored_values::<V>(|vec, i, v| vec.update_at(i, v))
+    }
+    #[test]
+    fn updates_with_pushes() -> Result<()> {
+        test_updates_with_pushes::<V>(|vec, i, v| vec.update_at(i, v))
+    }
+    #[test]
+    fn truncate_drops_updates() -> Result<()> {
+        test_truncate_drops_updates::<V>(|vec, i, v| vec.update_at(i, v))
+    }
+    #[test]
+    fn rollback_updates() -> Result<()> {
+        test_rollback_updates::<V>(|vec, i, v| vec.update_at(i, v))
+    }
+    #[test]
+    fn page_grows() -> Result<()> {
+        test_page_grows::<V>(|vec, i, v| vec.update_at(i, v), |vec| vec.verify())
+    }
+    #[test]
+    fn pages_grow_before_big_tail() -> Result<()> {
+        test_pages_grow_before_big_tail::<V>(|vec, i, v| vec.update_at(i, v), |vec| vec.verify())
+    }
+    #[test]
+    fn page_shrinks_while_another_grows() -> Result<()> {
+        test_page_shrinks_while_another_grows::<V>(
+            |vec, i, v| vec.update_at(i, v),
+            |vec| vec.verify(),
+        )
+    }
+    #[test]
+    fn clone_reads_while_pages_move() -> Result<()> {
+        test_clone_reads_while_pages_move::<V>(|vec, i, v| vec.update_at(i, v))
+    }
+}
+
+#[cfg(feature = "lz4")]
+mod lz4 {
+    use super::*;
+    use vecdb::LZ4Vec;
+    type V = LZ4Vec<usize, u64>;
+
+    #[test]
+    fn update_stored_values() -> Result<()> {
+        test_update_stored_values::<V>(|vec, i, v| vec.update_at(i, v))
+    }
+    #[test]
+    fn updates_with_pushes() -> Result<()> {
+        test_updates_with_pushes::<V>(|vec, i, v| vec.update_at(i, v))
+    }
+    #[test]
+    fn truncate_drops_updates() -> Result<()> {
+        test_truncate_drops_updates::<V>(|vec, i, v| vec.update_at(i, v))
+    }
+    #[test]
+    fn rollback_updates() -> Result<()> {
+        test_rollback_updates::<V>(|vec, i, v| vec.update_at(i, v))
+    }
+    #[test]
+    fn page_grows() -> Result<()> {
+        test_page_grows::<V>(|vec, i, v| vec.update_at(i, v), |vec| vec.verify())
+    }
+    #[test]
+    fn pages_grow_before_big_tail() -> Result<()> {
+        test_pages_grow_before_big_tail::<V>(|vec, i, v| vec.update_at(i, v), |vec| vec.verify())
+    }
+    #[test]
+    fn page_shrinks_while_another_grows() -> Result<()> {
+        test_page_shrinks_while_another_grows::<V>(
+            |vec, i, v| vec.update_at(i, v),
+            |vec| vec.verify(),
+        )
+    }
+    #[test]
+    fn clone_reads_while_pages_move() -> Result<()> {
+        test_clone_reads_while_pages_move::<V>(|vec, i, v| vec.update_at(i, v))
+    }
+}
+
+#[cfg(feature = "zstd")]
+mod zstd {
+    use super::*;
+    use vecdb::ZstdVec;
+    type V = ZstdVec<usize, u64>;
+
+    #[test]
+    fn update_stored_values() -> Result<()> {
+        test_update_stored_values::<V>(|vec, i, v| vec.update_at(i, v))
+    }
+    #[test]
+    fn updates_with_pushes() -> Result<()> {
+        test_updates_with_pushes::<V>(|vec, i, v| vec.update_at(i, v))
+    }
+    #[test]
+    fn truncate_drops_updates() -> Result<()> {
+        test_truncate_drops_updates::<V>(|vec, i, v| vec.update_at(i, v))
+    }
+    #[test]
+    fn rollback_updates() -> Result<()> {
+        test_rollback_updates::<V>(|vec, i, v| vec.update_at(i, v))
+    }
+    #[test]
+    fn page_grows() -> Result<()> {
+        test_page_grows::<V>(|vec, i, v| vec.update_at(i, v), |vec| vec.verify())
+    }
+    #[test]
+    fn pages_grow_before_big_tail() -> Result<()> {
+        test_pages_grow_before_big_tail::<V>(|vec, i, v| vec.update_at(i, v), |vec| vec.verify())
+    }
+    #[test]
+    fn page_shrinks_while_another_grows() -> Result<()> {
+        test_page_shrinks_while_another_grows::<V>(
+            |vec, i, v| vec.update_at(i, v),
+            |vec| vec.verify(),
+        )
+    }
+    #[test]
+    fn clone_reads_while_pages_move() -> Result<()> {
+        test_clone_reads_while_pages_move::<V>(|vec, i, v| vec.update_at(i, v))
+    }
+}
@@ -0,0 +1,363 @@
+//! Tests for per-vec compression level and page size configured via ImportOptions.
+//!
+//! These tests cover:
+//! - Page size persisted in the header and reused when reopening without options
+//! - Page size mismatch errors on import and forced import, and re-paging with convert_format_with
+//! - Legacy (unset) page size defaulting to 16 KiB
+//! - Invalid page sizes and compression levels rejected on import
+//! - Round trips with non-default levels and page sizes, including pages larger
+//!   than the IO source buffer
+
+use rawdb::Database;
+use tempfile::TempDir;
+use vecdb::{
+    AnyStoredVec, Error, ImportOptions, ImportableVec, MAX_UNCOMPRESSED_PAGE_SIZE, MonotonicVec,
+    Result, StoredVec, Version, WritableVec, convert_format_with,
+};
+
+fn setup_db() -> Result<(Database, TempDir)> {
+    let temp = TempDir::new()?;
+    let db = Database::open(temp.path())?;
+    Ok((db, temp))
+}
+
+/// Deterministic, poorly compressible values.
+fn noisy(i: u64) -> u64 {
+    i.wrapping_mul(0x9E37_79B9_7F4A_7C15).rotate_left(17)
+}
+
+// ============================================================================
+// Generic page size tests
+// ============================================================================
+
+fn test_page_size_persisted<V>() -> Result<()>
+where
+    V: StoredVec<I = usize, T = u64>,
+{
+    let (db, _temp) = setup_db()?;
+    let options = ImportOptions::new(&db, "vec", Version::ONE).with_page_size(4096);
+
+    {
+        let mut vec: V = V::import_with(options)?;
+        (0..10_000).for_each(|i| vec.push(i));
+        vec.write()?;
+    }
+
+    // Reopen without a page size: the persisted one is used
+    let vec: V = V::import(&db, "vec", Version::ONE)?;
+    assert_eq!(vec.header().page_size(), Some(4096));
+    assert_eq!(vec.len(), 10_000);
+    assert_eq!(vec.collect(), (0..10_000).collect::<Vec<_>>());
+    assert_eq!(vec.collect_range(511, 513), vec![511, 512]);
+
+    // Reopen with the same page size
+    let vec: V = V::import_with(options)?;
+    assert_eq!(vec.len(), 10_000);
+
+    Ok(())
+}
+
+fn test_page_size_mismatch<V>() -> Result<()>
+where
+    V: StoredVec<I = usize, T = u64>,
+{
+    let (db, _temp) = setup_db()?;
+    let options = ImportOptions::new(&db, "vec", Version::ONE).with_page_size(4096);
+    let other = options.with_page_size(64 * 1024);
+
+    {
+        let mut vec: V = V::import_with(options)?;
+        (0..1_000).for_each(|i| vec.push(i));
+        vec.write()?;
+    }
+
+    let Err(err) = V::import_with(other) else {
+        panic!("expected a page size mismatch");
+    };
+    assert!(matches!(
+        err,
+        Error::DifferentPageSize {
+            received: 4096,
+            expected: 65536
+        }
+    ));
+    assert!(!err.is_data_error());
+
+    // Re-paged through a conversion
+    let vec: V = convert_format_with::<V, V>(other)?;
+    assert_eq!(vec.collect(), (0..1_000).collect::<Vec<_>>());
+    assert_eq!(vec.header().page_size(), Some(64 * 1024));
+
+    // Forced imports keep the data too
+    let options = ImportOptions::new(&db, "forced", Version::ONE).with_page_size(4096);
+    {
+        let mut vec: V = V::forced_import_with(options)?;
+        (0..1_000).for_each(|i| vec.push(i));
+        vec.write()?;
+    }
+    assert!(matches!(
+        V::forced_import_with(options.with_page_size(64 * 1024)),
+        Err(Error::DifferentPageSize { .. })
+    ));
+    assert_eq!(V::forced_import_with(options)?.len(), 1_000);
+
+    Ok(())
+}
+
+fn test_default_page_size<V>() -> Result<()>
+where
+    V: StoredVec<I = usize, T = u64>,
+{
+    let (db, _temp) = setup_db()?;
+
+    {
+        let mut vec: V = V::import(&db, "vec", Version::ONE)?;
+        (0..5_000).for_each(|i| vec.push(i));
+        vec.write()?;
+        assert_eq!(vec.header().page_size(), None);
+    }
+
+    // Explicitly asking for the default matches vecs created without one
+    let options =
+        ImportOptions::new(&db, "vec", Version::ONE).with_page_size(MAX_UNCOMPRESSED_PAGE_SIZE);
+    let vec: V = V::import_with(options)?;
+    assert_eq!(vec.len(), 5_000);
+
+    Ok(())
+}
+
+fn test_invalid_page_size<V>() -> Result<()>
+where
+    V: StoredVec<I = usize, T = u64>,
+{
+    let (db, _temp) = setup_db()?;
+
+    for page_size in [0, 3, 6000, 4] {
+        let options = ImportOptions::new(&db, "vec", Version::ONE).with_page_size(page_size);
+        assert!(matches!(
+            V::forced_import_with(options),
+            Err(Error::InvalidArgument(_))
+        ));
+    }
+
+    Ok(())
+}
+
+/// 1 MiB pages of noisy u64s compress to more than the 512 KiB IO source buffer.
+fn test_large_pages_io_roundtrip<V>(
+    level: Option<i32>,
+    collect_io: fn(&V) -> Vec<u64>,
+) -> Result<()>
+where
+    V: StoredVec<I = usize, T = u64>,
+{
+    let (db, _temp) = setup_db()?;
+    let mut options = ImportOptions::new(&db, "vec", Version::ONE).with_page_size(1024 * 1024);
+    if let Some(level) = level {
+        options = options.with_compression_level(level);
+    }
+
+    let values = (0..300_000).map(noisy).collect::<Vec<_>>();
+
+    {
+        let mut vec: V = V::forced_import_with(options)?;
+        values.iter().for_each(|&v| vec.push(v));
+        vec.write()?;
+    }
+
+    let vec: V = V::forced_import_with(options)?;
+    assert_eq!(vec.len(), values.len());
+    assert_eq!(vec.collect(), values);
+    assert_eq!(vec.collect_one(200_000), Some(values[200_000]));
+    assert_eq!(collect_io(&vec), values);
+
+    Ok(())
+}
+
+// ============================================================================
+// MonotonicVec (always available)
+// ============================================================================
+
+mod monotonic {
+    use super::*;
+    type V = MonotonicVec<usize, u64>;
+
+    #[test]
+    fn page_size_persisted() -> Result<()> {
+        test_page_size_persisted::<V>()
+    }
+    #[test]
+    fn page_size_mismatch() -> Result<()> {
+        test_page_size_mismatch::<V>()
+    }
+    #[test]
+    fn default_page_size() -> Result<()> {
+        test_default_page_size::<V>()
+    }
+    #[test]
+    fn invalid_page_size() -> Result<()> {
+        test_invalid_page_size::<V>()
+    }
+
+    #[test]
+    fn lookups_with_small_pages() -> Result<()> {
+        let (db, _temp) = setup_db()?;
+        let options = ImportOptions::new(&db, "vec", Version::ONE).with_page_size(1024);
+
+        let mut vec: V = V::forced_import_with(options)?;
+        (0..2_000).for_each(|i| vec.push(i * 3));
+        vec.write()?;
+
+        assert_eq!(vec.page_size(), 1024);
+        assert_eq!(vec.get(1_500)?, Some(4_500));
+        assert_eq!(vec.predecessor(4_501)?, Some((1_500, 4_500)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn level_rejected() -> Result<()> {
+        let (db, _temp) = setup_db()?;
+        let options = ImportOptions::new(&db, "vec", Version::ONE).with_compression_level(1);
+        assert!(matches!(
+            V::forced_import_with(options),
+            Err(Error::InvalidArgument(_))
+        ));
+        Ok(())
+    }
+}
+
+#[cfg(feature = "pco")]
+mod pco {
+    use super::*;
+    use vecdb::PcoVec;
+    type V = PcoVec<usize, u64>;
+
+    #[test]
+    fn page_size_persisted() -> Result<()> {
+        test_page_size_persisted::<V>()
+    }
+    #[test]
+    fn page_size_mismatch() -> Result<()> {
+        test_page_size_mismatch::<V>()
+    }
+    #[test]
+    fn default_page_size() -> Result<()> {
+        test_default_page_size::<V>()
+    }
+    #[test]
+    fn invalid_page_size() -> Result<()> {
+        test_invalid_page_size::<V>()
+    }
+    #[test]
+    fn large_pages_io_roundtrip() -> Result<()> {
+        test_large_pages_io_roundtrip::<V>(Some(12), |vec| {
+            vec.fold_stored_io(0, vec.stored_len(), vec![], |mut acc, v| {
+                acc.push(v);
+                acc
+            })
+        })
+    }
+
+    #[test]
+    fn level_out_of_range() -> Result<()> {
+        let (db, _temp) = setup_db()?;
+        for level in [-1, 13] {
+            let options =
+                ImportOptions::new(&db, "vec", Version::ONE).with_compression_level(level);
+            assert!(matches!(
+                V::forced_import_with(options),
+                Err(Error::InvalidArgument(_))
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "lz4")]
+mod lz4 {
+    use super::*;
+    use vecdb::LZ4Vec;
+    type V = LZ4Vec<usize, u64>;
+
+    #[test]
+    fn page_size_persisted() -> Result<()> {
+        test_page_size_persisted::<V>()
+    }
+    #[test]
+    fn page_size_mismatch() -> Result<()> {
+        test_page_size_mismatch::<V>()
+    }
+    #[test]
+    fn default_page_size() -> Result<()> {
+        test_default_page_size::<V>()
+    }
+    #[test]
+    fn invalid_page_size() -> Result<()> {
+        test_invalid_page_size::<V>()
+    }
+    #[test]
+    fn large_pages_io_roundtrip() -> Result<()> {
+        test_large_pages_io_roundtrip::<V>(None, |vec| {
+            vec.fold_stored_io(0, vec.stored_len(), vec![], |mut acc, v| {
+                acc.push(v);
+                acc
+            })
+        })
+    }
+
+    #[test]
+    fn level_rejected() -> Result<()> {
+        let (db, _temp) = setup_db()?;
+        let options = ImportOptions::new(&db, "vec", Version::ONE).with_compression_level(1);
+        assert!(matches!(
+            V::forced_import_with(options),
+            Err(Error::InvalidArgument(_))
+        ));
+        Ok(())
+    }
+}
+
+#[cfg(feature = "zstd")]
+mod zstd {
+    use super::*;
+    use vecdb::ZstdVec;
+    type V = ZstdVec<usize, u64>;
+
+    #[test]
+    fn page_size_persisted() -> Result<()> {
+        test_page_size_persisted::<V>()
+    }
+    #[test]
+    fn page_size_mismatch() -> Result<()> {
+        test_page_size_mismatch::<V>()
+    }
+    #[test]
+    fn default_page_size() -> Result<()> {
+        test_default_page_size::<V>()
+    }
+    #[test]
+    fn invalid_page_size() -> Result<()> {
+        test_invalid_page_size::<V>()
+    }
+    #[test]
+    fn large_pages_io_roundtrip() -> Result<()> {
+        test_large_pages_io_roundtrip::<V>(Some(19), |vec| {
+            vec.fold_stored_io(0, vec.stored_len(), vec![], |mut acc, v| {
+                acc.push(v);
+                acc
+            })
+        })
+    }
+
+    #[test]
+    fn level_out_of_range() -> Result<()> {
+        let (db, _temp) = setup_db()?;
+        let options = ImportOptions::new(&db, "vec", Version::ONE).with_compression_level(100);
+        assert!(matches!(
+            V::forced_import_with(options),
+            Err(Error::InvalidArgument(_))
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,329 @@
+//! Tests for converting a vec between formats.
+//!
+//! These tests cover:
+//! - Values, stamp and computed version carried over, old regions replaced
+//! - Options applied to the converted vec
+//! - Failures before the swap leaving the old data intact
+//! - Leftovers of an interrupted conversion, and swaps interrupted halfway finished on import
+//! - Formats picked at runtime
+//! - Vecs created by forced imports reopening with forced imports, for each format pair
+
+use rawdb::Database;
+use tempfile::TempDir;
+use vecdb::{
+    AnyStoredVec, BytesVec, Error, Format, FormatAction, FormatValue, ImportOptions, ImportableVec,
+    MonotonicVec, RawDBError, ReadableVec, Result, Stamp, StoredVec, Version, WritableVec,
+    convert_format, convert_format_with,
+};
+
+const LEN: u64 = 10_000;
+
+fn setup_db() -> Result<(Database, TempDir)> {
+    let temp = TempDir::new()?;
+    let db = Database::open(temp.path())?;
+    Ok((db, temp))
+}
+
+fn options(db: &Database) -> ImportOptions<'_> {
+    ImportOptions::new(db, "vec", Version::ONE)
+}
+
+fn region_ids(db: &Database) -> Vec<String> {
+    let mut ids = db
+        .regions()
+        .id_to_index()
+        .keys()
+        .cloned()
+        .collect::<Vec<_>>();
+    ids.sort();
+    ids
+}
+
+/// Creates the source vec with a stamp, a computed version and saved changes.
+fn create<V>(db: &Database, values: impl Iterator<Item = u64>) -> Result<()>
+where
+    V: StoredVec<I = usize, T = u64>,
+{
+    let mut vec: V = V::import_with(options(db).with_saved_stamped_changes(10))?;
+    values.for_each(|v| vec.push(v));
+    vec.mut_header().update_computed_version(Version::new(7));
+    vec.stamped_write_with_changes(Stamp::new(42))?;
+    assert_eq!(vec.find_rollback_files()?.len(), 1);
+    Ok(())
+}
+
+// ============================================================================
+// Generic tests
+// ============================================================================
+
+fn test_convert<Src, Dst>(options: ImportOptions) -> Result<Dst>
+where
+    Src: StoredVec<I = usize, T = u64>,
+    Dst: StoredVec<I = usize, T = u64>,
+{
+    let db = options.db;
+    create::<Src>(db, 0..LEN)?;
+
+    let dst = convert_format_with::<Src, Dst>(options)?;
+    assert_eq!(dst.collect(), (0..LEN).collect::<Vec<_>>());
+    assert_eq!(dst.stamp(), Stamp::new(42));
+    assert_eq!(dst.header().computed_version(), Version::new(7));
+    assert!(dst.find_rollback_files()?.is_empty());
+
+    let mut names = dst.region_names();
+    names.sort();
+    assert_eq!(region_ids(db), names);
+
+    // The source layout is gone, raw and compressed vecs differ in version first
+    assert!(matches!(
+        Src::import_with(ImportOptions::new(db, "vec", Version::ONE)),
+        Err(Error::DifferentFormat { .. } | Error::DifferentVersion { .. })
+    ));
+    drop(dst);
+
+    let dst: Dst = Dst::import_with(options)?;
+    assert_eq!(dst.collect(), (0..LEN).collect::<Vec<_>>());
+    Ok(dst)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn bytes_to_monotonic() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let dst = test_convert::<BytesVec<usize, u64>, MonotonicVec<usize, u64>>(options(&db))?;
+    assert_eq!(dst.get_at(1234)?, Some(1234));
+    Ok(())
+}
+
+#[test]
+fn monotonic_to_bytes() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let _ = test_convert::<MonotonicVec<usize, u64>, BytesVec<usize, u64>>(options(&db))?;
+    Ok(())
+}
+
+#[test]
+fn options_apply_to_converted_vec() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let options = options(&db).with_page_size(1024).with_page_checksums(true);
+
+    let dst = test_convert::<BytesVec<usize, u64>, MonotonicVec<usize, u64>>(options)?;
+    assert_eq!(dst.page_size(), 1024);
+    assert!(dst.header().page_checksums());
+    dst.verify()?;
+    Ok(())
+}
+
+#[test]
+fn failure_leaves_source_intact() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    // Not monotonic, so the first sealed page fails to encode
+    create::<BytesVec<usize, u64>>(&db, (0..LEN).rev())?;
+    let ids = region_ids(&db);
+
+    let res = convert_format_with::<BytesVec<usize, u64>, MonotonicVec<usize, u64>>(options(&db));
+    assert!(matches!(res, Err(Error::InvalidArgument(_))));
+    assert_eq!(region_ids(&db), ids);
+
+    let vec: BytesVec<usize, u64> = BytesVec::import_with(options(&db))?;
+    assert_eq!(vec.collect(), (0..LEN).rev().collect::<Vec<_>>());
+    assert_eq!(vec.stamp(), Stamp::new(42));
+    assert_eq!(vec.find_rollback_files()?.len(), 1);
+    Ok(())
+}
+
+#[test]
+fn wrong_source_format() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    create::<MonotonicVec<usize, u64>>(&db, 0..LEN)?;
+    let ids = region_ids(&db);
+
+    let res = convert_format_with::<BytesVec<usize, u64>, MonotonicVec<usize, u64>>(options(&db));
+    assert!(matches!(
+        res,
+        Err(Error::DifferentFormat { .. } | Error::DifferentVersion { .. })
+    ));
+    assert_eq!(region_ids(&db), ids);
+    Ok(())
+}
+
+#[test]
+fn missing_source() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+
+    let res = convert_format_with::<BytesVec<usize, u64>, MonotonicVec<usize, u64>>(options(&db));
+    assert!(matches!(res, Err(Error::RawDB(RawDBError::RegionNotFound))));
+    assert!(region_ids(&db).is_empty());
+    Ok(())
+}
+
+#[test]
+fn interrupted_conversion_leftovers() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    {
+        // As if a previous conversion stopped halfway
+        let mut leftover: MonotonicVec<usize, u64> =
+            MonotonicVec::import(&db, "vec.converting", Version::ONE)?;
+        (0..100).for_each(|v| leftover.push(v));
+        leftover.write()?;
+    }
+
+    let _ = test_convert::<BytesVec<usize, u64>, MonotonicVec<usize, u64>>(options(&db))?;
+    Ok(())
+}
+
+/// Leaves the regions as if a conversion from bytes to monotonic stopped after `renames`
+/// of its swap.
+fn interrupted_swap(db: &Database, renames: &[(&str, &str)]) -> Result<()> {
+    create::<BytesVec<usize, u64>>(db, 0..LEN)?;
+    {
+        let mut copy: MonotonicVec<usize, u64> =
+            MonotonicVec::import(db, "vec.converting", Version::ONE)?;
+        (0..LEN).for_each(|v| copy.push(v));
+        copy.write()?;
+    }
+    for (from, to) in renames {
+        db.get_region(from).unwrap().rename(to)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn interrupted_swap_old_moved_out() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    interrupted_swap(&db, &[("vec/usize", "vec.replaced/usize")])?;
+
+    let vec: MonotonicVec<usize, u64> = MonotonicVec::import_with(options(&db))?;
+    assert_eq!(vec.collect(), (0..LEN).collect::<Vec<_>>());
+    assert_eq!(region_ids(&db), ["vec/usize", "vec/usize_pages"]);
+    Ok(())
+}
+
+#[test]
+fn interrupted_swap_copy_moving_in() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    interrupted_swap(
+        &db,
+        &[
+            ("vec/usize", "vec.replaced/usize"),
+            ("vec.converting/usize", "vec/usize"),
+        ],
+    )?;
+
+    let vec: MonotonicVec<usize, u64> = MonotonicVec::import_with(options(&db))?;
+    assert_eq!(vec.collect(), (0..LEN).collect::<Vec<_>>());
+    assert_eq!(region_ids(&db), ["vec/usize", "vec/usize_pages"]);
+    Ok(())
+}
+
+#[test]
+fn runtime_formats() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    create::<BytesVec<usize, u64>>(&db, 0..LEN)?;
+
+    convert_format::<usize, u64>(&db, "vec", Format::Bytes, Format::Monotonic)?;
+    let vec: MonotonicVec<usize, u64> = MonotonicVec::import_with(options(&db))?;
+    assert_eq!(vec.collect(), (0..LEN).collect::<Vec<_>>());
+    assert_eq!(vec.stamp(), Stamp::new(42));
+    drop(vec);
+
+    assert!(matches!(
+        convert_format::<usize, bool>(&db, "vec", Format::Bytes, Format::Monotonic),
+        Err(Error::UnsupportedFormat {
+            format: Format::Monotonic,
+            value_type: "bool"
+        })
+    ));
+    Ok(())
+}
+
+/// Creates the vec as `V` with a forced import.
+struct CreateForced<'a>(&'a Database);
+
+impl FormatAction<usize, u64> for CreateForced<'_> {
+    type Output = ();
+
+    fn run<V: StoredVec<I = usize, T = u64>>(self) -> Result<()> {
+        let mut vec = V::forced_import_with(options(self.0))?;
+        (0..LEN).for_each(|v| vec.push(v));
+        vec.stamped_write(Stamp::new(42))
+    }
+}
+
+/// Values and stamp of the vec, forced imported as `V`.
+struct ForcedImport<'a>(&'a Database);
+
+impl FormatAction<usize, u64> for ForcedImport<'_> {
+    type Output = (Vec<u64>, Stamp);
+
+    fn run<V: StoredVec<I = usize, T = u64>>(self) -> Result<Self::Output> {
+        let vec = V::forced_import_with(options(self.0))?;
+        Ok((vec.collect(), vec.stamp()))
+    }
+}
+
+#[test]
+fn forced_layout_is_kept() -> Result<()> {
+    let formats = [
+        Format::Bytes,
+        Format::ZeroCopy,
+        Format::Pco,
+        Format::LZ4,
+        Format::Zstd,
+        Format::Monotonic,
+    ];
+    for from in formats {
+        for to in formats {
+            if from == to {
+                continue;
+            }
+            let (db, _temp) = setup_db()?;
+            match u64::with_format(from, CreateForced(&db)) {
+                // Feature disabled
+                Err(Error::UnsupportedFormat { .. }) => continue,
+                res => res?,
+            }
+            match convert_format::<usize, u64>(&db, "vec", from, to) {
+                Err(Error::UnsupportedFormat { .. }) => continue,
+                res => res?,
+            }
+
+            let (values, stamp) = u64::with_format(to, ForcedImport(&db))?;
+            assert_eq!(values, (0..LEN).collect::<Vec<_>>(), "{from} to {to}");
+            assert_eq!(stamp, Stamp::new(42), "{from} to {to}");
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn forced_layout_is_kept_with_types() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    CreateForced(&db).run::<BytesVec<usize, u64>>()?;
+
+    let dst = convert_format_with::<BytesVec<usize, u64>, MonotonicVec<usize, u64>>(options(&db))?;
+    assert_eq!(dst.collect(), (0..LEN).collect::<Vec<_>>());
+    drop(dst);
+
+    let (values, _) = ForcedImport(&db).run::<MonotonicVec<usize, u64>>()?;
+    assert_eq!(values, (0..LEN).collect::<Vec<_>>());
+    Ok(())
+}
+
+#[cfg(all(feature = "pco", feature = "zstd"))]
+mod compressed {
+    use super::*;
+    use vecdb::{PcoVec, ZstdVec};
+
+    #[test]
+    fn pco_to_zstd() -> Result<()> {
+        let (db, _temp) = setup_db()?;
+        let options = options(&db).with_compression_level(19);
+        let dst = test_convert::<PcoVec<usize, u64>, ZstdVec<usize, u64>>(options)?;
+        assert_eq!(dst.compression_level(), Some(19));
+        Ok(())
+    }
+}
@@ -0,0 +1,361 @@
+//! Tests for trained compression dictionaries.
+//!
+//! These tests cover:
+//! - Training a dictionary from stored pages and compressing new pages with it
+//! - Dictionaries persisted and reused when reopening
+//! - Recompressing existing pages in batches, in place or moving later pages
+//! - Truncation and rollback into pages compressed with a dictionary
+//! - Resets on forced import and formats without dictionary support
+
+use rawdb::Database;
+use tempfile::TempDir;
+use vecdb::{AnyStoredVec, Error, ImportableVec, MonotonicVec, Result, Version, WritableVec};
+
+fn setup_db() -> Result<(Database, TempDir)> {
+    let temp = TempDir::new()?;
+    let db = Database::open(temp.path())?;
+    Ok((db, temp))
+}
+
+#[test]
+fn unsupported_format() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+
+    let mut vec: MonotonicVec<usize, u64> = MonotonicVec::forced_import(&db, "vec", Version::ONE)?;
+    (0..10_000).for_each(|i| vec.push(i));
+    vec.write()?;
+
+    assert!(matches!(
+        vec.train_dictionary(4096),
+        Err(Error::InvalidArgument(_))
+    ));
+    assert_eq!(vec.dictionary_id(), None);
+    assert_eq!(vec.recompress_pages(10)?, 0);
+
+    Ok(())
+}
+
+#[cfg(feature = "zstd")]
+mod zstd {
+    use super::*;
+    use vecdb::{AnyVec, ImportOptions, ReadableVec, Stamp, ZstdVec};
+
+    type Record = [u8; 32];
+    type V = ZstdVec<usize, Record>;
+
+    /// Small pages start cold, which is where dictionaries help.
+    const PAGE_SIZE: usize = 1024;
+    const PER_PAGE: usize = PAGE_SIZE / size_of::<Record>();
+    const DICTIONARY_SIZE: usize = 4096;
+
+    const KINDS: [&[u8; 16]; 4] = [
+        b"transfer:native_",
+        b"transfer:token__",
+        b"swap:exact_in___",
+        b"contract:call___",
+    ];
+
+    /// Structured record: constant tag, kind, incrementing id, a few noisy bytes
+    /// and a kind-dependent label.
+    fn record(i: usize) -> Record {
+        let hash = (i as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        let kind = (hash >> 60) as usize % KINDS.len();
+        let mut record = [0; 32];
+        record[..4].copy_from_slice(b"REC1");
+        record[4] = kind as u8;
+        record[8..12].copy_from_slice(&(i as u32).to_le_bytes());
+        record[12..16].copy_from_slice(&(hash as u32).to_le_bytes());
+        record[16..].copy_from_slice(KINDS[kind]);
+        record
+    }
+
+    fn records(range: std::ops::Range<usize>) -> Vec<Record> {
+        range.map(record).collect()
+    }
+
+    fn options(db: &Database) -> ImportOptions<'_> {
+        ImportOptions::new(db, "vec", Version::ONE).with_page_size(PAGE_SIZE)
+    }
+
+    fn region_len(vec: &V) -> usize {
+        vec.region().meta().len()
+    }
+
+    fn collect_io(vec: &V) -> Vec<Record> {
+        vec.fold_stored_io(0, vec.stored_len(), vec![], |mut acc, v| {
+            acc.push(v);
+            acc
+        })
+    }
+
+    fn collect_mmap(vec: &V) -> Vec<Record> {
+        vec.fold_stored_mmap(0, vec.stored_len(), vec![], |mut acc, v| {
+            acc.push(v);
+            acc
+        })
+    }
+
+    #[test]
+    fn nothing_to_train_from() -> Result<()> {
+        let (db, _temp) = setup_db()?;
+
+        let mut vec: V = V::forced_import_with(options(&db))?;
+        assert!(matches!(
+            vec.train_dictionary(DICTIONARY_SIZE),
+            Err(Error::InvalidArgument(_))
+        ));
+
+        // A partial page is stored raw, there is still nothing to sample
+        records(0..PER_PAGE - 1)
+            .into_iter()
+            .for_each(|r| vec.push(r));
+        vec.write()?;
+        assert!(matches!(
+            vec.train_dictionary(DICTIONARY_SIZE),
+            Err(Error::InvalidArgument(_))
+        ));
+        assert_eq!(vec.recompress_pages(10)?, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn new_pages_use_dictionary() -> Result<()> {
+        let (db, _temp) = setup_db()?;
+        let pages = 200;
+
+        let mut vec: V = V::import_with(options(&db))?;
+        records(0..PER_PAGE * pages)
+            .into_iter()
+            .for_each(|r| vec.push(r));
+        vec.write()?;
+        let cold_len = region_len(&vec);
+
+        let id = vec.train_dictionary(DICTIONARY_SIZE)?;
+        assert_eq!(vec.dictionary_id(), Some(id));
+        assert!(
+            vec.region_names()
+                .iter()
+                .any(|name| name.ends_with("_dict"))
+        );
+
+        records(PER_PAGE * pages..PER_PAGE * pages * 2)
+            .into_iter()
+            .for_each(|r| vec.push(r));
+        vec.write()?;
+        let warm_len = region_len(&vec) - cold_len;
+        assert!(
+            warm_len < cold_len,
+            "pages with a dictionary ({warm_len}) should be smaller than without ({cold_len})"
+        );
+
+        let expected = records(0..PER_PAGE * pages * 2);
+        assert_eq!(vec.collect(), expected);
+        drop(vec);
+
+        // The dictionary is persisted
+        let vec: V = V::import_with(options(&db))?;
+        assert_eq!(vec.dictionary_id(), Some(id));
+        assert_eq!(vec.collect(), expected);
+        assert_eq!(collect_io(&vec), expected);
+        assert_eq!(collect_mmap(&vec), expected);
+        assert_eq!(
+            vec.collect_range(PER_PAGE * 300 + 3, PER_PAGE * 300 + 5),
+            expected[PER_PAGE * 300 + 3..PER_PAGE * 300 + 5]
+        );
+        // Searches read first values of pages compressed with and without it
+        let target = PER_PAGE as u32 * 300 + 3;
+        assert_eq!(
+            vec.partition_point(|r| u32::from_le_bytes(r[8..12].try_into().unwrap()) < target),
+            target as usize
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn recompress_in_batches() -> Result<()> {
+        let (db, _temp) = setup_db()?;
+        let len = PER_PAGE * 100 + 7;
+        let expected = records(0..len);
+
+        let mut vec: V = V::import_with(options(&db))?;
+        expected.iter().for_each(|&r| vec.push(r));
+        vec.write()?;
+        let len_before = region_len(&vec);
+
+        vec.train_dictionary(DICTIONARY_SIZE)?;
+
+        let mut batches = 0;
+        loop {
+            let recompressed = vec.recompress_pages(16)?;
+            if recompressed == 0 {
+                break;
+            }
+            assert!(recompressed <= 16);
+            batches += 1;
+
+            assert_eq!(vec.collect(), expected);
+            assert_eq!(collect_io(&vec), expected);
+        }
+        assert_eq!(batches, 7);
+
+        // Smaller pages are rewritten in place
+        assert_eq!(region_len(&vec), len_before);
+
+        // Appending after recompression
+        vec.push(record(len));
+        vec.write()?;
+        drop(vec);
+
+        let vec: V = V::import_with(options(&db))?;
+        let mut expected = expected;
+        expected.push(record(len));
+        assert_eq!(vec.collect(), expected);
+        assert_eq!(collect_io(&vec), expected);
+        assert_eq!(collect_mmap(&vec), expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn recompress_moves_later_pages() -> Result<()> {
+        let (db, _temp) = setup_db()?;
+        let len = PER_PAGE * 60 + 3;
+        let expected = records(0..len);
+
+        // Highest level without a dictionary, then recompressing at the lowest
+        // one grows the pages and shifts the rest of the region.
+        let options_19 = options(&db).with_compression_level(19);
+        let options_fast = options(&db).with_compression_level(-7);
+        {
+            let mut vec: V = V::import_with(options_19)?;
+            expected.iter().for_each(|&r| vec.push(r));
+            vec.write()?;
+        }
+
+        let mut vec: V = V::import_with(options_fast)?;
+        let len_before = region_len(&vec);
+        vec.train_dictionary(256)?;
+        assert_eq!(vec.recompress_pages(5)?, 5);
+        assert!(region_len(&vec) > len_before);
+        assert_eq!(vec.collect(), expected);
+        assert_eq!(collect_io(&vec), expected);
+        assert_eq!(collect_mmap(&vec), expected);
+
+        while vec.recompress_pages(5)? != 0 {}
+        drop(vec);
+
+        let vec: V = V::import_with(options_fast)?;
+        assert_eq!(vec.collect(), expected);
+        assert_eq!(collect_io(&vec), expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn retrain_drops_unused_dictionaries() -> Result<()> {
+        let (db, _temp) = setup_db()?;
+        let expected = records(0..PER_PAGE * 50);
+
+        let mut vec: V = V::import_with(options(&db))?;
+        expected.iter().for_each(|&r| vec.push(r));
+        vec.write()?;
+
+        let first = vec.train_dictionary(DICTIONARY_SIZE)?;
+        // Unused by any page, so its id can be reused right away
+        let second = vec.train_dictionary(DICTIONARY_SIZE)?;
+        assert_ne!(first, second);
+        while vec.recompress_pages(8)? != 0 {}
+
+        // Every page now references the second dictionary, the first one is dropped
+        let third = vec.train_dictionary(DICTIONARY_SIZE)?;
+        assert_eq!(third, first);
+        assert_eq!(vec.recompress_pages(3)?, 3);
+        drop(vec);
+
+        // Pages reference both the second and third dictionaries
+        let vec: V = V::import_with(options(&db))?;
+        assert_eq!(vec.dictionary_id(), Some(third));
+        assert_eq!(vec.collect(), expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn truncate_into_dictionary_page() -> Result<()> {
+        let (db, _temp) = setup_db()?;
+        let expected = records(0..PER_PAGE * 20);
+
+        let mut vec: V = V::import_with(options(&db))?;
+        expected[..PER_PAGE * 10].iter().for_each(|&r| vec.push(r));
+        vec.write()?;
+        vec.train_dictionary(DICTIONARY_SIZE)?;
+        expected[PER_PAGE * 10..].iter().for_each(|&r| vec.push(r));
+        vec.write()?;
+
+        // Middle of a page compressed with the dictionary
+        let at = PER_PAGE * 15 + PER_PAGE / 2;
+        vec.truncate_if_needed_at(at)?;
+        vec.push(record(0));
+        vec.write()?;
+
+        let mut expected = expected[..at].to_vec();
+        expected.push(record(0));
+        assert_eq!(vec.collect(), expected);
+        assert_eq!(collect_io(&vec), expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rollback_with_dictionary() -> Result<()> {
+        let (db, _temp) = setup_db()?;
+        let expected = records(0..PER_PAGE * 20);
+
+        let mut vec: V = V::forced_import_with(options(&db).with_saved_stamped_changes(10))?;
+        expected[..PER_PAGE * 10].iter().for_each(|&r| vec.push(r));
+        vec.stamped_write_with_changes(Stamp::new(1))?;
+        vec.train_dictionary(DICTIONARY_SIZE)?;
+
+        expected[PER_PAGE * 10..].iter().for_each(|&r| vec.push(r));
+        vec.stamped_write_with_changes(Stamp::new(2))?;
+
+        vec.truncate_if_needed_at(PER_PAGE * 12 + 1)?;
+        vec.stamped_write_with_changes(Stamp::new(3))?;
+        assert_eq!(vec.collect(), expected[..PER_PAGE * 12 + 1]);
+
+        vec.rollback()?;
+        assert_eq!(vec.stamp(), Stamp::new(2));
+        assert_eq!(vec.collect(), expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn forced_import_reset_removes_dictionary() -> Result<()> {
+        let (db, _temp) = setup_db()?;
+
+        {
+            let mut vec: V = V::forced_import_with(options(&db))?;
+            records(0..PER_PAGE * 20)
+                .into_iter()
+                .for_each(|r| vec.push(r));
+            vec.write()?;
+            vec.train_dictionary(DICTIONARY_SIZE)?;
+        }
+
+        let mut bumped = options(&db);
+        bumped.version = Version::TWO;
+        let vec: V = V::forced_import_with(bumped)?;
+        assert_eq!(vec.len(), 0);
+        assert_eq!(vec.dictionary_id(), None);
+        assert!(
+            !vec.region_names()
+                .iter()
+                .any(|name| name.ends_with("_dict"))
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+//! Tests for vecs read with a runtime value type.
+//!
+//! These tests cover:
+//! - Raw vecs as numbers, bytes and formatted values
+//! - Compressed vecs, across pages and with the trailing raw page
+//! - Value types that don't match the vec
+
+use rawdb::Database;
+use tempfile::TempDir;
+use vecdb::{
+    AnyStoredVec, BytesVec, Catalog, DynType, DynValue, DynVec, Error, Formattable, ImportableVec,
+    Result, Version, WritableVec,
+};
+
+fn setup_db() -> Result<(Database, TempDir)> {
+    let temp = TempDir::new()?;
+    let db = Database::open(temp.path())?;
+    Ok((db, temp))
+}
+
+fn open(db: &Database, name: &str, value_type: DynType) -> Result<DynVec> {
+    let catalog = Catalog::open(db)?;
+    DynVec::open(db, catalog.find(name).next().unwrap(), value_type)
+}
+
+#[test]
+fn raw() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let mut vec: BytesVec<usize, i32> = BytesVec::import(&db, "delta", Version::ONE)?;
+    (0..10).for_each(|v| vec.push(v - 5));
+    vec.write()?;
+
+    let catalog = Catalog::open(&db)?;
+    let entry = catalog.get("delta", "usize").unwrap();
+    assert_eq!(entry.dyn_type(), Some(DynType::Signed(4)));
+    let vec = DynVec::open(&db, entry, DynType::Signed(4))?;
+    assert_eq!(vec.len(), 10);
+    assert_eq!(vec.read_i64_range(3, 6)?, [-2, -1, 0]);
+    assert_eq!(vec.read_f64_range(8, 100)?, [3.0, 4.0]);
+    assert_eq!(vec.read_bytes_range(0, 1)?, (-5i32).to_le_bytes());
+    assert_eq!(vec.get(9)?, Some(DynValue::Signed(4)));
+    assert_eq!(vec.get(10)?, None);
+
+    let mut s = String::new();
+    vec.read_range(4, 6)?
+        .iter()
+        .for_each(|value| value.fmt_into(&mut s));
+    assert_eq!(s, "-10");
+
+    // Same bytes, read as fixed bytes
+    let vec = open(&db, "delta", DynType::Bytes(4))?;
+    let mut json = vec![];
+    vec.get(0)?.unwrap().fmt_json(&mut json);
+    assert_eq!(json, b"\"fbffffff\"");
+    assert!(matches!(
+        vec.read_f64_range(0, 1),
+        Err(Error::InvalidArgument(_))
+    ));
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "zerocopy")]
+fn zerocopy() -> Result<()> {
+    use vecdb::ZeroCopyVec;
+
+    let (db, _temp) = setup_db()?;
+    let mut vec: ZeroCopyVec<usize, f32> = ZeroCopyVec::import(&db, "ratio", Version::ONE)?;
+    vec.push(0.1);
+    vec.push(2.5);
+    vec.write()?;
+
+    let vec = open(&db, "ratio", DynType::Float(4))?;
+    let mut s = String::new();
+    vec.get(0)?.unwrap().fmt_into(&mut s);
+    assert_eq!(s, "0.1");
+    assert_eq!(vec.read_f64_range(1, 2)?, [2.5]);
+    assert!(matches!(
+        vec.read_i64_range(0, 1),
+        Err(Error::InvalidArgument(_))
+    ));
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "pco")]
+fn pco_across_pages() -> Result<()> {
+    use vecdb::PcoVec;
+
+    let (db, _temp) = setup_db()?;
+    let mut vec: PcoVec<usize, u64> = PcoVec::import(&db, "height", Version::ONE)?;
+    (0..5_000).for_each(|v| vec.push(v * 3));
+    vec.write()?;
+
+    // Pages of 2048 values, the last one raw
+    let vec = open(&db, "height", DynType::Unsigned(8))?;
+    assert_eq!(vec.len(), 5_000);
+    let values = vec.read_i64_range(2_000, 4_200)?;
+    assert_eq!(values.len(), 2_200);
+    assert!(values.iter().zip(2_000..).all(|(&v, i)| v == i * 3));
+    assert_eq!(vec.read_f64_range(4_999, 5_000)?, [14_997.0]);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "zstd")]
+fn zstd_bytes() -> Result<()> {
+    use vecdb::ZstdVec;
+
+    let (db, _temp) = setup_db()?;
+    let mut vec: ZstdVec<usize, (u16, u16)> = ZstdVec::import(&db, "pair", Version::ONE)?;
+    (0..10_000).for_each(|v| vec.push((v as u16, 1)));
+    vec.write()?;
+
+    let vec = open(&db, "pair", DynType::Bytes(4))?;
+    assert_eq!(vec.len(), 10_000);
+    assert_eq!(
+        vec.read_bytes_range(4_095, 4_097)?,
+        [255, 15, 1, 0, 0, 16, 1, 0]
+    );
+    Ok(())
+}
+
+#[test]
+fn monotonic() -> Result<()> {
+    use vecdb::MonotonicVec;
+
+    let (db, _temp) = setup_db()?;
+    let mut vec: MonotonicVec<usize, u32> = MonotonicVec::import(&db, "first", Version::ONE)?;
+    (0..10_000).for_each(|v| vec.push(v / 3));
+    vec.write()?;
+
+    let vec = open(&db, "first", DynType::Unsigned(4))?;
+    assert_eq!(vec.read_i64_range(4_095, 4_098)?, [1_365, 1_365, 1_365]);
+    Ok(())
+}
+
+#[test]
+fn mismatched_types() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let mut vec: BytesVec<usize, u64> = BytesVec::import(&db, "big", Version::ONE)?;
+    vec.push(u64::MAX);
+    vec.write()?;
+
+    assert!(matches!(
+        open(&db, "big", DynType::Signed(8)),
+        Err(Error::DifferentValueType { .. })
+    ));
+    assert!(matches!(
+        open(&db, "big", DynType::Bytes(4)),
+        Err(Error::WrongLength {
+            received: 4,
+            expected: 8
+        })
+    ));
+    assert!(matches!(
+        open(&db, "big", DynType::Unsigned(3)),
+        Err(Error::InvalidArgument(_))
+    ));
+
+    let vec = open(&db, "big", DynType::Unsigned(8))?;
+    assert!(matches!(vec.read_i64_range(0, 1), Err(Error::Overflow)));
+    assert_eq!(vec.read_f64_range(0, 1)?, [u64::MAX as f64]);
+    Ok(())
+}
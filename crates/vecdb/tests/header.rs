@@ -0,0 +1,178 @@
+//! Tests for the vec header magic and checksum.
+//!
+//! These tests cover:
+//! - Regions that aren't vecs
+//! - Corrupted headers
+//! - Headers of older versions, read as is or upgraded
+//! - Reading a header without knowing the vec type
+
+use rawdb::Database;
+use tempfile::TempDir;
+use vecdb::{
+    AnyStoredVec, AnyVec, Bytes, BytesVec, Error, Format, HEADER_OFFSET, Header, HeaderV1,
+    ImportableVec, ReadableVec, Result, Stamp, Version, WritableVec, value_type_fingerprint,
+};
+
+const REGION: &str = "vec/usize";
+
+fn setup_db() -> Result<(Database, TempDir)> {
+    let temp = TempDir::new()?;
+    let db = Database::open(temp.path())?;
+    Ok((db, temp))
+}
+
+fn create(db: &Database) -> Result<()> {
+    let mut vec: BytesVec<usize, u64> = BytesVec::import(db, "vec", Version::ONE)?;
+    (0..100).for_each(|v| vec.push(v));
+    vec.stamped_write(Stamp::new(5))?;
+    Ok(())
+}
+
+fn header_bytes(db: &Database) -> Vec<u8> {
+    let region = db.get_region(REGION).expect("region");
+    region
+        .create_reader()
+        .unchecked_read(0, HEADER_OFFSET)
+        .to_vec()
+}
+
+fn import(db: &Database) -> Result<BytesVec<usize, u64>> {
+    BytesVec::import(db, "vec", Version::ONE)
+}
+
+#[test]
+fn header_starts_with_magic() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    create(&db)?;
+    assert_eq!(&header_bytes(&db)[..4], b"vecd");
+    Ok(())
+}
+
+#[test]
+fn not_a_vec() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let region = db.create_region_if_needed(REGION)?;
+    region.write_at(&[0xAB; 64], 0)?;
+    drop(region);
+
+    let err = import(&db).err();
+    assert!(matches!(err, Some(Error::NotAVec { .. })));
+    assert!(err.is_some_and(|e| e.is_data_error()));
+
+    // Left as is, it may belong to something else
+    let err = BytesVec::<usize, u64>::forced_import(&db, "vec", Version::ONE).err();
+    assert!(matches!(err, Some(Error::NotAVec { .. })));
+    assert_eq!(header_bytes(&db)[..4], [0xAB; 4]);
+    Ok(())
+}
+
+#[test]
+fn read_untyped() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    create(&db)?;
+
+    let header = Header::read(&db.get_region(REGION).expect("region"))?;
+    assert_eq!(header.format(), Format::Bytes);
+    assert_eq!(
+        header.vec_version(),
+        Version::ONE + Format::Bytes.layout_version()
+    );
+    assert_eq!(header.stamp(), Stamp::new(5));
+    assert_eq!(header.value_type(), value_type_fingerprint::<usize, u64>());
+    assert_ne!(header.value_type(), value_type_fingerprint::<usize, i64>());
+
+    let region = db.create_region_if_needed("other")?;
+    region.write_at(&[0xAB; 64], 0)?;
+    assert!(matches!(Header::read(&region), Err(Error::NotAVec { .. })));
+    Ok(())
+}
+
+#[test]
+fn corrupted_header() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    create(&db)?;
+
+    // Flip a bit of the stamp
+    let region = db.get_region(REGION).expect("region");
+    let byte = header_bytes(&db)[12];
+    region.write_at(&[byte ^ 1], 12)?;
+
+    let err = import(&db).err();
+    assert!(matches!(err, Some(Error::CorruptedHeader { .. })));
+    assert!(err.is_some_and(|e| e.is_data_error()));
+
+    let err = BytesVec::<usize, u64>::forced_import(&db, "vec", Version::ONE).err();
+    assert!(matches!(err, Some(Error::CorruptedHeader { .. })));
+    assert_eq!(region.meta().len(), HEADER_OFFSET + 800);
+    Ok(())
+}
+
+#[test]
+fn header_version_one() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+
+    let mut bytes = vec![0; HeaderV1::OFFSET];
+    bytes[0..8].copy_from_slice(&1u64.to_bytes());
+    bytes[8..16].copy_from_slice(&4u64.to_bytes());
+    bytes[16..24].copy_from_slice(&7u64.to_bytes());
+    bytes[24..32].copy_from_slice(&Stamp::new(42).to_bytes());
+    bytes[32] = Format::Bytes as u8;
+    (0..10u64).for_each(|v| bytes.extend(v.to_bytes()));
+    let region = db.create_region_if_needed(REGION)?;
+    region.write_at(&bytes, 0)?;
+
+    assert!(matches!(
+        import(&db),
+        Err(Error::OlderHeaderVersion {
+            received: Version::ONE,
+            ..
+        })
+    ));
+    assert!(matches!(
+        BytesVec::<usize, u64>::forced_import(&db, "vec", Version::new(4)),
+        Err(Error::OlderHeaderVersion { .. })
+    ));
+
+    let header = HeaderV1::read(&region)?;
+    assert_eq!(
+        header,
+        HeaderV1 {
+            vec_version: Version::new(4),
+            computed_version: Version::new(7),
+            stamp: Stamp::new(42),
+            format: Format::Bytes,
+        }
+    );
+    let values = region
+        .create_reader()
+        .unchecked_read(HeaderV1::OFFSET, 10 * size_of::<u64>())
+        .chunks(size_of::<u64>())
+        .map(u64::from_bytes)
+        .collect::<Result<Vec<_>>>()?;
+    assert_eq!(values, (0..10).collect::<Vec<_>>());
+    Ok(())
+}
+
+#[test]
+fn header_version_two_is_upgraded() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    create(&db)?;
+
+    // Same fields, without magic and checksum
+    let region = db.get_region(REGION).expect("region");
+    let mut bytes = header_bytes(&db);
+    bytes[0..4].copy_from_slice(&2u32.to_le_bytes());
+    bytes[27..].fill(0);
+    region.write_at(&bytes, 0)?;
+
+    let mut vec = import(&db)?;
+    assert_eq!(vec.collect(), (0..100).collect::<Vec<_>>());
+    assert_eq!(vec.stamp(), Stamp::new(5));
+    vec.push(100);
+    vec.write()?;
+    drop(vec);
+
+    assert_eq!(&header_bytes(&db)[..4], b"vecd");
+    assert_eq!(import(&db)?.len(), 101);
+    Ok(())
+}
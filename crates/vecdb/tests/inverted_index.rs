@@ -0,0 +1,233 @@
+//! Tests for `InvertedIndex`, mapping the values of a source vec to their indices.
+//!
+//! These tests cover:
+//! - Incremental computes across full and partial segments
+//! - `indices_of` and `count_of` matching a scan of the source
+//! - Truncations of the source and of the index
+//! - Following a source whose stamp went back
+//! - Reopening, version changes and corrupted regions
+//! - Lookups in corrupted postings returning errors
+
+use rawdb::Database;
+use tempfile::TempDir;
+use vecdb::{
+    AnyStoredVec, AnyVec, BytesVec, Error, Exit, ImportableVec, InvertedIndex, ReadableVec, Result,
+    Stamp, Version, WritableVec,
+};
+
+const SEGMENT_LEN: usize = 1 << 16;
+
+fn setup_db() -> Result<(Database, TempDir)> {
+    let temp = TempDir::new()?;
+    let db = Database::open(temp.path())?;
+    Ok((db, temp))
+}
+
+fn value(i: usize) -> u32 {
+    (i as u32).wrapping_mul(7919) % 97
+}
+
+/// Checks the lookups of `index` against a scan of `source`.
+fn check_lookups(index: &InvertedIndex<usize, u32>, source: &BytesVec<usize, u32>) -> Result<()> {
+    let values = source.collect();
+    assert_eq!(index.len(), values.len());
+    for target in [0, 1, 42, 96, 97, 1_000] {
+        let expected = (0..values.len())
+            .filter(|&i| values[i] == target)
+            .collect::<Vec<_>>();
+        assert_eq!(index.count_of(&target)?, expected.len(), "{target}");
+        assert_eq!(index.indices_of(&target)?, expected, "{target}");
+    }
+    Ok(())
+}
+
+fn push_values(source: &mut BytesVec<usize, u32>, to: usize, value: impl Fn(usize) -> u32) {
+    (source.len()..to).for_each(|i| source.push(value(i)));
+}
+
+#[test]
+fn incremental_compute() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let exit = Exit::new();
+    let mut source: BytesVec<usize, u32> = BytesVec::import(&db, "source", Version::ONE)?;
+    let mut index: InvertedIndex<usize, u32> = InvertedIndex::import(&db, "source", Version::ONE)?;
+    assert!(index.is_empty());
+    assert_eq!(index.indices_of(&1)?, Vec::<usize>::new());
+
+    push_values(&mut source, SEGMENT_LEN * 2 + 100, value);
+    source.stamped_write(Stamp::new(1))?;
+    index.compute(0, &source, &exit)?;
+    assert_eq!(index.stamp(), Stamp::new(1));
+    check_lookups(&index, &source)?;
+
+    // The partial last segment is extended, sealed ones are kept
+    push_values(&mut source, SEGMENT_LEN * 3 + 5, value);
+    source.stamped_write(Stamp::new(2))?;
+    index.compute(index.len(), &source, &exit)?;
+    assert_eq!(index.stamp(), Stamp::new(2));
+    check_lookups(&index, &source)?;
+
+    // Nothing new to index
+    index.compute(index.len(), &source, &exit)?;
+    check_lookups(&index, &source)?;
+    Ok(())
+}
+
+#[test]
+fn truncation() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let exit = Exit::new();
+    let mut source: BytesVec<usize, u32> = BytesVec::import(&db, "source", Version::ONE)?;
+    let mut index: InvertedIndex<usize, u32> = InvertedIndex::import(&db, "source", Version::ONE)?;
+    push_values(&mut source, SEGMENT_LEN * 2 + 100, value);
+    source.stamped_write(Stamp::new(1))?;
+    index.compute(0, &source, &exit)?;
+
+    // Truncating the index within a segment skips its postings past the end
+    index.truncate_if_needed_at(SEGMENT_LEN + 10)?;
+    assert_eq!(index.len(), SEGMENT_LEN + 10);
+    source.truncate_if_needed_at(SEGMENT_LEN + 10)?;
+    check_lookups(&index, &source)?;
+
+    // Values rewritten past `max_from` are indexed again
+    push_values(&mut source, SEGMENT_LEN * 2, |i| value(i) + 1);
+    source.stamped_write(Stamp::new(2))?;
+    index.compute(SEGMENT_LEN, &source, &exit)?;
+    check_lookups(&index, &source)?;
+
+    // A shorter source truncates the index
+    source.truncate_if_needed_at(100)?;
+    source.stamped_write(Stamp::new(3))?;
+    index.compute(usize::MAX, &source, &exit)?;
+    check_lookups(&index, &source)?;
+    Ok(())
+}
+
+#[test]
+fn source_rollback() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let exit = Exit::new();
+    let mut source: BytesVec<usize, u32> = BytesVec::import(&db, "source", Version::ONE)?;
+    let mut index: InvertedIndex<usize, u32> = InvertedIndex::import(&db, "source", Version::ONE)?;
+    push_values(&mut source, SEGMENT_LEN + 100, value);
+    source.stamped_write(Stamp::new(1))?;
+    index.compute(0, &source, &exit)?;
+    push_values(&mut source, SEGMENT_LEN * 3, value);
+    source.stamped_write(Stamp::new(2))?;
+    index.compute(index.len(), &source, &exit)?;
+
+    // The source goes back to stamp 1, with other values past its first segment
+    source.truncate_if_needed_at(SEGMENT_LEN)?;
+    push_values(&mut source, SEGMENT_LEN + 100, |i| value(i) + 3);
+    source.stamped_write(Stamp::new(1))?;
+    index.compute(usize::MAX, &source, &exit)?;
+    assert_eq!(index.stamp(), Stamp::new(1));
+    check_lookups(&index, &source)?;
+
+    push_values(&mut source, SEGMENT_LEN * 2 + 7, value);
+    source.stamped_write(Stamp::new(3))?;
+    index.compute(usize::MAX, &source, &exit)?;
+    assert_eq!(index.stamp(), Stamp::new(3));
+    check_lookups(&index, &source)?;
+
+    // Explicit rollback
+    index.rollback_to(Stamp::new(1))?;
+    assert_eq!(index.len(), SEGMENT_LEN);
+    assert_eq!(index.stamp(), Stamp::new(1));
+    Ok(())
+}
+
+#[test]
+fn reopen_and_version() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let exit = Exit::new();
+    let mut source: BytesVec<usize, u32> = BytesVec::import(&db, "source", Version::ONE)?;
+    push_values(&mut source, SEGMENT_LEN + 100, value);
+    source.stamped_write(Stamp::new(1))?;
+    {
+        let mut index: InvertedIndex<usize, u32> =
+            InvertedIndex::import(&db, "source", Version::ONE)?;
+        index.compute(0, &source, &exit)?;
+        index.truncate_if_needed_at(SEGMENT_LEN + 50)?;
+    }
+
+    let index: InvertedIndex<usize, u32> = InvertedIndex::import(&db, "source", Version::ONE)?;
+    assert_eq!(index.len(), SEGMENT_LEN + 50);
+    assert_eq!(index.stamp(), Stamp::new(1));
+    source.truncate_if_needed_at(SEGMENT_LEN + 50)?;
+    check_lookups(&index, &source)?;
+    drop(index);
+
+    // A new version indexes everything again
+    let mut source_values = source.collect();
+    source.truncate_if_needed_at(0)?;
+    source_values.iter_mut().for_each(|v| *v += 1);
+    source_values.iter().for_each(|&v| source.push(v));
+    source.stamped_write(Stamp::new(1))?;
+    let mut index: InvertedIndex<usize, u32> = InvertedIndex::import(&db, "source", Version::TWO)?;
+    index.compute(usize::MAX, &source, &exit)?;
+    check_lookups(&index, &source)?;
+    Ok(())
+}
+
+#[test]
+fn corrupted_regions() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let exit = Exit::new();
+    let mut source: BytesVec<usize, u32> = BytesVec::import(&db, "source", Version::ONE)?;
+    push_values(&mut source, 1_000, value);
+    source.stamped_write(Stamp::new(1))?;
+    {
+        let mut index: InvertedIndex<usize, u32> =
+            InvertedIndex::import(&db, "source", Version::ONE)?;
+        index.compute(0, &source, &exit)?;
+    }
+
+    db.get_region("source/usize_segments")
+        .unwrap()
+        .truncate_write(0, &[1, 2, 3])?;
+    let err = InvertedIndex::<usize, u32>::import(&db, "source", Version::ONE).unwrap_err();
+    assert!(matches!(err, Error::CorruptedRegion { .. }));
+
+    let mut index: InvertedIndex<usize, u32> =
+        InvertedIndex::forced_import(&db, "source", Version::ONE)?;
+    assert!(index.is_empty());
+    index.compute(0, &source, &exit)?;
+    check_lookups(&index, &source)?;
+    Ok(())
+}
+
+#[test]
+fn corrupted_postings() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let exit = Exit::new();
+    let mut source: BytesVec<usize, u32> = BytesVec::import(&db, "source", Version::ONE)?;
+    push_values(&mut source, 1_000, value);
+    source.stamped_write(Stamp::new(1))?;
+    let mut index: InvertedIndex<usize, u32> = InvertedIndex::import(&db, "source", Version::ONE)?;
+    index.compute(0, &source, &exit)?;
+
+    // A directory larger than the segment
+    let postings = db.get_region("source/usize_postings").unwrap();
+    postings.write_at(&[0xFF; 4], 0)?;
+    assert!(matches!(
+        index.indices_of(&42),
+        Err(Error::WrongLength { .. })
+    ));
+    assert!(matches!(
+        index.count_of(&42),
+        Err(Error::WrongLength { .. })
+    ));
+
+    // A segment past the end of the region
+    postings.truncate(8)?;
+    assert!(matches!(
+        index.indices_of(&42),
+        Err(Error::CorruptedRegion { .. })
+    ));
+    assert!(matches!(
+        index.count_of(&42),
+        Err(Error::CorruptedRegion { .. })
+    ));
+    Ok(())
+}
@@ -0,0 +1,265 @@
+//! Tests for migrating vecs across versions.
+//!
+//! These tests cover:
+//! - Chained steps with value and type changes
+//! - Plain and forced imports
+//! - Stamp and computed version carried over
+//! - Reset, or error, when no path exists
+//! - Migrations to another format
+//! - Default forced layout import for downstream `ImportableVec` implementors
+
+use rawdb::Database;
+use tempfile::TempDir;
+use vecdb::{
+    AnyStoredVec, AnyVec, BytesVec, Error, ImportOptions, ImportableVec, Migrations, MonotonicVec,
+    ReadableVec, Result, Stamp, StoredVec, Version, WritableVec,
+};
+
+const LEN: u32 = 10_000;
+const V3: Version = Version::new(3);
+
+fn setup_db() -> Result<(Database, TempDir)> {
+    let temp = TempDir::new()?;
+    let db = Database::open(temp.path())?;
+    Ok((db, temp))
+}
+
+fn options(db: &Database, version: Version) -> ImportOptions<'_> {
+    ImportOptions::new(db, "vec", version)
+}
+
+/// Creates a vec at `version` with a stamp and a computed version.
+fn create<V>(db: &Database, version: Version, forced: bool) -> Result<()>
+where
+    V: StoredVec<I = usize, T = u32>,
+{
+    let options = options(db, version).with_saved_stamped_changes(10);
+    let mut vec = if forced {
+        V::forced_import_with(options)?
+    } else {
+        V::import_with(options)?
+    };
+    (0..LEN).for_each(|v| vec.push(v));
+    vec.mut_header().update_computed_version(Version::new(7));
+    vec.stamped_write_with_changes(Stamp::new(42))?;
+    Ok(())
+}
+
+fn migrations(db: &Database) -> Migrations<'_, BytesVec<usize, u64>> {
+    Migrations::new(options(db, V3))
+        .step::<BytesVec<usize, u32>, BytesVec<usize, u32>>(Version::ONE, Version::TWO, |v| v * 2)
+        .step::<BytesVec<usize, u32>, BytesVec<usize, u64>>(Version::TWO, V3, |v| u64::from(v) + 1)
+}
+
+fn expected_v3() -> Vec<u64> {
+    (0..LEN).map(|v| u64::from(v) * 2 + 1).collect()
+}
+
+fn assert_carried_over<V: StoredVec>(vec: &V) -> Result<()> {
+    assert_eq!(vec.stamp(), Stamp::new(42));
+    assert_eq!(vec.header().computed_version(), Version::new(7));
+    assert!(vec.find_rollback_files()?.is_empty());
+    Ok(())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn chain_from_first_version() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    create::<BytesVec<usize, u32>>(&db, Version::ONE, false)?;
+
+    let vec = migrations(&db).import()?;
+    assert_eq!(vec.collect(), expected_v3());
+    assert_carried_over(&vec)?;
+    drop(vec);
+
+    // Stored at the target version now
+    let vec: BytesVec<usize, u64> = BytesVec::import_with(options(&db, V3))?;
+    assert_eq!(vec.collect(), expected_v3());
+    Ok(())
+}
+
+#[test]
+fn chain_from_middle_version() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    create::<BytesVec<usize, u32>>(&db, Version::TWO, false)?;
+
+    let vec = migrations(&db).import()?;
+    assert_eq!(
+        vec.collect(),
+        (0..LEN).map(|v| u64::from(v) + 1).collect::<Vec<_>>()
+    );
+    assert_carried_over(&vec)?;
+    Ok(())
+}
+
+#[test]
+fn forced_chain() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    create::<BytesVec<usize, u32>>(&db, Version::ONE, true)?;
+
+    let vec = migrations(&db).forced_import()?;
+    assert_eq!(vec.collect(), expected_v3());
+    assert_carried_over(&vec)?;
+    drop(vec);
+
+    let vec: BytesVec<usize, u64> = BytesVec::forced_import_with(options(&db, V3))?;
+    assert_eq!(vec.collect(), expected_v3());
+    Ok(())
+}
+
+#[test]
+fn already_at_target_version() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    {
+        let mut vec: BytesVec<usize, u64> = BytesVec::import_with(options(&db, V3))?;
+        vec.push(5);
+        vec.write()?;
+    }
+
+    let vec = migrations(&db).import()?;
+    assert_eq!(vec.collect(), vec![5]);
+    Ok(())
+}
+
+#[test]
+fn new_vec() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let vec = migrations(&db).forced_import()?;
+    assert!(vec.is_empty());
+    Ok(())
+}
+
+#[test]
+fn no_path_resets_when_forced() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    create::<BytesVec<usize, u32>>(&db, Version::new(9), true)?;
+
+    let vec = migrations(&db).forced_import()?;
+    assert!(vec.is_empty());
+    assert_eq!(vec.stamp(), Stamp::default());
+    Ok(())
+}
+
+#[test]
+fn no_path_errors_when_not_forced() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    create::<BytesVec<usize, u32>>(&db, Version::new(9), false)?;
+
+    assert!(matches!(
+        migrations(&db).import(),
+        Err(Error::DifferentVersion { .. })
+    ));
+
+    // Left untouched
+    let vec: BytesVec<usize, u32> = BytesVec::import_with(options(&db, Version::new(9)))?;
+    assert_eq!(vec.len(), LEN as usize);
+    Ok(())
+}
+
+#[test]
+fn broken_chain_resets_when_forced() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    create::<BytesVec<usize, u32>>(&db, Version::ONE, true)?;
+
+    // Nothing leads from version 2 to 3
+    let vec = Migrations::<BytesVec<usize, u64>>::new(options(&db, V3))
+        .step::<BytesVec<usize, u32>, BytesVec<usize, u32>>(Version::ONE, Version::TWO, |v| v)
+        .forced_import()?;
+    assert!(vec.is_empty());
+    Ok(())
+}
+
+#[test]
+fn migrate_to_another_format() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    create::<BytesVec<usize, u32>>(&db, Version::ONE, false)?;
+
+    let vec = Migrations::<MonotonicVec<usize, u64>>::new(options(&db, Version::TWO))
+        .step::<BytesVec<usize, u32>, MonotonicVec<usize, u64>>(
+            Version::ONE,
+            Version::TWO,
+            u64::from,
+        )
+        .import()?;
+    assert_eq!(vec.collect(), (0..LEN).map(u64::from).collect::<Vec<_>>());
+    assert_carried_over(&vec)?;
+    Ok(())
+}
+
+#[test]
+fn failed_step_leaves_data_intact() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    create::<BytesVec<usize, u32>>(&db, Version::ONE, false)?;
+
+    // Not monotonic, so the first sealed page fails to encode
+    let res = Migrations::<MonotonicVec<usize, u64>>::new(options(&db, Version::TWO))
+        .step::<BytesVec<usize, u32>, MonotonicVec<usize, u64>>(Version::ONE, Version::TWO, |v| {
+            u64::from(LEN - v)
+        })
+        .import();
+    assert!(matches!(res, Err(Error::InvalidArgument(_))));
+
+    let vec: BytesVec<usize, u32> = BytesVec::import_with(options(&db, Version::ONE))?;
+    assert_eq!(vec.collect(), (0..LEN).collect::<Vec<_>>());
+    assert_eq!(vec.stamp(), Stamp::new(42));
+    Ok(())
+}
+
+/// Implements only the required methods, like downstream wrappers do.
+struct Wrapper(BytesVec<usize, u32>);
+
+impl ImportableVec for Wrapper {
+    fn import(db: &Database, name: &str, version: Version) -> Result<Self> {
+        BytesVec::import(db, name, version).map(Self)
+    }
+
+    fn import_with(options: ImportOptions) -> Result<Self> {
+        BytesVec::import_with(options).map(Self)
+    }
+
+    fn forced_import(db: &Database, name: &str, version: Version) -> Result<Self> {
+        BytesVec::forced_import(db, name, version).map(Self)
+    }
+
+    fn forced_import_with(options: ImportOptions) -> Result<Self> {
+        BytesVec::forced_import_with(options).map(Self)
+    }
+}
+
+#[test]
+fn forced_layout_import_defaults_to_import_with() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    create::<BytesVec<usize, u32>>(&db, Version::ONE, false)?;
+
+    let Wrapper(vec) = Wrapper::import_forced_layout_with(options(&db, Version::ONE))?;
+    assert_eq!(vec.collect(), (0..LEN).collect::<Vec<_>>());
+    Ok(())
+}
+
+#[cfg(feature = "pco")]
+mod compressed {
+    use super::*;
+    use vecdb::PcoVec;
+
+    #[test]
+    fn forced_chain_between_compressed_vecs() -> Result<()> {
+        let (db, _temp) = setup_db()?;
+        create::<PcoVec<usize, u32>>(&db, Version::ONE, true)?;
+
+        let vec = Migrations::<PcoVec<usize, u64>>::new(options(&db, Version::TWO))
+            .step::<PcoVec<usize, u32>, PcoVec<usize, u64>>(Version::ONE, Version::TWO, |v| {
+                u64::from(v) << 32
+            })
+            .forced_import()?;
+        assert_eq!(
+            vec.collect(),
+            (0..LEN).map(|v| u64::from(v) << 32).collect::<Vec<_>>()
+        );
+        assert_carried_over(&vec)?;
+        Ok(())
+    }
+}
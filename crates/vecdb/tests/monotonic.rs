@@ -0,0 +1,197 @@
+//! Tests for MonotonicVec (Elias-Fano encoded pages).
+//!
+//! These tests cover:
+//! - Random access across compressed pages, the raw tail and pushed values
+//! - successor / predecessor / rank lookups, including duplicates and bounds
+//! - Sparse values with wide gaps (non-zero low bits)
+//! - Read-only clones sharing the same lookups
+//! - Rejection of non-monotonic values on write, across writes, and on `try_push`
+
+use rawdb::Database;
+use tempfile::TempDir;
+use vecdb::{
+    AnyStoredVec, Error, ImportableVec, MonotonicVec, ReadableVec, Result, StoredVec, Version,
+    WritableVec,
+};
+
+const PER_PAGE_U64: usize = 16 * 1024 / size_of::<u64>(); // 2048
+
+fn setup_db() -> Result<(Database, TempDir)> {
+    let temp = TempDir::new()?;
+    let db = Database::open(temp.path())?;
+    Ok((db, temp))
+}
+
+/// "First tx index of block": every block holds between 0 and 6 txs.
+fn first_tx_indexes(blocks: usize) -> Vec<u64> {
+    let mut total = 0;
+    (0..blocks)
+        .map(|i| {
+            let first = total;
+            total += (i as u64).wrapping_mul(2_654_435_761) % 7;
+            first
+        })
+        .collect()
+}
+
+#[test]
+fn get_across_compressed_raw_and_pushed() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let values = first_tx_indexes(PER_PAGE_U64 * 3 + 100);
+
+    let mut vec: MonotonicVec<usize, u64> = MonotonicVec::forced_import(&db, "vec", Version::ONE)?;
+    let (stored, pushed) = values.split_at(PER_PAGE_U64 * 3 + 50);
+    stored.iter().for_each(|&v| vec.push(v));
+    vec.write()?;
+    pushed.iter().for_each(|&v| vec.push(v));
+
+    for (i, &v) in values.iter().enumerate() {
+        assert_eq!(vec.get(i)?, Some(v), "index {i}");
+    }
+    assert_eq!(vec.get(values.len())?, None);
+    assert_eq!(vec.collect(), values);
+
+    Ok(())
+}
+
+#[test]
+fn successor_and_predecessor() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+
+    let mut vec: MonotonicVec<usize, u32> = MonotonicVec::forced_import(&db, "vec", Version::ONE)?;
+    for v in [10, 10, 20, 30, 30, 30, 45] {
+        vec.push(v);
+    }
+    vec.write()?;
+
+    assert_eq!(vec.successor(0)?, Some((0, 10)));
+    assert_eq!(vec.successor(10)?, Some((0, 10)));
+    assert_eq!(vec.successor(11)?, Some((2, 20)));
+    assert_eq!(vec.successor(30)?, Some((3, 30)));
+    assert_eq!(vec.successor(45)?, Some((6, 45)));
+    assert_eq!(vec.successor(46)?, None);
+
+    assert_eq!(vec.predecessor(9)?, None);
+    assert_eq!(vec.predecessor(10)?, Some((1, 10)));
+    assert_eq!(vec.predecessor(29)?, Some((2, 20)));
+    assert_eq!(vec.predecessor(30)?, Some((5, 30)));
+    assert_eq!(vec.predecessor(u32::MAX)?, Some((6, 45)));
+
+    assert_eq!(vec.rank(0)?, 0);
+    assert_eq!(vec.rank(10)?, 0);
+    assert_eq!(vec.rank(11)?, 2);
+    assert_eq!(vec.rank(31)?, 6);
+    assert_eq!(vec.rank(100)?, 7);
+
+    Ok(())
+}
+
+#[test]
+fn which_block_contains_tx() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let firsts = first_tx_indexes(PER_PAGE_U64 * 4);
+
+    let mut vec: MonotonicVec<usize, u64> = MonotonicVec::forced_import(&db, "vec", Version::ONE)?;
+    firsts.iter().for_each(|&v| vec.push(v));
+    vec.write()?;
+
+    let total_txs = *firsts.last().unwrap();
+    for tx in (0..total_txs).step_by(97) {
+        let expected = firsts.partition_point(|&f| f <= tx) - 1;
+        let (block, first) = vec.predecessor(tx)?.unwrap();
+        assert_eq!(block, expected, "tx {tx}");
+        assert_eq!(first, firsts[expected]);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn sparse_values_survive_reopen() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let values = (0..PER_PAGE_U64 as u64 * 2 + 7)
+        .map(|i| i * i * 1_000_003 + (1 << 40))
+        .collect::<Vec<_>>();
+
+    {
+        let mut vec: MonotonicVec<usize, u64> =
+            MonotonicVec::forced_import(&db, "vec", Version::ONE)?;
+        values.iter().for_each(|&v| vec.push(v));
+        vec.write()?;
+    }
+
+    let vec: MonotonicVec<usize, u64> = MonotonicVec::forced_import(&db, "vec", Version::ONE)?;
+    assert_eq!(vec.stored_len(), values.len());
+    assert_eq!(vec.collect(), values);
+    for i in (0..values.len()).step_by(13) {
+        assert_eq!(vec.get(i)?, Some(values[i]));
+        assert_eq!(vec.successor(values[i] - 1)?, Some((i, values[i])));
+        assert_eq!(vec.predecessor(values[i] + 1)?, Some((i, values[i])));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn read_only_clone_lookups() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let values = first_tx_indexes(PER_PAGE_U64 + 10);
+
+    let mut vec: MonotonicVec<usize, u64> = MonotonicVec::forced_import(&db, "vec", Version::ONE)?;
+    values.iter().for_each(|&v| vec.push(v));
+    vec.write()?;
+    vec.push(u64::MAX);
+
+    let ro = vec.read_only_clone();
+    assert_eq!(ro.get(PER_PAGE_U64)?, Some(values[PER_PAGE_U64]));
+    assert_eq!(ro.get(values.len())?, None);
+    assert_eq!(
+        ro.predecessor(u64::MAX)?,
+        Some((values.len() - 1, values[values.len() - 1]))
+    );
+    assert_eq!(ro.rank(u64::MAX)?, values.len());
+
+    Ok(())
+}
+
+#[test]
+fn non_monotonic_page_is_rejected() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+
+    let mut vec: MonotonicVec<usize, u32> = MonotonicVec::forced_import(&db, "vec", Version::ONE)?;
+    // One full u32 page, so it gets compressed rather than stored raw
+    (0..PER_PAGE_U64 as u32 * 2).rev().for_each(|v| vec.push(v));
+    assert!(matches!(vec.write(), Err(Error::InvalidArgument(_))));
+
+    Ok(())
+}
+
+#[test]
+fn decrease_across_writes_is_rejected() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+
+    let mut vec: MonotonicVec<usize, u32> = MonotonicVec::forced_import(&db, "vec", Version::ONE)?;
+    [10, 20, 30].into_iter().for_each(|v| vec.push(v));
+    vec.write()?;
+
+    vec.push(25);
+    assert!(matches!(vec.write(), Err(Error::InvalidArgument(_))));
+
+    Ok(())
+}
+
+#[test]
+fn try_push_rejects_decrease() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+
+    let mut vec: MonotonicVec<usize, u32> = MonotonicVec::forced_import(&db, "vec", Version::ONE)?;
+    vec.try_push(10)?;
+    vec.write()?;
+    vec.try_push(10)?;
+    assert!(matches!(vec.try_push(9), Err(Error::InvalidArgument(_))));
+    vec.write()?;
+
+    assert_eq!(vec.collect(), vec![10, 10]);
+
+    Ok(())
+}
@@ -0,0 +1,258 @@
+//! Tests for per-page stats (zone maps) of compressed vectors.
+//!
+//! These tests cover:
+//! - Stats stored for sealed pages and kept up to date by updates and truncations
+//! - `min`, `max` and `sum` matching a scan of every value, around pushed and updated values
+//! - Predicate scans skipping pages whose stats exclude a match, on read-write and read-only vecs
+//! - Adding stats to the pages of a vec created without them, rejecting formats without them
+
+#![cfg(feature = "pco")]
+
+use rawdb::Database;
+use tempfile::TempDir;
+use vecdb::{
+    AnyStoredVec, Error, ImportOptions, ImportableVec, PcoVec, ReadableVec, Result, StoredVec,
+    Version, WritableVec,
+};
+
+const PER_PAGE_U64: usize = 16 * 1024 / size_of::<u64>(); // 2048
+
+fn setup_db() -> Result<(Database, TempDir)> {
+    let temp = TempDir::new()?;
+    let db = Database::open(temp.path())?;
+    Ok((db, temp))
+}
+
+fn options(db: &Database) -> ImportOptions<'_> {
+    ImportOptions::new(db, "vec", Version::ONE).with_page_stats(true)
+}
+
+fn value(i: usize) -> u64 {
+    (i as u64 * 7919) % 10_007
+}
+
+/// Checks the aggregates of `vec` against a scan of `values` over ranges around page edges.
+fn check_aggregates<V>(vec: &V, values: &[u64])
+where
+    V: ReadableVec<usize, u64>,
+{
+    let len = values.len();
+    let bounds = [
+        (0, len),
+        (0, PER_PAGE_U64),
+        (1, PER_PAGE_U64 * 2 - 1),
+        (PER_PAGE_U64 - 3, PER_PAGE_U64 * 3 + 7),
+        (PER_PAGE_U64 * 2, len),
+        (len - 5, len + 10),
+        (10, 10),
+    ];
+    for (from, to) in bounds {
+        let range = &values[from.min(len)..to.min(len)];
+        assert_eq!(
+            vec.min(from, to),
+            range.iter().copied().min(),
+            "{from}..{to}"
+        );
+        assert_eq!(
+            vec.max(from, to),
+            range.iter().copied().max(),
+            "{from}..{to}"
+        );
+        assert_eq!(
+            vec.sum(from, to),
+            (!range.is_empty()).then(|| range.iter().sum()),
+            "{from}..{to}"
+        );
+    }
+}
+
+#[test]
+fn aggregates() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let len = PER_PAGE_U64 * 4 + 100;
+    let mut values = (0..len).map(value).collect::<Vec<_>>();
+
+    let mut vec: PcoVec<usize, u64> = PcoVec::import_with(options(&db))?;
+    assert!(vec.header().page_stats());
+    values.iter().for_each(|&v| vec.push(v));
+    check_aggregates(&vec, &values);
+    vec.write()?;
+    check_aggregates(&vec, &values);
+    check_aggregates(&vec.read_only_clone(), &values);
+
+    let page = &values[PER_PAGE_U64..PER_PAGE_U64 * 2];
+    let stats = vec.page_stats(1).unwrap();
+    assert_eq!(stats.min, *page.iter().min().unwrap());
+    assert_eq!(stats.max, *page.iter().max().unwrap());
+    assert_eq!(stats.sum, page.iter().sum::<u64>());
+    assert_eq!((stats.count, stats.nulls), (PER_PAGE_U64 as u32, 0));
+    // The last page is raw
+    assert_eq!(vec.page_stats(4), None);
+
+    // Pushed values and unsaved updates are read value by value
+    vec.update(PER_PAGE_U64 + 5, 1_000_000)?;
+    values[PER_PAGE_U64 + 5] = 1_000_000;
+    (0..50).for_each(|i| {
+        vec.push(i);
+        values.push(i);
+    });
+    check_aggregates(&vec, &values);
+
+    vec.write()?;
+    assert_eq!(vec.page_stats(1).unwrap().max, 1_000_000);
+    drop(vec);
+
+    let vec: PcoVec<usize, u64> = PcoVec::import(&db, "vec", Version::ONE)?;
+    assert!(vec.header().page_stats());
+    check_aggregates(&vec, &values);
+    Ok(())
+}
+
+#[test]
+fn truncation() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let mut vec: PcoVec<usize, u64> = PcoVec::import_with(options(&db))?;
+    let mut values = (0..PER_PAGE_U64 * 3).map(value).collect::<Vec<_>>();
+    values.iter().for_each(|&v| vec.push(v));
+    vec.write()?;
+
+    // Truncating into a sealed page makes it raw until it fills up again
+    vec.truncate_if_needed_at(PER_PAGE_U64 + 10)?;
+    values.truncate(PER_PAGE_U64 + 10);
+    check_aggregates(&vec, &values);
+    vec.write()?;
+    assert_eq!(vec.page_stats(1), None);
+
+    (0..PER_PAGE_U64 as u64).for_each(|i| {
+        vec.push(i * 3);
+        values.push(i * 3);
+    });
+    vec.write()?;
+    assert!(vec.page_stats(1).is_some());
+    check_aggregates(&vec, &values);
+    Ok(())
+}
+
+#[test]
+fn floats_with_nan() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let mut vec: PcoVec<usize, f64> = PcoVec::import_with(options(&db))?;
+    let values = (0..PER_PAGE_U64 * 3)
+        .map(|i| if i == 10 { f64::NAN } else { (i % 100) as f64 })
+        .collect::<Vec<_>>();
+    values.iter().for_each(|&v| vec.push(v));
+    vec.write()?;
+
+    let stats = vec.page_stats(0).unwrap();
+    assert_eq!((stats.min, stats.max, stats.nulls), (0.0, 99.0, 1));
+    assert_eq!(vec.page_stats(1).unwrap().nulls, 0);
+
+    // Pages with nulls are folded like any other vec
+    let scan = |from: usize, to: usize| {
+        values[from..to]
+            .iter()
+            .fold(None, |acc: Option<f64>, &v| match acc {
+                Some(cur) if cur <= v => Some(cur),
+                _ => Some(v),
+            })
+    };
+    for (from, to) in [(0, values.len()), (11, values.len()), (5, PER_PAGE_U64 * 2)] {
+        let (min, expected) = (vec.min(from, to), scan(from, to));
+        assert_eq!(min.map(f64::to_bits), expected.map(f64::to_bits));
+    }
+    assert!(vec.sum(0, values.len()).unwrap().is_nan());
+    assert_eq!(
+        vec.sum(PER_PAGE_U64, values.len()),
+        Some(values[PER_PAGE_U64..].iter().sum())
+    );
+    Ok(())
+}
+
+#[test]
+fn predicate_scan() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let mut vec: PcoVec<usize, u64> = PcoVec::import_with(options(&db))?;
+    // Increasing values: only one page can hold each match
+    let values = (0..PER_PAGE_U64 as u64 * 4 + 10).collect::<Vec<_>>();
+    values.iter().for_each(|&v| vec.push(v));
+    vec.write()?;
+
+    let target = PER_PAGE_U64 as u64 * 2 + 7;
+    let mut visited = 0;
+    let mut found = vec![];
+    vec.for_each_where_at(
+        0,
+        values.len(),
+        |stats| (stats.min..=stats.max).contains(&target),
+        |i, v| {
+            visited += 1;
+            if v == target {
+                found.push(i);
+            }
+        },
+    );
+    assert_eq!(found, vec![target as usize]);
+    // The matching page and the raw last page
+    assert_eq!(visited, PER_PAGE_U64 + 10);
+
+    // Read-only clones skip the same pages
+    let mut visited = 0;
+    vec.read_only_clone().for_each_where_at(
+        0,
+        values.len(),
+        |stats| (stats.min..=stats.max).contains(&target),
+        |_, _| visited += 1,
+    );
+    assert_eq!(visited, PER_PAGE_U64 + 10);
+
+    // Without stats every value is visited
+    let mut plain: PcoVec<usize, u64> = PcoVec::import(&db, "plain", Version::ONE)?;
+    values.iter().for_each(|&v| plain.push(v));
+    plain.write()?;
+    let mut visited = 0;
+    plain.for_each_where_at(0, values.len(), |_| false, |_, _| visited += 1);
+    assert_eq!(visited, values.len());
+    Ok(())
+}
+
+#[test]
+fn added_stats() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let plain = ImportOptions::new(&db, "vec", Version::ONE);
+    let values = (0..PER_PAGE_U64 * 2 + 10).map(value).collect::<Vec<_>>();
+    {
+        let mut vec: PcoVec<usize, u64> = PcoVec::import_with(plain)?;
+        values.iter().for_each(|&v| vec.push(v));
+        vec.write()?;
+        assert!(!vec.header().page_stats());
+        assert_eq!(vec.page_stats(0), None);
+    }
+
+    // Reopening with stats computes them for the sealed pages
+    {
+        let vec: PcoVec<usize, u64> = PcoVec::import_with(options(&db))?;
+        assert!(vec.header().page_stats());
+        let stats = vec.page_stats(1).unwrap();
+        let page = &values[PER_PAGE_U64..PER_PAGE_U64 * 2];
+        assert_eq!(stats.min, *page.iter().min().unwrap());
+        assert_eq!(stats.max, *page.iter().max().unwrap());
+        assert_eq!(stats.sum, page.iter().sum::<u64>());
+        assert_eq!(vec.page_stats(2), None);
+        check_aggregates(&vec, &values);
+    }
+
+    let vec: PcoVec<usize, u64> = PcoVec::import_with(plain)?;
+    assert!(vec.header().page_stats());
+    assert!(vec.page_stats(0).is_some());
+    assert_eq!(vec.collect(), values);
+    Ok(())
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn unsupported_format() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let err = vecdb::ZstdVec::<usize, u64>::import_with(options(&db)).unwrap_err();
+    assert!(matches!(err, Error::InvalidArgument(_)));
+    Ok(())
+}
@@ -0,0 +1,160 @@
+//! Tests for parallel range reads and folds with rayon.
+//!
+//! These tests cover:
+//! - `par_fold_range`, `par_sum`, `par_min` and `par_max` matching their sequential versions
+//! - `par_for_each_chunk` visiting every value once, in chunks aligned on `read_chunk_len`
+//! - Stored, pushed and updated values, and ranges past the end
+
+#![cfg(feature = "rayon")]
+
+use std::sync::Mutex;
+
+use rawdb::Database;
+use tempfile::TempDir;
+use vecdb::{BytesVec, ImportableVec, ReadableVec, Result, StoredVec, Version};
+
+const LEN: usize = 100_000;
+
+fn setup_db() -> Result<(Database, TempDir)> {
+    let temp = TempDir::new()?;
+    let db = Database::open(temp.path())?;
+    Ok((db, temp))
+}
+
+fn value(i: usize) -> u64 {
+    (i as u64 * 7919) % 10_007
+}
+
+fn check_parallel<V>(vec: &V)
+where
+    V: ReadableVec<usize, u64> + Sync,
+{
+    let len = vec.len();
+    let unit = vec.read_chunk_len();
+    for (from, to) in [
+        (0, len),
+        (1, len - 1),
+        (unit - 3, unit * 5 + 2),
+        (len - 5, len + 10),
+        (7, 7),
+    ] {
+        assert_eq!(vec.par_sum(from, to), vec.sum(from, to), "{from}..{to}");
+        assert_eq!(vec.par_min(from, to), vec.min(from, to), "{from}..{to}");
+        assert_eq!(vec.par_max(from, to), vec.max(from, to), "{from}..{to}");
+        assert_eq!(
+            vec.par_fold_range(
+                from,
+                to,
+                || 0usize,
+                |n, v| n + (v % 2) as usize,
+                |a, b| a + b
+            ),
+            vec.fold_range(from, to, 0usize, |n, v| n + (v % 2) as usize),
+            "{from}..{to}"
+        );
+
+        let chunks = Mutex::new(vec![]);
+        vec.par_for_each_chunk(from, to, |start, values| {
+            chunks.lock().unwrap().push((start, values.to_vec()));
+        });
+        let mut chunks = chunks.into_inner().unwrap();
+        chunks.sort_by_key(|(start, _)| *start);
+        for (start, _) in chunks.iter().skip(1) {
+            assert_eq!(start % unit, 0, "{from}..{to}");
+        }
+        let values = chunks
+            .into_iter()
+            .flat_map(|(_, values)| values)
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec.collect_range(from, to), "{from}..{to}");
+    }
+}
+
+// ============================================================================
+// Generic tests
+// ============================================================================
+
+type Update<V> = fn(&mut V, usize, u64) -> Result<()>;
+
+fn test_parallel<V>(update: Update<V>) -> Result<()>
+where
+    V: StoredVec<I = usize, T = u64> + Sync,
+{
+    let (db, _temp) = setup_db()?;
+    let mut vec: V = V::import(&db, "vec", Version::ONE)?;
+    (0..LEN).for_each(|i| vec.push(value(i)));
+    check_parallel(&vec);
+
+    vec.write()?;
+    check_parallel(&vec);
+    check_parallel(&vec.read_only_clone());
+
+    (LEN..LEN + 1_000).for_each(|i| vec.push(value(i)));
+    update(&mut vec, LEN / 2, 1_000_000)?;
+    update(&mut vec, 3, 0)?;
+    check_parallel(&vec);
+    Ok(())
+}
+
+// ============================================================================
+// Per-format modules
+// ============================================================================
+
+mod bytes {
+    use super::*;
+
+    #[test]
+    fn parallel() -> Result<()> {
+        test_parallel::<BytesVec<usize, u64>>(|vec, i, v| vec.update_at(i, v))
+    }
+}
+
+#[cfg(feature = "zerocopy")]
+mod zerocopy {
+    use super::*;
+
+    #[test]
+    fn parallel() -> Result<()> {
+        test_parallel::<vecdb::ZeroCopyVec<usize, u64>>(|vec, i, v| vec.update_at(i, v))
+    }
+}
+
+#[cfg(feature = "pco")]
+mod pco {
+    use super::*;
+
+    #[test]
+    fn parallel() -> Result<()> {
+        test_parallel::<vecdb::PcoVec<usize, u64>>(|vec, i, v| vec.update_at(i, v))
+    }
+
+    #[test]
+    fn chunks_follow_pages() -> Result<()> {
+        let (db, _temp) = setup_db()?;
+        let vec: vecdb::PcoVec<usize, u64> = vecdb::PcoVec::import(&db, "vec", Version::ONE)?;
+        assert_eq!(vec.read_chunk_len(), 16 * 1024 / size_of::<u64>());
+        let raw: BytesVec<usize, u64> = BytesVec::import(&db, "raw", Version::ONE)?;
+        assert_eq!(raw.read_chunk_len(), vecdb::PAGE_SIZE / size_of::<u64>());
+        Ok(())
+    }
+}
+
+#[cfg(feature = "lz4")]
+mod lz4 {
+    use super::*;
+
+    #[test]
+    fn parallel() -> Result<()> {
+        test_parallel::<vecdb::LZ4Vec<usize, u64>>(|vec, i, v| vec.update_at(i, v))
+    }
+}
+
+#[cfg(feature = "zstd")]
+mod zstd {
+    use super::*;
+
+    #[test]
+    fn parallel() -> Result<()> {
+        test_parallel::<vecdb::ZstdVec<usize, u64>>(|vec, i, v| vec.update_at(i, v))
+    }
+}
@@ -0,0 +1,204 @@
+//! Tests for Parquet export of vec sets.
+//!
+//! These tests cover:
+//! - Column types picked from the value types, with `Option` values as nullable columns
+//!   and other types as strings
+//! - Row groups aligned on multiples of the row group length, and ranges past the end
+//! - Overriding column types, and rejecting unknown columns and unparseable cells
+//! - Rejecting empty tables and repeated columns
+
+#![cfg(feature = "parquet")]
+
+use std::fs::File;
+
+use rawdb::Database;
+use tempfile::TempDir;
+use vecdb::{
+    AnyStoredVec, BytesVec, Error, ImportableVec, ParquetTable, Result, Version, WritableVec,
+    arrow_array::{
+        Array, RecordBatch, StringArray,
+        cast::AsArray,
+        types::{Float64Type, Int32Type, UInt32Type, UInt64Type},
+    },
+    arrow_schema::DataType,
+    parquet::{arrow::arrow_reader::ParquetRecordBatchReaderBuilder, basic::Type},
+};
+
+fn setup_db() -> Result<(Database, TempDir)> {
+    let temp = TempDir::new()?;
+    let db = Database::open(temp.path())?;
+    Ok((db, temp))
+}
+
+/// Writes `table` to a file in `temp` and reads it back, with the row counts of its
+/// row groups.
+fn write_and_read(table: &ParquetTable, temp: &TempDir) -> Result<(Vec<usize>, Vec<RecordBatch>)> {
+    let path = temp.path().join("table.parquet");
+    table.write(File::create(&path)?)?;
+    let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(&path)?)?;
+    let row_groups = builder
+        .metadata()
+        .row_groups()
+        .iter()
+        .map(|g| g.num_rows() as usize)
+        .collect();
+    let batches = builder
+        .with_batch_size(1 << 20)
+        .build()?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok((row_groups, batches))
+}
+
+#[test]
+fn column_types() -> Result<()> {
+    let (db, temp) = setup_db()?;
+    let mut price: BytesVec<usize, f64> = BytesVec::import(&db, "price", Version::ONE)?;
+    let mut delta: BytesVec<usize, i32> = BytesVec::import(&db, "delta", Version::ONE)?;
+    let mut fee: BytesVec<usize, Option<u32>> = BytesVec::import(&db, "fee", Version::ONE)?;
+    let mut flag: BytesVec<usize, bool> = BytesVec::import(&db, "flag", Version::ONE)?;
+    let mut big: BytesVec<usize, u128> = BytesVec::import(&db, "big", Version::ONE)?;
+    for i in 0..100 {
+        price.push(i as f64 / 4.0);
+        delta.push(50 - i);
+        fee.push((i % 3 != 0).then_some(i as u32));
+        flag.push(i % 2 == 0);
+        big.push(u128::MAX - i as u128);
+    }
+    price.write()?;
+
+    let table =
+        ParquetTable::new(vec![&price, &delta, &fee, &flag, &big])?.with_index_column("height");
+    let (row_groups, batches) = write_and_read(&table, &temp)?;
+    assert_eq!(row_groups, [100]);
+    let batch = &batches[0];
+
+    let schema = batch.schema();
+    let types = schema
+        .fields()
+        .iter()
+        .map(|f| (f.data_type().clone(), f.is_nullable()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        types,
+        [
+            (DataType::UInt64, false),
+            (DataType::Float64, false),
+            (DataType::Int32, false),
+            (DataType::UInt32, true),
+            (DataType::Boolean, false),
+            (DataType::Utf8, false),
+        ]
+    );
+
+    assert_eq!(batch.column(0).as_primitive::<UInt64Type>().value(7), 7);
+    assert_eq!(batch.column(1).as_primitive::<Float64Type>().value(7), 1.75);
+    assert_eq!(batch.column(2).as_primitive::<Int32Type>().value(70), -20);
+    let fee = batch.column(3).as_primitive::<UInt32Type>();
+    assert_eq!(fee.null_count(), 34);
+    assert!(fee.is_null(9));
+    assert_eq!(fee.value(10), 10);
+    assert!(!batch.column(4).as_boolean().value(7));
+    let big = batch
+        .column(5)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!(big.value(1), (u128::MAX - 1).to_string());
+    Ok(())
+}
+
+#[test]
+fn aligned_row_groups() -> Result<()> {
+    let (db, temp) = setup_db()?;
+    let mut a: BytesVec<usize, u64> = BytesVec::import(&db, "a", Version::ONE)?;
+    let mut b: BytesVec<usize, u64> = BytesVec::import(&db, "b", Version::ONE)?;
+    (0..1_000).for_each(|i| a.push(i * 2));
+    (0..950).for_each(|i| b.push(i * 3));
+
+    // Rows stop at the shortest column
+    let table = ParquetTable::new(vec![&a, &b])?
+        .with_range(130, None)
+        .with_row_group_len(200);
+    assert_eq!(table.range(), (130, 950));
+    let (row_groups, batches) = write_and_read(&table, &temp)?;
+    assert_eq!(row_groups, [70, 200, 200, 200, 150]);
+
+    let values = batches
+        .iter()
+        .flat_map(|batch| {
+            batch
+                .column(0)
+                .as_primitive::<UInt64Type>()
+                .values()
+                .to_vec()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(values, (130..950).map(|i| i * 2).collect::<Vec<_>>());
+    let b = &batches.last().unwrap().column(1);
+    assert_eq!(
+        b.as_primitive::<UInt64Type>().values().last(),
+        Some(&(949 * 3))
+    );
+
+    // An empty range still writes the schema
+    let table = ParquetTable::new(vec![&a])?.with_range(10, Some(5));
+    let (row_groups, batches) = write_and_read(&table, &temp)?;
+    assert!(row_groups.is_empty());
+    assert!(batches.is_empty());
+    Ok(())
+}
+
+#[test]
+fn column_type_overrides() -> Result<()> {
+    let (db, temp) = setup_db()?;
+    let mut count: BytesVec<usize, u64> = BytesVec::import(&db, "count", Version::ONE)?;
+    let mut big: BytesVec<usize, u128> = BytesVec::import(&db, "big", Version::ONE)?;
+    (0..10).for_each(|i| count.push(i));
+    (0..10).for_each(|i| big.push(i as u128 * 1_000));
+
+    let table = ParquetTable::new(vec![&count, &big])?
+        .with_column_type("count", DataType::Utf8)?
+        .with_column_type("big", DataType::UInt32)?;
+    let path = temp.path().join("table.parquet");
+    assert_eq!(table.write(File::create(&path)?)?, 10);
+
+    let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(&path)?)?;
+    let columns = builder
+        .metadata()
+        .file_metadata()
+        .schema_descr()
+        .columns()
+        .to_vec();
+    assert_eq!(columns[0].physical_type(), Type::BYTE_ARRAY);
+    assert_eq!(columns[1].physical_type(), Type::INT32);
+    let batch = builder.build()?.next().unwrap()?;
+    assert_eq!(batch.column(1).as_primitive::<UInt32Type>().value(9), 9_000);
+
+    assert!(matches!(
+        ParquetTable::new(vec![&count])?.with_column_type("missing", DataType::UInt64),
+        Err(Error::Arrow(_))
+    ));
+    assert!(
+        ParquetTable::new(vec![&count])?
+            .with_column_type("count", DataType::Date32)
+            .is_err()
+    );
+
+    // Cells that don't parse fail the export
+    big.push(u128::MAX);
+    let table = ParquetTable::new(vec![&big])?.with_column_type("big", DataType::UInt64)?;
+    assert!(matches!(
+        table.write(File::create(&path)?),
+        Err(Error::Arrow(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn rejected_tables() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let vec: BytesVec<usize, u64> = BytesVec::import(&db, "vec", Version::ONE)?;
+    assert!(ParquetTable::new(vec![]).is_err());
+    assert!(ParquetTable::new(vec![&vec, &vec]).is_err());
+    Ok(())
+}
@@ -1372,3 +1372,110 @@ mod zstd {
         test_read_only_clone_mixed_pages::<V>()
     }
 }
+
+mod monotonic {
+    use super::*;
+    use vecdb::MonotonicVec;
+    type V = MonotonicVec<usize, u32>;
+
+    #[test]
+    fn small_write_raw_survives_reopen() -> Result<()> {
+        test_small_write_raw_survives_reopen::<V>()
+    }
+    #[test]
+    fn fast_append_multiple_small_writes() -> Result<()> {
+        test_fast_append_multiple_small_writes::<V>()
+    }
+    #[test]
+    fn fast_append_survives_reopen() -> Result<()> {
+        test_fast_append_survives_reopen::<V>()
+    }
+    #[test]
+    fn full_page_compressed_partial_raw() -> Result<()> {
+        test_full_page_compressed_partial_raw::<V>()
+    }
+    #[test]
+    fn exact_page_boundary() -> Result<()> {
+        test_exact_page_boundary::<V>()
+    }
+    #[test]
+    fn fast_append_overflow() -> Result<()> {
+        test_fast_append_overflow::<V>()
+    }
+    #[test]
+    fn fast_append_fills_exactly() -> Result<()> {
+        test_fast_append_fills_exactly::<V>()
+    }
+    #[test]
+    fn incremental_growth_across_pages() -> Result<()> {
+        test_incremental_growth_across_pages::<V>()
+    }
+    #[test]
+    fn truncate_into_raw_page() -> Result<()> {
+        test_truncate_into_raw_page::<V>()
+    }
+    #[test]
+    fn truncate_to_page_boundary() -> Result<()> {
+        test_truncate_to_page_boundary::<V>()
+    }
+    #[test]
+    fn truncate_into_compressed_page() -> Result<()> {
+        test_truncate_into_compressed_page::<V>()
+    }
+    #[test]
+    fn reset_clears_raw_pages() -> Result<()> {
+        test_reset_clears_raw_pages::<V>()
+    }
+    #[test]
+    fn reset_after_multi_page() -> Result<()> {
+        test_reset_after_multi_page::<V>()
+    }
+    #[test]
+    fn read_spanning_compressed_and_raw() -> Result<()> {
+        test_read_spanning_compressed_and_raw::<V>()
+    }
+    #[test]
+    fn multiple_pages_with_raw_tail() -> Result<()> {
+        test_multiple_pages_with_raw_tail::<V>()
+    }
+    #[test]
+    fn write_reopen_append_cycle() -> Result<()> {
+        test_write_reopen_append_cycle::<V>()
+    }
+    #[test]
+    fn write_reopen_cycle_crossing_pages() -> Result<()> {
+        test_write_reopen_cycle_crossing_pages::<V>()
+    }
+    #[test]
+    fn noop_write_on_raw_page() -> Result<()> {
+        test_noop_write_on_raw_page::<V>()
+    }
+    #[test]
+    fn noop_write_after_multi_page() -> Result<()> {
+        test_noop_write_after_multi_page::<V>()
+    }
+    #[test]
+    fn fold_over_mixed_pages() -> Result<()> {
+        test_fold_over_mixed_pages::<V>()
+    }
+    #[test]
+    fn pushed_and_stored_raw_page() -> Result<()> {
+        test_pushed_and_stored_raw_page::<V>()
+    }
+    #[test]
+    fn pushed_and_stored_mixed_pages() -> Result<()> {
+        test_pushed_and_stored_mixed_pages::<V>()
+    }
+    #[test]
+    fn single_value_writes() -> Result<()> {
+        test_single_value_writes::<V>()
+    }
+    #[test]
+    fn truncate_to_zero_then_rebuild() -> Result<()> {
+        test_truncate_to_zero_then_rebuild::<V>()
+    }
+    #[test]
+    fn read_only_clone_mixed_pages() -> Result<()> {
+        test_read_only_clone_mixed_pages::<V>()
+    }
+}
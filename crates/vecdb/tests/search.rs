@@ -0,0 +1,181 @@
+//! Tests for `partition_point` and `search_sorted` on sorted vecs.
+//!
+//! These tests cover:
+//! - Matching `slice::partition_point` across pages, the raw last page and pushed values
+//! - Duplicates, values before the first and after the last, and empty ranges
+//! - Unsaved updates and truncations
+//! - Read-only clones
+
+use rawdb::Database;
+use tempfile::TempDir;
+use vecdb::{BytesVec, MonotonicVec, ReadableVec, Result, StoredVec, Version};
+
+const PER_PAGE_U64: usize = 16 * 1024 / size_of::<u64>(); // 2048
+
+fn setup_db() -> Result<(Database, TempDir)> {
+    let temp = TempDir::new()?;
+    let db = Database::open(temp.path())?;
+    Ok((db, temp))
+}
+
+/// Non-decreasing, every value repeated 0 to 3 times.
+fn sorted_values(len: usize) -> Vec<u64> {
+    let mut value = 10;
+    (0..len)
+        .map(|i| {
+            value += (i as u64).wrapping_mul(2_654_435_761) % 4 / 2;
+            value
+        })
+        .collect()
+}
+
+/// Checks searches of `vec` against the ones of `values`.
+fn check_searches<V>(vec: &V, values: &[u64])
+where
+    V: ReadableVec<usize, u64>,
+{
+    let len = values.len();
+    let last = *values.last().unwrap();
+    let targets = [
+        0,
+        10,
+        11,
+        values[PER_PAGE_U64],
+        values[len / 2] + 1,
+        last,
+        last + 1,
+    ];
+    for target in targets {
+        let expected = values.partition_point(|&v| v < target);
+        assert_eq!(vec.search_sorted(&target), expected, "{target}");
+        assert_eq!(vec.partition_point(|&v| v < target), expected, "{target}");
+        assert_eq!(
+            vec.partition_point(|&v| v <= target),
+            values.partition_point(|&v| v <= target),
+            "{target}"
+        );
+    }
+
+    let bounds = [
+        (0, len),
+        (1, PER_PAGE_U64 * 2 - 1),
+        (PER_PAGE_U64 + 3, len + 10),
+        (len - 5, len),
+        (PER_PAGE_U64, PER_PAGE_U64),
+        (len + 1, len + 5),
+    ];
+    for (from, to) in bounds {
+        let range = &values[from.min(len)..to.min(len)];
+        for target in targets {
+            assert_eq!(
+                vec.search_sorted_range(from, to, &target),
+                from.min(len) + range.partition_point(|&v| v < target),
+                "{from}..{to} {target}"
+            );
+        }
+    }
+}
+
+// ============================================================================
+// Generic tests
+// ============================================================================
+
+type Update<V> = fn(&mut V, usize, u64) -> Result<()>;
+
+fn test_search<V>(update: Update<V>) -> Result<()>
+where
+    V: StoredVec<I = usize, T = u64>,
+{
+    let (db, _temp) = setup_db()?;
+    let mut values = sorted_values(PER_PAGE_U64 * 4 + 100);
+
+    let mut vec: V = V::import(&db, "vec", Version::ONE)?;
+    let (stored, pushed) = values.split_at(PER_PAGE_U64 * 3 + 50);
+    stored.iter().for_each(|&v| vec.push(v));
+    vec.write()?;
+    pushed.iter().for_each(|&v| vec.push(v));
+    check_searches(&vec, &values);
+
+    vec.write()?;
+    check_searches(&vec, &values);
+    check_searches(&vec.read_only_clone(), &values);
+
+    // Unsaved updates, keeping the values sorted
+    let index = (PER_PAGE_U64 * 2..)
+        .find(|&i| values[i - 1] != values[i])
+        .unwrap();
+    update(&mut vec, index, values[index - 1])?;
+    values[index] = values[index - 1];
+    check_searches(&vec, &values);
+    vec.write()?;
+    check_searches(&vec, &values);
+
+    // Truncation into a stored page
+    vec.truncate_if_needed_at(PER_PAGE_U64 + 7)?;
+    values.truncate(PER_PAGE_U64 + 7);
+    check_searches(&vec, &values);
+
+    Ok(())
+}
+
+// ============================================================================
+// Per-format modules
+// ============================================================================
+
+mod bytes {
+    use super::*;
+
+    #[test]
+    fn search() -> Result<()> {
+        test_search::<BytesVec<usize, u64>>(|vec, i, v| vec.update_at(i, v))
+    }
+}
+
+mod monotonic {
+    use super::*;
+
+    #[test]
+    fn search() -> Result<()> {
+        test_search::<MonotonicVec<usize, u64>>(|vec, i, v| vec.update_at(i, v))
+    }
+}
+
+#[cfg(feature = "zerocopy")]
+mod zerocopy {
+    use super::*;
+
+    #[test]
+    fn search() -> Result<()> {
+        test_search::<vecdb::ZeroCopyVec<usize, u64>>(|vec, i, v| vec.update_at(i, v))
+    }
+}
+
+#[cfg(feature = "pco")]
+mod pco {
+    use super::*;
+
+    #[test]
+    fn search() -> Result<()> {
+        test_search::<vecdb::PcoVec<usize, u64>>(|vec, i, v| vec.update_at(i, v))
+    }
+}
+
+#[cfg(feature = "lz4")]
+mod lz4 {
+    use super::*;
+
+    #[test]
+    fn search() -> Result<()> {
+        test_search::<vecdb::LZ4Vec<usize, u64>>(|vec, i, v| vec.update_at(i, v))
+    }
+}
+
+#[cfg(feature = "zstd")]
+mod zstd {
+    use super::*;
+
+    #[test]
+    fn search() -> Result<()> {
+        test_search::<vecdb::ZstdVec<usize, u64>>(|vec, i, v| vec.update_at(i, v))
+    }
+}
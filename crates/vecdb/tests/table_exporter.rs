@@ -0,0 +1,127 @@
+//! Tests for row-oriented export of vec sets.
+//!
+//! These tests cover:
+//! - CSV with a header, JSON arrays of objects and NDJSON, with an index column
+//! - Value types mixed in one table, `None` values and escaped column names
+//! - Rows stopping at the shortest column, ranges and long tables
+//! - Rejecting empty tables and repeated columns
+
+#![cfg(feature = "serde")]
+
+use rawdb::Database;
+use tempfile::TempDir;
+use vecdb::{
+    AnyStoredVec, BytesVec, Error, ImportableVec, Result, TableExporter, Version, WritableVec,
+    WriterFormat,
+};
+
+fn setup_db() -> Result<(Database, TempDir)> {
+    let temp = TempDir::new()?;
+    let db = Database::open(temp.path())?;
+    Ok((db, temp))
+}
+
+fn export(table: TableExporter, format: WriterFormat) -> Result<String> {
+    let mut out = vec![];
+    table.with_format(format).write(&mut out)?;
+    Ok(String::from_utf8(out).unwrap())
+}
+
+#[test]
+fn formats() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let mut price: BytesVec<usize, f64> = BytesVec::import(&db, "price", Version::ONE)?;
+    let mut fee: BytesVec<usize, Option<u32>> = BytesVec::import(&db, "fee", Version::ONE)?;
+    let mut flag: BytesVec<usize, bool> = BytesVec::import(&db, "fl\"ag", Version::ONE)?;
+    for i in 0..5 {
+        price.push(i as f64 / 2.0);
+        fee.push((i % 2 == 0).then_some(i * 10));
+        flag.push(i > 2);
+    }
+    // The shortest column ends the table
+    fee.push(None);
+    price.write()?;
+
+    let table = || {
+        TableExporter::new(vec![&price, &fee, &flag])
+            .unwrap()
+            .with_range(1, None)
+            .with_index_column("height")
+    };
+    assert_eq!(table().range(), (1, 5));
+
+    assert_eq!(
+        export(table(), WriterFormat::Csv)?,
+        "height,price,fee,\"fl\"\"ag\"\n\
+         1,0.5,,false\n\
+         2,1.0,20,false\n\
+         3,1.5,,true\n\
+         4,2.0,40,true\n"
+    );
+    assert_eq!(
+        export(table().with_range(3, Some(5)), WriterFormat::Json)?,
+        "[{\"height\":3,\"price\":1.5,\"fee\":null,\"fl\\\"ag\":true},\
+         {\"height\":4,\"price\":2.0,\"fee\":40,\"fl\\\"ag\":true}]"
+    );
+    assert_eq!(
+        export(table().with_range(0, Some(2)), WriterFormat::NdJson)?,
+        "{\"height\":0,\"price\":0.0,\"fee\":0,\"fl\\\"ag\":false}\n\
+         {\"height\":1,\"price\":0.5,\"fee\":null,\"fl\\\"ag\":false}\n"
+    );
+
+    // Without an index column, and empty ranges
+    let table = TableExporter::new(vec![&flag, &fee])?.with_range(4, Some(9));
+    assert_eq!(
+        export(table, WriterFormat::Csv)?,
+        "\"fl\"\"ag\",fee\ntrue,40\n"
+    );
+    let table = TableExporter::new(vec![&price])?.with_range(7, None);
+    assert_eq!(export(table, WriterFormat::Csv)?, "price\n");
+    let table = TableExporter::new(vec![&price])?.with_range(7, None);
+    assert_eq!(export(table, WriterFormat::Json)?, "[]");
+    let table = TableExporter::new(vec![&price])?.with_range(7, None);
+    assert_eq!(export(table, WriterFormat::NdJson)?, "");
+    Ok(())
+}
+
+#[test]
+fn long_table() -> Result<()> {
+    const LEN: usize = 100_000;
+    let (db, _temp) = setup_db()?;
+    let mut a: BytesVec<usize, u64> = BytesVec::import(&db, "a", Version::ONE)?;
+    let mut b: BytesVec<usize, i32> = BytesVec::import(&db, "b", Version::ONE)?;
+    for i in 0..LEN {
+        a.push(i as u64 * 3);
+        b.push(-(i as i32));
+    }
+    a.write()?;
+    b.write()?;
+
+    let mut out = vec![];
+    let rows = TableExporter::new(vec![&a, &b])?
+        .with_index_column("i")
+        .write(&mut out)?;
+    assert_eq!(rows, LEN);
+    let csv = String::from_utf8(out).unwrap();
+    let lines = csv.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), LEN + 1);
+    for i in [0, 4_095, 4_096, 77_777, LEN - 1] {
+        assert_eq!(lines[i + 1], format!("{i},{},{}", i * 3, -(i as i32)));
+    }
+    Ok(())
+}
+
+#[test]
+fn rejected_tables() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let vec: BytesVec<usize, u64> = BytesVec::import(&db, "vec", Version::ONE)?;
+    assert!(matches!(
+        TableExporter::new(vec![]),
+        Err(Error::InvalidArgument(_))
+    ));
+    assert!(matches!(
+        TableExporter::new(vec![&vec, &vec]),
+        Err(Error::InvalidArgument(_))
+    ));
+    Ok(())
+}
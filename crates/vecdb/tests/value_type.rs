@@ -0,0 +1,128 @@
+//! Tests for the value type fingerprint stored in the header.
+//!
+//! These tests cover:
+//! - Reopening with another value type or size
+//! - Forced imports resetting on mismatch
+//! - Headers written before the fingerprint was stored
+
+use rawdb::Database;
+use tempfile::TempDir;
+use vecdb::{
+    AnyStoredVec, BytesVec, Error, ImportableVec, ReadableVec, Result, StoredVec, Version,
+    WritableVec,
+};
+
+fn setup_db() -> Result<(Database, TempDir)> {
+    let temp = TempDir::new()?;
+    let db = Database::open(temp.path())?;
+    Ok((db, temp))
+}
+
+fn create<V>(db: &Database, forced: bool) -> Result<()>
+where
+    V: StoredVec<T = u64>,
+{
+    let mut vec = if forced {
+        V::forced_import(db, "vec", Version::ONE)?
+    } else {
+        V::import(db, "vec", Version::ONE)?
+    };
+    (0..100).for_each(|v| vec.push(v));
+    vec.write()?;
+    Ok(())
+}
+
+// ============================================================================
+// Generic tests
+// ============================================================================
+
+fn test_mismatch<V, Other>() -> Result<()>
+where
+    V: StoredVec<T = u64>,
+    Other: StoredVec,
+{
+    let (db, _temp) = setup_db()?;
+    create::<V>(&db, false)?;
+
+    let err = Other::import(&db, "vec", Version::ONE).err();
+    assert!(matches!(err, Some(Error::DifferentValueType { .. })));
+    assert!(err.is_some_and(|e| e.is_data_error()));
+
+    // Still readable as its own type
+    let vec = V::import(&db, "vec", Version::ONE)?;
+    assert_eq!(vec.len(), 100);
+    Ok(())
+}
+
+fn test_forced_mismatch_resets<V, Other>() -> Result<()>
+where
+    V: StoredVec<T = u64>,
+    Other: StoredVec,
+{
+    let (db, _temp) = setup_db()?;
+    create::<V>(&db, true)?;
+
+    let other = Other::forced_import(&db, "vec", Version::ONE)?;
+    assert!(other.is_empty());
+    Ok(())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn different_value_type_same_size() -> Result<()> {
+    test_mismatch::<BytesVec<usize, u64>, BytesVec<usize, f64>>()
+}
+
+#[test]
+fn different_value_size() -> Result<()> {
+    test_mismatch::<BytesVec<usize, u64>, BytesVec<usize, u32>>()
+}
+
+#[test]
+fn forced_import_resets() -> Result<()> {
+    test_forced_mismatch_resets::<BytesVec<usize, u64>, BytesVec<usize, f64>>()
+}
+
+#[test]
+fn header_without_fingerprint() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    create::<BytesVec<usize, u64>>(&db, false)?;
+
+    // As written before the fingerprint was stored, with header version TWO
+    let region = db.get_region("vec/usize").expect("region");
+    region.write_at(&2u32.to_le_bytes(), 0)?;
+    region.write_at(&[0; 9], 23)?;
+    db.flush()?;
+
+    // Accepted as any type, then stamped with the one it's opened as on the next write
+    let mut vec: BytesVec<usize, u64> = BytesVec::import(&db, "vec", Version::ONE)?;
+    assert_eq!(vec.collect(), (0..100).collect::<Vec<_>>());
+    vec.push(100);
+    vec.write()?;
+    drop(vec);
+
+    assert!(matches!(
+        BytesVec::<usize, f64>::import(&db, "vec", Version::ONE),
+        Err(Error::DifferentValueType { .. })
+    ));
+    Ok(())
+}
+
+#[cfg(feature = "pco")]
+mod compressed {
+    use super::*;
+    use vecdb::PcoVec;
+
+    #[test]
+    fn different_value_type_same_size() -> Result<()> {
+        test_mismatch::<PcoVec<usize, u64>, PcoVec<usize, f64>>()
+    }
+
+    #[test]
+    fn forced_import_resets() -> Result<()> {
+        test_forced_mismatch_resets::<PcoVec<usize, u64>, PcoVec<usize, f64>>()
+    }
+}
@@ -0,0 +1,235 @@
+//! Tests for cursor-backed value writers.
+//!
+//! These tests cover:
+//! - `write_next` matching the stored, pushed and updated values of a range
+//! - CSV, JSON and NDJSON output of `write_all`, flushed once per read chunk
+//! - Empty ranges and ranges past the end, and `None` values
+//! - Pages failing their checksum ending writes with an error, not a truncated output
+
+use rawdb::Database;
+use tempfile::TempDir;
+use vecdb::{
+    AnyStoredVec, AnyVecWithWriter, BytesVec, Error, HEADER_OFFSET, ImportOptions, ImportableVec,
+    MonotonicVec, READ_CHUNK_SIZE, Result, StoredVec, Version, WritableVec, WriterFormat,
+};
+
+const LEN: usize = 10_000;
+
+fn setup_db() -> Result<(Database, TempDir)> {
+    let temp = TempDir::new()?;
+    let db = Database::open(temp.path())?;
+    Ok((db, temp))
+}
+
+fn value(i: usize) -> u64 {
+    (i as u64 * 7919) % 10_007
+}
+
+/// Output that counts the calls to `write`.
+#[derive(Default)]
+struct Output {
+    bytes: Vec<u8>,
+    writes: usize,
+}
+
+impl std::io::Write for Output {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.writes += 1;
+        self.bytes.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn write_all(vec: &dyn AnyVecWithWriter, from: i64, to: i64, format: WriterFormat) -> Output {
+    let mut out = Output::default();
+    let count = vec
+        .create_writer_with_format(Some(from), Some(to), format)
+        .write_all(&mut out)
+        .unwrap();
+    assert_eq!(
+        count,
+        to.min(vec.len() as i64).max(from) as usize - from as usize
+    );
+    out
+}
+
+fn check_writer<V>(vec: &V, expected: &[u64])
+where
+    V: AnyVecWithWriter,
+{
+    for (from, to) in [(0, LEN), (3, 4_500), (LEN - 5, LEN + 10), (7, 7)] {
+        let expected = &expected[from..to.min(expected.len())];
+
+        let mut writer = vec.create_writer(Some(from as i64), Some(to as i64));
+        let mut values = vec![];
+        let mut buf = String::new();
+        loop {
+            buf.clear();
+            match writer.write_next(&mut buf) {
+                Ok(()) => values.push(buf.parse::<u64>().unwrap()),
+                Err(Error::IteratorEnded) => break,
+                Err(e) => panic!("{e}"),
+            }
+        }
+        assert_eq!(values, expected, "{from}..{to}");
+
+        let lines = expected
+            .iter()
+            .map(|v| format!("{v}\n"))
+            .collect::<String>();
+        let csv = write_all(vec, from as i64, to as i64, WriterFormat::Csv);
+        assert_eq!(String::from_utf8(csv.bytes).unwrap(), lines);
+        let ndjson = write_all(vec, from as i64, to as i64, WriterFormat::NdJson);
+        assert_eq!(String::from_utf8(ndjson.bytes).unwrap(), lines);
+
+        let json = write_all(vec, from as i64, to as i64, WriterFormat::Json);
+        let array = expected.iter().map(u64::to_string).collect::<Vec<_>>();
+        assert_eq!(
+            String::from_utf8(json.bytes).unwrap(),
+            format!("[{}]", array.join(","))
+        );
+        // One write per read chunk, plus the closing bracket
+        assert_eq!(json.writes, expected.len().div_ceil(READ_CHUNK_SIZE) + 1);
+    }
+}
+
+// ============================================================================
+// Generic tests
+// ============================================================================
+
+type Update<V> = fn(&mut V, usize, u64) -> Result<()>;
+
+fn test_writer<V>(update: Update<V>) -> Result<()>
+where
+    V: StoredVec<I = usize, T = u64> + AnyVecWithWriter,
+{
+    let (db, _temp) = setup_db()?;
+    let mut vec: V = V::import(&db, "vec", Version::ONE)?;
+    let mut expected = (0..LEN).map(value).collect::<Vec<_>>();
+    expected.iter().for_each(|v| vec.push(*v));
+    check_writer(&vec, &expected);
+
+    vec.write()?;
+    check_writer(&vec, &expected);
+
+    update(&mut vec, LEN / 2, 1_000_000)?;
+    expected[LEN / 2] = 1_000_000;
+    check_writer(&vec, &expected);
+    Ok(())
+}
+
+#[test]
+fn option_values() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let mut vec: BytesVec<usize, Option<u32>> = BytesVec::import(&db, "vec", Version::ONE)?;
+    [Some(1), None, Some(3)]
+        .into_iter()
+        .for_each(|v| vec.push(v));
+
+    let csv = write_all(&vec, 0, 3, WriterFormat::Csv);
+    assert_eq!(csv.bytes, b"1\n\n3\n");
+    let json = write_all(&vec, 0, 3, WriterFormat::Json);
+    assert_eq!(json.bytes, b"[1,null,3]");
+    let ndjson = write_all(&vec, 1, 3, WriterFormat::NdJson);
+    assert_eq!(ndjson.bytes, b"null\n3\n");
+    Ok(())
+}
+
+#[test]
+fn corrupt_page() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let options = ImportOptions::new(&db, "vec", Version::ONE).with_page_checksums(true);
+    let mut vec: MonotonicVec<usize, u64> = MonotonicVec::import_with(options)?;
+    (0..LEN as u64).for_each(|v| vec.push(v));
+    vec.write()?;
+
+    // Flips a byte of the first page
+    let at = HEADER_OFFSET + 3;
+    let byte = vec.region().create_reader().read(at, 1)[0];
+    vec.region().write_at(&[byte ^ 0xFF], at)?;
+
+    for format in [WriterFormat::Csv, WriterFormat::Json, WriterFormat::NdJson] {
+        let mut out = Output::default();
+        let res = vec
+            .create_writer_with_format(Some(0), Some(LEN as i64), format)
+            .write_all(&mut out);
+        assert!(
+            matches!(
+                res,
+                Err(Error::WrongLength {
+                    received: 0,
+                    expected: LEN
+                })
+            ),
+            "{format:?}"
+        );
+        assert!(!out.bytes.ends_with(b"]"));
+    }
+
+    let mut buf = String::new();
+    assert!(matches!(
+        vec.create_writer(Some(0), None).write_next(&mut buf),
+        Err(Error::WrongLength {
+            received: 0,
+            expected: LEN
+        })
+    ));
+    Ok(())
+}
+
+// ============================================================================
+// Per-format modules
+// ============================================================================
+
+mod bytes {
+    use super::*;
+
+    #[test]
+    fn writer() -> Result<()> {
+        test_writer::<BytesVec<usize, u64>>(|vec, i, v| vec.update_at(i, v))
+    }
+}
+
+#[cfg(feature = "zerocopy")]
+mod zerocopy {
+    use super::*;
+
+    #[test]
+    fn writer() -> Result<()> {
+        test_writer::<vecdb::ZeroCopyVec<usize, u64>>(|vec, i, v| vec.update_at(i, v))
+    }
+}
+
+#[cfg(feature = "pco")]
+mod pco {
+    use super::*;
+
+    #[test]
+    fn writer() -> Result<()> {
+        test_writer::<vecdb::PcoVec<usize, u64>>(|vec, i, v| vec.update_at(i, v))
+    }
+}
+
+#[cfg(feature = "lz4")]
+mod lz4 {
+    use super::*;
+
+    #[test]
+    fn writer() -> Result<()> {
+        test_writer::<vecdb::LZ4Vec<usize, u64>>(|vec, i, v| vec.update_at(i, v))
+    }
+}
+
+#[cfg(feature = "zstd")]
+mod zstd {
+    use super::*;
+
+    #[test]
+    fn writer() -> Result<()> {
+        test_writer::<vecdb::ZstdVec<usize, u64>>(|vec, i, v| vec.update_at(i, v))
+    }
+}
@@ -0,0 +1,179 @@
+use vecdb::{DynType, Format, Stamp};
+
+use crate::{Error, Result};
+
+pub const USAGE: &str = "Usage: vecdb <DATABASE_DIR> <COMMAND> [ARGS]
+
+Commands:
+  ls                              Vecs with their format, value type, length and size,
+                                  then regions and change directories no vec claims
+  head <VEC> [COUNT]              First values, 10 by default
+  tail <VEC> [COUNT]              Last values, 10 by default
+  get <VEC> <INDEX>...            Values at indices, negative ones counting from the end
+  stats <VEC>                     Count, holes, min, max and sum of the values
+  verify [VEC]...                 Headers, pages, holes and values of every vec or the given ones
+  compact                         Reclaims the disk space of removed and shrunk regions
+  rollback <VEC> --to <STAMP>     Undoes the changes stamped after STAMP with change files
+  convert <VEC> --format <FORMAT> Rewrites a vec in another format: bytes, zerocopy, pco,
+                                  lz4, zstd or monotonic
+  rm <VEC>...                     Removes vecs with their regions and change files
+  rm --orphans                    Removes companion regions and change directories of
+                                  vecs that don't exist anymore
+
+VEC is a vec name, or name/index when several vecs share it. Commands reading values take
+--type <TYPE> for vecs whose value type isn't a known primitive: a primitive like u32 or
+f64, or bytes:N for values of N bytes. rollback and convert need a primitive value type and
+a usize index.";
+
+/// A parsed command line, without the database directory.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Ls,
+    Head {
+        vec: String,
+        count: usize,
+        value_type: Option<DynType>,
+    },
+    Tail {
+        vec: String,
+        count: usize,
+        value_type: Option<DynType>,
+    },
+    Get {
+        vec: String,
+        indexes: Vec<i64>,
+        value_type: Option<DynType>,
+    },
+    Stats {
+        vec: String,
+        value_type: Option<DynType>,
+    },
+    Verify {
+        vecs: Vec<String>,
+    },
+    Compact,
+    Rollback {
+        vec: String,
+        to: Stamp,
+    },
+    Convert {
+        vec: String,
+        format: Format,
+    },
+    Rm {
+        vecs: Vec<String>,
+    },
+    RmOrphans,
+}
+
+impl Command {
+    /// Parses the arguments following the database directory.
+    pub fn parse(args: &[String]) -> Result<Self> {
+        let (name, args) = args.split_first().ok_or_else(|| usage("missing command"))?;
+
+        let mut value_type = None;
+        let mut to = None;
+        let mut format = None;
+        let mut orphans = false;
+        let mut positional = vec![];
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| usage(&format!("missing value of {arg}")))
+            };
+            match arg.as_str() {
+                "--type" => value_type = Some(parse_type(value()?)?),
+                "--to" => to = Some(Stamp::new(parse(value()?, "stamp")?)),
+                "--format" => format = Some(value()?.parse::<Format>()?),
+                "--orphans" => orphans = true,
+                // Negative indices are values, not options
+                _ if arg.starts_with("--") => return Err(usage(&format!("unknown option {arg}"))),
+                _ => positional.push(arg.clone()),
+            }
+        }
+
+        let command = match (name.as_str(), &positional[..]) {
+            ("ls", []) => Self::Ls,
+            ("head", [vec, rest @ ..]) if rest.len() <= 1 => Self::Head {
+                vec: vec.clone(),
+                count: count(rest)?,
+                value_type: value_type.take(),
+            },
+            ("tail", [vec, rest @ ..]) if rest.len() <= 1 => Self::Tail {
+                vec: vec.clone(),
+                count: count(rest)?,
+                value_type: value_type.take(),
+            },
+            ("get", [vec, indexes @ ..]) if !indexes.is_empty() => Self::Get {
+                vec: vec.clone(),
+                indexes: indexes
+                    .iter()
+                    .map(|index| parse(index, "index"))
+                    .collect::<Result<_>>()?,
+                value_type: value_type.take(),
+            },
+            ("stats", [vec]) => Self::Stats {
+                vec: vec.clone(),
+                value_type: value_type.take(),
+            },
+            ("verify", vecs) => Self::Verify {
+                vecs: vecs.to_vec(),
+            },
+            ("compact", []) => Self::Compact,
+            ("rollback", [vec]) => Self::Rollback {
+                vec: vec.clone(),
+                to: to
+                    .take()
+                    .ok_or_else(|| usage("rollback needs --to <STAMP>"))?,
+            },
+            ("convert", [vec]) => Self::Convert {
+                vec: vec.clone(),
+                format: format
+                    .take()
+                    .ok_or_else(|| usage("convert needs --format <FORMAT>"))?,
+            },
+            ("rm", []) if orphans => {
+                orphans = false;
+                Self::RmOrphans
+            }
+            ("rm", vecs) if !vecs.is_empty() => Self::Rm {
+                vecs: vecs.to_vec(),
+            },
+            (
+                "ls" | "head" | "tail" | "get" | "stats" | "compact" | "rollback" | "convert"
+                | "rm",
+                _,
+            ) => {
+                return Err(usage(&format!("invalid arguments for {name}")));
+            }
+            _ => return Err(usage(&format!("unknown command {name}"))),
+        };
+
+        if value_type.is_some() || to.is_some() || format.is_some() || orphans {
+            return Err(usage(&format!("option not supported by {name}")));
+        }
+        Ok(command)
+    }
+}
+
+/// Parses a primitive name, or `bytes:N`.
+pub fn parse_type(s: &str) -> Result<DynType> {
+    if let Some(size) = s.strip_prefix("bytes:") {
+        return Ok(DynType::Bytes(parse(size, "size")?));
+    }
+    DynType::from_name(s).ok_or_else(|| usage(&format!("unknown type {s}")))
+}
+
+fn count(rest: &[String]) -> Result<usize> {
+    rest.first().map_or(Ok(10), |count| parse(count, "count"))
+}
+
+fn parse<T: std::str::FromStr>(s: &str, what: &str) -> Result<T> {
+    s.parse()
+        .map_err(|_| usage(&format!("invalid {what}: {s}")))
+}
+
+fn usage(message: &str) -> Error {
+    Error::Usage(message.to_string())
+}
@@ -0,0 +1,28 @@
+use std::{io, result};
+
+use thiserror::Error;
+
+pub type Result<T, E = Error> = result::Result<T, E>;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    IO(#[from] io::Error),
+    #[error(transparent)]
+    RawDB(#[from] rawdb::Error),
+    #[error(transparent)]
+    VecDB(#[from] vecdb::Error),
+
+    #[error("{0}")]
+    Usage(String),
+    #[error("No vec named {0:?}")]
+    VecNotFound(String),
+    #[error("{0:?} names several vecs, add the index: {1}")]
+    AmbiguousVec(String, String),
+    #[error("Index {index} is out of range for {len} values")]
+    IndexOutOfRange { index: i64, len: usize },
+    #[error("{0}")]
+    Unsupported(String),
+    #[error("Verification found {0} problems")]
+    VerificationFailed(usize),
+}
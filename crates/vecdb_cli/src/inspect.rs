@@ -0,0 +1,327 @@
+use std::io::Write;
+
+use rawdb::Database;
+use vecdb::{COMPANION_SUFFIXES, Catalog, DynType, DynValue, DynVec, Formattable, Header};
+
+use crate::{CHUNK_LEN, Error, Result, dyn_type, find, open};
+
+pub(crate) fn ls(db: &Database, out: &mut impl Write) -> Result<()> {
+    let catalog = Catalog::open(db)?;
+
+    let mut rows = vec![
+        [
+            "VEC", "FORMAT", "TYPE", "LEN", "STORED", "RATIO", "STAMP", "VERSION", "ROLLBACK",
+        ]
+        .map(String::from),
+    ];
+    for entry in catalog.entries() {
+        let value_type = match (entry.value_type_name, entry.value_size) {
+            (Some(name), _) => name.to_string(),
+            (None, Some(size)) => format!("bytes:{size}"),
+            (None, None) => "?".to_string(),
+        };
+        rows.push([
+            entry.region_name(),
+            entry.format.to_string(),
+            value_type,
+            entry.len.map_or("?".to_string(), |len| len.to_string()),
+            human_bytes(entry.stored_bytes),
+            entry
+                .compression_ratio()
+                .map_or("-".to_string(), |ratio| format!("{ratio:.2}")),
+            u64::from(entry.stamp).to_string(),
+            entry.vec_version.to_string(),
+            if entry.has_rollback_files {
+                "yes"
+            } else {
+                "no"
+            }
+            .to_string(),
+        ]);
+    }
+
+    let widths = (0..rows[0].len())
+        .map(|column| rows.iter().map(|row| row[column].len()).max().unwrap())
+        .collect::<Vec<_>>();
+    for row in &rows {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{cell:width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        writeln!(out, "{}", line.trim_end())?;
+    }
+
+    if !catalog.other_regions().is_empty() {
+        writeln!(out, "\nOther regions:")?;
+        for id in catalog.other_regions() {
+            writeln!(out, "  {id}")?;
+        }
+    }
+    if !catalog.orphaned_changes().is_empty() {
+        writeln!(out, "\nOrphaned change directories:")?;
+        for path in catalog.orphaned_changes() {
+            writeln!(out, "  {}", path.display())?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn head(
+    db: &Database,
+    vec: &str,
+    count: usize,
+    value_type: Option<DynType>,
+    out: &mut impl Write,
+) -> Result<()> {
+    let catalog = Catalog::open(db)?;
+    let vec = open(db, find(&catalog, vec)?, value_type)?;
+    write_values(&vec, 0, count.min(vec.len()), out)
+}
+
+pub(crate) fn tail(
+    db: &Database,
+    vec: &str,
+    count: usize,
+    value_type: Option<DynType>,
+    out: &mut impl Write,
+) -> Result<()> {
+    let catalog = Catalog::open(db)?;
+    let vec = open(db, find(&catalog, vec)?, value_type)?;
+    write_values(&vec, vec.len().saturating_sub(count), vec.len(), out)
+}
+
+pub(crate) fn get(
+    db: &Database,
+    vec: &str,
+    indexes: &[i64],
+    value_type: Option<DynType>,
+    out: &mut impl Write,
+) -> Result<()> {
+    let catalog = Catalog::open(db)?;
+    let vec = open(db, find(&catalog, vec)?, value_type)?;
+    let len = vec.len();
+    for &index in indexes {
+        let resolved = if index < 0 {
+            len.checked_sub(index.unsigned_abs() as usize)
+        } else {
+            Some(index as usize).filter(|&index| index < len)
+        };
+        let index = resolved.ok_or(Error::IndexOutOfRange { index, len })?;
+        write_values(&vec, index, index + 1, out)?;
+    }
+    Ok(())
+}
+
+/// Writes `index\tvalue` lines for `from..to`, with an empty value for holes.
+fn write_values(vec: &DynVec, from: usize, to: usize, out: &mut impl Write) -> Result<()> {
+    let holes = vec.holes()?;
+    let mut buf = vec![];
+    for chunk_from in (from..to).step_by(CHUNK_LEN) {
+        let values = vec.read_range(chunk_from, (chunk_from + CHUNK_LEN).min(to))?;
+        buf.clear();
+        for (index, value) in (chunk_from..).zip(&values) {
+            index.write_to(&mut buf);
+            buf.push(b'\t');
+            if !holes.contains(&index) {
+                value.write_to(&mut buf);
+            }
+            buf.push(b'\n');
+        }
+        out.write_all(&buf)?;
+    }
+    Ok(())
+}
+
+pub(crate) fn stats(
+    db: &Database,
+    vec: &str,
+    value_type: Option<DynType>,
+    out: &mut impl Write,
+) -> Result<()> {
+    let catalog = Catalog::open(db)?;
+    let vec = open(db, find(&catalog, vec)?, value_type)?;
+    if let DynType::Bytes(_) = vec.value_type() {
+        return Err(Error::Unsupported(format!(
+            "Stats of {}/{} need a numeric value type, pass --type",
+            vec.name(),
+            vec.index()
+        )));
+    }
+
+    let holes = vec.holes()?;
+    let mut stats = Stats::new(vec.value_type());
+    for from in (0..vec.len()).step_by(CHUNK_LEN) {
+        let values = vec.read_range(from, from + CHUNK_LEN)?;
+        (from..)
+            .zip(values)
+            .filter(|(index, _)| !holes.contains(index))
+            .for_each(|(_, value)| stats.add(value));
+    }
+
+    writeln!(out, "count\t{}", vec.len() - holes.len())?;
+    writeln!(out, "holes\t{}", holes.len())?;
+    match stats {
+        Stats::Integers { min, max, sum } => {
+            writeln!(out, "min\t{}", fmt_opt(min))?;
+            writeln!(out, "max\t{}", fmt_opt(max))?;
+            writeln!(out, "sum\t{sum}")?;
+        }
+        Stats::Floats { min, max, sum, nan } => {
+            writeln!(out, "nan\t{nan}")?;
+            writeln!(out, "min\t{}", fmt_opt(min))?;
+            writeln!(out, "max\t{}", fmt_opt(max))?;
+            writeln!(out, "sum\t{sum}")?;
+        }
+    }
+    Ok(())
+}
+
+/// Min, max and sum of numeric values, integers summed without overflow and floats skipping
+/// NaN.
+enum Stats {
+    Integers {
+        min: Option<i128>,
+        max: Option<i128>,
+        sum: i128,
+    },
+    Floats {
+        min: Option<f64>,
+        max: Option<f64>,
+        sum: f64,
+        nan: usize,
+    },
+}
+
+impl Stats {
+    fn new(value_type: DynType) -> Self {
+        match value_type {
+            DynType::Float(_) => Self::Floats {
+                min: None,
+                max: None,
+                sum: 0.0,
+                nan: 0,
+            },
+            _ => Self::Integers {
+                min: None,
+                max: None,
+                sum: 0,
+            },
+        }
+    }
+
+    fn add(&mut self, value: DynValue) {
+        let value = match value {
+            DynValue::Unsigned(v) => v as i128,
+            DynValue::Signed(v) => v as i128,
+            DynValue::Bool(v) => v as i128,
+            DynValue::F32(v) => return self.add_float(v as f64),
+            DynValue::F64(v) => return self.add_float(v),
+            DynValue::Bytes(_) => unreachable!("bytes are rejected before reading"),
+        };
+        if let Self::Integers { min, max, sum } = self {
+            *min = Some(min.map_or(value, |min| min.min(value)));
+            *max = Some(max.map_or(value, |max| max.max(value)));
+            *sum += value;
+        }
+    }
+
+    fn add_float(&mut self, value: f64) {
+        if let Self::Floats { min, max, sum, nan } = self {
+            if value.is_nan() {
+                *nan += 1;
+                return;
+            }
+            *min = Some(min.map_or(value, |min| min.min(value)));
+            *max = Some(max.map_or(value, |max| max.max(value)));
+            *sum += value;
+        }
+    }
+}
+
+pub(crate) fn verify(db: &Database, vecs: &[String], out: &mut impl Write) -> Result<()> {
+    let catalog = Catalog::open(db)?;
+    let entries = if vecs.is_empty() {
+        catalog.entries().iter().collect()
+    } else {
+        vecs.iter()
+            .map(|vec| find(&catalog, vec))
+            .collect::<Result<Vec<_>>>()?
+    };
+
+    let mut problems = 0;
+    for entry in entries {
+        let name = entry.region_name();
+        let Some(value_type) = dyn_type(entry, None) else {
+            writeln!(out, "{name}: skipped, unknown value size")?;
+            continue;
+        };
+        let verified = DynVec::open(db, entry, value_type).and_then(|vec| {
+            vec.verify()?;
+            for from in (0..vec.len()).step_by(CHUNK_LEN) {
+                vec.read_bytes_range(from, from + CHUNK_LEN)?;
+            }
+            Ok(vec.len())
+        });
+        match verified {
+            Ok(len) => writeln!(out, "{name}: ok, {len} values")?,
+            Err(e) => {
+                problems += 1;
+                writeln!(out, "{name}: {e}")?;
+            }
+        }
+    }
+
+    if vecs.is_empty() {
+        for id in catalog.other_regions() {
+            if COMPANION_SUFFIXES.iter().any(|suffix| id.ends_with(suffix)) {
+                writeln!(out, "{id}: warning, orphaned companion region")?;
+                continue;
+            }
+            let Some(region) = db.get_region(id) else {
+                continue;
+            };
+            match Header::read(&region) {
+                Ok(_)
+                | Err(vecdb::Error::NotAVec { .. })
+                | Err(vecdb::Error::WrongLength { .. }) => {}
+                Err(e) => {
+                    problems += 1;
+                    writeln!(out, "{id}: {e}")?;
+                }
+            }
+        }
+        for path in catalog.orphaned_changes() {
+            writeln!(
+                out,
+                "{}: warning, orphaned change directory",
+                path.display()
+            )?;
+        }
+    }
+
+    if problems > 0 {
+        return Err(Error::VerificationFailed(problems));
+    }
+    Ok(())
+}
+
+/// Bytes in B, KiB, MiB or GiB with one decimal, like rawdb's disk usage.
+pub(crate) fn human_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 3] = ["KiB", "MiB", "GiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+fn fmt_opt<T: ToString>(value: Option<T>) -> String {
+    value.map_or("-".to_string(), |value| value.to_string())
+}
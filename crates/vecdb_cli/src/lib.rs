@@ -0,0 +1,101 @@
+//! Inspection and maintenance of vecdb databases from the command line.
+//!
+//! The `vecdb` binary runs a [`Command`] on a database directory:
+//!
+//! - `ls`: vecs with their format, value type, length, stored size and stamp, then regions and
+//!   change directories that no vec claims
+//! - `head`, `tail` and `get`: values by index, holes printed empty
+//! - `stats`: count, holes, min, max and sum
+//! - `verify`: headers, pages, checksums, dictionaries and holes, then every value decoded
+//! - `compact`: flushes and reclaims the disk space of removed and shrunk regions
+//! - `rollback --to`: undoes the changes stamped after a stamp, from the vec's change files
+//! - `convert --format`: rewrites a vec in another format with [`vecdb::convert_format_with`]
+//! - `rm`: removes vecs, or with `--orphans` the regions and change directories left behind
+//!
+//! Vecs are found with a [`vecdb::Catalog`] and read with [`vecdb::DynVec`], so reading
+//! commands work on any vec whose value size is known. Commands writing vecs go through the
+//! typed vecs and need a primitive value type and a `usize` index.
+
+use std::io::Write;
+
+use rawdb::Database;
+use vecdb::{Catalog, CatalogEntry, DynType, DynVec};
+
+mod args;
+mod error;
+mod inspect;
+mod maintain;
+mod typed;
+
+pub use args::*;
+pub use error::*;
+
+/// Values are read and decoded in chunks of this many.
+const CHUNK_LEN: usize = 1 << 16;
+
+/// Runs `command` on `db`, writing its output to `out`.
+pub fn run(db: &Database, command: &Command, out: &mut impl Write) -> Result<()> {
+    match command {
+        Command::Ls => inspect::ls(db, out),
+        Command::Head {
+            vec,
+            count,
+            value_type,
+        } => inspect::head(db, vec, *count, *value_type, out),
+        Command::Tail {
+            vec,
+            count,
+            value_type,
+        } => inspect::tail(db, vec, *count, *value_type, out),
+        Command::Get {
+            vec,
+            indexes,
+            value_type,
+        } => inspect::get(db, vec, indexes, *value_type, out),
+        Command::Stats { vec, value_type } => inspect::stats(db, vec, *value_type, out),
+        Command::Verify { vecs } => inspect::verify(db, vecs, out),
+        Command::Compact => maintain::compact(db, out),
+        Command::Rollback { vec, to } => maintain::rollback(db, vec, *to, out),
+        Command::Convert { vec, format } => maintain::convert(db, vec, *format, out),
+        Command::Rm { vecs } => maintain::rm(db, vecs, out),
+        Command::RmOrphans => maintain::rm_orphans(db, out),
+    }
+}
+
+/// The vec `vec` names: a vec name when a single vec has it, or `name/index`.
+fn find<'a>(catalog: &'a Catalog, vec: &str) -> Result<&'a CatalogEntry> {
+    let entries = catalog.find(vec).collect::<Vec<_>>();
+    match entries[..] {
+        [entry] => Ok(entry),
+        [] => vec
+            .rsplit_once('/')
+            .and_then(|(name, index)| catalog.get(name, index))
+            .ok_or_else(|| Error::VecNotFound(vec.to_string())),
+        _ => Err(Error::AmbiguousVec(
+            vec.to_string(),
+            entries
+                .iter()
+                .map(|entry| entry.region_name())
+                .collect::<Vec<_>>()
+                .join(", "),
+        )),
+    }
+}
+
+/// The type to read `entry` with: `value_type` if given, else its primitive type or bytes of
+/// its value size.
+fn dyn_type(entry: &CatalogEntry, value_type: Option<DynType>) -> Option<DynType> {
+    value_type
+        .or_else(|| entry.dyn_type())
+        .or_else(|| entry.value_size.map(DynType::Bytes))
+}
+
+fn open(db: &Database, entry: &CatalogEntry, value_type: Option<DynType>) -> Result<DynVec> {
+    let value_type = dyn_type(entry, value_type).ok_or_else(|| {
+        Error::Unsupported(format!(
+            "Value size of {} is unknown, pass --type",
+            entry.region_name()
+        ))
+    })?;
+    Ok(DynVec::open(db, entry, value_type)?)
+}
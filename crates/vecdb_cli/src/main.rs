@@ -0,0 +1,44 @@
+use std::{
+    env,
+    io::{self, Write},
+    path::Path,
+    process::ExitCode,
+};
+
+use rawdb::Database;
+use vecdb_cli::{Command, Error, USAGE, run};
+
+fn main() -> ExitCode {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let Some((path, args)) = args.split_first() else {
+        eprintln!("{USAGE}");
+        return ExitCode::FAILURE;
+    };
+
+    let result = Command::parse(args).and_then(|command| {
+        let path = Path::new(path);
+        if !path.is_dir() {
+            return Err(Error::Usage(format!(
+                "{} isn't a directory",
+                path.display()
+            )));
+        }
+        let db = Database::open(path)?;
+        let mut out = io::stdout().lock();
+        run(&db, &command, &mut out)?;
+        out.flush()?;
+        Ok(())
+    });
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e @ Error::Usage(_)) => {
+            eprintln!("vecdb: {e}\n\n{USAGE}");
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            eprintln!("vecdb: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use std::{fs, io::Write, marker::PhantomData};
+
+use rawdb::Database;
+use vecdb::{
+    COMPANION_SUFFIXES, Catalog, CatalogEntry, Format, ImportOptions, Stamp, StoredVec, VecValue,
+    Version, convert_format_with,
+};
+
+use crate::{
+    Error, Result, find,
+    inspect::human_bytes,
+    typed::{FormatAction, Primitive, TypedAction, run_typed},
+};
+
+pub(crate) fn compact(db: &Database, out: &mut impl Write) -> Result<()> {
+    let before = db.disk_usage()?;
+    db.compact()?;
+    writeln!(out, "Disk usage: {before} -> {}", db.disk_usage()?)?;
+    Ok(())
+}
+
+pub(crate) fn rollback(db: &Database, vec: &str, to: Stamp, out: &mut impl Write) -> Result<()> {
+    let catalog = Catalog::open(db)?;
+    let entry = find(&catalog, vec)?;
+    let (value_type, version) = typed_vec(entry)?;
+
+    let stamp = run_typed(
+        value_type,
+        Rollback {
+            db,
+            name: &entry.name,
+            version,
+            forced_layout: entry.forced_layout,
+            format: entry.format,
+            to,
+        },
+    )?;
+    db.flush()?;
+
+    writeln!(
+        out,
+        "{}: rolled back from stamp {} to {}",
+        entry.region_name(),
+        u64::from(entry.stamp),
+        u64::from(stamp)
+    )?;
+    Ok(())
+}
+
+/// Rolls a vec back to the last stamp at or before `to`, then writes it. Nothing is written
+/// if the change files don't go back that far.
+struct Rollback<'a> {
+    db: &'a Database,
+    name: &'a str,
+    version: Version,
+    forced_layout: bool,
+    format: Format,
+    to: Stamp,
+}
+
+impl TypedAction for Rollback<'_> {
+    type Output = Stamp;
+
+    fn run<T: Primitive>(self) -> Result<Stamp> {
+        T::with_format(self.format, self)
+    }
+}
+
+impl<T: VecValue> FormatAction<T> for Rollback<'_> {
+    type Output = Stamp;
+
+    fn run<V: StoredVec<I = usize, T = T>>(self) -> Result<Stamp> {
+        let options = ImportOptions::new(self.db, self.name, self.version);
+        let mut vec = if self.forced_layout {
+            V::import_forced_layout_with(options)?
+        } else {
+            V::import_with(options)?
+        };
+        let stamp = vec.rollback_before(Stamp::new(u64::from(self.to) + 1))?;
+        if stamp > self.to {
+            return Err(Error::Unsupported(format!(
+                "Change files of {} only go back to stamp {}",
+                self.name,
+                u64::from(stamp)
+            )));
+        }
+        vec.write()?;
+        Ok(stamp)
+    }
+}
+
+pub(crate) fn convert(
+    db: &Database,
+    vec: &str,
+    format: Format,
+    out: &mut impl Write,
+) -> Result<()> {
+    let catalog = Catalog::open(db)?;
+    let entry = find(&catalog, vec)?;
+    let region_name = entry.region_name();
+    if entry.format == format {
+        writeln!(out, "{region_name}: already {format}")?;
+        return Ok(());
+    }
+    let (value_type, version) = typed_vec(entry)?;
+
+    run_typed(
+        value_type,
+        Convert {
+            db,
+            name: &entry.name,
+            version,
+            from: entry.format,
+            to: format,
+        },
+    )?;
+    db.flush()?;
+
+    let stored_bytes = Catalog::open(db)?
+        .get(&entry.name, &entry.index)
+        .map_or(0, |converted| converted.stored_bytes);
+    writeln!(
+        out,
+        "{region_name}: converted from {} to {format}, {} -> {}",
+        entry.format,
+        human_bytes(entry.stored_bytes),
+        human_bytes(stored_bytes)
+    )?;
+    Ok(())
+}
+
+/// Converts a vec from format `from` to format `to` with [`convert_format_with`].
+struct Convert<'a> {
+    db: &'a Database,
+    name: &'a str,
+    version: Version,
+    from: Format,
+    to: Format,
+}
+
+impl TypedAction for Convert<'_> {
+    type Output = ();
+
+    fn run<T: Primitive>(self) -> Result<()> {
+        T::with_format(self.from, self)
+    }
+}
+
+impl<T: Primitive> FormatAction<T> for Convert<'_> {
+    type Output = ();
+
+    fn run<Src: StoredVec<I = usize, T = T>>(self) -> Result<()> {
+        T::with_format(
+            self.to,
+            ConvertTo::<Src> {
+                convert: self,
+                _src: PhantomData,
+            },
+        )
+    }
+}
+
+/// [`Convert`] once the source vec type is known.
+struct ConvertTo<'a, Src> {
+    convert: Convert<'a>,
+    _src: PhantomData<Src>,
+}
+
+impl<T: VecValue, Src: StoredVec<I = usize, T = T>> FormatAction<T> for ConvertTo<'_, Src> {
+    type Output = ();
+
+    fn run<Dst: StoredVec<I = usize, T = T>>(self) -> Result<()> {
+        let Convert {
+            db, name, version, ..
+        } = self.convert;
+        convert_format_with::<Src, Dst>(ImportOptions::new(db, name, version))?;
+        Ok(())
+    }
+}
+
+/// Primitive value type and import version of a vec, for commands going through typed vecs.
+fn typed_vec(entry: &CatalogEntry) -> Result<(&'static str, Version)> {
+    let region_name = entry.region_name();
+    if entry.index != "usize" {
+        return Err(Error::Unsupported(format!(
+            "{region_name} is indexed by {}, only usize indexes are supported",
+            entry.index
+        )));
+    }
+    let value_type = entry.value_type_name.ok_or_else(|| {
+        Error::Unsupported(format!(
+            "Value type of {region_name} isn't a primitive, only primitives are supported"
+        ))
+    })?;
+    let version = entry.import_version().ok_or(vecdb::Error::Underflow)?;
+    Ok((value_type, version))
+}
+
+pub(crate) fn rm(db: &Database, vecs: &[String], out: &mut impl Write) -> Result<()> {
+    let catalog = Catalog::open(db)?;
+    // All found before anything is removed
+    let entries = vecs
+        .iter()
+        .map(|vec| find(&catalog, vec))
+        .collect::<Result<Vec<_>>>()?;
+
+    for entry in entries {
+        for id in &entry.regions {
+            db.remove_region_if_exists(id)?;
+        }
+        let changes = db
+            .path()
+            .join("changes")
+            .join(&entry.name)
+            .join(&entry.index);
+        if changes.is_dir() {
+            fs::remove_dir_all(changes)?;
+        }
+        writeln!(out, "Removed {}", entry.region_name())?;
+    }
+    db.flush()?;
+    Ok(())
+}
+
+pub(crate) fn rm_orphans(db: &Database, out: &mut impl Write) -> Result<()> {
+    let catalog = Catalog::open(db)?;
+    for id in catalog.other_regions() {
+        if COMPANION_SUFFIXES.iter().any(|suffix| id.ends_with(suffix)) {
+            db.remove_region(id)?;
+            writeln!(out, "Removed {id}")?;
+        }
+    }
+    for path in catalog.orphaned_changes() {
+        fs::remove_dir_all(path)?;
+        writeln!(out, "Removed {}", path.display())?;
+    }
+    db.flush()?;
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use vecdb::{
+    BytesVec, Format, LZ4Vec, MonotonicVec, PcoVec, StoredVec, VecValue, ZeroCopyVec, ZstdVec,
+};
+
+use crate::{Error, Result};
+
+/// Work done with the primitive value type of a vec, for commands that go through typed vecs.
+pub(crate) trait TypedAction {
+    type Output;
+
+    fn run<T: Primitive>(self) -> Result<Self::Output>;
+}
+
+/// Work done with a typed vec of `T` values indexed by `usize`.
+pub(crate) trait FormatAction<T: VecValue> {
+    type Output;
+
+    fn run<V: StoredVec<I = usize, T = T>>(self) -> Result<Self::Output>;
+}
+
+/// A primitive value type and the formats that can store it.
+pub(crate) trait Primitive: VecValue {
+    fn with_format<A: FormatAction<Self>>(format: Format, action: A) -> Result<A::Output>;
+}
+
+/// Runs `action` with the primitive named `value_type`.
+pub(crate) fn run_typed<A: TypedAction>(value_type: &str, action: A) -> Result<A::Output> {
+    match value_type {
+        "u8" => action.run::<u8>(),
+        "u16" => action.run::<u16>(),
+        "u32" => action.run::<u32>(),
+        "u64" => action.run::<u64>(),
+        "usize" => action.run::<usize>(),
+        "i8" => action.run::<i8>(),
+        "i16" => action.run::<i16>(),
+        "i32" => action.run::<i32>(),
+        "i64" => action.run::<i64>(),
+        "f32" => action.run::<f32>(),
+        "f64" => action.run::<f64>(),
+        "bool" => action.run::<bool>(),
+        _ => Err(Error::Unsupported(format!(
+            "{value_type} isn't a primitive value type"
+        ))),
+    }
+}
+
+macro_rules! impl_primitive {
+    ($($t:ty => [$($format:ident => $vec:ident),*];)*) => {
+        $(
+            impl Primitive for $t {
+                fn with_format<A: FormatAction<Self>>(
+                    format: Format,
+                    action: A,
+                ) -> Result<A::Output> {
+                    match format {
+                        $(Format::$format => action.run::<$vec<usize, $t>>(),)*
+                        #[allow(unreachable_patterns)]
+                        _ => Err(Error::Unsupported(format!(
+                            "{format} vecs can't hold {} values",
+                            stringify!($t)
+                        ))),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive! {
+    u8 => [Bytes => BytesVec, ZeroCopy => ZeroCopyVec, Pco => PcoVec, LZ4 => LZ4Vec, Zstd => ZstdVec, Monotonic => MonotonicVec];
+    u16 => [Bytes => BytesVec, ZeroCopy => ZeroCopyVec, Pco => PcoVec, LZ4 => LZ4Vec, Zstd => ZstdVec, Monotonic => MonotonicVec];
+    u32 => [Bytes => BytesVec, ZeroCopy => ZeroCopyVec, Pco => PcoVec, LZ4 => LZ4Vec, Zstd => ZstdVec, Monotonic => MonotonicVec];
+    u64 => [Bytes => BytesVec, ZeroCopy => ZeroCopyVec, Pco => PcoVec, LZ4 => LZ4Vec, Zstd => ZstdVec, Monotonic => MonotonicVec];
+    usize => [Bytes => BytesVec, ZeroCopy => ZeroCopyVec, LZ4 => LZ4Vec, Zstd => ZstdVec, Monotonic => MonotonicVec];
+    i8 => [Bytes => BytesVec, ZeroCopy => ZeroCopyVec, Pco => PcoVec, LZ4 => LZ4Vec, Zstd => ZstdVec];
+    i16 => [Bytes => BytesVec, ZeroCopy => ZeroCopyVec, Pco => PcoVec, LZ4 => LZ4Vec, Zstd => ZstdVec];
+    i32 => [Bytes => BytesVec, ZeroCopy => ZeroCopyVec, Pco => PcoVec, LZ4 => LZ4Vec, Zstd => ZstdVec];
+    i64 => [Bytes => BytesVec, ZeroCopy => ZeroCopyVec, Pco => PcoVec, LZ4 => LZ4Vec, Zstd => ZstdVec];
+    f32 => [Bytes => BytesVec, ZeroCopy => ZeroCopyVec, Pco => PcoVec, LZ4 => LZ4Vec, Zstd => ZstdVec];
+    f64 => [Bytes => BytesVec, ZeroCopy => ZeroCopyVec, Pco => PcoVec, LZ4 => LZ4Vec, Zstd => ZstdVec];
+    bool => [Bytes => BytesVec, LZ4 => LZ4Vec, Zstd => ZstdVec];
+}
@@ -0,0 +1,310 @@
+//! Tests for the command-line tool.
+//!
+//! These tests cover:
+//! - Parsing commands and their options
+//! - Listing vecs, regions and change directories no vec claims
+//! - Values by index, with holes and negative indices, and stats
+//! - Verifying vecs, with a damaged page
+//! - Rolling back, converting and removing vecs, then reading them back as typed vecs
+
+use std::fs;
+
+use rawdb::Database;
+use tempfile::TempDir;
+use vecdb::{
+    AnyStoredVec, BytesVec, Catalog, DynType, Format, HEADER_OFFSET, ImportOptions, ImportableVec,
+    PcoVec, ReadableVec, Stamp, Version, WritableVec, ZeroCopyVec,
+};
+use vecdb_cli::{Command, Error, Result, run};
+
+fn setup_db() -> Result<(Database, TempDir)> {
+    let temp = TempDir::new()?;
+    let db = Database::open(temp.path())?;
+    Ok((db, temp))
+}
+
+fn exec(db: &Database, args: &[&str]) -> Result<String> {
+    let args = args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+    let mut out = vec![];
+    run(db, &Command::parse(&args)?, &mut out)?;
+    Ok(String::from_utf8(out).unwrap())
+}
+
+#[test]
+fn parse() -> Result<()> {
+    let parse =
+        |args: &[&str]| Command::parse(&args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>());
+
+    assert_eq!(
+        parse(&["get", "price", "0", "-1", "--type", "bytes:4"])?,
+        Command::Get {
+            vec: "price".to_string(),
+            indexes: vec![0, -1],
+            value_type: Some(DynType::Bytes(4)),
+        }
+    );
+    assert_eq!(
+        parse(&["head", "price"])?,
+        Command::Head {
+            vec: "price".to_string(),
+            count: 10,
+            value_type: None,
+        }
+    );
+    assert_eq!(
+        parse(&["convert", "price", "--format", "PCO"])?,
+        Command::Convert {
+            vec: "price".to_string(),
+            format: Format::Pco,
+        }
+    );
+    assert_eq!(parse(&["rm", "--orphans"])?, Command::RmOrphans);
+
+    for args in [
+        &[][..],
+        &["dump"],
+        &["rollback", "price"],
+        &["ls", "--type", "u8"],
+        &["head", "price", "ten"],
+        &["get", "price"],
+        &["stats", "price", "--type", "u128"],
+    ] {
+        assert!(matches!(parse(args), Err(Error::Usage(_))), "{args:?}");
+    }
+    assert!(matches!(
+        parse(&["convert", "price", "--format", "csv"]),
+        Err(Error::VecDB(vecdb::Error::InvalidArgument(_)))
+    ));
+    Ok(())
+}
+
+#[test]
+fn ls() -> Result<()> {
+    let (db, temp) = setup_db()?;
+    let mut price: PcoVec<usize, f64> = PcoVec::import(&db, "price", Version::TWO)?;
+    (0..10_000).for_each(|v| price.push(v as f64));
+    price.stamped_write(Stamp::new(7))?;
+    let mut pair: BytesVec<usize, (u8, u8)> = BytesVec::import(&db, "pair", Version::ONE)?;
+    pair.push((1, 2));
+    pair.write()?;
+
+    db.create_region_if_needed("removed/usize_holes")?
+        .write_at(&[0; 8], 0)?;
+    let orphaned = temp.path().join("changes").join("removed").join("usize");
+    fs::create_dir_all(&orphaned)?;
+    fs::write(orphaned.join("3"), [])?;
+
+    let out = exec(&db, &["ls"])?;
+    let lines = out.lines().collect::<Vec<_>>();
+    assert!(lines[0].starts_with("VEC          FORMAT  TYPE  LEN    STORED"));
+    let pair = lines[1].split_whitespace().collect::<Vec<_>>();
+    assert_eq!(pair[..4], ["pair/usize", "bytes", "?", "?"]);
+    let price = lines[2].split_whitespace().collect::<Vec<_>>();
+    assert_eq!(price[..4], ["price/usize", "pco", "f64", "10000"]);
+    assert_eq!(price[price.len() - 3..], ["7", "5", "no"]);
+    assert_eq!(lines[4..6], ["Other regions:", "  removed/usize_holes"]);
+    assert_eq!(lines[7], "Orphaned change directories:");
+    assert!(lines[8].ends_with("usize"));
+    Ok(())
+}
+
+#[test]
+fn values() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let mut delta: BytesVec<usize, i32> = BytesVec::import(&db, "delta", Version::ONE)?;
+    (0..20).for_each(|v| delta.push(v - 10));
+    delta.write()?;
+    delta.delete(2);
+    delta.write()?;
+
+    assert_eq!(exec(&db, &["head", "delta", "3"])?, "0\t-10\n1\t-9\n2\t\n");
+    assert_eq!(exec(&db, &["tail", "delta", "2"])?, "18\t8\n19\t9\n");
+    assert_eq!(exec(&db, &["get", "delta", "-1", "5"])?, "19\t9\n5\t-5\n");
+    assert_eq!(
+        exec(&db, &["get", "delta", "0", "--type", "bytes:4"])?,
+        "0\tf6ffffff\n"
+    );
+    assert!(matches!(
+        exec(&db, &["get", "delta", "-21"]),
+        Err(Error::IndexOutOfRange {
+            index: -21,
+            len: 20
+        })
+    ));
+    assert!(matches!(
+        exec(&db, &["head", "missing"]),
+        Err(Error::VecNotFound(_))
+    ));
+
+    // Holes are left out
+    assert_eq!(
+        exec(&db, &["stats", "delta"])?,
+        "count\t19\nholes\t1\nmin\t-10\nmax\t9\nsum\t-2\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn float_stats_and_ambiguous_names() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let mut ratio: PcoVec<usize, f32> = PcoVec::import(&db, "ratio", Version::ONE)?;
+    [0.5, f32::NAN, -1.5, 4.0]
+        .into_iter()
+        .for_each(|v| ratio.push(v));
+    ratio.write()?;
+    drop(ratio);
+    // The same vec under another index type, whose value type isn't known then
+    for suffix in ["", "_pages"] {
+        let bytes = db
+            .get_region(&format!("ratio/usize{suffix}"))
+            .unwrap()
+            .create_reader()
+            .read_all()
+            .to_vec();
+        db.create_region_if_needed(&format!("ratio/u32{suffix}"))?
+            .write_at(&bytes, 0)?;
+    }
+
+    assert!(matches!(
+        exec(&db, &["stats", "ratio"]),
+        Err(Error::AmbiguousVec(..))
+    ));
+    assert_eq!(
+        exec(&db, &["stats", "ratio/usize"])?,
+        "count\t4\nholes\t0\nnan\t1\nmin\t-1.5\nmax\t4\nsum\t3\n"
+    );
+    assert_eq!(exec(&db, &["head", "ratio/u32", "1"])?, "0\t0000003f\n");
+    assert!(matches!(
+        exec(&db, &["stats", "ratio/u32"]),
+        Err(Error::Unsupported(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn verify() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let options = ImportOptions::new(&db, "height", Version::ONE).with_page_checksums(true);
+    let mut height: PcoVec<usize, u32> = PcoVec::import_with(options)?;
+    (0..10_000).for_each(|v| height.push(v));
+    height.write()?;
+    let mut flag: BytesVec<usize, bool> = BytesVec::import(&db, "flag", Version::ONE)?;
+    flag.push(true);
+    flag.write()?;
+    drop(height);
+
+    assert_eq!(
+        exec(&db, &["verify"])?,
+        "flag/usize: ok, 1 values\nheight/usize: ok, 10000 values\n"
+    );
+
+    db.get_region("height/usize")
+        .unwrap()
+        .write_at(&[0xFF; 4], HEADER_OFFSET + 8)?;
+    let mut out = vec![];
+    let command = Command::Verify {
+        vecs: vec!["height".to_string()],
+    };
+    assert!(matches!(
+        run(&db, &command, &mut out),
+        Err(Error::VerificationFailed(1))
+    ));
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "height/usize: Checksum mismatch in height/usize_pages at page 0\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn rollback() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let options = ImportOptions::new(&db, "height", Version::TWO).with_saved_stamped_changes(2);
+    let mut height: BytesVec<usize, u32> = BytesVec::import_with(options)?;
+    for stamp in 1..=4 {
+        height.push(stamp);
+        height.stamped_write_with_changes(Stamp::new(stamp as u64))?;
+    }
+    drop(height);
+
+    assert!(matches!(
+        exec(&db, &["rollback", "height", "--to", "1"]),
+        Err(Error::Unsupported(_))
+    ));
+    assert_eq!(
+        exec(&db, &["rollback", "height", "--to", "2"])?,
+        "height/usize: rolled back from stamp 4 to 2\n"
+    );
+
+    let height: BytesVec<usize, u32> = BytesVec::import(&db, "height", Version::TWO)?;
+    assert_eq!(height.stamp(), Stamp::new(2));
+    assert_eq!(height.collect(), [1, 2]);
+    Ok(())
+}
+
+#[test]
+fn convert() -> Result<()> {
+    let (db, _temp) = setup_db()?;
+    let mut price: BytesVec<usize, u64> = BytesVec::import(&db, "price", Version::TWO)?;
+    (0..10_000).for_each(|v| price.push(v * 10));
+    price.stamped_write(Stamp::new(3))?;
+    drop(price);
+
+    let out = exec(&db, &["convert", "price", "--format", "pco"])?;
+    assert!(out.starts_with("price/usize: converted from bytes to pco, 78.2 KiB -> "));
+
+    let price: PcoVec<usize, u64> = PcoVec::import(&db, "price", Version::TWO)?;
+    assert_eq!(price.stamp(), Stamp::new(3));
+    assert_eq!(price.collect().len(), 10_000);
+    assert_eq!(price.collect_one(9_999), Some(99_990));
+    assert_eq!(
+        exec(&db, &["convert", "price", "--format", "pco"])?,
+        "price/usize: already pco\n"
+    );
+    drop(price);
+
+    exec(&db, &["convert", "price", "--format", "zerocopy"])?;
+    let price: ZeroCopyVec<usize, u64> = ZeroCopyVec::import(&db, "price", Version::TWO)?;
+    assert_eq!(price.collect_one(10), Some(100));
+    assert!(matches!(
+        exec(&db, &["convert", "missing", "--format", "pco"]),
+        Err(Error::VecNotFound(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn rm() -> Result<()> {
+    let (db, temp) = setup_db()?;
+    let options = ImportOptions::new(&db, "height", Version::ONE).with_saved_stamped_changes(2);
+    let mut height: BytesVec<usize, u32> = BytesVec::import_with(options)?;
+    height.push(1);
+    height.stamped_write_with_changes(Stamp::new(1))?;
+    height.delete(0);
+    height.stamped_write_with_changes(Stamp::new(2))?;
+    let mut price: BytesVec<usize, u64> = BytesVec::import(&db, "price", Version::ONE)?;
+    price.push(1);
+    price.write()?;
+    drop((height, price));
+
+    assert_eq!(exec(&db, &["rm", "height"])?, "Removed height/usize\n");
+    let catalog = Catalog::open(&db)?;
+    assert_eq!(catalog.entries().len(), 1);
+    assert!(catalog.other_regions().is_empty());
+    assert!(!temp.path().join("changes/height/usize").exists());
+
+    db.create_region_if_needed("removed/usize_pages")?
+        .write_at(&[0; 16], 0)?;
+    db.create_region_if_needed("custom")?
+        .write_at(b"not a vec", 0)?;
+    let orphaned = temp.path().join("changes").join("removed").join("usize");
+    fs::create_dir_all(&orphaned)?;
+    fs::write(orphaned.join("3"), [])?;
+
+    let out = exec(&db, &["rm", "--orphans"])?;
+    assert!(out.starts_with("Removed removed/usize_pages\nRemoved "));
+    let catalog = Catalog::open(&db)?;
+    assert_eq!(catalog.other_regions(), ["custom"]);
+    assert!(catalog.orphaned_changes().is_empty());
+    Ok(())
+}
@@ -0,0 +1,193 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Attribute, DataEnum, DeriveInput, Fields, Ident, Index};
+
+/// Integer types accepted as the `repr` of a fieldless enum.
+const ENUM_REPRS: [&str; 3] = ["u8", "u16", "u32"];
+
+/// Idents listed in the `#[repr(...)]` attributes of the item.
+pub fn reprs(attrs: &[Attribute]) -> syn::Result<Vec<Ident>> {
+    let mut reprs = vec![];
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("repr")) {
+        attr.parse_nested_meta(|meta| {
+            if let Some(ident) = meta.path.get_ident() {
+                reprs.push(ident.clone());
+            }
+            // Skip arguments like `align(8)`
+            if meta.input.peek(syn::token::Paren) {
+                let _content;
+                syn::parenthesized!(_content in meta.input);
+            }
+            Ok(())
+        })?;
+    }
+    Ok(reprs)
+}
+
+/// Structs with several fields, or named ones: fields are concatenated in order,
+/// then zero-padded to the size of the struct.
+pub fn derive_struct(input: &DeriveInput, fields: &Fields) -> syn::Result<TokenStream> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "Bytes can't be derived for generic structs with several fields, the array size must be known at compile time",
+        ));
+    }
+
+    let name = &input.ident;
+    let types = fields.iter().map(|field| &field.ty).collect::<Vec<_>>();
+    let members = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => quote! { #ident },
+            None => {
+                let index = Index::from(i);
+                quote! { #index }
+            }
+        })
+        .collect::<Vec<_>>();
+    let values = (0..types.len())
+        .map(|i| format_ident!("field_{i}"))
+        .collect::<Vec<_>>();
+
+    let is_repr_c = reprs(&input.attrs)?.iter().any(|repr| repr == "C");
+
+    let construct = match fields {
+        Fields::Named(_) => quote! { Self { #(#members: #values),* } },
+        _ => quote! { Self(#(#values),*) },
+    };
+
+    Ok(quote! {
+        impl ::vecdb::Bytes for #name {
+            // Vecs store values in slots of `size_of::<Self>()`, padding goes at the end
+            type Array = [u8; ::std::mem::size_of::<Self>()];
+
+            // Fields in declaration order with no padding between them
+            const IS_NATIVE_LAYOUT: bool = #is_repr_c
+                #(&& <#types as ::vecdb::Bytes>::IS_NATIVE_LAYOUT)*
+                && ::std::mem::size_of::<Self>() == 0 #(+ ::std::mem::size_of::<#types>())*;
+
+            // Niche of the first field that has one
+            const NICHE: Option<::vecdb::Niche> = {
+                let mut niche = None;
+                let mut pos = 0;
+                #(
+                    if let (None, Some(inner)) = (niche, <#types as ::vecdb::Bytes>::NICHE) {
+                        niche = Some(inner.at(pos));
+                    }
+                    pos += ::std::mem::size_of::<#types>();
+                )*
+                let _ = pos;
+                niche
+            };
+
+            fn to_bytes(&self) -> Self::Array {
+                let mut buf = [0u8; ::std::mem::size_of::<Self>()];
+                let mut pos = 0;
+                #(
+                    let len = ::std::mem::size_of::<#types>();
+                    ::vecdb::Bytes::write_bytes_to(&self.#members, &mut buf[pos..pos + len]);
+                    pos += len;
+                )*
+                let _ = pos;
+                buf
+            }
+
+            fn from_bytes(bytes: &[u8]) -> ::vecdb::Result<Self> {
+                let len = ::std::mem::size_of::<Self>();
+                if bytes.len() != len {
+                    return Err(::vecdb::Error::WrongLength {
+                        expected: len,
+                        received: bytes.len(),
+                    });
+                }
+                let mut pos = 0;
+                #(
+                    let len = ::std::mem::size_of::<#types>();
+                    let #values = <#types as ::vecdb::Bytes>::from_bytes(&bytes[pos..pos + len])?;
+                    pos += len;
+                )*
+                let _ = pos;
+                Ok(#construct)
+            }
+        }
+    })
+}
+
+/// Fieldless enums with an explicit integer `repr`, stored as their discriminant.
+pub fn derive_enum(input: &DeriveInput, data: &DataEnum) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+
+    if let Some(variant) = data
+        .variants
+        .iter()
+        .find(|variant| !matches!(variant.fields, Fields::Unit))
+    {
+        return Err(syn::Error::new_spanned(
+            variant,
+            "Bytes can only be derived for enums without fields",
+        ));
+    }
+
+    let repr = reprs(&input.attrs)?
+        .into_iter()
+        .find(|repr| ENUM_REPRS.iter().any(|accepted| repr == accepted))
+        .ok_or_else(|| {
+            syn::Error::new_spanned(
+                &input.ident,
+                "Bytes can only be derived for enums with #[repr(u8)], #[repr(u16)] or #[repr(u32)]",
+            )
+        })?;
+
+    let variants = data
+        .variants
+        .iter()
+        .map(|variant| &variant.ident)
+        .collect::<Vec<_>>();
+
+    Ok(quote! {
+        impl ::vecdb::Bytes for #name {
+            type Array = <#repr as ::vecdb::Bytes>::Array;
+            // First value that isn't a discriminant
+            const NICHE: Option<::vecdb::Niche> = {
+                const NICHE: Option<<#repr as ::vecdb::Bytes>::Array> = {
+                    let mut value: #repr = 0;
+                    loop {
+                        if true #(&& value != #name::#variants as #repr)* {
+                            break Some(value.to_le_bytes());
+                        }
+                        if value == #repr::MAX {
+                            break None;
+                        }
+                        value += 1;
+                    }
+                };
+                match &NICHE {
+                    Some(bytes) => Some(::vecdb::Niche::new(bytes)),
+                    None => None,
+                }
+            };
+
+            fn to_bytes(&self) -> Self::Array {
+                let discriminant = match self {
+                    #(Self::#variants => Self::#variants as #repr,)*
+                };
+                ::vecdb::Bytes::to_bytes(&discriminant)
+            }
+
+            fn from_bytes(bytes: &[u8]) -> ::vecdb::Result<Self> {
+                let discriminant = <#repr as ::vecdb::Bytes>::from_bytes(bytes)?;
+                #(
+                    if discriminant == Self::#variants as #repr {
+                        return Ok(Self::#variants);
+                    }
+                )*
+                Err(::vecdb::Error::InvalidDiscriminant {
+                    name: stringify!(#name),
+                    value: discriminant as u64,
+                })
+            }
+        }
+    })
+}
@@ -0,0 +1,170 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DataStruct, DeriveInput, Fields, Path};
+
+/// Structs with named fields: one stored vec per field, `BytesVec` unless the
+/// field has a `#[column(...)]` attribute naming another vec type.
+pub fn derive(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) if !fields.named.is_empty() => &fields.named,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "Columnar can only be derived for structs with named fields",
+            ));
+        }
+    };
+
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "Columnar can't be derived for generic structs",
+        ));
+    }
+
+    let name = &input.ident;
+    let vis = &input.vis;
+    let columns_name = format_ident!("{name}Columns");
+
+    let idents = fields
+        .iter()
+        .map(|field| field.ident.as_ref().expect("named field"))
+        .collect::<Vec<_>>();
+    let types = fields.iter().map(|field| &field.ty).collect::<Vec<_>>();
+    let values = idents
+        .iter()
+        .map(|ident| format_ident!("column_{ident}"))
+        .collect::<Vec<_>>();
+    let vec_types = fields
+        .iter()
+        .map(|field| {
+            let mut vec = None;
+            for attr in field
+                .attrs
+                .iter()
+                .filter(|attr| attr.path().is_ident("column"))
+            {
+                if vec.is_some() {
+                    return Err(syn::Error::new_spanned(attr, "duplicate column attribute"));
+                }
+                vec = Some(attr.parse_args::<Path>()?);
+            }
+            let vec = vec.map_or_else(|| quote! { ::vecdb::BytesVec }, |vec| quote! { #vec });
+            let ty = &field.ty;
+            Ok(quote! { #vec<I, #ty> })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let doc = format!("Columns of [`{name}`], one stored vec per field.");
+
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(Debug)]
+        #vis struct #columns_name<I: ::vecdb::VecIndex> {
+            #(pub #idents: #vec_types,)*
+        }
+
+        impl ::vecdb::Columnar for #name {
+            type Columns<I: ::vecdb::VecIndex> = #columns_name<I>;
+        }
+
+        impl<I: ::vecdb::VecIndex> ::vecdb::Columns<I, #name> for #columns_name<I>
+        where
+            #(#vec_types: ::vecdb::StoredVec<I = I, T = #types>,)*
+        {
+            fn import_with(options: ::vecdb::ImportOptions) -> ::vecdb::Result<Self> {
+                Ok(Self {
+                    #(#idents: {
+                        let name = format!("{}_{}", options.name, stringify!(#idents));
+                        <#vec_types as ::vecdb::ImportableVec>::import_with(
+                            ::vecdb::ImportOptions { name: &name, ..options },
+                        )?
+                    },)*
+                })
+            }
+
+            fn forced_import_with(options: ::vecdb::ImportOptions) -> ::vecdb::Result<Self> {
+                Ok(Self {
+                    #(#idents: {
+                        let name = format!("{}_{}", options.name, stringify!(#idents));
+                        <#vec_types as ::vecdb::ImportableVec>::forced_import_with(
+                            ::vecdb::ImportOptions { name: &name, ..options },
+                        )?
+                    },)*
+                })
+            }
+
+            fn vecs(&self) -> Vec<&dyn ::vecdb::AnyStoredVec> {
+                vec![#(&self.#idents),*]
+            }
+
+            fn vecs_mut(&mut self) -> Vec<&mut dyn ::vecdb::AnyStoredVec> {
+                vec![#(&mut self.#idents),*]
+            }
+
+            fn push(&mut self, value: #name) {
+                #(::vecdb::WritableVec::push(&mut self.#idents, value.#idents);)*
+            }
+
+            fn read_into_at(
+                &self,
+                from: usize,
+                to: usize,
+                buf: &mut Vec<#name>,
+            ) -> ::vecdb::Result<()> {
+                let len = to.saturating_sub(from);
+                #(
+                    let mut #values = Vec::with_capacity(len);
+                    ::vecdb::ReadableVec::try_read_into_at(&self.#idents, from, to, &mut #values)
+                        .map_err(|error| ::vecdb::Error::Column {
+                            name: stringify!(#idents),
+                            source: Box::new(error),
+                        })?;
+                    if #values.len() != len {
+                        return Err(::vecdb::Error::WrongLength {
+                            received: #values.len(),
+                            expected: len,
+                        });
+                    }
+                    let mut #values = #values.into_iter();
+                )*
+                buf.reserve(len);
+                // Every column has `len` values
+                while let (#(Some(#values),)*) = (#(#values.next(),)*) {
+                    buf.push(#name { #(#idents: #values),* });
+                }
+                Ok(())
+            }
+
+            fn reset_unsaved(&mut self) {
+                #(::vecdb::WritableVec::reset_unsaved(&mut self.#idents);)*
+            }
+
+            fn is_dirty(&self) -> bool {
+                false #(|| ::vecdb::WritableVec::is_dirty(&self.#idents))*
+            }
+
+            fn rollback(&mut self) -> ::vecdb::Result<()> {
+                #(::vecdb::WritableVec::rollback(&mut self.#idents)?;)*
+                Ok(())
+            }
+
+            fn rollback_before(&mut self, stamp: ::vecdb::Stamp) -> ::vecdb::Result<::vecdb::Stamp> {
+                let mut rolled_back = None;
+                #(
+                    let column = ::vecdb::WritableVec::rollback_before(&mut self.#idents, stamp)?;
+                    rolled_back = Some(rolled_back.map_or(column, |min: ::vecdb::Stamp| min.min(column)));
+                )*
+                Ok(rolled_back.unwrap_or_default())
+            }
+
+            fn remove(self) -> ::vecdb::Result<()> {
+                #(::vecdb::AnyStoredVec::remove(self.#idents)?;)*
+                Ok(())
+            }
+        }
+    })
+}
@@ -2,20 +2,31 @@ use proc_macro::TokenStream;
 use quote::quote;
 use syn::{Data, DataStruct, DeriveInput, Fields, parse_macro_input};
 
-/// Derives the `Bytes` trait for single-field tuple structs.
+mod bytes;
+mod columnar;
+
+/// Derives the `Bytes` trait for structs and fieldless enums.
 ///
-/// This macro enables custom wrapper types to work with `BytesVec`, `LZ4Vec`, `ZstdVec`,
+/// This macro enables custom types to work with `BytesVec`, `LZ4Vec`, `ZstdVec`,
 /// and other vecdb vector types that require the `Bytes` trait.
 ///
 /// # Requirements
 ///
-/// - Must be a tuple struct with exactly one field
-/// - The inner type must implement `Bytes`
-/// - Supports generic type parameters
+/// - Single-field tuple structs delegate to their inner type and support generic type parameters
+/// - Other structs concatenate their fields in declaration order, zero-padded to
+///   `size_of::<Self>()` since vecs store values in slots of that size. They can't be
+///   generic, the size of their array is computed at compile time
+/// - Enums must be fieldless with `#[repr(u8)]`, `#[repr(u16)]` or `#[repr(u32)]`, they're
+///   stored as their discriminant and unknown discriminants fail with `Error::InvalidDiscriminant`
+/// - Fields must implement `Bytes` with a fixed-size `[u8; N]` array
+///
+/// Structs set `IS_NATIVE_LAYOUT`, enabling bulk copies, when they're `#[repr(C)]`
+/// (or `#[repr(transparent)]` for wrappers), every field has a native layout and
+/// there's no padding.
 ///
 /// # Generated Implementation
 ///
-/// The derive generates a `Bytes` implementation that delegates to the inner type:
+/// For wrappers, the derive generates a `Bytes` implementation that delegates to the inner type:
 ///
 /// ```rust,ignore
 /// impl Bytes for Wrapper<T> where T: Bytes {
@@ -40,6 +51,22 @@ use syn::{Data, DataStruct, DeriveInput, Fields, parse_macro_input};
 ///
 /// #[derive(Bytes)]
 /// struct Timestamp<T>(T); // Generic types supported
+///
+/// #[derive(Bytes)]
+/// #[repr(C)]
+/// struct Ohlc {
+///     open: f64,
+///     high: f64,
+///     low: f64,
+///     close: f64,
+/// }
+///
+/// #[derive(Bytes)]
+/// #[repr(u8)]
+/// enum Side {
+///     Buy = 1,
+///     Sell = 2,
+/// }
 /// ```
 #[proc_macro_derive(Bytes)]
 pub fn derive_bytes(input: TokenStream) -> TokenStream {
@@ -53,16 +80,36 @@ pub fn derive_bytes(input: TokenStream) -> TokenStream {
             fields: Fields::Unnamed(fields),
             ..
         }) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+        Data::Struct(DataStruct {
+            fields: fields @ (Fields::Named(_) | Fields::Unnamed(_)),
+            ..
+        }) if !fields.is_empty() => {
+            return bytes::derive_struct(&input, fields)
+                .unwrap_or_else(syn::Error::into_compile_error)
+                .into();
+        }
+        Data::Enum(data) => {
+            return bytes::derive_enum(&input, data)
+                .unwrap_or_else(syn::Error::into_compile_error)
+                .into();
+        }
         _ => {
             return syn::Error::new_spanned(
                 &input.ident,
-                "Bytes can only be derived for single-field tuple structs",
+                "Bytes can only be derived for structs with fields and fieldless enums",
             )
             .to_compile_error()
             .into();
         }
     };
 
+    let is_native_layout = match bytes::reprs(&input.attrs) {
+        Ok(reprs) => reprs
+            .iter()
+            .any(|repr| repr == "C" || repr == "transparent"),
+        Err(e) => return e.into_compile_error().into(),
+    };
+
     // Check if we have generic parameters
     let has_generics = !generics.params.is_empty();
 
@@ -76,6 +123,9 @@ pub fn derive_bytes(input: TokenStream) -> TokenStream {
         quote! {
             impl #impl_generics ::vecdb::Bytes for #struct_name #ty_generics #where_clause {
                 type Array = <#inner_type as ::vecdb::Bytes>::Array;
+                const IS_NATIVE_LAYOUT: bool =
+                    #is_native_layout && <#inner_type as ::vecdb::Bytes>::IS_NATIVE_LAYOUT;
+                const NICHE: Option<::vecdb::Niche> = <#inner_type as ::vecdb::Bytes>::NICHE;
 
                 fn to_bytes(&self) -> Self::Array {
                     self.0.to_bytes()
@@ -90,6 +140,9 @@ pub fn derive_bytes(input: TokenStream) -> TokenStream {
         quote! {
             impl ::vecdb::Bytes for #struct_name {
                 type Array = <#inner_type as ::vecdb::Bytes>::Array;
+                const IS_NATIVE_LAYOUT: bool =
+                    #is_native_layout && <#inner_type as ::vecdb::Bytes>::IS_NATIVE_LAYOUT;
+                const NICHE: Option<::vecdb::Niche> = <#inner_type as ::vecdb::Bytes>::NICHE;
 
                 fn to_bytes(&self) -> Self::Array {
                     self.0.to_bytes()
@@ -233,3 +286,38 @@ pub fn derive_pco(input: TokenStream) -> TokenStream {
 
     TokenStream::from(expanded)
 }
+
+/// Derives `Columnar` for structs with named fields, storing them in a `ColumnarVec`.
+///
+/// Generates a `{Name}Columns<I>` struct with one public stored vec per field, named
+/// `{name}_{field}` in the database. Fields are stored in a `BytesVec` unless they pick
+/// another vec type with `#[column(...)]`, which must be a `StoredVec` of the field type.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use vecdb::{Columnar, ColumnarVec, PcoVec};
+///
+/// #[derive(Debug, Clone, Columnar)]
+/// struct Trade {
+///     #[column(PcoVec)]
+///     price: f64,
+///     #[column(PcoVec)]
+///     amount: u64,
+///     side: Side,
+/// }
+///
+/// // Generates:
+/// pub struct TradeColumns<I: VecIndex> {
+///     pub price: PcoVec<I, f64>,
+///     pub amount: PcoVec<I, u64>,
+///     pub side: BytesVec<I, Side>,
+/// }
+/// ```
+#[proc_macro_derive(Columnar, attributes(column))]
+pub fn derive_columnar(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    columnar::derive(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,207 @@
+use tempfile::TempDir;
+use vecdb::{
+    AnyStoredVec, Bytes, BytesVec, Database, Error, ImportableVec, ReadableVec, Version,
+    WritableVec,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Bytes)]
+#[repr(C)]
+struct Ohlc {
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+}
+
+// Not repr(C), fields of different sizes
+#[derive(Debug, Clone, Copy, PartialEq, Bytes)]
+struct Trade(u64, u16, i32);
+
+// repr(C) with padding after `kind`
+#[derive(Debug, Clone, Copy, PartialEq, Bytes)]
+#[repr(C)]
+struct Padded {
+    kind: u8,
+    amount: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Bytes)]
+#[repr(transparent)]
+struct Height(u32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Bytes)]
+#[repr(u8)]
+enum Side {
+    Buy = 1,
+    Sell = 2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Bytes)]
+#[repr(u16)]
+enum Venue {
+    Spot,
+    Perp = 300,
+    Option,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Bytes)]
+struct Fill {
+    side: Side,
+    venue: Venue,
+    ohlc: Ohlc,
+}
+
+fn roundtrip<T: Bytes + Copy + PartialEq + std::fmt::Debug>(value: T) -> vecdb::Result<()> {
+    let bytes = value.to_bytes();
+    assert_eq!(T::from_bytes(bytes.as_ref())?, value);
+    Ok(())
+}
+
+#[test]
+fn test_derive_named_struct() -> vecdb::Result<()> {
+    let ohlc = Ohlc {
+        open: 1.0,
+        high: 2.0,
+        low: 0.5,
+        close: 1.5,
+    };
+    let bytes = ohlc.to_bytes();
+    assert_eq!(bytes.len(), 32);
+    assert_eq!(&bytes[8..16], &2.0f64.to_le_bytes());
+    roundtrip(ohlc)
+}
+
+#[test]
+fn test_derive_tuple_struct() -> vecdb::Result<()> {
+    let bytes = Trade(1, 2, -3).to_bytes();
+    assert_eq!(bytes.len(), size_of::<Trade>());
+    assert_eq!(&bytes[8..10], &2u16.to_le_bytes());
+    assert_eq!(&bytes[10..14], &(-3i32).to_le_bytes());
+    assert!(bytes[14..].iter().all(|b| *b == 0));
+    roundtrip(Trade(u64::MAX, 7, i32::MIN))
+}
+
+#[test]
+fn test_derive_nested() -> vecdb::Result<()> {
+    let fill = Fill {
+        side: Side::Sell,
+        venue: Venue::Perp,
+        ohlc: Ohlc {
+            open: 3.0,
+            high: 4.0,
+            low: 2.0,
+            close: 3.5,
+        },
+    };
+    assert_eq!(fill.to_bytes().len(), size_of::<Fill>());
+    roundtrip(fill)
+}
+
+#[test]
+fn test_derive_native_layout() {
+    let little = cfg!(target_endian = "little");
+    assert_eq!(Ohlc::IS_NATIVE_LAYOUT, little);
+    assert_eq!(Height::IS_NATIVE_LAYOUT, little);
+    const { assert!(!Trade::IS_NATIVE_LAYOUT) };
+    const { assert!(!Padded::IS_NATIVE_LAYOUT) };
+    const { assert!(!Side::IS_NATIVE_LAYOUT) };
+    const { assert!(!Fill::IS_NATIVE_LAYOUT) };
+}
+
+#[test]
+fn test_derive_wrong_length() {
+    assert!(matches!(
+        Ohlc::from_bytes(&[0; 31]),
+        Err(Error::WrongLength {
+            expected: 32,
+            received: 31
+        })
+    ));
+}
+
+#[test]
+fn test_derive_enum() -> vecdb::Result<()> {
+    assert_eq!(Side::Buy.to_bytes(), [1]);
+    assert_eq!(Venue::Option.to_bytes(), 301u16.to_le_bytes());
+    roundtrip(Side::Buy)?;
+    roundtrip(Side::Sell)?;
+    roundtrip(Venue::Spot)?;
+    roundtrip(Venue::Option)?;
+
+    let err = Side::from_bytes(&[3]).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::InvalidDiscriminant {
+            name: "Side",
+            value: 3
+        }
+    ));
+    assert!(err.is_data_error());
+    Ok(())
+}
+
+#[test]
+fn test_derive_records_vec() -> vecdb::Result<()> {
+    let temp = TempDir::new()?;
+    let db = Database::open(temp.path())?;
+
+    let values = (0..1000)
+        .map(|i| Ohlc {
+            open: i as f64,
+            high: i as f64 + 1.0,
+            low: i as f64 - 1.0,
+            close: i as f64 + 0.5,
+        })
+        .collect::<Vec<_>>();
+
+    let mut vec: BytesVec<usize, Ohlc> = BytesVec::import(&db, "ohlc", Version::TWO)?;
+    values.iter().for_each(|v| vec.push(*v));
+    vec.write()?;
+    drop(vec);
+
+    let vec: BytesVec<usize, Ohlc> = BytesVec::import(&db, "ohlc", Version::TWO)?;
+    assert_eq!(vec.collect(), values);
+    assert_eq!(vec.collect_range(10, 12), values[10..12]);
+
+    let mut sides: BytesVec<usize, Side> = BytesVec::import(&db, "sides", Version::TWO)?;
+    sides.push(Side::Buy);
+    sides.push(Side::Sell);
+    sides.write()?;
+    assert_eq!(sides.collect(), vec![Side::Buy, Side::Sell]);
+
+    // Padded records keep their slot size
+    let trades = (0..1000)
+        .map(|i| Trade(i, i as u16, -(i as i32)))
+        .collect::<Vec<_>>();
+    let mut vec: BytesVec<usize, Trade> = BytesVec::import(&db, "trades", Version::TWO)?;
+    trades.iter().for_each(|v| vec.push(*v));
+    vec.write()?;
+    drop(vec);
+    let vec: BytesVec<usize, Trade> = BytesVec::import(&db, "trades", Version::TWO)?;
+    assert_eq!(vec.collect(), trades);
+    assert_eq!(vec.collect_range(500, 502), trades[500..502]);
+
+    Ok(())
+}
+
+#[test]
+fn test_derive_option_niche() -> vecdb::Result<()> {
+    // Unused discriminants and fields with a niche leave no room for a tag byte
+    assert_eq!(size_of::<Option<Side>>(), size_of::<Side>());
+    assert_eq!(None::<Side>.to_bytes().as_ref(), [0]);
+    roundtrip(Some(Side::Buy))?;
+    roundtrip(None::<Side>)?;
+
+    assert_eq!(size_of::<Option<Fill>>(), size_of::<Fill>());
+    roundtrip(None::<Fill>)?;
+    roundtrip(Some(Fill {
+        side: Side::Buy,
+        venue: Venue::Spot,
+        ohlc: Ohlc {
+            open: 0.0,
+            high: 0.0,
+            low: 0.0,
+            close: 0.0,
+        },
+    }))
+}
@@ -0,0 +1,250 @@
+use tempfile::TempDir;
+use vecdb::{
+    AnyStoredVec, AnyVec, Bytes, BytesVec, Columnar, ColumnarVec, Columns, Database, Error,
+    ReadableVec, Stamp, Version, WritableVec,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Bytes)]
+#[repr(u8)]
+enum Side {
+    Buy = 1,
+    Sell = 2,
+}
+
+#[derive(Debug, Clone, PartialEq, Columnar)]
+struct Trade {
+    #[column(vecdb::PcoVec)]
+    price: f64,
+    #[column(vecdb::PcoVec)]
+    amount: u64,
+    side: Side,
+    // Same name as a local of the generated code
+    from: u32,
+}
+
+fn trade(i: u64) -> Trade {
+    Trade {
+        price: i as f64 * 1.5,
+        amount: i * 10,
+        side: if i.is_multiple_of(2) {
+            Side::Buy
+        } else {
+            Side::Sell
+        },
+        from: i as u32,
+    }
+}
+
+fn setup() -> vecdb::Result<(Database, TempDir)> {
+    let temp = TempDir::new()?;
+    let db = Database::open(temp.path())?;
+    Ok((db, temp))
+}
+
+fn import(db: &Database) -> vecdb::Result<ColumnarVec<usize, Trade>> {
+    ColumnarVec::import(db, "trades", Version::ONE)
+}
+
+#[test]
+fn test_columnar_roundtrip() -> vecdb::Result<()> {
+    let (db, _temp) = setup()?;
+    let trades = (0..10_000).map(trade).collect::<Vec<_>>();
+
+    let mut vec = import(&db)?;
+    trades.iter().for_each(|t| vec.push(t.clone()));
+    assert!(vec.is_dirty());
+    assert_eq!(vec.len(), 10_000);
+    vec.write()?;
+    drop(vec);
+
+    let vec = import(&db)?;
+    assert_eq!(vec.len(), 10_000);
+    assert_eq!(vec.collect(), trades);
+    assert_eq!(vec.collect_range(4095, 4098), trades[4095..4098]);
+    assert_eq!(vec.collect_one(7), Some(trade(7)));
+    assert_eq!(vec.collect_one(10_000), None);
+    assert_eq!(
+        vec.fold(0, |acc, t| acc + t.amount),
+        trades.iter().map(|t| t.amount).sum::<u64>()
+    );
+
+    let mut regions = vec.region_names();
+    regions.sort();
+    assert!(regions.contains(&"trades_price/usize".to_string()));
+    assert!(regions.contains(&"trades_side/usize".to_string()));
+    Ok(())
+}
+
+#[test]
+fn test_columnar_single_column() -> vecdb::Result<()> {
+    let (db, _temp) = setup()?;
+    let mut vec = import(&db)?;
+    (0..100).for_each(|i| vec.push(trade(i)));
+    vec.write()?;
+
+    let columns = vec.columns();
+    let _: &vecdb::PcoVec<usize, u64> = &columns.amount;
+    let _: &BytesVec<usize, Side> = &columns.side;
+    assert_eq!(columns.amount.fold(0, |acc, v| acc + v), 49_500);
+    assert_eq!(
+        columns.side.collect_range(0, 3),
+        vec![Side::Buy, Side::Sell, Side::Buy]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_columnar_truncate_and_pushed() -> vecdb::Result<()> {
+    let (db, _temp) = setup()?;
+    let mut vec = import(&db)?;
+    (0..100).for_each(|i| vec.push(trade(i)));
+    vec.write()?;
+
+    // Reads see stored and pushed values
+    (100..110).for_each(|i| vec.push(trade(i)));
+    assert_eq!(
+        vec.collect_range(98, 102),
+        (98..102).map(trade).collect::<Vec<_>>()
+    );
+
+    vec.truncate_if_needed(50)?;
+    assert_eq!(vec.len(), 50);
+    vec.write()?;
+    drop(vec);
+
+    let mut vec = import(&db)?;
+    assert_eq!(vec.collect(), (0..50).map(trade).collect::<Vec<_>>());
+    assert!(vec.checked_push(51, trade(51)).is_err());
+    vec.checked_push(50, trade(50))?;
+
+    vec.reset()?;
+    assert!(vec.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_columnar_rollback() -> vecdb::Result<()> {
+    let (db, _temp) = setup()?;
+    let options =
+        vecdb::ImportOptions::new(&db, "trades", Version::ONE).with_saved_stamped_changes(10);
+    let mut vec: ColumnarVec<usize, Trade> = ColumnarVec::import_with(options)?;
+
+    for stamp in 1..=3 {
+        (vec.len() as u64..stamp * 10).for_each(|i| vec.push(trade(i)));
+        vec.stamped_write_with_changes(Stamp::new(stamp))?;
+    }
+    assert_eq!(vec.stamp(), Stamp::new(3));
+    assert_eq!(vec.len(), 30);
+
+    assert_eq!(vec.rollback_before(Stamp::new(3))?, Stamp::new(2));
+    assert_eq!(vec.collect(), (0..20).map(trade).collect::<Vec<_>>());
+
+    vec.rollback()?;
+    assert_eq!(vec.stamp(), Stamp::new(1));
+    assert_eq!(vec.collect(), (0..10).map(trade).collect::<Vec<_>>());
+    Ok(())
+}
+
+#[test]
+fn test_columnar_columns_out_of_sync() -> vecdb::Result<()> {
+    let (db, _temp) = setup()?;
+    let mut vec = import(&db)?;
+    (0..10).for_each(|i| vec.push(trade(i)));
+    vec.columns_mut().price.push(10.0);
+    assert_eq!(vec.len(), 10);
+
+    // Zipping stops at the shortest column
+    let mut buf = vec![];
+    vec.try_read_into_at(0, 11, &mut buf)?;
+    assert_eq!(buf, (0..10).map(trade).collect::<Vec<_>>());
+
+    buf.clear();
+    assert!(matches!(
+        vec.columns().read_into_at(5, 11, &mut buf),
+        Err(Error::WrongLength {
+            received: 5,
+            expected: 6
+        })
+    ));
+    assert!(buf.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_columnar_corrupt_column() -> vecdb::Result<()> {
+    let (db, _temp) = setup()?;
+    let options = vecdb::ImportOptions::new(&db, "trades", Version::ONE).with_page_checksums(true);
+    let mut vec: ColumnarVec<usize, Trade> = ColumnarVec::import_with(options)?;
+    let len = 5000;
+    (0..len).for_each(|i| vec.push(trade(i)));
+    vec.write()?;
+
+    // Flips a byte of the first page of prices
+    let region = vec.columns().price.region();
+    let at = vecdb::HEADER_OFFSET + 3;
+    let byte = region.create_reader().read(at, 1)[0];
+    region.write_at(&[byte ^ 0xFF], at)?;
+
+    let mut buf = vec![];
+    match vec.try_read_into_at(0, 10, &mut buf) {
+        Err(Error::Column { name, source }) => {
+            assert_eq!(name, "price");
+            assert!(matches!(*source, Error::ChecksumMismatch { page: 0, .. }));
+        }
+        result => panic!("expected a column error, got {result:?}"),
+    }
+    assert!(buf.is_empty());
+
+    vec.try_read_into_at(len as usize - 10, len as usize, &mut buf)?;
+    assert_eq!(buf, (len - 10..len).map(trade).collect::<Vec<_>>());
+    Ok(())
+}
+
+#[test]
+fn test_columnar_partial_rollback() -> vecdb::Result<()> {
+    let (db, _temp) = setup()?;
+    let options =
+        vecdb::ImportOptions::new(&db, "trades", Version::ONE).with_saved_stamped_changes(10);
+    let mut vec: ColumnarVec<usize, Trade> = ColumnarVec::import_with(options)?;
+    for stamp in 1..=2 {
+        (vec.len() as u64..stamp * 10).for_each(|i| vec.push(trade(i)));
+        vec.stamped_write_with_changes(Stamp::new(stamp))?;
+    }
+
+    // The last column can't roll back while its change file is missing, the ones
+    // before it still do
+    let path = vec.columns().from.find_rollback_files()?[&Stamp::new(2)].clone();
+    let moved = path.with_extension("moved");
+    std::fs::rename(&path, &moved)?;
+    assert!(vec.rollback().is_err());
+    std::fs::rename(&moved, &path)?;
+    let columns = vec.columns();
+    assert_eq!(columns.price.stamp(), Stamp::new(1));
+    assert_eq!(columns.side.len(), 10);
+    assert_eq!(columns.from.stamp(), Stamp::new(2));
+    assert_eq!(columns.from.len(), 20);
+
+    // Rolling the rest back on its own brings the columns back in sync
+    WritableVec::rollback(&mut vec.columns_mut().from)?;
+    vec.write()?;
+    assert_eq!(vec.stamp(), Stamp::new(1));
+    assert_eq!(vec.collect(), (0..10).map(trade).collect::<Vec<_>>());
+    Ok(())
+}
+
+#[test]
+fn test_columnar_forced_import() -> vecdb::Result<()> {
+    let (db, _temp) = setup()?;
+    let mut vec = import(&db)?;
+    (0..10).for_each(|i| vec.push(trade(i)));
+    vec.write()?;
+    drop(vec);
+
+    let vec: ColumnarVec<usize, Trade> = ColumnarVec::forced_import(&db, "trades", Version::TWO)?;
+    assert!(vec.is_empty());
+    assert_eq!(vec.version(), Version::TWO);
+
+    vec.remove()?;
+    assert!(db.get_region("trades_price/usize").is_none());
+    Ok(())
+}
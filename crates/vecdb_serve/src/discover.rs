@@ -0,0 +1,90 @@
+use log::debug;
+use rawdb::Database;
+use vecdb::{
+    BytesVec, Format, Header, ImportableVec, LZ4Vec, MonotonicVec, PcoVec, Result, Version,
+    ZeroCopyVec, ZstdVec, value_type_fingerprint,
+};
+
+use crate::ServedVec;
+
+type Opened = Result<Option<Box<dyn ServedVec>>>;
+
+/// Opens the vecs of `db` indexed by `usize` whose header fingerprint matches a primitive
+/// value type. Regions that aren't such vecs are left alone.
+pub(crate) fn open_vecs(db: &Database) -> Result<Vec<Box<dyn ServedVec>>> {
+    // Collected first, importing needs the regions lock
+    let ids = db
+        .regions()
+        .id_to_index()
+        .keys()
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let mut vecs = vec![];
+    for id in ids {
+        let Some(name) = id.strip_suffix("/usize") else {
+            continue;
+        };
+        let Some(region) = db.get_region(&id) else {
+            continue;
+        };
+        let header = match Header::read(&region) {
+            Ok(header) => header,
+            Err(e) => {
+                debug!("Skipping region {id}: {e}");
+                continue;
+            }
+        };
+        match open(db, name, &header)? {
+            Some(vec) => vecs.push(vec),
+            None => debug!("Skipping {id}: unknown value type"),
+        }
+    }
+    Ok(vecs)
+}
+
+fn open(db: &Database, name: &str, header: &Header) -> Opened {
+    let format = header.format();
+    // Importing adds the layout version back
+    let Some(version) =
+        u32::from(header.vec_version()).checked_sub(u32::from(format.layout_version()))
+    else {
+        return Ok(None);
+    };
+    let version = Version::new(version);
+    let value_type = header.value_type();
+
+    macro_rules! open_as {
+        ($t:ty $(, $format:ident => $vec:ident)*) => {
+            if value_type == value_type_fingerprint::<usize, $t>() {
+                return match format {
+                    Format::Bytes => boxed(BytesVec::<usize, $t>::import(db, name, version)),
+                    Format::LZ4 => boxed(LZ4Vec::<usize, $t>::import(db, name, version)),
+                    Format::Zstd => boxed(ZstdVec::<usize, $t>::import(db, name, version)),
+                    $(Format::$format => boxed($vec::<usize, $t>::import(db, name, version)),)*
+                    #[allow(unreachable_patterns)]
+                    _ => Ok(None),
+                };
+            }
+        };
+    }
+
+    open_as!(u8, ZeroCopy => ZeroCopyVec, Pco => PcoVec, Monotonic => MonotonicVec);
+    open_as!(u16, ZeroCopy => ZeroCopyVec, Pco => PcoVec, Monotonic => MonotonicVec);
+    open_as!(u32, ZeroCopy => ZeroCopyVec, Pco => PcoVec, Monotonic => MonotonicVec);
+    open_as!(u64, ZeroCopy => ZeroCopyVec, Pco => PcoVec, Monotonic => MonotonicVec);
+    open_as!(usize, ZeroCopy => ZeroCopyVec, Monotonic => MonotonicVec);
+    open_as!(i8, ZeroCopy => ZeroCopyVec, Pco => PcoVec);
+    open_as!(i16, ZeroCopy => ZeroCopyVec, Pco => PcoVec);
+    open_as!(i32, ZeroCopy => ZeroCopyVec, Pco => PcoVec);
+    open_as!(i64, ZeroCopy => ZeroCopyVec, Pco => PcoVec);
+    open_as!(f32, ZeroCopy => ZeroCopyVec, Pco => PcoVec);
+    open_as!(f64, ZeroCopy => ZeroCopyVec, Pco => PcoVec);
+    open_as!(bool);
+
+    Ok(None)
+}
+
+fn boxed(vec: Result<impl ServedVec + 'static>) -> Opened {
+    Ok(Some(Box::new(vec?)))
+}
@@ -0,0 +1,190 @@
+use std::io::{self, BufRead, Write};
+
+/// Largest request head read from a connection.
+const MAX_HEAD_SIZE: usize = 16 * 1024;
+
+/// An HTTP request, without body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    /// Path and query, as sent.
+    pub target: String,
+    pub headers: Vec<(String, String)>,
+}
+
+impl Request {
+    pub fn get(target: &str) -> Self {
+        Self {
+            method: "GET".to_string(),
+            target: target.to_string(),
+            headers: vec![],
+        }
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Value of the header `name`, compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+
+    /// Percent-decoded path segments, without the leading slash.
+    pub fn path_segments(&self) -> Option<Vec<String>> {
+        let path = self.target.split('?').next().unwrap_or_default();
+        path.strip_prefix('/')?
+            .split('/')
+            .map(percent_decode)
+            .collect()
+    }
+
+    /// Percent-decoded value of the query parameter `name`.
+    pub fn query(&self, name: &str) -> Option<String> {
+        let (_, query) = self.target.split_once('?')?;
+        query.split('&').find_map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key)? == name).then(|| percent_decode(value))?
+        })
+    }
+
+    /// Reads a request head, or `None` if the connection closed before one started.
+    pub fn read(reader: &mut impl BufRead) -> io::Result<Option<Self>> {
+        let invalid = |message| io::Error::new(io::ErrorKind::InvalidData, message);
+        let mut line = String::new();
+        let mut read = 0;
+        let mut next_line = |line: &mut String| -> io::Result<usize> {
+            line.clear();
+            let n = reader.read_line(line)?;
+            read += n;
+            if read > MAX_HEAD_SIZE {
+                return Err(invalid("request head too large"));
+            }
+            let len = line.trim_end_matches(['\r', '\n']).len();
+            line.truncate(len);
+            Ok(n)
+        };
+
+        if next_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let mut parts = line.split(' ');
+        let (Some(method), Some(target), Some(_version), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid("invalid request line"));
+        };
+        let mut request = Self {
+            method: method.to_string(),
+            target: target.to_string(),
+            headers: vec![],
+        };
+
+        loop {
+            if next_line(&mut line)? == 0 {
+                return Err(invalid("connection closed in request head"));
+            }
+            if line.is_empty() {
+                return Ok(Some(request));
+            }
+            let (name, value) = line
+                .split_once(':')
+                .ok_or_else(|| invalid("invalid header"))?;
+            request
+                .headers
+                .push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+}
+
+/// An HTTP response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    pub fn new(status: u16, content_type: &str, body: Vec<u8>) -> Self {
+        Self {
+            status,
+            headers: vec![("Content-Type".to_string(), content_type.to_string())],
+            body,
+        }
+    }
+
+    /// A `text/plain` response, for errors.
+    pub fn text(status: u16, text: &str) -> Self {
+        Self::new(
+            status,
+            "text/plain; charset=utf-8",
+            text.as_bytes().to_vec(),
+        )
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Value of the header `name`, compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+
+    /// Writes the response as HTTP/1.1, closing the connection after it.
+    pub fn write(&self, out: &mut impl Write) -> io::Result<()> {
+        write!(out, "HTTP/1.1 {} {}\r\n", self.status, reason(self.status))?;
+        for (name, value) in &self.headers {
+            write!(out, "{name}: {value}\r\n")?;
+        }
+        write!(
+            out,
+            "Content-Length: {}\r\nConnection: close\r\n\r\n",
+            self.body.len()
+        )?;
+        out.write_all(&self.body)?;
+        out.flush()
+    }
+}
+
+fn find_header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|(_, v)| v.as_str())
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        304 => "Not Modified",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        500 => "Internal Server Error",
+        _ => "",
+    }
+}
+
+/// Decodes `%XX` escapes, or `None` if the result isn't UTF-8.
+fn percent_decode(s: &str) -> Option<String> {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let hex = s.get(i + 1..i + 3)?;
+                out.push(u8::from_str_radix(hex, 16).ok()?);
+                i += 3;
+                continue;
+            }
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8(out).ok()
+}
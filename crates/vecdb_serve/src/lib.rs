@@ -0,0 +1,19 @@
+//! Read-only HTTP access to vecdb vecs.
+//!
+//! A [`Router`] holds vecs by name and answers:
+//!
+//! - `GET /vecs`: name, index, value type, length, version and stamp of every vec, as JSON
+//! - `GET /vecs/{name}?from=&to=&format=json|csv`: values of a range, indices counting from
+//!   the end when negative, with an `ETag` and `If-None-Match` support
+//! - `GET /vecs/{name}/schema`: JSON schema of the value type
+//!
+//! Vecs are either registered with [`Router::with_vec`] or found in a database by
+//! [`Router::discover`]. [`Router::serve`] answers HTTP/1.1 requests on a listener, one
+//! thread per connection, and the `vecdb-serve` binary serves a database directory.
+
+mod discover;
+mod http;
+mod router;
+
+pub use http::*;
+pub use router::*;
@@ -0,0 +1,42 @@
+use std::{env, net::TcpListener, path::PathBuf, process::ExitCode};
+
+use rawdb::Database;
+use vecdb_serve::Router;
+
+const USAGE: &str = "Usage: vecdb-serve <DATABASE_DIR> [ADDRESS]
+
+Serves the vecs of a database read-only over HTTP, on 127.0.0.1:3110 by default.";
+
+fn main() -> ExitCode {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let (path, address) = match &args[..] {
+        [path] => (path, "127.0.0.1:3110"),
+        [path, address] => (path, address.as_str()),
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match run(PathBuf::from(path), address) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("vecdb-serve: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(path: PathBuf, address: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let db = Database::open(&path)?;
+    let router = Router::discover(&db)?;
+    let listener = TcpListener::bind(address)?;
+    eprintln!(
+        "Serving {} vecs of {} on http://{}",
+        router.names().count(),
+        path.display(),
+        listener.local_addr()?
+    );
+    router.serve(listener)?;
+    Ok(())
+}
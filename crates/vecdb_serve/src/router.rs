@@ -0,0 +1,187 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, BufReader},
+    net::{TcpListener, TcpStream},
+    thread,
+};
+
+use log::warn;
+use rawdb::Database;
+use serde_json::json;
+use vecdb::{AnyExportableVec, AnyStoredVec, AnyVecWithSchema, Result, Stamp, WriterFormat};
+
+use crate::{Request, Response, discover};
+
+/// A vec a [`Router`] can serve: exportable, with a value schema and a stamp for `ETag`s.
+pub trait ServedVec: AnyExportableVec + AnyVecWithSchema {
+    fn stamp(&self) -> Stamp;
+}
+
+impl<V> ServedVec for V
+where
+    V: AnyExportableVec + AnyVecWithSchema + AnyStoredVec,
+{
+    fn stamp(&self) -> Stamp {
+        AnyStoredVec::stamp(self)
+    }
+}
+
+/// Answers read-only requests on a set of vecs, see the [crate docs](crate).
+#[derive(Default)]
+pub struct Router {
+    vecs: BTreeMap<String, Box<dyn ServedVec>>,
+}
+
+impl Router {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serves the vecs of `db` whose value type is a primitive number indexed by `usize`,
+    /// which their header identifies. Other vecs can be added with [`Self::with_vec`].
+    pub fn discover(db: &Database) -> Result<Self> {
+        let mut router = Self::new();
+        for vec in discover::open_vecs(db)? {
+            router = router.with_vec_boxed(vec);
+        }
+        Ok(router)
+    }
+
+    /// Serves `vec` under its name, replacing a vec with the same name.
+    pub fn with_vec(self, vec: impl ServedVec + 'static) -> Self {
+        self.with_vec_boxed(Box::new(vec))
+    }
+
+    pub fn with_vec_boxed(mut self, vec: Box<dyn ServedVec>) -> Self {
+        self.vecs.insert(vec.name().to_string(), vec);
+        self
+    }
+
+    /// Names of the served vecs, sorted.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.vecs.keys().map(String::as_str)
+    }
+
+    pub fn handle(&self, request: &Request) -> Response {
+        if request.method != "GET" {
+            return Response::text(405, "Method not allowed").with_header("Allow", "GET");
+        }
+        let Some(segments) = request.path_segments() else {
+            return Response::text(400, "Invalid path");
+        };
+        match segments.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+            ["vecs"] => self.list(),
+            ["vecs", name] => self.with_served(name, |vec| values(vec, request)),
+            ["vecs", name, "schema"] => self.with_served(name, schema),
+            _ => Response::text(404, "Not found"),
+        }
+    }
+
+    /// Answers requests on `listener` until it fails, one thread per connection.
+    pub fn serve(&self, listener: TcpListener) -> io::Result<()> {
+        thread::scope(|scope| {
+            for stream in listener.incoming() {
+                let stream = stream?;
+                scope.spawn(move || {
+                    if let Err(e) = self.handle_connection(stream) {
+                        warn!("Connection failed: {e}");
+                    }
+                });
+            }
+            Ok(())
+        })
+    }
+
+    fn handle_connection(&self, stream: TcpStream) -> io::Result<()> {
+        let mut reader = BufReader::new(&stream);
+        let response = match Request::read(&mut reader) {
+            Ok(Some(request)) => self.handle(&request),
+            Ok(None) => return Ok(()),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData => Response::text(400, &e.to_string()),
+            Err(e) => return Err(e),
+        };
+        response.write(&mut &stream)
+    }
+
+    fn with_served(&self, name: &str, f: impl FnOnce(&dyn ServedVec) -> Response) -> Response {
+        match self.vecs.get(name) {
+            Some(vec) => f(vec.as_ref()),
+            None => Response::text(404, &format!("No vec named {name:?}")),
+        }
+    }
+
+    fn list(&self) -> Response {
+        let vecs = self
+            .vecs
+            .values()
+            .map(|vec| {
+                json!({
+                    "name": vec.name(),
+                    "index": vec.index_type_to_string(),
+                    "type": vec.value_type_to_string(),
+                    "len": vec.len(),
+                    "version": u32::from(vec.version()),
+                    "stamp": u64::from(vec.stamp()),
+                })
+            })
+            .collect::<Vec<_>>();
+        Response::new(
+            200,
+            "application/json",
+            json!(vecs).to_string().into_bytes(),
+        )
+    }
+}
+
+/// Values of the requested range, as [`collect_signed_range`](vecdb::ReadableVec::collect_signed_range)
+/// would select them, streamed through a cursor.
+fn values(vec: &dyn ServedVec, request: &Request) -> Response {
+    let bound = |name| match request.query(name) {
+        None => Ok(None),
+        Some(value) => value
+            .parse::<i64>()
+            .map(Some)
+            .map_err(|_| Response::text(400, &format!("Invalid {name}: {value:?}"))),
+    };
+    let (from, to) = match (bound("from"), bound("to")) {
+        (Ok(from), Ok(to)) => (from, to),
+        (Err(response), _) | (_, Err(response)) => return response,
+    };
+    let (format, content_type) = match request.query("format").as_deref() {
+        None | Some("json") => (WriterFormat::Json, "application/json"),
+        Some("csv") => (WriterFormat::Csv, "text/csv; charset=utf-8"),
+        Some(format) => return Response::text(400, &format!("Invalid format: {format:?}")),
+    };
+
+    let etag = format!("\"{}\"", vec.etag(vec.stamp(), to));
+    let matches = |tag: &str| {
+        let tag = tag.trim();
+        tag == "*" || tag == etag
+    };
+    if request
+        .header("If-None-Match")
+        .is_some_and(|tags| tags.split(',').any(matches))
+    {
+        return Response {
+            status: 304,
+            headers: vec![("ETag".to_string(), etag)],
+            body: vec![],
+        };
+    }
+
+    let mut body = vec![];
+    if let Err(e) = vec
+        .create_writer_with_format(from, to, format)
+        .write_all(&mut body)
+    {
+        return Response::text(500, &e.to_string());
+    }
+    Response::new(200, content_type, body).with_header("ETag", &etag)
+}
+
+fn schema(vec: &dyn ServedVec) -> Response {
+    match serde_json::to_vec(&vec.value_schema()) {
+        Ok(body) => Response::new(200, "application/schema+json", body),
+        Err(e) => Response::text(500, &e.to_string()),
+    }
+}
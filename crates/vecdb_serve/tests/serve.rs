@@ -0,0 +1,173 @@
+//! Tests for the read-only HTTP router.
+//!
+//! These tests cover:
+//! - Discovering primitive vecs of every format, and skipping other regions
+//! - Listing vecs, signed ranges in JSON and CSV, schemas and errors
+//! - `ETag` and `If-None-Match`
+//! - Requests over TCP
+
+use std::{
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+    thread,
+};
+
+use rawdb::Database;
+use serde_json::Value;
+use tempfile::TempDir;
+use vecdb::{
+    AnyStoredVec, BytesVec, ImportableVec, MonotonicVec, PcoVec, Result, Stamp, Version,
+    WritableVec, ZeroCopyVec,
+};
+use vecdb_serve::{Request, Response, Router};
+
+fn setup() -> Result<(Database, TempDir)> {
+    let temp = TempDir::new()?;
+    let db = Database::open(temp.path())?;
+
+    let mut price: PcoVec<usize, f64> = PcoVec::import(&db, "price", Version::TWO)?;
+    let mut height: MonotonicVec<usize, u32> = MonotonicVec::import(&db, "height", Version::ONE)?;
+    let mut delta: ZeroCopyVec<usize, i16> = ZeroCopyVec::import(&db, "delta", Version::ONE)?;
+    let mut flag: BytesVec<usize, bool> = BytesVec::import(&db, "flag", Version::ONE)?;
+    let mut pair: BytesVec<usize, (u8, u8)> = BytesVec::import(&db, "pair", Version::ONE)?;
+    for i in 0..10 {
+        price.push(i as f64 * 1.5);
+        height.push(i * 2);
+        delta.push(-(i as i16));
+        flag.push(i % 3 == 0);
+        pair.push((i as u8, 0));
+    }
+    price.stamped_write(Stamp::new(7))?;
+    height.write()?;
+    delta.write()?;
+    flag.write()?;
+    pair.write()?;
+    Ok((db, temp))
+}
+
+fn get(router: &Router, target: &str) -> Response {
+    router.handle(&Request::get(target))
+}
+
+fn body(response: &Response) -> &str {
+    std::str::from_utf8(&response.body).unwrap()
+}
+
+#[test]
+fn discover_and_list() -> Result<()> {
+    let (db, _temp) = setup()?;
+    let router = Router::discover(&db)?;
+    // `pair` has no primitive value type
+    assert_eq!(
+        router.names().collect::<Vec<_>>(),
+        ["delta", "flag", "height", "price"]
+    );
+
+    let response = get(&router, "/vecs");
+    assert_eq!(response.status, 200);
+    assert_eq!(response.header("content-type"), Some("application/json"));
+    let list: Value = serde_json::from_slice(&response.body).unwrap();
+    let price = &list.as_array().unwrap()[3];
+    assert_eq!(price["name"], "price");
+    assert_eq!(price["index"], "usize");
+    assert_eq!(price["type"], "f64");
+    assert_eq!(price["len"], 10);
+    assert_eq!(price["version"], 5);
+    assert_eq!(price["stamp"], 7);
+    Ok(())
+}
+
+#[test]
+fn values() -> Result<()> {
+    let (db, _temp) = setup()?;
+    let router = Router::discover(&db)?;
+
+    let response = get(&router, "/vecs/price?from=2&to=5");
+    assert_eq!(response.status, 200);
+    assert_eq!(body(&response), "[3.0,4.5,6.0]");
+    assert_eq!(body(&get(&router, "/vecs/height?from=-3")), "[14,16,18]");
+    assert_eq!(body(&get(&router, "/vecs/delta?to=-8")), "[0,-1]");
+    assert_eq!(body(&get(&router, "/vecs/flag?from=20")), "[]");
+
+    let response = get(&router, "/vecs/flag?from=2&to=4&format=csv");
+    assert_eq!(response.status, 200);
+    assert_eq!(
+        response.header("Content-Type"),
+        Some("text/csv; charset=utf-8")
+    );
+    assert_eq!(body(&response), "false\ntrue\n");
+
+    let response = get(&router, "/vecs/price/schema");
+    assert_eq!(response.status, 200);
+    let schema: Value = serde_json::from_slice(&response.body).unwrap();
+    assert_eq!(schema["type"], "number");
+
+    assert_eq!(get(&router, "/vecs/pair").status, 404);
+    assert_eq!(get(&router, "/vecs/price?from=x").status, 400);
+    assert_eq!(get(&router, "/vecs/price?format=xml").status, 400);
+    assert_eq!(get(&router, "/other").status, 404);
+    let post = Request {
+        method: "POST".to_string(),
+        ..Request::get("/vecs")
+    };
+    assert_eq!(router.handle(&post).status, 405);
+    Ok(())
+}
+
+#[test]
+fn etags() -> Result<()> {
+    let (db, _temp) = setup()?;
+    let router = Router::discover(&db)?;
+
+    let response = get(&router, "/vecs/price?to=-1");
+    let etag = response.header("ETag").unwrap().to_string();
+    assert_eq!(etag, "\"9-5-7\"");
+
+    let cached = Request::get("/vecs/price?to=-1&format=csv").with_header("If-None-Match", &etag);
+    let response = router.handle(&cached);
+    assert_eq!(response.status, 304);
+    assert!(response.body.is_empty());
+    assert_eq!(response.header("etag"), Some(etag.as_str()));
+
+    let stale = Request::get("/vecs/price").with_header("If-None-Match", "\"1-5-7\", \"2-5-7\"");
+    assert_eq!(router.handle(&stale).status, 200);
+    let any = Request::get("/vecs/price").with_header("If-None-Match", "*");
+    assert_eq!(router.handle(&any).status, 304);
+    Ok(())
+}
+
+#[test]
+fn tcp() -> Result<()> {
+    let (db, _temp) = setup()?;
+    let router = Router::discover(&db)?;
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let address = listener.local_addr()?;
+    thread::spawn(move || router.serve(listener));
+
+    let request = |head: &str| -> Result<String> {
+        let mut stream = TcpStream::connect(address)?;
+        stream.write_all(head.as_bytes())?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+        Ok(response)
+    };
+
+    let response = request("GET /vecs/height?from=1&to=3 HTTP/1.1\r\nHost: localhost\r\n\r\n")?;
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{response}");
+    assert!(response.contains("\r\nETag: \"3-4-0\"\r\n"), "{response}");
+    assert!(response.contains("\r\nContent-Length: 5\r\n"), "{response}");
+    assert!(response.ends_with("\r\n\r\n[2,4]"), "{response}");
+
+    let response = request("GET /vecs/height HTTP/1.1\r\nIf-None-Match: \"10-4-0\"\r\n\r\n")?;
+    assert!(
+        response.starts_with("HTTP/1.1 304 Not Modified\r\n"),
+        "{response}"
+    );
+
+    let response = request("nonsense\r\n\r\n")?;
+    assert!(
+        response.starts_with("HTTP/1.1 400 Bad Request\r\n"),
+        "{response}"
+    );
+    Ok(())
+}